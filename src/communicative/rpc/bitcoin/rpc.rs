@@ -1,7 +1,7 @@
 use crate::communicative::rpc::bitcoin::error::ValidateRPCError;
 use crate::communicative::rpc::bitcoin::rpcholder::RPCHolder;
 use crate::operative::Chain;
-use bitcoin::{Block, BlockHash};
+use bitcoin::{Block, BlockHash, Transaction, Txid};
 use bitcoincore_rpc::{json::GetBlockchainInfoResult, Auth, Client, RpcApi};
 
 /// Validates the Bitcoin RPC.
@@ -87,3 +87,45 @@ pub fn get_block(
 
     Ok(block)
 }
+
+/// Returns a confirmed transaction along with the height of the block it is confirmed in.
+/// Requires the Bitcoin node to maintain a transaction index (`-txindex`).
+pub fn get_transaction(
+    rpc_holder: &RPCHolder,
+    txid: &Txid,
+) -> Result<(Transaction, u64), bitcoincore_rpc::Error> {
+    let rpc_url = rpc_holder.url();
+    let rpc_user = rpc_holder.user();
+    let rpc_password = rpc_holder.password();
+
+    let rpc_client = match Client::new(&rpc_url, Auth::UserPass(rpc_user, rpc_password)) {
+        Ok(client) => client,
+        Err(err) => return Err(err),
+    };
+
+    let tx_info = match rpc_client.get_raw_transaction_info(txid, None) {
+        Ok(tx_info) => tx_info,
+        Err(err) => return Err(err),
+    };
+
+    let block_hash: BlockHash = match tx_info.blockhash {
+        Some(block_hash) => block_hash,
+        None => {
+            return Err(bitcoincore_rpc::Error::ReturnedError(
+                "Transaction is not confirmed.".to_string(),
+            ))
+        }
+    };
+
+    let height = match rpc_client.get_block_header_info(&block_hash) {
+        Ok(header_info) => header_info.height as u64,
+        Err(err) => return Err(err),
+    };
+
+    let tx: Transaction = match tx_info.transaction() {
+        Ok(tx) => tx,
+        Err(_) => return Err(bitcoincore_rpc::Error::UnexpectedStructure),
+    };
+
+    Ok((tx, height))
+}
@@ -2,6 +2,7 @@ use super::element_type::CallElementType;
 use crate::constructive::entity::account::Account;
 use crate::constructive::entity::contract::Contract;
 use crate::constructive::valtype::long_val::LongVal;
+use crate::constructive::valtype::maybe_common::common_table::CommonTable;
 use crate::constructive::valtype::maybe_common::maybe_common::MaybeCommon;
 use crate::constructive::valtype::short_val::ShortVal;
 use crate::inscriptive::registery::account_registery::ACCOUNT_REGISTERY;
@@ -18,9 +19,12 @@ use crate::{
     executive::stack::stack_item::StackItem,
 };
 use bit_vec::BitVec;
+use secp::Point;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // Represents a single element of calldata.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CallElement {
     U8(u8),
     U16(u16),
@@ -40,6 +44,7 @@ impl CallElement {
         bit_stream: &mut bit_vec::Iter<'_>,
         element_type: CallElementType,
         registery: &REGISTERY,
+        common_table: &CommonTable,
     ) -> Result<Self, CPEDecodingError> {
         // Match on the calldata element type.
         match element_type {
@@ -108,16 +113,17 @@ impl CallElement {
             // Decode the u32.
             CallElementType::U32 => {
                 // Decode the `ShortVal` from `MaybeCommon<ShortVal>`.
-                let short_val = MaybeCommon::<ShortVal>::decode_cpe(bit_stream)
-                    .map_err(|e| {
-                        CPEDecodingError::CalldataCPEDecodingError(
-                            CalldataCPEDecodingError::U32DecodingError(match e {
-                                CPEDecodingError::MaybeCommonCPEDecodingError(err) => err,
-                                _ => return CPEDecodingError::UnexpectedError,
-                            }),
-                        )
-                    })?
-                    .value();
+                let short_val =
+                    MaybeCommon::<ShortVal>::decode_cpe_with_table(bit_stream, common_table)
+                        .map_err(|e| {
+                            CPEDecodingError::CalldataCPEDecodingError(
+                                CalldataCPEDecodingError::U32DecodingError(match e {
+                                    CPEDecodingError::MaybeCommonCPEDecodingError(err) => err,
+                                    _ => return CPEDecodingError::UnexpectedError,
+                                }),
+                            )
+                        })?
+                        .value();
 
                 // Construct the `CalldataElement`.
                 let element = CallElement::U32(short_val);
@@ -129,16 +135,17 @@ impl CallElement {
             // Decode the u64.
             CallElementType::U64 => {
                 // Decode the `LongVal` from `MaybeCommon<LongVal>`.
-                let long_val = MaybeCommon::<LongVal>::decode_cpe(bit_stream)
-                    .map_err(|e| {
-                        CPEDecodingError::CalldataCPEDecodingError(
-                            CalldataCPEDecodingError::U64DecodingError(match e {
-                                CPEDecodingError::MaybeCommonCPEDecodingError(err) => err,
-                                _ => return CPEDecodingError::UnexpectedError,
-                            }),
-                        )
-                    })?
-                    .value();
+                let long_val =
+                    MaybeCommon::<LongVal>::decode_cpe_with_table(bit_stream, common_table)
+                        .map_err(|e| {
+                            CPEDecodingError::CalldataCPEDecodingError(
+                                CalldataCPEDecodingError::U64DecodingError(match e {
+                                    CPEDecodingError::MaybeCommonCPEDecodingError(err) => err,
+                                    _ => return CPEDecodingError::UnexpectedError,
+                                }),
+                            )
+                        })?
+                        .value();
 
                 let element = CallElement::U64(long_val);
 
@@ -319,16 +326,17 @@ impl CallElement {
             // Decode the `Payable`.
             CallElementType::Payable => {
                 // Decode the `ShortVal` from `MaybeCommon<ShortVal>`.
-                let short_val = MaybeCommon::<ShortVal>::decode_cpe(bit_stream)
-                    .map_err(|e| {
-                        CPEDecodingError::CalldataCPEDecodingError(
-                            CalldataCPEDecodingError::U32DecodingError(match e {
-                                CPEDecodingError::MaybeCommonCPEDecodingError(err) => err,
-                                _ => return CPEDecodingError::UnexpectedError,
-                            }),
-                        )
-                    })?
-                    .value();
+                let short_val =
+                    MaybeCommon::<ShortVal>::decode_cpe_with_table(bit_stream, common_table)
+                        .map_err(|e| {
+                            CPEDecodingError::CalldataCPEDecodingError(
+                                CalldataCPEDecodingError::U32DecodingError(match e {
+                                    CPEDecodingError::MaybeCommonCPEDecodingError(err) => err,
+                                    _ => return CPEDecodingError::UnexpectedError,
+                                }),
+                            )
+                        })?
+                        .value();
 
                 // Construct the `CalldataElement`.
                 let element = CallElement::Payable(short_val);
@@ -360,6 +368,24 @@ impl CallElement {
         }
     }
 
+    /// Sets the rank of an `Account` or `Contract` element from the given ranks.
+    /// Entities not in the ranks are left unranked.
+    pub(crate) fn rerank(
+        &mut self,
+        account_ranks: &HashMap<Point, u32>,
+        contract_ranks: &HashMap<[u8; 32], u32>,
+    ) {
+        match self {
+            CallElement::Account(account) => {
+                account.set_rank(account_ranks.get(&account.key()).copied())
+            }
+            CallElement::Contract(contract) => {
+                contract.set_rank(contract_ranks.get(&contract.contract_id()).copied())
+            }
+            _ => {}
+        }
+    }
+
     /// Returns the element in the pure bytes format to be pushed/used for stack operations.
     pub fn stack_item(&self) -> StackItem {
         match self {
@@ -394,6 +420,10 @@ impl CallElement {
 
 impl CompactPayloadEncoding for CallElement {
     fn encode_cpe(&self) -> Option<BitVec> {
+        self.encode_cpe_with_table(&CommonTable::genesis())
+    }
+
+    fn encode_cpe_with_table(&self, common_table: &CommonTable) -> Option<BitVec> {
         match self {
             CallElement::U8(u8_value) => {
                 // Get the u8 value.
@@ -422,15 +452,19 @@ impl CompactPayloadEncoding for CallElement {
                 Some(bits)
             }
             CallElement::U32(short_val) => {
-                // Encode the `ShortVal`.
-                let bits = short_val.encode_cpe()?;
+                // Encode the `ShortVal` as `MaybeCommon<ShortVal>`.
+                let bits =
+                    MaybeCommon::<ShortVal>::new_with_table(short_val.to_owned(), common_table)
+                        .encode_cpe()?;
 
                 // Return the bits.
                 Some(bits)
             }
             CallElement::U64(long_val) => {
-                // Encode the `LongVal`.
-                let bits = long_val.encode_cpe()?;
+                // Encode the `LongVal` as `MaybeCommon<LongVal>`.
+                let bits =
+                    MaybeCommon::<LongVal>::new_with_table(long_val.to_owned(), common_table)
+                        .encode_cpe()?;
 
                 // Return the bits.
                 Some(bits)
@@ -498,8 +532,10 @@ impl CompactPayloadEncoding for CallElement {
                 Some(bits)
             }
             CallElement::Payable(short_val) => {
-                // Encode the `ShortVal`.
-                let bits = short_val.encode_cpe()?;
+                // Encode the `ShortVal` as `MaybeCommon<ShortVal>`.
+                let bits =
+                    MaybeCommon::<ShortVal>::new_with_table(short_val.to_owned(), common_table)
+                        .encode_cpe()?;
 
                 // Return the bits.
                 Some(bits)
@@ -1,3 +1,4 @@
+use crate::constructive::valtype::maybe_common::common_table::CommonTable;
 use async_trait::async_trait;
 use bit_vec::BitVec;
 
@@ -6,6 +7,12 @@ use bit_vec::BitVec;
 pub trait CompactPayloadEncoding {
     /// Encode the struct into a bitvec.
     fn encode_cpe(&self) -> Option<BitVec>;
+
+    /// Encode the struct into a bitvec, using the given common value table.
+    /// Structs carrying `MaybeCommon` values override this; others encode as usual.
+    fn encode_cpe_with_table(&self, _common_table: &CommonTable) -> Option<BitVec> {
+        self.encode_cpe()
+    }
 }
 
 // Compact payload decoding is implemented individually for each struct that implements `CompactPayloadEncoding`, rather than using a trait.
@@ -10,3 +10,76 @@ pub enum LiftupCPEDecodingError {
     // Unable to find a matching `Lift` at the given transaction input iterator position.
     NoMatchingLiftAtInputIter(u32),
 }
+
+/// Error type for `Deploy` CPE decoding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DeployCPEDecodingError {
+    // Byte length exceeds the allowed bound.
+    ByteLengthOutOfBounds(u32),
+    // Unable to collect the bytes from the bit stream.
+    FailedToCollectBytes,
+    // The bytecode does not decompile into a valid program.
+    InvalidProgramBytecode,
+    // The storage seed is invalid.
+    InvalidStorageSeed,
+}
+
+/// Error type for `Call` CPE decoding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CallCPEDecodingError {
+    // Unable to find the program of the given contract id.
+    ProgramNotFound([u8; 32]),
+    // The program has no callable methods.
+    NoCallableMethods([u8; 32]),
+    // Unable to find the method at the given call method index.
+    MethodNotFound(u8),
+}
+
+/// Error type for `Swapout` CPE decoding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SwapoutCPEDecodingError {
+    // Unknown swapout type.
+    UnknownSwapoutType(u8),
+    // Unable to collect the witness program bits.
+    FailedToCollectWitnessProgramBits,
+    // Unable to collect the P2TR/other swapout type bit.
+    FailedToCollectSwapoutTypeBit,
+    // Witness version out of the future segwit range (2 to 16).
+    InvalidWitnessVersion(u8),
+    // Witness program length out of the 2 to 40 bytes range.
+    InvalidWitnessProgramLength(u8),
+}
+
+/// Error type for `Recharge` CPE decoding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RechargeCPEDecodingError {
+    // The account has no live VTXOs to recharge.
+    NoVTXOsToRecharge,
+}
+
+/// Error type for `Revive` CPE decoding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReviveCPEDecodingError {
+    // The account has no expired VTXOs to revive.
+    NoExpiredVTXOsToRevive,
+}
+
+/// Error type for `Claim` CPE decoding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ClaimCPEDecodingError {
+    // The account has no swept outputs to claim.
+    NoSweptOutputsToClaim,
+}
+
+/// Error type for `Entry` CPE decoding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EntryCPEDecodingError {
+    // Unable to collect a branch bit.
+    FailedToCollectBranchBit,
+    // Both uppermost branches are set to off.
+    UppermostBranchesBothOff,
+    // Uppermost left branch is on, but both liftup and recharge are off.
+    UppermostLeftBranchEmpty,
+    // The reserved branch is set.
+    ReservedBranch,
+}
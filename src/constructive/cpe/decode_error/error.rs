@@ -1,7 +1,12 @@
 use super::{
     calldata_error::CalldataCPEDecodingError,
     entity_error::{AccountCPEDecodingError, ContractCPEDecodingError},
-    entry_error::LiftupCPEDecodingError,
+    entry_error::{
+        CallCPEDecodingError, ClaimCPEDecodingError, DeployCPEDecodingError, EntryCPEDecodingError,
+        LiftupCPEDecodingError, RechargeCPEDecodingError, ReviveCPEDecodingError,
+        SwapoutCPEDecodingError,
+    },
+    payload_error::PayloadCPEDecodingError,
     valtype_error::{
         AtomicValCPEDecodingError, LongValCPEDecodingError, MaybeCommonCPEDecodingError,
         ShortValCPEDecodingError,
@@ -26,8 +31,24 @@ pub enum CPEDecodingError {
     ContractCPEDecodingError(ContractCPEDecodingError),
     // Liftup CPE decoding error.
     LiftupCPEDecodingError(LiftupCPEDecodingError),
+    // Recharge CPE decoding error.
+    RechargeCPEDecodingError(RechargeCPEDecodingError),
+    // Call CPE decoding error.
+    CallCPEDecodingError(CallCPEDecodingError),
+    // Deploy CPE decoding error.
+    DeployCPEDecodingError(DeployCPEDecodingError),
+    // Swapout CPE decoding error.
+    SwapoutCPEDecodingError(SwapoutCPEDecodingError),
+    // Revive CPE decoding error.
+    ReviveCPEDecodingError(ReviveCPEDecodingError),
+    // Claim CPE decoding error.
+    ClaimCPEDecodingError(ClaimCPEDecodingError),
+    // Entry CPE decoding error.
+    EntryCPEDecodingError(EntryCPEDecodingError),
     // Calldata CPE decoding error.
     CalldataCPEDecodingError(CalldataCPEDecodingError),
+    // Payload CPE decoding error.
+    PayloadCPEDecodingError(PayloadCPEDecodingError),
     // Unexpected error.
     UnexpectedError,
 }
@@ -2,4 +2,5 @@ pub mod calldata_error;
 pub mod entity_error;
 pub mod entry_error;
 pub mod error;
+pub mod payload_error;
 pub mod valtype_error;
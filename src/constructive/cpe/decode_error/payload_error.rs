@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// Error type for `Payload` CPE decoding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PayloadCPEDecodingError {
+    // Failed to locate a payload envelope in the reveal script.
+    EnvelopeNotFound,
+    // Payload envelope is malformed.
+    MalformedEnvelope,
+    // Failed to collect the payload header bytes.
+    FailedToCollectHeader,
+    // Payload version is not supported.
+    UnsupportedVersion(u8),
+    // Common table version is not scheduled.
+    UnknownCommonTable(u8),
+    // Common table is not active at the current epoch height.
+    InactiveCommonTable(u8),
+    // Payload carries no entries.
+    NoEntries,
+    // Payload body has leftover bits beyond the byte padding.
+    TrailingBits,
+    // Payload body padding bits are not zero.
+    NonZeroPadding,
+}
@@ -0,0 +1,199 @@
+use crate::{
+    constructive::{
+        cpe::{cpe::CompactPayloadEncoding, decode_error::error::CPEDecodingError},
+        entity::account::Account,
+        valtype::{
+            atomic_val::AtomicVal,
+            maybe_common::{common_table::CommonTable, maybe_common::MaybeCommon},
+            short_val::ShortVal,
+        },
+    },
+    inscriptive::registery::account_registery::ACCOUNT_REGISTERY,
+    transmutive::{
+        hash::{Hash, HashTag},
+        secp::authenticable::AuthSighash,
+    },
+};
+use async_trait::async_trait;
+use bit_vec::BitVec;
+use secp::Point;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Maximum number of payments a `BatchMove` can carry.
+pub const BATCH_MOVE_MAX_PAYMENTS: usize = 64;
+
+/// Upper bound of the `AtomicVal` encoding the number of payments minus one.
+const NUM_PAYMENTS_UPPER_BOUND: u8 = (BATCH_MOVE_MAX_PAYMENTS - 1) as u8;
+
+/// A `BatchMove` moves sats from an `Account` to a list of `Account`s in one go.
+/// Payments are applied atomically; either all of them go through, or none does.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BatchMove {
+    from: Account,
+    payments: Vec<(Account, u32)>,
+}
+
+impl BatchMove {
+    /// Creates a new `BatchMove`.
+    /// Returns `None` if there are no payments, or more than `BATCH_MOVE_MAX_PAYMENTS`.
+    pub fn new(from: Account, payments: Vec<(Account, u32)>) -> Option<BatchMove> {
+        if payments.is_empty() || payments.len() > BATCH_MOVE_MAX_PAYMENTS {
+            return None;
+        }
+
+        Some(BatchMove { from, payments })
+    }
+
+    pub fn from(&self) -> Account {
+        self.from
+    }
+
+    pub fn payments(&self) -> Vec<(Account, u32)> {
+        self.payments.clone()
+    }
+
+    /// Returns the receiving accounts, in payment order.
+    pub fn recipients(&self) -> Vec<Account> {
+        self.payments.iter().map(|(to, _)| *to).collect()
+    }
+
+    /// Returns the total amount moved.
+    pub fn total_amount(&self) -> u64 {
+        self.payments.iter().map(|(_, amount)| *amount as u64).sum()
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        match serde_json::to_vec(self) {
+            Ok(bytes) => bytes,
+            Err(_) => vec![],
+        }
+    }
+
+    pub fn validate_account(&self, account: Account) -> bool {
+        self.from.key() == account.key()
+    }
+
+    /// Sets the ranks of the receiving accounts from the given ranks, keyed by account keys.
+    /// Accounts not in the ranks are left unranked.
+    pub(crate) fn rerank(&mut self, account_ranks: &HashMap<Point, u32>) {
+        for (to, _) in self.payments.iter_mut() {
+            to.set_rank(account_ranks.get(&to.key()).copied());
+        }
+    }
+
+    /// Applies the payments to the given balances, keyed by account keys.
+    ///
+    /// The sender must cover the total amount, and no receiving balance may overflow.
+    /// Returns `false` and leaves the balances untouched otherwise.
+    pub fn apply(&self, balances: &mut HashMap<[u8; 32], u64>) -> bool {
+        let from_key = self.from.key().serialize_xonly();
+
+        // Compute the resulting balances on a scratch copy of the touched entries.
+        let mut updated = HashMap::<[u8; 32], u64>::new();
+
+        let from_balance = balances.get(&from_key).copied().unwrap_or(0);
+        match from_balance.checked_sub(self.total_amount()) {
+            Some(balance) => updated.insert(from_key, balance),
+            None => return false,
+        };
+
+        for (to, amount) in self.payments.iter() {
+            let to_key = to.key().serialize_xonly();
+            let to_balance = match updated.get(&to_key) {
+                Some(balance) => *balance,
+                None => balances.get(&to_key).copied().unwrap_or(0),
+            };
+
+            match to_balance.checked_add(*amount as u64) {
+                Some(balance) => updated.insert(to_key, balance),
+                None => return false,
+            };
+        }
+
+        // Commit all at once.
+        balances.extend(updated);
+
+        true
+    }
+
+    /// Compact payload decoding for `BatchMove`.
+    /// Decodes a `BatchMove` from a bit stream. The sender is the entry account.
+    pub async fn decode_cpe<'a>(
+        bit_stream: &mut bit_vec::Iter<'a>,
+        from: Account,
+        account_registery: &ACCOUNT_REGISTERY,
+        common_table: &CommonTable,
+    ) -> Result<BatchMove, CPEDecodingError> {
+        // Decode the number of payments.
+        let num_payments =
+            AtomicVal::decode_cpe(bit_stream, NUM_PAYMENTS_UPPER_BOUND)?.value() as usize + 1;
+
+        let mut payments = Vec::<(Account, u32)>::with_capacity(num_payments);
+
+        for _ in 0..num_payments {
+            // Decode the receiving account.
+            let to = Account::decode_cpe(bit_stream, account_registery).await?;
+
+            // Decode the amount.
+            let amount = MaybeCommon::<ShortVal>::decode_cpe_with_table(bit_stream, common_table)?
+                .value()
+                .value();
+
+            payments.push((to, amount));
+        }
+
+        // Return the `BatchMove`.
+        Ok(BatchMove { from, payments })
+    }
+}
+
+#[async_trait]
+impl CompactPayloadEncoding for BatchMove {
+    fn encode_cpe(&self) -> Option<BitVec> {
+        self.encode_cpe_with_table(&CommonTable::genesis())
+    }
+
+    fn encode_cpe_with_table(&self, common_table: &CommonTable) -> Option<BitVec> {
+        let mut bits = BitVec::new();
+
+        if self.payments.is_empty() || self.payments.len() > BATCH_MOVE_MAX_PAYMENTS {
+            return None;
+        }
+
+        // Extend the number of payments.
+        let num_payments = (self.payments.len() - 1) as u8;
+        bits.extend(AtomicVal::new(num_payments, NUM_PAYMENTS_UPPER_BOUND).encode_cpe()?);
+
+        for (to, amount) in self.payments.iter() {
+            // Extend the receiving account.
+            bits.extend(to.encode_cpe()?);
+
+            // Extend the amount.
+            bits.extend(
+                MaybeCommon::<ShortVal>::new_with_table(ShortVal::new(*amount), common_table)
+                    .encode_cpe()?,
+            );
+        }
+
+        // Return the bits.
+        Some(bits)
+    }
+}
+
+impl AuthSighash for BatchMove {
+    fn auth_sighash(&self) -> [u8; 32] {
+        let mut preimage: Vec<u8> = Vec::<u8>::new();
+
+        preimage.extend(self.from.key().serialize_xonly());
+
+        // Commit to the number of payments, then to each payment in order.
+        preimage.extend((self.payments.len() as u32).to_le_bytes());
+        for (to, amount) in self.payments.iter() {
+            preimage.extend(to.key().serialize_xonly());
+            preimage.extend(amount.to_le_bytes());
+        }
+
+        preimage.hash(Some(HashTag::SighashCombinator))
+    }
+}
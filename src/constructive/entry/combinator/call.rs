@@ -1,22 +1,65 @@
-use crate::constructive::{entity::account::Account, entity::contract::Contract};
-use crate::transmutive::hash::Hash;
-use crate::transmutive::{hash::HashTag, secp::authenticable::AuthSighash};
+use crate::{
+    constructive::{
+        calldata::element::CallElement,
+        cpe::{
+            cpe::CompactPayloadEncoding,
+            decode_error::{entry_error::CallCPEDecodingError, error::CPEDecodingError},
+        },
+        entity::{account::Account, contract::Contract},
+        valtype::{atomic_val::AtomicVal, maybe_common::common_table::CommonTable},
+    },
+    executive::program::program::Program,
+    inscriptive::{registery::registery::REGISTERY, repo::repo::PROGRAM_REPO},
+    transmutive::{
+        hash::{Hash, HashTag},
+        secp::authenticable::AuthSighash,
+    },
+};
+use async_trait::async_trait;
+use bit_vec::BitVec;
+use secp::Point;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Call {
     from: Account,
     contract: Contract,
-    calldata: Vec<Vec<u8>>,
+    call_method: AtomicVal,
+    calldata: Vec<CallElement>,
 }
 
 impl Call {
-    pub fn new(from: Account, contract: Contract, calldata: Vec<Vec<u8>>) -> Call {
-        Call {
+    /// Creates a new `Call` to a callable method of the contract program.
+    /// Returns `None` if the method is not callable or the calldata does not match its element types.
+    pub fn new(
+        from: Account,
+        contract: Contract,
+        program: &Program,
+        method_index: u8,
+        calldata: Vec<CallElement>,
+    ) -> Option<Call> {
+        // Callable methods are ordered first.
+        let callable_methods_len = program.callable_methods_len();
+        if method_index as usize >= callable_methods_len {
+            return None;
+        }
+
+        // Check the calldata against the method call element types.
+        let method = program.method_by_index(method_index)?;
+        let calldata_types: Vec<_> = calldata.iter().map(|e| e.element_type()).collect();
+        if calldata_types != method.call_element_types() {
+            return None;
+        }
+
+        let call_method = AtomicVal::new(method_index, (callable_methods_len - 1) as u8);
+
+        Some(Call {
             from,
             contract,
+            call_method,
             calldata,
-        }
+        })
     }
 
     pub fn from(&self) -> Account {
@@ -27,7 +70,11 @@ impl Call {
         self.contract
     }
 
-    pub fn calldata(&self) -> Vec<Vec<u8>> {
+    pub fn call_method(&self) -> AtomicVal {
+        self.call_method
+    }
+
+    pub fn calldata(&self) -> Vec<CallElement> {
         self.calldata.clone()
     }
 
@@ -41,6 +88,120 @@ impl Call {
     pub fn validate_account(&self, account: Account) -> bool {
         self.from.key() == account.key()
     }
+
+    /// Sets the ranks of the contract and the calldata entities from the given ranks.
+    /// Entities not in the ranks are left unranked.
+    pub(crate) fn rerank(
+        &mut self,
+        account_ranks: &HashMap<Point, u32>,
+        contract_ranks: &HashMap<[u8; 32], u32>,
+    ) {
+        self.contract
+            .set_rank(contract_ranks.get(&self.contract.contract_id()).copied());
+
+        for element in self.calldata.iter_mut() {
+            element.rerank(account_ranks, contract_ranks);
+        }
+    }
+
+    /// Compact payload decoding for `Call`.
+    /// Decodes a `Call` from a bit stream. The caller is the entry account.
+    /// Calldata is decoded through the call element types of the target method.
+    pub async fn decode_cpe<'a>(
+        bit_stream: &mut bit_vec::Iter<'a>,
+        from: Account,
+        registery: &REGISTERY,
+        program_repo: &PROGRAM_REPO,
+        common_table: &CommonTable,
+    ) -> Result<Call, CPEDecodingError> {
+        // Get the contract registery.
+        let contract_registery = {
+            let _registery = registery.lock().await;
+            _registery.contract_registery()
+        };
+
+        // Decode the contract.
+        let contract = Contract::decode_cpe(bit_stream, &contract_registery).await?;
+
+        // Retrieve the contract program.
+        let program = {
+            let _program_repo = program_repo.lock().await;
+            _program_repo
+                .program_by_contract_id(contract.contract_id())
+                .ok_or(CPEDecodingError::CallCPEDecodingError(
+                    CallCPEDecodingError::ProgramNotFound(contract.contract_id()),
+                ))?
+        };
+
+        // Make sure the program has at least one callable method.
+        let callable_methods_len = program.callable_methods_len();
+        if callable_methods_len == 0 {
+            return Err(CPEDecodingError::CallCPEDecodingError(
+                CallCPEDecodingError::NoCallableMethods(contract.contract_id()),
+            ));
+        }
+
+        // Decode the call method.
+        let call_method = AtomicVal::decode_cpe(bit_stream, (callable_methods_len - 1) as u8)?;
+
+        // Make sure the call method points to a callable method.
+        if call_method.value() as usize >= callable_methods_len {
+            return Err(CPEDecodingError::CallCPEDecodingError(
+                CallCPEDecodingError::MethodNotFound(call_method.value()),
+            ));
+        }
+
+        // Retrieve the method.
+        let method = program.method_by_call_method(call_method).ok_or(
+            CPEDecodingError::CallCPEDecodingError(CallCPEDecodingError::MethodNotFound(
+                call_method.value(),
+            )),
+        )?;
+
+        // Decode the calldata elements.
+        let mut calldata = Vec::<CallElement>::new();
+        for element_type in method.call_element_types() {
+            calldata.push(
+                CallElement::decode_cpe(bit_stream, element_type, registery, common_table).await?,
+            );
+        }
+
+        // Construct the `Call`.
+        let call = Call {
+            from,
+            contract,
+            call_method,
+            calldata,
+        };
+
+        // Return the `Call`.
+        Ok(call)
+    }
+}
+
+#[async_trait]
+impl CompactPayloadEncoding for Call {
+    fn encode_cpe(&self) -> Option<BitVec> {
+        self.encode_cpe_with_table(&CommonTable::genesis())
+    }
+
+    fn encode_cpe_with_table(&self, common_table: &CommonTable) -> Option<BitVec> {
+        let mut bits = BitVec::new();
+
+        // Extend the contract.
+        bits.extend(self.contract.encode_cpe()?);
+
+        // Extend the call method.
+        bits.extend(self.call_method.encode_cpe()?);
+
+        // Extend the calldata elements. They are not prefixed.
+        for element in self.calldata.iter() {
+            bits.extend(element.encode_cpe_with_table(common_table)?);
+        }
+
+        // Return the bits.
+        Some(bits)
+    }
 }
 
 impl AuthSighash for Call {
@@ -49,9 +210,12 @@ impl AuthSighash for Call {
 
         preimage.extend(self.from.key().serialize_xonly());
         preimage.extend(self.contract.contract_id());
+        preimage.push(self.call_method.value());
 
-        for calldata in self.calldata.iter() {
-            preimage.extend(calldata);
+        for element in self.calldata.iter() {
+            let item = element.stack_item();
+            preimage.extend(item.len().to_le_bytes());
+            preimage.extend(item.bytes());
         }
 
         preimage.hash(Some(HashTag::SighashCombinator))
@@ -1,24 +1,168 @@
 use crate::{
-    constructive::entity::account::Account, transmutive::secp::authenticable::AuthSighash,
+    constructive::{
+        cpe::{
+            cpe::CompactPayloadEncoding,
+            decode_error::{entry_error::ClaimCPEDecodingError, error::CPEDecodingError},
+        },
+        entity::account::Account,
+        txo::vtxo::VTXO,
+    },
+    inscriptive::set::swept_set::{SweptSet, SWEPT_SET},
+    transmutive::{
+        hash::{Hash, HashTag},
+        secp::authenticable::AuthSighash,
+    },
 };
+use async_trait::async_trait;
+use bit_vec::BitVec;
+use bitcoin::hashes::Hash as _;
 use serde::{Deserialize, Serialize};
 
+/// A `Claim` collects an account's VTXOs that were swept by the operator along with an expired projector.
+/// Swept VTXOs are tracked in the swept set by every node, keyed by the original account.
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct Claim;
+pub struct Claim {
+    account: Account,
+    swept_vtxos: Vec<VTXO>,
+}
 
 impl Claim {
-    pub fn new() -> Self {
-        Self {}
+    /// Creates a new `Claim` from a list of swept VTXOs.
+    pub fn new(account: Account, swept_vtxos: Vec<VTXO>) -> Option<Claim> {
+        // At least one VTXO must be claimed.
+        if swept_vtxos.is_empty() {
+            return None;
+        }
+
+        for swept_vtxo in swept_vtxos.iter() {
+            // Swept VTXO must be fully specified.
+            if swept_vtxo.outpoint().is_none() || swept_vtxo.value().is_none() {
+                return None;
+            }
+
+            // Swept VTXO must belong to the account.
+            if swept_vtxo.account_key() != account.key() {
+                return None;
+            }
+        }
+
+        let claim = Claim {
+            account,
+            swept_vtxos,
+        };
+
+        Some(claim)
+    }
+
+    /// Returns the claiming account.
+    pub fn account(&self) -> Account {
+        self.account
+    }
+
+    /// Returns the swept VTXOs being claimed.
+    pub fn swept_vtxos(&self) -> Vec<VTXO> {
+        self.swept_vtxos.clone()
+    }
+
+    /// Returns the total value being claimed.
+    pub fn value(&self) -> u64 {
+        self.swept_vtxos
+            .iter()
+            .map(|swept_vtxo| swept_vtxo.value().unwrap_or(0))
+            .sum()
+    }
+
+    /// Serializes the `Claim` to a byte vector.
+    pub fn serialize(&self) -> Vec<u8> {
+        match serde_json::to_vec(self) {
+            Ok(bytes) => bytes,
+            Err(_) => vec![],
+        }
+    }
+
+    /// Validates the `Claim` against an `Account`.
+    pub fn validate_account(&self, account: Account) -> bool {
+        if self.account.key() != account.key() {
+            return false;
+        }
+
+        self.swept_vtxos
+            .iter()
+            .all(|swept_vtxo| swept_vtxo.account_key() == account.key())
+    }
+
+    /// Validates the `Claim` against the swept set.
+    /// Every swept VTXO must still be unclaimed in the swept set.
+    pub fn validate(&self, swept_set: &SweptSet) -> bool {
+        self.swept_vtxos
+            .iter()
+            .all(|swept_vtxo| swept_set.is_swept(swept_vtxo))
     }
 
-    // TODO: Implement this.
-    pub fn validate_account(&self, _account: Account) -> bool {
-        true
+    /// Applies the `Claim` to the swept set.
+    /// Removes the swept VTXOs and returns the value to be credited to the account.
+    pub fn apply(&self, swept_set: &mut SweptSet) -> Option<u64> {
+        if !self.validate(swept_set) {
+            return None;
+        }
+
+        for swept_vtxo in self.swept_vtxos.iter() {
+            if !swept_set.remove_vtxo(swept_vtxo) {
+                return None;
+            }
+        }
+
+        Some(self.value())
+    }
+
+    /// Compact payload decoding for `Claim`.
+    /// A `Claim` collects all swept VTXOs of the account, so nothing beyond the branch bits is encoded.
+    /// The swept VTXOs are retrieved from the swept set, ordered by outpoint.
+    pub async fn decode_cpe(
+        account: Account,
+        swept_set: &SWEPT_SET,
+    ) -> Result<Claim, CPEDecodingError> {
+        // Collect the swept VTXOs of the account.
+        let mut swept_vtxos = {
+            let _swept_set = swept_set.lock().await;
+            _swept_set.swept_vtxos_by_account_key(&account.key())
+        };
+
+        // Order the swept VTXOs by outpoint.
+        swept_vtxos.sort_by_key(|swept_vtxo| swept_vtxo.outpoint());
+
+        // Construct the `Claim`.
+        Claim::new(account, swept_vtxos).ok_or(CPEDecodingError::ClaimCPEDecodingError(
+            ClaimCPEDecodingError::NoSweptOutputsToClaim,
+        ))
+    }
+}
+
+#[async_trait]
+impl CompactPayloadEncoding for Claim {
+    fn encode_cpe(&self) -> Option<BitVec> {
+        // That's it. We're not encoding the swept VTXOs themselves.
+        // They are read directly from the swept set.
+        Some(BitVec::new())
     }
 }
 
 impl AuthSighash for Claim {
     fn auth_sighash(&self) -> [u8; 32] {
-        [0xffu8; 32]
+        let mut preimage: Vec<u8> = Vec::<u8>::new();
+
+        preimage.extend(self.account.key().serialize_xonly());
+
+        for swept_vtxo in self.swept_vtxos.iter() {
+            match swept_vtxo.outpoint() {
+                Some(outpoint) => {
+                    preimage.extend(outpoint.txid.to_byte_array());
+                    preimage.extend(outpoint.vout.to_le_bytes());
+                }
+                None => return [0; 32],
+            };
+        }
+
+        preimage.hash(Some(HashTag::SighashCombinator))
     }
 }
@@ -1,6 +1,7 @@
 use super::{
-    add::Add, call::Call, claim::Claim, deploy::Deploy, liftup::Liftup, r#move::Move,
-    recharge::Recharge, reserved::Reserved, revive::Revive, sub::Sub, swapout::Swapout,
+    add::Add, batch_move::BatchMove, call::Call, claim::Claim, deploy::Deploy, liftup::Liftup,
+    r#move::Move, recharge::Recharge, reserved::Reserved, revive::Revive, sub::Sub,
+    swapout::Swapout,
 };
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +10,7 @@ pub enum Combinator {
     Liftup(Liftup),
     Recharge(Recharge),
     Move(Move),
+    BatchMove(BatchMove),
     Call(Call),
     Add(Add),
     Sub(Sub),
@@ -35,6 +37,11 @@ impl Combinator {
         Combinator::Move(r#move)
     }
 
+    /// Create a new `BatchMove` combinator.
+    pub fn new_batch_move(batch_move: BatchMove) -> Combinator {
+        Combinator::BatchMove(batch_move)
+    }
+
     /// Create a new `Call` combinator.
     pub fn new_call(call: Call) -> Combinator {
         Combinator::Call(call)
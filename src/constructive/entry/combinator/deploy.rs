@@ -1,23 +1,323 @@
-use crate::constructive::entity::account::Account;
-use crate::transmutive::secp::authenticable::AuthSighash;
+use crate::{
+    constructive::{
+        cpe::{
+            cpe::CompactPayloadEncoding,
+            decode_error::{entry_error::DeployCPEDecodingError, error::CPEDecodingError},
+        },
+        entity::account::Account,
+        valtype::short_val::ShortVal,
+    },
+    executive::{
+        program::{compiler::compiler::ProgramCompiler, program::Program},
+        stack::limits::{MAX_KEY_LENGTH, MAX_STACK_ITEM_SIZE, MIN_KEY_LENGTH, MIN_VALUE_LENGTH},
+    },
+    inscriptive::{registery::contract_registery::CONTRACT_REGISTERY, repo::repo::PROGRAM_REPO},
+    transmutive::{
+        hash::{Hash, HashTag},
+        secp::authenticable::AuthSighash,
+    },
+};
+use async_trait::async_trait;
+use bit_vec::BitVec;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+/// Maximum byte length of the deployed program bytecode, as bound by the compact payload decoding.
+pub const MAX_BYTECODE_LENGTH: usize = MAX_STACK_ITEM_SIZE as usize;
+
+/// A `Deploy` deploys a new contract program with an initial storage and balance seed.
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct Deploy;
+pub struct Deploy {
+    deployer: Account,
+    // Distinguishes redeployments of the same bytecode by the same deployer.
+    nonce: u32,
+    bytecode: Vec<u8>,
+    storage_seed: Vec<(Vec<u8>, Vec<u8>)>,
+    balance_seed: u32,
+}
 
 impl Deploy {
-    pub fn new() -> Self {
-        Self {}
+    /// Creates a new `Deploy` from a `Program`.
+    /// Returns `None` if the program fails to compile, its bytecode exceeds `MAX_BYTECODE_LENGTH`,
+    /// or the storage seed is invalid.
+    pub fn new(
+        deployer: Account,
+        nonce: u32,
+        program: &Program,
+        storage_seed: Vec<(Vec<u8>, Vec<u8>)>,
+        balance_seed: u32,
+    ) -> Option<Deploy> {
+        // Compile the program into bytecode.
+        let bytecode = program.compile().ok()?;
+
+        // Make sure the bytecode can be decoded back.
+        if bytecode.len() > MAX_BYTECODE_LENGTH {
+            return None;
+        }
+
+        // Validate the storage seed.
+        if !Self::validate_storage_seed(&storage_seed) {
+            return None;
+        }
+
+        let deploy = Deploy {
+            deployer,
+            nonce,
+            bytecode,
+            storage_seed,
+            balance_seed,
+        };
+
+        Some(deploy)
+    }
+
+    /// Returns the deploying account.
+    pub fn deployer(&self) -> Account {
+        self.deployer
+    }
+
+    /// Returns the deployment nonce.
+    pub fn nonce(&self) -> u32 {
+        self.nonce
+    }
+
+    /// Returns the program bytecode.
+    pub fn bytecode(&self) -> Vec<u8> {
+        self.bytecode.clone()
+    }
+
+    /// Decompiles and returns the deployed program.
+    pub fn program(&self) -> Option<Program> {
+        Program::decompile(&mut self.bytecode.clone().into_iter()).ok()
+    }
+
+    /// Returns the initial storage seed.
+    pub fn storage_seed(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.storage_seed.clone()
+    }
+
+    /// Returns the initial balance seed.
+    pub fn balance_seed(&self) -> u32 {
+        self.balance_seed
     }
 
-    // TODO: Implement this.
-    pub fn validate_account(&self, _account: Account) -> bool {
+    /// Returns the contract id of the deployed contract.
+    /// The contract id commits to the deployer key, the deployment nonce and the program bytecode,
+    /// so that the same program can be redeployed under a new nonce.
+    pub fn contract_id(&self) -> [u8; 32] {
+        let mut preimage: Vec<u8> = Vec::<u8>::new();
+
+        preimage.extend(self.deployer.key().serialize_xonly());
+        preimage.extend(self.nonce.to_le_bytes());
+        preimage.extend(self.bytecode.clone());
+
+        preimage.hash(Some(HashTag::ContractId))
+    }
+
+    /// Serializes the `Deploy` to a byte vector.
+    pub fn serialize(&self) -> Vec<u8> {
+        match serde_json::to_vec(self) {
+            Ok(bytes) => bytes,
+            Err(_) => vec![],
+        }
+    }
+
+    /// Validates the `Deploy` against an `Account`.
+    pub fn validate_account(&self, account: Account) -> bool {
+        self.deployer.key() == account.key()
+    }
+
+    /// Registers the deployed contract in the contract registery, and stores its program.
+    pub async fn register(
+        &self,
+        contract_registery: &CONTRACT_REGISTERY,
+        program_repo: &PROGRAM_REPO,
+    ) -> bool {
+        // Decompile the program.
+        let program = match self.program() {
+            Some(program) => program,
+            None => return false,
+        };
+
+        // Register the contract.
+        {
+            let mut _contract_registery = contract_registery.lock().await;
+            if !_contract_registery.batch_update(vec![self.contract_id()], HashMap::new()) {
+                return false;
+            }
+        }
+
+        // Store the program.
+        let mut _program_repo = program_repo.lock().await;
+        _program_repo.insert_program(self.contract_id(), &program)
+    }
+
+    /// Checks storage seed keys and values against the storage limits, and rejects duplicate keys.
+    fn validate_storage_seed(storage_seed: &Vec<(Vec<u8>, Vec<u8>)>) -> bool {
+        for (index, (key, value)) in storage_seed.iter().enumerate() {
+            if key.len() < MIN_KEY_LENGTH as usize || key.len() > MAX_KEY_LENGTH as usize {
+                return false;
+            }
+
+            if value.len() < MIN_VALUE_LENGTH as usize || value.len() > MAX_STACK_ITEM_SIZE as usize
+            {
+                return false;
+            }
+
+            if storage_seed[..index].iter().any(|(k, _)| k == key) {
+                return false;
+            }
+        }
+
         true
     }
+
+    /// Compact payload decoding for `Deploy`.
+    /// Decodes a `Deploy` from a bit stream.
+    pub fn decode_cpe<'a>(
+        bit_stream: &mut bit_vec::Iter<'a>,
+        deployer: Account,
+    ) -> Result<Deploy, CPEDecodingError> {
+        // Decode the nonce.
+        let nonce = ShortVal::decode_cpe(bit_stream)?.value();
+
+        // Decode the bytecode.
+        let bytecode = decode_bytes(bit_stream, MAX_BYTECODE_LENGTH)?;
+
+        // Make sure the bytecode decompiles into a valid program.
+        if Program::decompile(&mut bytecode.clone().into_iter()).is_err() {
+            return Err(CPEDecodingError::DeployCPEDecodingError(
+                DeployCPEDecodingError::InvalidProgramBytecode,
+            ));
+        }
+
+        // Decode the number of storage seed entries.
+        let num_entries = ShortVal::decode_cpe(bit_stream)?.value();
+
+        // Decode the storage seed entries.
+        let mut storage_seed = Vec::<(Vec<u8>, Vec<u8>)>::new();
+        for _ in 0..num_entries {
+            let key = decode_bytes(bit_stream, MAX_KEY_LENGTH as usize)?;
+            let value = decode_bytes(bit_stream, MAX_STACK_ITEM_SIZE as usize)?;
+            storage_seed.push((key, value));
+        }
+
+        // Validate the storage seed.
+        if !Self::validate_storage_seed(&storage_seed) {
+            return Err(CPEDecodingError::DeployCPEDecodingError(
+                DeployCPEDecodingError::InvalidStorageSeed,
+            ));
+        }
+
+        // Decode the balance seed.
+        let balance_seed = ShortVal::decode_cpe(bit_stream)?.value();
+
+        // Construct the `Deploy`.
+        let deploy = Deploy {
+            deployer,
+            nonce,
+            bytecode,
+            storage_seed,
+            balance_seed,
+        };
+
+        // Return the `Deploy`.
+        Ok(deploy)
+    }
+}
+
+/// Encodes a length-prefixed byte slice.
+fn encode_bytes(bytes: &[u8]) -> Option<BitVec> {
+    let mut bits = BitVec::new();
+
+    // Extend the byte length.
+    bits.extend(ShortVal::new(bytes.len() as u32).encode_cpe()?);
+
+    // Extend the bytes.
+    bits.extend(BitVec::from_bytes(bytes));
+
+    Some(bits)
+}
+
+/// Decodes a length-prefixed byte slice.
+fn decode_bytes<'a>(
+    bit_stream: &mut bit_vec::Iter<'a>,
+    max_len: usize,
+) -> Result<Vec<u8>, CPEDecodingError> {
+    // Decode the byte length.
+    let len = ShortVal::decode_cpe(bit_stream)?.value() as usize;
+
+    // Make sure the byte length is within bounds.
+    if len > max_len {
+        return Err(CPEDecodingError::DeployCPEDecodingError(
+            DeployCPEDecodingError::ByteLengthOutOfBounds(len as u32),
+        ));
+    }
+
+    // Collect the bits.
+    let bits: BitVec = bit_stream.by_ref().take(len * 8).collect();
+
+    // Ensure the collected bits are the correct length.
+    if bits.len() != len * 8 {
+        return Err(CPEDecodingError::DeployCPEDecodingError(
+            DeployCPEDecodingError::FailedToCollectBytes,
+        ));
+    }
+
+    Ok(bits.to_bytes())
+}
+
+#[async_trait]
+impl CompactPayloadEncoding for Deploy {
+    fn encode_cpe(&self) -> Option<BitVec> {
+        let mut bits = BitVec::new();
+
+        // Extend the nonce.
+        bits.extend(ShortVal::new(self.nonce).encode_cpe()?);
+
+        // Make sure the bytecode can be decoded back.
+        if self.bytecode.len() > MAX_BYTECODE_LENGTH {
+            return None;
+        }
+
+        // Extend the bytecode.
+        bits.extend(encode_bytes(&self.bytecode)?);
+
+        // Extend the number of storage seed entries.
+        bits.extend(ShortVal::new(self.storage_seed.len() as u32).encode_cpe()?);
+
+        // Extend the storage seed entries.
+        for (key, value) in self.storage_seed.iter() {
+            bits.extend(encode_bytes(key)?);
+            bits.extend(encode_bytes(value)?);
+        }
+
+        // Extend the balance seed.
+        bits.extend(ShortVal::new(self.balance_seed).encode_cpe()?);
+
+        // Return the bits.
+        Some(bits)
+    }
 }
 
 impl AuthSighash for Deploy {
     fn auth_sighash(&self) -> [u8; 32] {
-        [0xffu8; 32]
+        let mut preimage: Vec<u8> = Vec::<u8>::new();
+
+        preimage.extend(self.deployer.key().serialize_xonly());
+        preimage.extend(self.nonce.to_le_bytes());
+        preimage.extend((self.bytecode.len() as u32).to_le_bytes());
+        preimage.extend(self.bytecode.clone());
+
+        for (key, value) in self.storage_seed.iter() {
+            preimage.extend((key.len() as u32).to_le_bytes());
+            preimage.extend(key);
+            preimage.extend((value.len() as u32).to_le_bytes());
+            preimage.extend(value);
+        }
+
+        preimage.extend(self.balance_seed.to_le_bytes());
+
+        preimage.hash(Some(HashTag::SighashCombinator))
     }
 }
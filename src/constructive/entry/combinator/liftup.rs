@@ -81,8 +81,8 @@ impl Liftup {
     /// Decodes a `Liftup` from a bit stream and `TxHolder`.
     pub async fn decode_cpe<'a>(
         bit_stream: &mut bit_vec::Iter<'a>,
-        txholder: &'a mut TxHolder,
-        epoch_dir: &'a EPOCH_DIRECTORY,
+        txholder: &mut TxHolder,
+        epoch_dir: &EPOCH_DIRECTORY,
         account_key: Point,
     ) -> Result<Liftup, CPEDecodingError> {
         // Decode the number of lifts.
@@ -1,4 +1,5 @@
 pub mod add;
+pub mod batch_move;
 pub mod call;
 pub mod claim;
 pub mod combinator;
@@ -1,11 +1,23 @@
 use crate::{
-    constructive::entity::account::Account,
+    constructive::{
+        cpe::{cpe::CompactPayloadEncoding, decode_error::error::CPEDecodingError},
+        entity::account::Account,
+        valtype::{
+            maybe_common::{common_table::CommonTable, maybe_common::MaybeCommon},
+            short_val::ShortVal,
+        },
+    },
+    inscriptive::registery::account_registery::ACCOUNT_REGISTERY,
     transmutive::{
         hash::{Hash, HashTag},
         secp::authenticable::AuthSighash,
     },
 };
+use async_trait::async_trait;
+use bit_vec::BitVec;
+use secp::Point;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Move {
@@ -41,6 +53,55 @@ impl Move {
     pub fn validate_account(&self, account: Account) -> bool {
         self.from.key() == account.key()
     }
+
+    /// Sets the rank of the receiving account from the given ranks, keyed by account keys.
+    /// The account is left unranked if it is not in the ranks.
+    pub(crate) fn rerank(&mut self, account_ranks: &HashMap<Point, u32>) {
+        self.to.set_rank(account_ranks.get(&self.to.key()).copied());
+    }
+
+    /// Compact payload decoding for `Move`.
+    /// Decodes a `Move` from a bit stream. The sender is the entry account.
+    pub async fn decode_cpe<'a>(
+        bit_stream: &mut bit_vec::Iter<'a>,
+        from: Account,
+        account_registery: &ACCOUNT_REGISTERY,
+        common_table: &CommonTable,
+    ) -> Result<Move, CPEDecodingError> {
+        // Decode the receiving account.
+        let to = Account::decode_cpe(bit_stream, account_registery).await?;
+
+        // Decode the amount.
+        let amount = MaybeCommon::<ShortVal>::decode_cpe_with_table(bit_stream, common_table)?
+            .value()
+            .value();
+
+        // Return the `Move`.
+        Ok(Move { from, to, amount })
+    }
+}
+
+#[async_trait]
+impl CompactPayloadEncoding for Move {
+    fn encode_cpe(&self) -> Option<BitVec> {
+        self.encode_cpe_with_table(&CommonTable::genesis())
+    }
+
+    fn encode_cpe_with_table(&self, common_table: &CommonTable) -> Option<BitVec> {
+        let mut bits = BitVec::new();
+
+        // Extend the receiving account.
+        bits.extend(self.to.encode_cpe()?);
+
+        // Extend the amount.
+        bits.extend(
+            MaybeCommon::<ShortVal>::new_with_table(ShortVal::new(self.amount), common_table)
+                .encode_cpe()?,
+        );
+
+        // Return the bits.
+        Some(bits)
+    }
 }
 
 impl AuthSighash for Move {
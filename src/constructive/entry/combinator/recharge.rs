@@ -1,10 +1,20 @@
 use crate::{
-    constructive::{entity::account::Account, txo::vtxo::VTXO},
+    constructive::{
+        cpe::{
+            cpe::CompactPayloadEncoding,
+            decode_error::{entry_error::RechargeCPEDecodingError, error::CPEDecodingError},
+        },
+        entity::account::Account,
+        txo::vtxo::VTXO,
+    },
+    inscriptive::{baked::EXPIRY_BLOCKS, set::vtxo_set::VTXO_SET},
     transmutive::{
         hash::{Hash, HashTag},
         secp::authenticable::AuthSighash,
     },
 };
+use async_trait::async_trait;
+use bit_vec::BitVec;
 use bitcoin::hashes::Hash as _;
 use serde::{Deserialize, Serialize};
 
@@ -57,6 +67,52 @@ impl Recharge {
 
         true
     }
+
+    /// Compact payload decoding for `Recharge`.
+    /// A `Recharge` refreshes all live VTXOs of the account, so nothing beyond the branch bit is encoded.
+    /// The VTXOs are retrieved from the VTXO set, ordered by outpoint.
+    pub async fn decode_cpe(
+        account: Account,
+        vtxo_set: &VTXO_SET,
+        bitcoin_height: u32,
+    ) -> Result<Recharge, CPEDecodingError> {
+        // Retrieve the account VTXOs.
+        let account_vtxos = {
+            let _vtxo_set = vtxo_set.lock().await;
+            _vtxo_set.vtxo_set_by_account(&account).unwrap_or_default()
+        };
+
+        // Collect the live VTXOs.
+        let mut recharge_vtxos: Vec<VTXO> = account_vtxos
+            .into_iter()
+            .filter(|vtxo| match vtxo.at_bitcoin_height() {
+                Some(height) => bitcoin_height < height.saturating_add(EXPIRY_BLOCKS),
+                None => true,
+            })
+            .collect();
+
+        // There must be at least one VTXO to recharge.
+        if recharge_vtxos.is_empty() {
+            return Err(CPEDecodingError::RechargeCPEDecodingError(
+                RechargeCPEDecodingError::NoVTXOsToRecharge,
+            ));
+        }
+
+        // Order the VTXOs by outpoint.
+        recharge_vtxos.sort_by_key(|vtxo| vtxo.outpoint());
+
+        // Return the `Recharge`.
+        Ok(Recharge { recharge_vtxos })
+    }
+}
+
+#[async_trait]
+impl CompactPayloadEncoding for Recharge {
+    fn encode_cpe(&self) -> Option<BitVec> {
+        // That's it. We're not encoding the VTXOs themselves.
+        // They are read directly from the VTXO set.
+        Some(BitVec::new())
+    }
 }
 
 impl AuthSighash for Recharge {
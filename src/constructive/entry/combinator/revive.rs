@@ -1,24 +1,205 @@
 use crate::{
-    constructive::entity::account::Account, transmutive::secp::authenticable::AuthSighash,
+    constructive::{
+        cpe::{
+            cpe::CompactPayloadEncoding,
+            decode_error::{entry_error::ReviveCPEDecodingError, error::CPEDecodingError},
+        },
+        entity::account::Account,
+        txo::vtxo::VTXO,
+    },
+    inscriptive::{
+        baked::EXPIRY_BLOCKS,
+        set::vtxo_set::{VTXOSet, VTXO_SET},
+    },
+    transmutive::{
+        hash::{Hash, HashTag},
+        secp::authenticable::AuthSighash,
+    },
 };
+use async_trait::async_trait;
+use bit_vec::BitVec;
+use bitcoin::hashes::Hash as _;
 use serde::{Deserialize, Serialize};
 
+/// A `Revive` re-animates an account's expired VTXOs back into its rollup balance.
+/// This is for accounts that went offline past the three-month timelock before the operator swept.
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct Revive;
+pub struct Revive {
+    account: Account,
+    expired_vtxos: Vec<VTXO>,
+}
 
 impl Revive {
-    pub fn new() -> Self {
-        Self {}
+    /// Creates a new `Revive` from a list of expired VTXOs.
+    pub fn new(account: Account, vtxos: Vec<VTXO>) -> Option<Revive> {
+        // At least one VTXO must be revived.
+        if vtxos.is_empty() {
+            return None;
+        }
+
+        for vtxo in vtxos.iter() {
+            // VTXO must be fully specified.
+            if vtxo.outpoint().is_none()
+                || vtxo.value().is_none()
+                || vtxo.at_bitcoin_height().is_none()
+            {
+                return None;
+            }
+
+            // VTXO must belong to the account.
+            if vtxo.account_key() != account.key() {
+                return None;
+            }
+        }
+
+        let revive = Revive {
+            account,
+            expired_vtxos: vtxos,
+        };
+
+        Some(revive)
+    }
+
+    /// Returns the reviving account.
+    pub fn account(&self) -> Account {
+        self.account
+    }
+
+    /// Returns the expired VTXOs being revived.
+    pub fn vtxos(&self) -> Vec<VTXO> {
+        self.expired_vtxos.clone()
     }
 
-    /// Validate the account.
-    pub fn validate_account(&self, _account: Account) -> bool {
-        true
+    /// Returns the total value being revived.
+    pub fn value(&self) -> u64 {
+        self.expired_vtxos
+            .iter()
+            .map(|vtxo| vtxo.value().unwrap_or(0))
+            .sum()
+    }
+
+    /// Serializes the `Revive` to a byte vector.
+    pub fn serialize(&self) -> Vec<u8> {
+        match serde_json::to_vec(self) {
+            Ok(bytes) => bytes,
+            Err(_) => vec![],
+        }
+    }
+
+    /// Validates the `Revive` against an `Account`.
+    pub fn validate_account(&self, account: Account) -> bool {
+        if self.account.key() != account.key() {
+            return false;
+        }
+
+        self.expired_vtxos
+            .iter()
+            .all(|vtxo| vtxo.account_key() == account.key())
+    }
+
+    /// Returns whether all VTXOs have expired at the given bitcoin height.
+    pub fn is_expired(&self, bitcoin_height: u32) -> bool {
+        self.expired_vtxos
+            .iter()
+            .all(|vtxo| match vtxo.at_bitcoin_height() {
+                Some(height) => bitcoin_height >= height.saturating_add(EXPIRY_BLOCKS),
+                None => false,
+            })
+    }
+
+    /// Validates the `Revive` against the VTXO set at the given bitcoin height.
+    /// Every VTXO must be expired, and still be present in the account's VTXO set.
+    pub fn validate(&self, vtxo_set: &VTXOSet, bitcoin_height: u32) -> bool {
+        if !self.is_expired(bitcoin_height) {
+            return false;
+        }
+
+        let account_vtxos = match vtxo_set.vtxo_set_by_account(&self.account) {
+            Some(vtxos) => vtxos,
+            None => return false,
+        };
+
+        self.expired_vtxos.iter().all(|vtxo| {
+            account_vtxos
+                .iter()
+                .any(|account_vtxo| account_vtxo == vtxo)
+        })
+    }
+
+    /// Applies the `Revive` to the VTXO set.
+    /// Removes the expired VTXOs and returns the value to be credited to the account.
+    pub fn apply(&self, vtxo_set: &mut VTXOSet, bitcoin_height: u32) -> Option<u64> {
+        if !self.validate(vtxo_set, bitcoin_height) {
+            return None;
+        }
+
+        for vtxo in self.expired_vtxos.iter() {
+            if !vtxo_set.remove_vtxo(vtxo) {
+                return None;
+            }
+        }
+
+        Some(self.value())
+    }
+
+    /// Compact payload decoding for `Revive`.
+    /// A `Revive` revives all expired VTXOs of the account, so nothing beyond the branch bits is encoded.
+    /// The VTXOs are retrieved from the VTXO set, ordered by outpoint.
+    pub async fn decode_cpe(
+        account: Account,
+        vtxo_set: &VTXO_SET,
+        bitcoin_height: u32,
+    ) -> Result<Revive, CPEDecodingError> {
+        // Retrieve the account VTXOs.
+        let account_vtxos = {
+            let _vtxo_set = vtxo_set.lock().await;
+            _vtxo_set.vtxo_set_by_account(&account).unwrap_or_default()
+        };
+
+        // Collect the expired VTXOs.
+        let mut expired_vtxos: Vec<VTXO> = account_vtxos
+            .into_iter()
+            .filter(|vtxo| match vtxo.at_bitcoin_height() {
+                Some(height) => bitcoin_height >= height.saturating_add(EXPIRY_BLOCKS),
+                None => false,
+            })
+            .collect();
+
+        // Order the VTXOs by outpoint.
+        expired_vtxos.sort_by_key(|vtxo| vtxo.outpoint());
+
+        // Construct the `Revive`.
+        Revive::new(account, expired_vtxos).ok_or(CPEDecodingError::ReviveCPEDecodingError(
+            ReviveCPEDecodingError::NoExpiredVTXOsToRevive,
+        ))
+    }
+}
+
+#[async_trait]
+impl CompactPayloadEncoding for Revive {
+    fn encode_cpe(&self) -> Option<BitVec> {
+        // That's it. We're not encoding the VTXOs themselves.
+        // They are read directly from the VTXO set.
+        Some(BitVec::new())
     }
 }
 
 impl AuthSighash for Revive {
     fn auth_sighash(&self) -> [u8; 32] {
-        [0xffu8; 32]
+        let mut preimage: Vec<u8> = Vec::<u8>::new();
+
+        preimage.extend(self.account.key().serialize_xonly());
+
+        for vtxo in self.expired_vtxos.iter() {
+            match vtxo.outpoint() {
+                Some(outpoint) => {
+                    preimage.extend(outpoint.txid.to_byte_array());
+                    preimage.extend(outpoint.vout.to_le_bytes());
+                }
+                None => return [0; 32],
+            };
+        }
+
+        preimage.hash(Some(HashTag::SighashCombinator))
     }
 }
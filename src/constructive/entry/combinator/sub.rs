@@ -1,10 +1,19 @@
 use crate::{
-    constructive::entity::account::Account,
+    constructive::{
+        cpe::{cpe::CompactPayloadEncoding, decode_error::error::CPEDecodingError},
+        entity::account::Account,
+        valtype::{
+            maybe_common::{common_table::CommonTable, maybe_common::MaybeCommon},
+            short_val::ShortVal,
+        },
+    },
     transmutive::{
         hash::{Hash, HashTag},
         secp::authenticable::AuthSighash,
     },
 };
+use async_trait::async_trait;
+use bit_vec::BitVec;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -36,6 +45,35 @@ impl Sub {
     pub fn validate_account(&self, account: Account) -> bool {
         self.account.key() == account.key()
     }
+
+    /// Compact payload decoding for `Sub`.
+    /// Decodes a `Sub` from a bit stream. The account is the entry account.
+    pub fn decode_cpe<'a>(
+        bit_stream: &mut bit_vec::Iter<'a>,
+        account: Account,
+        common_table: &CommonTable,
+    ) -> Result<Sub, CPEDecodingError> {
+        // Decode the amount.
+        let amount = MaybeCommon::<ShortVal>::decode_cpe_with_table(bit_stream, common_table)?
+            .value()
+            .value();
+
+        // Return the `Sub`.
+        Ok(Sub { account, amount })
+    }
+}
+
+#[async_trait]
+impl CompactPayloadEncoding for Sub {
+    fn encode_cpe(&self) -> Option<BitVec> {
+        self.encode_cpe_with_table(&CommonTable::genesis())
+    }
+
+    fn encode_cpe_with_table(&self, common_table: &CommonTable) -> Option<BitVec> {
+        // Encode the amount.
+        MaybeCommon::<ShortVal>::new_with_table(ShortVal::new(self.amount), common_table)
+            .encode_cpe()
+    }
 }
 
 impl AuthSighash for Sub {
@@ -1,21 +1,57 @@
 use crate::{
-    constructive::entity::account::Account,
+    constructive::{
+        cpe::{
+            cpe::CompactPayloadEncoding,
+            decode_error::{entry_error::SwapoutCPEDecodingError, error::CPEDecodingError},
+        },
+        entity::account::Account,
+        valtype::{
+            atomic_val::AtomicVal,
+            maybe_common::{common_table::CommonTable, maybe_common::MaybeCommon},
+            short_val::ShortVal,
+        },
+    },
     transmutive::{
         hash::{Hash, HashTag},
         secp::authenticable::AuthSighash,
     },
 };
+use async_trait::async_trait;
+use bit_vec::BitVec;
 use serde::{Deserialize, Serialize};
 
+/// Upper bound of the `AtomicVal` encoding the non-P2TR swapout types.
+const SWAPOUT_TYPE_UPPER_BOUND: u8 = 4;
+
+/// Lowest witness version of a future segwit witness program.
+const MIN_FUTURE_WITNESS_VERSION: u8 = 2;
+
+/// Highest witness version.
+const MAX_WITNESS_VERSION: u8 = 16;
+
+/// Witness program length bounds (BIP-141).
+const MIN_WITNESS_PROGRAM_LEN: usize = 2;
+const MAX_WITNESS_PROGRAM_LEN: usize = 40;
+
+/// Upper bound of the `AtomicVal` encoding the future witness version minus two.
+const WITNESS_VERSION_UPPER_BOUND: u8 = 15;
+
+/// Upper bound of the `AtomicVal` encoding the future witness program length minus two.
+const WITNESS_PROGRAM_LEN_UPPER_BOUND: u8 = 63;
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum SwapoutType {
     P2WPKH([u8; 20]),
     P2WSH([u8; 32]),
     P2TR([u8; 32]),
+    P2PKH([u8; 20]),
+    P2SH([u8; 20]),
+    // Future segwit versions 2 to 16, with their witness program.
+    WitnessProgram(u8, Vec<u8>),
 }
 
 /// A swapout is a transaction output that contains a scriptPubKey and an amount.
-/// Legacy addresses are not supported.
+/// Segwit (v0, v1 and future versions) and legacy P2PKH/P2SH outputs are supported.
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Swapout {
     account: Account,
@@ -51,15 +87,80 @@ impl Swapout {
         }
     }
 
+    /// Creates a new swapout for a legacy P2PKH address.
+    pub fn new_p2pkh(account: Account, amount: u32, pubkey_hash: [u8; 20]) -> Swapout {
+        Swapout {
+            account,
+            amount,
+            swapout_type: SwapoutType::P2PKH(pubkey_hash),
+        }
+    }
+
+    /// Creates a new swapout for a legacy P2SH address.
+    pub fn new_p2sh(account: Account, amount: u32, script_hash: [u8; 20]) -> Swapout {
+        Swapout {
+            account,
+            amount,
+            swapout_type: SwapoutType::P2SH(script_hash),
+        }
+    }
+
+    /// Creates a new swapout for a future segwit version (v2 to v16) witness program.
+    pub fn new_witness_program(
+        account: Account,
+        amount: u32,
+        version: u8,
+        witness_program: Vec<u8>,
+    ) -> Option<Swapout> {
+        if !(MIN_FUTURE_WITNESS_VERSION..=MAX_WITNESS_VERSION).contains(&version) {
+            return None;
+        }
+
+        if !(MIN_WITNESS_PROGRAM_LEN..=MAX_WITNESS_PROGRAM_LEN).contains(&witness_program.len()) {
+            return None;
+        }
+
+        Some(Swapout {
+            account,
+            amount,
+            swapout_type: SwapoutType::WitnessProgram(version, witness_program),
+        })
+    }
+
     /// Creates a new swapout from a scriptPubKey.
     pub fn from_spk(account: Account, amount: u32, spk: Vec<u8>) -> Option<Swapout> {
-        let swapout_type = match spk.len() {
-            22 => SwapoutType::P2WPKH(spk[2..].try_into().ok()?),
-            34 => match spk[0] {
-                0x00 => SwapoutType::P2WSH(spk[2..].try_into().ok()?),
-                0x51 => SwapoutType::P2TR(spk[2..].try_into().ok()?),
-                _ => return None,
-            },
+        // Legacy P2PKH: OP_DUP OP_HASH160 <20> OP_EQUALVERIFY OP_CHECKSIG
+        if spk.len() == 25 && spk[..3] == [0x76, 0xa9, 0x14] && spk[23..] == [0x88, 0xac] {
+            return Some(Swapout::new_p2pkh(
+                account,
+                amount,
+                spk[3..23].try_into().ok()?,
+            ));
+        }
+
+        // Legacy P2SH: OP_HASH160 <20> OP_EQUAL
+        if spk.len() == 23 && spk[..2] == [0xa9, 0x14] && spk[22] == 0x87 {
+            return Some(Swapout::new_p2sh(
+                account,
+                amount,
+                spk[2..22].try_into().ok()?,
+            ));
+        }
+
+        // Segwit: <version> <program length> <program>
+        if spk.len() < 2 || spk[1] as usize != spk.len() - 2 {
+            return None;
+        }
+
+        let program = spk[2..].to_vec();
+
+        let swapout_type = match (spk[0], program.len()) {
+            (0x00, 20) => SwapoutType::P2WPKH(program.try_into().ok()?),
+            (0x00, 32) => SwapoutType::P2WSH(program.try_into().ok()?),
+            (0x51, 32) => SwapoutType::P2TR(program.try_into().ok()?),
+            (0x52..=0x60, _) => {
+                return Swapout::new_witness_program(account, amount, spk[0] - 0x50, program)
+            }
             _ => return None,
         };
 
@@ -70,21 +171,25 @@ impl Swapout {
         })
     }
 
-    /// Returns the witness version for the swapout.
-    pub fn witness_version(&self) -> u8 {
+    /// Returns the witness version for the swapout, or `None` for legacy outputs.
+    pub fn witness_version(&self) -> Option<u8> {
         match &self.swapout_type {
-            SwapoutType::P2WPKH(_) => 0,
-            SwapoutType::P2WSH(_) => 0,
-            SwapoutType::P2TR(_) => 1,
+            SwapoutType::P2WPKH(_) => Some(0),
+            SwapoutType::P2WSH(_) => Some(0),
+            SwapoutType::P2TR(_) => Some(1),
+            SwapoutType::WitnessProgram(version, _) => Some(*version),
+            SwapoutType::P2PKH(_) | SwapoutType::P2SH(_) => None,
         }
     }
 
-    /// Returns the witness program for the swapout.
-    pub fn witness_program(&self) -> &[u8] {
+    /// Returns the witness program for the swapout, or `None` for legacy outputs.
+    pub fn witness_program(&self) -> Option<&[u8]> {
         match &self.swapout_type {
-            SwapoutType::P2WPKH(witness_program) => witness_program,
-            SwapoutType::P2WSH(witness_program) => witness_program,
-            SwapoutType::P2TR(witness_program) => witness_program,
+            SwapoutType::P2WPKH(witness_program) => Some(witness_program),
+            SwapoutType::P2WSH(witness_program) => Some(witness_program),
+            SwapoutType::P2TR(witness_program) => Some(witness_program),
+            SwapoutType::WitnessProgram(_, witness_program) => Some(witness_program),
+            SwapoutType::P2PKH(_) | SwapoutType::P2SH(_) => None,
         }
     }
 
@@ -108,6 +213,26 @@ impl Swapout {
                 spk.push(0x20);
                 spk.extend(witness_program);
             }
+            SwapoutType::P2PKH(pubkey_hash) => {
+                spk.push(0x76);
+                spk.push(0xa9);
+                spk.push(0x14);
+                spk.extend(pubkey_hash);
+                spk.push(0x88);
+                spk.push(0xac);
+            }
+            SwapoutType::P2SH(script_hash) => {
+                spk.push(0xa9);
+                spk.push(0x14);
+                spk.extend(script_hash);
+                spk.push(0x87);
+            }
+            SwapoutType::WitnessProgram(version, witness_program) => {
+                // OP_2 to OP_16
+                spk.push(0x50 + version);
+                spk.push(witness_program.len() as u8);
+                spk.extend(witness_program);
+            }
         }
 
         spk
@@ -142,6 +267,195 @@ impl Swapout {
     pub fn validate_account(&self, account: Account) -> bool {
         self.account.key() == account.key()
     }
+
+    /// Compact payload decoding for `Swapout`.
+    /// Decodes a `Swapout` from a bit stream. The account is the entry account.
+    pub fn decode_cpe<'a>(
+        bit_stream: &mut bit_vec::Iter<'a>,
+        account: Account,
+        common_table: &CommonTable,
+    ) -> Result<Swapout, CPEDecodingError> {
+        // Decode the amount.
+        let amount = MaybeCommon::<ShortVal>::decode_cpe_with_table(bit_stream, common_table)?
+            .value()
+            .value();
+
+        // P2TR is the common case, and is signaled with a single bit.
+        let is_p2tr = !bit_stream
+            .next()
+            .ok_or(CPEDecodingError::SwapoutCPEDecodingError(
+                SwapoutCPEDecodingError::FailedToCollectSwapoutTypeBit,
+            ))?;
+
+        if is_p2tr {
+            let taproot_key = collect_bytes(bit_stream, 32)?;
+            return Ok(Swapout {
+                account,
+                amount,
+                swapout_type: SwapoutType::P2TR(taproot_key.try_into().map_err(|_| {
+                    CPEDecodingError::SwapoutCPEDecodingError(
+                        SwapoutCPEDecodingError::FailedToCollectWitnessProgramBits,
+                    )
+                })?),
+            });
+        }
+
+        // Decode the swapout type.
+        let swapout_type_index =
+            AtomicVal::decode_cpe(bit_stream, SWAPOUT_TYPE_UPPER_BOUND)?.value();
+
+        // Construct the swapout type.
+        let swapout_type = match swapout_type_index {
+            0 => SwapoutType::P2WPKH(collect_array(bit_stream)?),
+            1 => SwapoutType::P2WSH(collect_array(bit_stream)?),
+            2 => SwapoutType::P2PKH(collect_array(bit_stream)?),
+            3 => SwapoutType::P2SH(collect_array(bit_stream)?),
+            4 => {
+                // Decode the witness version.
+                let version = AtomicVal::decode_cpe(bit_stream, WITNESS_VERSION_UPPER_BOUND)?
+                    .value()
+                    + MIN_FUTURE_WITNESS_VERSION;
+                if version > MAX_WITNESS_VERSION {
+                    return Err(CPEDecodingError::SwapoutCPEDecodingError(
+                        SwapoutCPEDecodingError::InvalidWitnessVersion(version),
+                    ));
+                }
+
+                // Decode the witness program length.
+                let witness_program_len =
+                    AtomicVal::decode_cpe(bit_stream, WITNESS_PROGRAM_LEN_UPPER_BOUND)?.value()
+                        as usize
+                        + MIN_WITNESS_PROGRAM_LEN;
+                if witness_program_len > MAX_WITNESS_PROGRAM_LEN {
+                    return Err(CPEDecodingError::SwapoutCPEDecodingError(
+                        SwapoutCPEDecodingError::InvalidWitnessProgramLength(
+                            witness_program_len as u8,
+                        ),
+                    ));
+                }
+
+                SwapoutType::WitnessProgram(
+                    version,
+                    collect_bytes(bit_stream, witness_program_len)?,
+                )
+            }
+            _ => {
+                return Err(CPEDecodingError::SwapoutCPEDecodingError(
+                    SwapoutCPEDecodingError::UnknownSwapoutType(swapout_type_index),
+                ))
+            }
+        };
+
+        // Construct the `Swapout`.
+        let swapout = Swapout {
+            account,
+            amount,
+            swapout_type,
+        };
+
+        // Return the `Swapout`.
+        Ok(swapout)
+    }
+}
+
+/// Collects the given number of bytes from the bit stream.
+fn collect_bytes<'a>(
+    bit_stream: &mut bit_vec::Iter<'a>,
+    len: usize,
+) -> Result<Vec<u8>, CPEDecodingError> {
+    let bits: BitVec = bit_stream.by_ref().take(len * 8).collect();
+
+    // Ensure the collected bits are the correct length.
+    if bits.len() != len * 8 {
+        return Err(CPEDecodingError::SwapoutCPEDecodingError(
+            SwapoutCPEDecodingError::FailedToCollectWitnessProgramBits,
+        ));
+    }
+
+    Ok(bits.to_bytes())
+}
+
+/// Collects a fixed-size byte array from the bit stream.
+fn collect_array<'a, const N: usize>(
+    bit_stream: &mut bit_vec::Iter<'a>,
+) -> Result<[u8; N], CPEDecodingError> {
+    collect_bytes(bit_stream, N)?.try_into().map_err(|_| {
+        CPEDecodingError::SwapoutCPEDecodingError(
+            SwapoutCPEDecodingError::FailedToCollectWitnessProgramBits,
+        )
+    })
+}
+
+#[async_trait]
+impl CompactPayloadEncoding for Swapout {
+    fn encode_cpe(&self) -> Option<BitVec> {
+        self.encode_cpe_with_table(&CommonTable::genesis())
+    }
+
+    fn encode_cpe_with_table(&self, common_table: &CommonTable) -> Option<BitVec> {
+        let mut bits = BitVec::new();
+
+        // Extend the amount.
+        bits.extend(
+            MaybeCommon::<ShortVal>::new_with_table(ShortVal::new(self.amount), common_table)
+                .encode_cpe()?,
+        );
+
+        // P2TR is the common case, and is signaled with a single bit.
+        if let SwapoutType::P2TR(taproot_key) = &self.swapout_type {
+            bits.push(false);
+            bits.extend(BitVec::from_bytes(taproot_key));
+            return Some(bits);
+        }
+        bits.push(true);
+
+        // Extend the swapout type and its program.
+        match &self.swapout_type {
+            SwapoutType::P2WPKH(witness_program) => {
+                bits.extend(AtomicVal::new(0, SWAPOUT_TYPE_UPPER_BOUND).encode_cpe()?);
+                bits.extend(BitVec::from_bytes(witness_program));
+            }
+            SwapoutType::P2WSH(witness_program) => {
+                bits.extend(AtomicVal::new(1, SWAPOUT_TYPE_UPPER_BOUND).encode_cpe()?);
+                bits.extend(BitVec::from_bytes(witness_program));
+            }
+            SwapoutType::P2PKH(pubkey_hash) => {
+                bits.extend(AtomicVal::new(2, SWAPOUT_TYPE_UPPER_BOUND).encode_cpe()?);
+                bits.extend(BitVec::from_bytes(pubkey_hash));
+            }
+            SwapoutType::P2SH(script_hash) => {
+                bits.extend(AtomicVal::new(3, SWAPOUT_TYPE_UPPER_BOUND).encode_cpe()?);
+                bits.extend(BitVec::from_bytes(script_hash));
+            }
+            SwapoutType::WitnessProgram(version, witness_program) => {
+                if !(MIN_FUTURE_WITNESS_VERSION..=MAX_WITNESS_VERSION).contains(version) {
+                    return None;
+                }
+                if !(MIN_WITNESS_PROGRAM_LEN..=MAX_WITNESS_PROGRAM_LEN)
+                    .contains(&witness_program.len())
+                {
+                    return None;
+                }
+
+                bits.extend(AtomicVal::new(4, SWAPOUT_TYPE_UPPER_BOUND).encode_cpe()?);
+
+                // Extend the witness version and the witness program length.
+                let version = version - MIN_FUTURE_WITNESS_VERSION;
+                bits.extend(AtomicVal::new(version, WITNESS_VERSION_UPPER_BOUND).encode_cpe()?);
+                let witness_program_len = (witness_program.len() - MIN_WITNESS_PROGRAM_LEN) as u8;
+                bits.extend(
+                    AtomicVal::new(witness_program_len, WITNESS_PROGRAM_LEN_UPPER_BOUND)
+                        .encode_cpe()?,
+                );
+
+                bits.extend(BitVec::from_bytes(witness_program));
+            }
+            SwapoutType::P2TR(_) => return None,
+        }
+
+        // Return the bits.
+        Some(bits)
+    }
 }
 
 impl AuthSighash for Swapout {
@@ -150,7 +464,9 @@ impl AuthSighash for Swapout {
 
         preimage.extend(self.account.key().serialize_xonly());
         preimage.extend(self.amount.to_le_bytes());
-        preimage.extend(self.witness_program());
+
+        // Commit to the full scriptPubKey, as programs of different types may share a length.
+        preimage.extend(self.spk());
 
         preimage.hash(Some(HashTag::SighashCombinator))
     }
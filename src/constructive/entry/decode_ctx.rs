@@ -0,0 +1,74 @@
+use crate::inscriptive::{
+    epoch::dir::EPOCH_DIRECTORY,
+    registery::registery::REGISTERY,
+    repo::repo::PROGRAM_REPO,
+    set::{swept_set::SWEPT_SET, vtxo_set::VTXO_SET},
+};
+use std::sync::Arc;
+
+/// The state needed to decode an `Entry` from a bit stream.
+pub struct EntryDecodeCtx {
+    // Epoch directory for matching `Lift` outputs.
+    epoch_dir: EPOCH_DIRECTORY,
+    // Registery for resolving accounts and contracts.
+    registery: REGISTERY,
+    // Program repository for resolving contract call methods.
+    program_repo: PROGRAM_REPO,
+    // VTXO set for resolving `Recharge` and `Revive` VTXOs.
+    vtxo_set: VTXO_SET,
+    // Swept set for resolving `Claim` swept VTXOs.
+    swept_set: SWEPT_SET,
+    // Bitcoin height at which VTXO expiry is evaluated.
+    bitcoin_height: u32,
+}
+
+impl EntryDecodeCtx {
+    /// Creates a new `EntryDecodeCtx`.
+    pub fn new(
+        epoch_dir: &EPOCH_DIRECTORY,
+        registery: &REGISTERY,
+        program_repo: &PROGRAM_REPO,
+        vtxo_set: &VTXO_SET,
+        swept_set: &SWEPT_SET,
+        bitcoin_height: u32,
+    ) -> EntryDecodeCtx {
+        EntryDecodeCtx {
+            epoch_dir: Arc::clone(epoch_dir),
+            registery: Arc::clone(registery),
+            program_repo: Arc::clone(program_repo),
+            vtxo_set: Arc::clone(vtxo_set),
+            swept_set: Arc::clone(swept_set),
+            bitcoin_height,
+        }
+    }
+
+    /// Returns the epoch directory.
+    pub fn epoch_dir(&self) -> &EPOCH_DIRECTORY {
+        &self.epoch_dir
+    }
+
+    /// Returns the registery.
+    pub fn registery(&self) -> &REGISTERY {
+        &self.registery
+    }
+
+    /// Returns the program repository.
+    pub fn program_repo(&self) -> &PROGRAM_REPO {
+        &self.program_repo
+    }
+
+    /// Returns the VTXO set.
+    pub fn vtxo_set(&self) -> &VTXO_SET {
+        &self.vtxo_set
+    }
+
+    /// Returns the swept set.
+    pub fn swept_set(&self) -> &SWEPT_SET {
+        &self.swept_set
+    }
+
+    /// Returns the bitcoin height.
+    pub fn bitcoin_height(&self) -> u32 {
+        self.bitcoin_height
+    }
+}
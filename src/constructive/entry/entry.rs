@@ -1,16 +1,30 @@
 use super::combinator::{
-    add::Add, call::Call, claim::Claim, combinator::Combinator, deploy::Deploy, liftup::Liftup,
-    r#move::Move, recharge::Recharge, reserved::Reserved, revive::Revive, sub::Sub,
-    swapout::Swapout,
+    add::Add, batch_move::BatchMove, call::Call, claim::Claim, combinator::Combinator,
+    deploy::Deploy, liftup::Liftup, r#move::Move, recharge::Recharge, reserved::Reserved,
+    revive::Revive, sub::Sub, swapout::Swapout,
 };
+use super::decode_ctx::EntryDecodeCtx;
 use crate::{
-    constructive::entity::account::Account,
+    constructive::{
+        cpe::{
+            cpe::CompactPayloadEncoding,
+            decode_error::{entry_error::EntryCPEDecodingError, error::CPEDecodingError},
+        },
+        entity::account::Account,
+        txn::txholder::TxHolder,
+        valtype::maybe_common::common_table::CommonTable,
+    },
+    inscriptive::registery::account_registery::ACCOUNT_REGISTERY,
     transmutive::{
         hash::{Hash, HashTag},
         secp::authenticable::AuthSighash,
     },
 };
+use async_trait::async_trait;
+use bit_vec::BitVec;
+use secp::Point;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// The uppermost left branch of an entry.
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -100,7 +114,7 @@ pub enum LowerLeftBranch {
 #[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum LowerRightBranch {
     RecoveryBranch(RecoveryBranch),
-    Reserved(Reserved),
+    ReservedBranch(ReservedBranch),
 }
 
 /// The recovery branch of an entry. Descend from the lower right branch.
@@ -110,12 +124,28 @@ pub enum RecoveryBranch {
     Claim(Claim),
 }
 
+/// The reserved branch of an entry. Descend from the lower right branch.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ReservedBranch {
+    BatchMove(BatchMove),
+    Reserved(Reserved),
+}
+
 impl UppermostRightBranch {
     /// Create a branch containing a `Move` combinator.
     pub fn new_move(r#move: Move) -> Self {
         Self::TransactiveBranch(TransactiveBranch::Move(r#move))
     }
 
+    /// Create a branch containing a `BatchMove` combinator.
+    pub fn new_batch_move(batch_move: BatchMove) -> Self {
+        Self::UpperRightBranch(UpperRightBranch::RightBranch(
+            RightBranch::LowerRightBranch(LowerRightBranch::ReservedBranch(
+                ReservedBranch::BatchMove(batch_move),
+            )),
+        ))
+    }
+
     /// Create a branch containing a `Call` combinator.
     pub fn new_call(call: Call) -> Self {
         Self::TransactiveBranch(TransactiveBranch::Call(call))
@@ -166,7 +196,9 @@ impl UppermostRightBranch {
     /// Create a branch containing a `Reserved` combinator.
     pub fn new_reserved(reserved: Reserved) -> Self {
         Self::UpperRightBranch(UpperRightBranch::RightBranch(
-            RightBranch::LowerRightBranch(LowerRightBranch::Reserved(reserved)),
+            RightBranch::LowerRightBranch(LowerRightBranch::ReservedBranch(
+                ReservedBranch::Reserved(reserved),
+            )),
         ))
     }
 
@@ -201,8 +233,16 @@ impl UppermostRightBranch {
                                 RecoveryBranch::Claim(claim) => Combinator::Claim(claim.clone()),
                             }
                         }
-                        LowerRightBranch::Reserved(reserved) => {
-                            Combinator::Reserved(reserved.clone())
+                        // Reserved branch.
+                        LowerRightBranch::ReservedBranch(reserved_branch) => {
+                            match reserved_branch {
+                                ReservedBranch::BatchMove(batch_move) => {
+                                    Combinator::BatchMove(batch_move.clone())
+                                }
+                                ReservedBranch::Reserved(reserved) => {
+                                    Combinator::Reserved(reserved.clone())
+                                }
+                            }
                         }
                     },
                 },
@@ -236,7 +276,7 @@ impl Entry {
     // Liftup and/or recharge ONLY.
     pub fn new_nop(account: Account, liftup: Option<Liftup>, recharge: Option<Recharge>) -> Entry {
         let uppermost_left_branch = {
-            if liftup.is_some() && recharge.is_some() {
+            if liftup.is_some() || recharge.is_some() {
                 Some(UppermostLeftBranch::new(liftup, recharge))
             } else {
                 None
@@ -268,6 +308,25 @@ impl Entry {
         Self::new(account, uppermost_left_branch, uppermost_right_branch)
     }
 
+    /// Creates a new batch move entry.
+    pub fn new_batch_move(
+        account: Account,
+        batch_move: BatchMove,
+        liftup: Option<Liftup>,
+        recharge: Option<Recharge>,
+    ) -> Entry {
+        let uppermost_left_branch = {
+            if liftup.is_some() || recharge.is_some() {
+                Some(UppermostLeftBranch::new(liftup, recharge))
+            } else {
+                None
+            }
+        };
+
+        let uppermost_right_branch = Some(UppermostRightBranch::new_batch_move(batch_move));
+        Self::new(account, uppermost_left_branch, uppermost_right_branch)
+    }
+
     pub fn new_call(
         account: Account,
         call: Call,
@@ -327,6 +386,28 @@ impl Entry {
         Self::new(account, uppermost_left_branch, uppermost_right_branch)
     }
 
+    /// Creates a new deploy entry.
+    pub fn new_deploy(
+        account: Account,
+        deploy: Deploy,
+        liftup: Option<Liftup>,
+        recharge: Option<Recharge>,
+    ) -> Entry {
+        let uppermost_left_branch = {
+            if liftup.is_some() || recharge.is_some() {
+                Some(UppermostLeftBranch::new(liftup, recharge))
+            } else {
+                None
+            }
+        };
+        let uppermost_right_branch = Some(UppermostRightBranch::UpperRightBranch(
+            UpperRightBranch::RightBranch(RightBranch::LowerLeftBranch(LowerLeftBranch::Deploy(
+                deploy,
+            ))),
+        ));
+        Self::new(account, uppermost_left_branch, uppermost_right_branch)
+    }
+
     /// Creates a new swapout entry.
     pub fn new_swapout(
         account: Account,
@@ -349,6 +430,50 @@ impl Entry {
         Self::new(account, uppermost_left_branch, uppermost_right_branch)
     }
 
+    /// Creates a new revive entry.
+    pub fn new_revive(
+        account: Account,
+        revive: Revive,
+        liftup: Option<Liftup>,
+        recharge: Option<Recharge>,
+    ) -> Entry {
+        let uppermost_left_branch = {
+            if liftup.is_some() || recharge.is_some() {
+                Some(UppermostLeftBranch::new(liftup, recharge))
+            } else {
+                None
+            }
+        };
+        let uppermost_right_branch = Some(UppermostRightBranch::UpperRightBranch(
+            UpperRightBranch::RightBranch(RightBranch::LowerRightBranch(
+                LowerRightBranch::RecoveryBranch(RecoveryBranch::Revive(revive)),
+            )),
+        ));
+        Self::new(account, uppermost_left_branch, uppermost_right_branch)
+    }
+
+    /// Creates a new claim entry.
+    pub fn new_claim(
+        account: Account,
+        claim: Claim,
+        liftup: Option<Liftup>,
+        recharge: Option<Recharge>,
+    ) -> Entry {
+        let uppermost_left_branch = {
+            if liftup.is_some() || recharge.is_some() {
+                Some(UppermostLeftBranch::new(liftup, recharge))
+            } else {
+                None
+            }
+        };
+        let uppermost_right_branch = Some(UppermostRightBranch::UpperRightBranch(
+            UpperRightBranch::RightBranch(RightBranch::LowerRightBranch(
+                LowerRightBranch::RecoveryBranch(RecoveryBranch::Claim(claim)),
+            )),
+        ));
+        Self::new(account, uppermost_left_branch, uppermost_right_branch)
+    }
+
     /// Creates a new reserved entry.
     pub fn new_reserved(
         account: Account,
@@ -365,7 +490,7 @@ impl Entry {
         };
         let uppermost_right_branch = Some(UppermostRightBranch::UpperRightBranch(
             UpperRightBranch::RightBranch(RightBranch::LowerRightBranch(
-                LowerRightBranch::Reserved(reserved),
+                LowerRightBranch::ReservedBranch(ReservedBranch::Reserved(reserved)),
             )),
         ));
         Self::new(account, uppermost_left_branch, uppermost_right_branch)
@@ -406,6 +531,32 @@ impl Entry {
         Some(uppermost_right_branch.main_combinator())
     }
 
+    /// Sets the ranks of the encoded accounts and contracts from the given ranks.
+    /// Entities not in the ranks are left unranked.
+    pub(crate) fn rerank(
+        &mut self,
+        account_ranks: &HashMap<Point, u32>,
+        contract_ranks: &HashMap<[u8; 32], u32>,
+    ) {
+        self.account
+            .set_rank(account_ranks.get(&self.account.key()).copied());
+
+        match &mut self.uppermost_right_branch {
+            Some(UppermostRightBranch::TransactiveBranch(transactive_branch)) => {
+                match transactive_branch {
+                    TransactiveBranch::Move(r#move) => r#move.rerank(account_ranks),
+                    TransactiveBranch::Call(call) => call.rerank(account_ranks, contract_ranks),
+                }
+            }
+            Some(UppermostRightBranch::UpperRightBranch(UpperRightBranch::RightBranch(
+                RightBranch::LowerRightBranch(LowerRightBranch::ReservedBranch(
+                    ReservedBranch::BatchMove(batch_move),
+                )),
+            ))) => batch_move.rerank(account_ranks),
+            _ => {}
+        }
+    }
+
     pub fn serialize(&self) -> Vec<u8> {
         match serde_json::to_vec(self) {
             Ok(bytes) => bytes,
@@ -444,6 +595,11 @@ impl Entry {
                         return false;
                     }
                 }
+                Combinator::BatchMove(batch_move) => {
+                    if !batch_move.validate_account(account) {
+                        return false;
+                    }
+                }
                 Combinator::Call(call) => {
                     if !call.validate_account(account) {
                         return false;
@@ -527,6 +683,7 @@ impl AuthSighash for Entry {
 
                 match &uppermost_right_branch.main_combinator() {
                     Combinator::Move(r#move) => preimage.extend(r#move.auth_sighash()),
+                    Combinator::BatchMove(batch_move) => preimage.extend(batch_move.auth_sighash()),
                     Combinator::Call(call) => preimage.extend(call.auth_sighash()),
                     Combinator::Add(add) => preimage.extend(add.auth_sighash()),
                     Combinator::Sub(sub) => preimage.extend(sub.auth_sighash()),
@@ -547,3 +704,334 @@ impl AuthSighash for Entry {
         preimage.hash(Some(HashTag::SighashEntry))
     }
 }
+
+impl Entry {
+    /// Compact payload decoding for `Entry`.
+    /// Decodes an `Entry` from a bit stream by walking the entry tree branch bits.
+    /// Common values are looked up in the given table.
+    pub async fn decode_cpe<'a>(
+        bit_stream: &mut bit_vec::Iter<'a>,
+        txholder: &mut TxHolder,
+        ctx: &EntryDecodeCtx,
+        common_table: &CommonTable,
+    ) -> Result<Entry, CPEDecodingError> {
+        // Get the account registery.
+        let account_registery = {
+            let _registery = ctx.registery().lock().await;
+            _registery.account_registery()
+        };
+
+        // Decode the account.
+        let account = Account::decode_cpe(bit_stream, &account_registery).await?;
+
+        // Decode the uppermost left branch.
+        let uppermost_left_branch = match decode_branch_bit(bit_stream)? {
+            true => {
+                // Decode the liftup.
+                let liftup = match decode_branch_bit(bit_stream)? {
+                    true => Some(
+                        Liftup::decode_cpe(bit_stream, txholder, ctx.epoch_dir(), account.key())
+                            .await?,
+                    ),
+                    false => None,
+                };
+
+                // Decode the recharge.
+                let recharge = match decode_branch_bit(bit_stream)? {
+                    true => Some(
+                        Recharge::decode_cpe(account, ctx.vtxo_set(), ctx.bitcoin_height()).await?,
+                    ),
+                    false => None,
+                };
+
+                // Liftup and recharge cannot be both off.
+                if liftup.is_none() && recharge.is_none() {
+                    return Err(CPEDecodingError::EntryCPEDecodingError(
+                        EntryCPEDecodingError::UppermostLeftBranchEmpty,
+                    ));
+                }
+
+                Some(UppermostLeftBranch::new(liftup, recharge))
+            }
+            false => None,
+        };
+
+        // Decode the uppermost right branch.
+        let uppermost_right_branch = match decode_branch_bit(bit_stream)? {
+            true => Some(match decode_branch_bit(bit_stream)? {
+                // Transactive branch.
+                false => {
+                    UppermostRightBranch::TransactiveBranch(match decode_branch_bit(bit_stream)? {
+                        false => TransactiveBranch::Move(
+                            Move::decode_cpe(bit_stream, account, &account_registery, common_table)
+                                .await?,
+                        ),
+                        true => TransactiveBranch::Call(
+                            Call::decode_cpe(
+                                bit_stream,
+                                account,
+                                ctx.registery(),
+                                ctx.program_repo(),
+                                common_table,
+                            )
+                            .await?,
+                        ),
+                    })
+                }
+                // Upper right branch.
+                true => {
+                    UppermostRightBranch::UpperRightBranch(match decode_branch_bit(bit_stream)? {
+                        // Liquidity branch.
+                        false => UpperRightBranch::LiquidityBranch(
+                            match decode_branch_bit(bit_stream)? {
+                                false => LiquidityBranch::Add(Add::decode_cpe(
+                                    bit_stream,
+                                    account,
+                                    common_table,
+                                )?),
+                                true => LiquidityBranch::Sub(Sub::decode_cpe(
+                                    bit_stream,
+                                    account,
+                                    common_table,
+                                )?),
+                            },
+                        ),
+                        // Right branch.
+                        true => UpperRightBranch::RightBranch(
+                            Self::decode_right_branch(
+                                bit_stream,
+                                account,
+                                &account_registery,
+                                ctx,
+                                common_table,
+                            )
+                            .await?,
+                        ),
+                    })
+                }
+            }),
+            false => None,
+        };
+
+        // Uppermost branches cannot be both off.
+        if uppermost_left_branch.is_none() && uppermost_right_branch.is_none() {
+            return Err(CPEDecodingError::EntryCPEDecodingError(
+                EntryCPEDecodingError::UppermostBranchesBothOff,
+            ));
+        }
+
+        // Return the `Entry`.
+        Ok(Entry::new(
+            account,
+            uppermost_left_branch,
+            uppermost_right_branch,
+        ))
+    }
+
+    /// Decodes the right branch of an entry.
+    async fn decode_right_branch<'a>(
+        bit_stream: &mut bit_vec::Iter<'a>,
+        account: Account,
+        account_registery: &ACCOUNT_REGISTERY,
+        ctx: &EntryDecodeCtx,
+        common_table: &CommonTable,
+    ) -> Result<RightBranch, CPEDecodingError> {
+        let right_branch = match decode_branch_bit(bit_stream)? {
+            // Lower left branch.
+            false => RightBranch::LowerLeftBranch(match decode_branch_bit(bit_stream)? {
+                false => LowerLeftBranch::Deploy(Deploy::decode_cpe(bit_stream, account)?),
+                true => LowerLeftBranch::Swapout(Swapout::decode_cpe(
+                    bit_stream,
+                    account,
+                    common_table,
+                )?),
+            }),
+            // Lower right branch.
+            true => RightBranch::LowerRightBranch(match decode_branch_bit(bit_stream)? {
+                // Recovery branch.
+                false => LowerRightBranch::RecoveryBranch(match decode_branch_bit(bit_stream)? {
+                    false => RecoveryBranch::Revive(
+                        Revive::decode_cpe(account, ctx.vtxo_set(), ctx.bitcoin_height()).await?,
+                    ),
+                    true => {
+                        RecoveryBranch::Claim(Claim::decode_cpe(account, ctx.swept_set()).await?)
+                    }
+                }),
+                // Reserved branch.
+                true => LowerRightBranch::ReservedBranch(match decode_branch_bit(bit_stream)? {
+                    false => ReservedBranch::BatchMove(
+                        BatchMove::decode_cpe(bit_stream, account, account_registery, common_table)
+                            .await?,
+                    ),
+                    // Reserved fails the entry.
+                    true => {
+                        return Err(CPEDecodingError::EntryCPEDecodingError(
+                            EntryCPEDecodingError::ReservedBranch,
+                        ))
+                    }
+                }),
+            }),
+        };
+
+        Ok(right_branch)
+    }
+}
+
+/// Decodes a single branch bit.
+fn decode_branch_bit<'a>(bit_stream: &mut bit_vec::Iter<'a>) -> Result<bool, CPEDecodingError> {
+    bit_stream
+        .next()
+        .ok_or(CPEDecodingError::EntryCPEDecodingError(
+            EntryCPEDecodingError::FailedToCollectBranchBit,
+        ))
+}
+
+#[async_trait]
+impl CompactPayloadEncoding for Entry {
+    fn encode_cpe(&self) -> Option<BitVec> {
+        self.encode_cpe_with_table(&CommonTable::genesis())
+    }
+
+    fn encode_cpe_with_table(&self, common_table: &CommonTable) -> Option<BitVec> {
+        let mut bits = BitVec::new();
+
+        // Uppermost branches cannot be both off.
+        if self.uppermost_left_branch.is_none() && self.uppermost_right_branch.is_none() {
+            return None;
+        }
+
+        // Extend the account.
+        bits.extend(self.account.encode_cpe()?);
+
+        // Extend the uppermost left branch.
+        match &self.uppermost_left_branch {
+            Some(uppermost_left_branch) => {
+                // Liftup and recharge cannot be both off.
+                if uppermost_left_branch.liftup.is_none()
+                    && uppermost_left_branch.recharge.is_none()
+                {
+                    return None;
+                }
+
+                bits.push(true);
+
+                // Extend the liftup.
+                match &uppermost_left_branch.liftup {
+                    Some(liftup) => {
+                        bits.push(true);
+                        bits.extend(liftup.encode_cpe()?);
+                    }
+                    None => bits.push(false),
+                }
+
+                // Extend the recharge.
+                match &uppermost_left_branch.recharge {
+                    Some(recharge) => {
+                        bits.push(true);
+                        bits.extend(recharge.encode_cpe()?);
+                    }
+                    None => bits.push(false),
+                }
+            }
+            None => bits.push(false),
+        }
+
+        // Extend the uppermost right branch.
+        let uppermost_right_branch = match &self.uppermost_right_branch {
+            Some(uppermost_right_branch) => uppermost_right_branch,
+            None => {
+                bits.push(false);
+                return Some(bits);
+            }
+        };
+        bits.push(true);
+
+        // Extend the branch bits and the main combinator.
+        match uppermost_right_branch {
+            UppermostRightBranch::TransactiveBranch(transactive_branch) => {
+                bits.push(false);
+                match transactive_branch {
+                    TransactiveBranch::Move(r#move) => {
+                        bits.push(false);
+                        bits.extend(r#move.encode_cpe_with_table(common_table)?);
+                    }
+                    TransactiveBranch::Call(call) => {
+                        bits.push(true);
+                        bits.extend(call.encode_cpe_with_table(common_table)?);
+                    }
+                }
+            }
+            UppermostRightBranch::UpperRightBranch(upper_right_branch) => {
+                bits.push(true);
+                match upper_right_branch {
+                    UpperRightBranch::LiquidityBranch(liquidity_branch) => {
+                        bits.push(false);
+                        match liquidity_branch {
+                            LiquidityBranch::Add(add) => {
+                                bits.push(false);
+                                bits.extend(add.encode_cpe_with_table(common_table)?);
+                            }
+                            LiquidityBranch::Sub(sub) => {
+                                bits.push(true);
+                                bits.extend(sub.encode_cpe_with_table(common_table)?);
+                            }
+                        }
+                    }
+                    UpperRightBranch::RightBranch(right_branch) => {
+                        bits.push(true);
+                        match right_branch {
+                            RightBranch::LowerLeftBranch(lower_left_branch) => {
+                                bits.push(false);
+                                match lower_left_branch {
+                                    LowerLeftBranch::Deploy(deploy) => {
+                                        bits.push(false);
+                                        bits.extend(deploy.encode_cpe()?);
+                                    }
+                                    LowerLeftBranch::Swapout(swapout) => {
+                                        bits.push(true);
+                                        bits.extend(swapout.encode_cpe_with_table(common_table)?);
+                                    }
+                                }
+                            }
+                            RightBranch::LowerRightBranch(lower_right_branch) => {
+                                bits.push(true);
+                                match lower_right_branch {
+                                    LowerRightBranch::RecoveryBranch(recovery_branch) => {
+                                        bits.push(false);
+                                        match recovery_branch {
+                                            RecoveryBranch::Revive(revive) => {
+                                                bits.push(false);
+                                                bits.extend(revive.encode_cpe()?);
+                                            }
+                                            RecoveryBranch::Claim(claim) => {
+                                                bits.push(true);
+                                                bits.extend(claim.encode_cpe()?);
+                                            }
+                                        }
+                                    }
+                                    LowerRightBranch::ReservedBranch(reserved_branch) => {
+                                        bits.push(true);
+                                        match reserved_branch {
+                                            ReservedBranch::BatchMove(batch_move) => {
+                                                bits.push(false);
+                                                bits.extend(
+                                                    batch_move
+                                                        .encode_cpe_with_table(common_table)?,
+                                                );
+                                            }
+                                            // Reserved cannot be encoded.
+                                            ReservedBranch::Reserved(_) => return None,
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Return the bits.
+        Some(bits)
+    }
+}
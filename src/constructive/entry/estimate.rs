@@ -0,0 +1,131 @@
+use super::{combinator::combinator::Combinator, entry::Entry};
+use crate::{
+    constructive::{
+        calldata::element::CallElement, cpe::cpe::CompactPayloadEncoding,
+        valtype::maybe_common::common_table::CommonTable,
+    },
+    inscriptive::registery::registery::REGISTERY,
+};
+use secp::Point;
+use std::collections::HashMap;
+
+/// Witness data is discounted by this factor: one weight unit per byte, four weight units per vbyte.
+pub const WITNESS_SCALE_FACTOR: u64 = 4;
+
+/// Number of payload bits that make up one witness vbyte.
+pub const BITS_PER_WITNESS_VBYTE: u64 = 8 * WITNESS_SCALE_FACTOR;
+
+/// Data-availability cost of an `Entry` once it is placed in a payload.
+///
+/// Payloads are carried in the witness, so each entry bit costs 1/32 of a vbyte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntryEstimate {
+    bits: u64,
+}
+
+impl EntryEstimate {
+    /// Estimates the entry against the current registery ranks.
+    ///
+    /// Ranks carried by the entry are ignored; registered accounts and contracts are
+    /// re-ranked, and unregistered accounts are priced with their full key.
+    /// Returns `None` if the entry calls an unregistered contract, or cannot be encoded.
+    pub async fn new(
+        entry: &Entry,
+        registery: &REGISTERY,
+        common_table: &CommonTable,
+    ) -> Option<EntryEstimate> {
+        let (account_registery, contract_registery) = {
+            let _registery = registery.lock().await;
+            (
+                _registery.account_registery(),
+                _registery.contract_registery(),
+            )
+        };
+
+        let (account_keys, contract_ids) = encoded_entities(entry);
+
+        // Look up the account ranks.
+        let mut account_ranks = HashMap::<Point, u32>::new();
+        {
+            let _account_registery = account_registery.lock().await;
+            for key in account_keys {
+                if let Some(rank) = _account_registery
+                    .account_by_key(key)
+                    .and_then(|account| account.rank())
+                {
+                    account_ranks.insert(key, rank);
+                }
+            }
+        }
+
+        // Look up the contract ranks.
+        let mut contract_ranks = HashMap::<[u8; 32], u32>::new();
+        {
+            let _contract_registery = contract_registery.lock().await;
+            for contract_id in contract_ids {
+                if let Some(rank) = _contract_registery
+                    .contract_by_contract_id(contract_id)
+                    .and_then(|contract| contract.rank())
+                {
+                    contract_ranks.insert(contract_id, rank);
+                }
+            }
+        }
+
+        let mut entry = entry.clone();
+        entry.rerank(&account_ranks, &contract_ranks);
+
+        EntryEstimate::from_entry(&entry, common_table)
+    }
+
+    /// Estimates the entry as is, using the ranks it already carries.
+    pub fn from_entry(entry: &Entry, common_table: &CommonTable) -> Option<EntryEstimate> {
+        let bits = entry.encode_cpe_with_table(common_table)?.len() as u64;
+
+        Some(EntryEstimate { bits })
+    }
+
+    /// Returns the exact number of payload bits the entry takes.
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    /// Returns the share of witness vbytes the entry takes.
+    pub fn vbytes(&self) -> f64 {
+        self.bits as f64 / BITS_PER_WITNESS_VBYTE as f64
+    }
+
+    /// Returns the fee in satoshis at the given sat/vB fee rate, rounded up.
+    pub fn fee(&self, sat_per_vb: u64) -> u64 {
+        self.bits
+            .saturating_mul(sat_per_vb)
+            .div_ceil(BITS_PER_WITNESS_VBYTE)
+    }
+}
+
+/// Returns the account keys and contract ids whose ranks affect the entry encoding.
+fn encoded_entities(entry: &Entry) -> (Vec<Point>, Vec<[u8; 32]>) {
+    let mut account_keys = vec![entry.account().key()];
+    let mut contract_ids = Vec::<[u8; 32]>::new();
+
+    match entry.main_combinator() {
+        Some(Combinator::Move(r#move)) => account_keys.push(r#move.to().key()),
+        Some(Combinator::BatchMove(batch_move)) => {
+            account_keys.extend(batch_move.recipients().iter().map(|account| account.key()))
+        }
+        Some(Combinator::Call(call)) => {
+            contract_ids.push(call.contract().contract_id());
+
+            for element in call.calldata() {
+                match element {
+                    CallElement::Account(account) => account_keys.push(account.key()),
+                    CallElement::Contract(contract) => contract_ids.push(contract.contract_id()),
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+
+    (account_keys, contract_ids)
+}
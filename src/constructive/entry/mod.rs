@@ -1,2 +1,4 @@
 pub mod combinator;
+pub mod decode_ctx;
 pub mod entry;
+pub mod estimate;
@@ -2,6 +2,7 @@ pub mod calldata;
 pub mod cpe;
 pub mod entity;
 pub mod entry;
+pub mod payload;
 pub mod taproot;
 pub mod txn;
 pub mod txo;
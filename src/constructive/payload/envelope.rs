@@ -0,0 +1,146 @@
+use crate::transmutive::codec::prefix::Prefix;
+
+/// Envelope tag identifying a payload envelope.
+pub const ENVELOPE_TAG: &[u8] = b"brollup";
+
+/// Maximum number of bytes pushed per envelope chunk.
+pub const MAX_ENVELOPE_CHUNK_SIZE: usize = 520;
+
+// Opcodes used by the envelope.
+const OP_FALSE: u8 = 0x00;
+const OP_PUSHDATA1: u8 = 0x4c;
+const OP_PUSHDATA2: u8 = 0x4d;
+const OP_PUSHDATA4: u8 = 0x4e;
+const OP_1NEGATE: u8 = 0x4f;
+const OP_1: u8 = 0x51;
+const OP_16: u8 = 0x60;
+const OP_IF: u8 = 0x63;
+const OP_ENDIF: u8 = 0x68;
+
+/// A single script instruction, either a data push or a non-push opcode.
+#[derive(Clone, PartialEq)]
+enum Instruction {
+    Push(Vec<u8>),
+    Op(u8),
+}
+
+/// Wraps payload bytes in an inscription-style envelope:
+/// `OP_FALSE OP_IF <tag> <chunk>..<chunk> OP_ENDIF`.
+/// The envelope is placed in the Payload TXO reveal script ahead of the spending condition.
+pub fn envelope(payload_bytes: &[u8]) -> Vec<u8> {
+    let mut script = Vec::<u8>::new();
+
+    // OP_FALSE OP_IF
+    script.push(OP_FALSE);
+    script.push(OP_IF);
+
+    // Push the envelope tag.
+    script.extend(ENVELOPE_TAG.to_vec().prefix_pushdata());
+
+    // Push the payload in chunks.
+    for chunk in chunks(payload_bytes) {
+        script.extend(chunk.prefix_pushdata());
+    }
+
+    // OP_ENDIF
+    script.push(OP_ENDIF);
+
+    script
+}
+
+/// Extracts the payload bytes from the first payload envelope found in a script.
+/// Returns `None` if no envelope is found or the envelope is malformed.
+pub fn parse_envelope(script: &[u8]) -> Option<Vec<u8>> {
+    let instructions = instructions(script)?;
+
+    // Locate the envelope opening.
+    let start = instructions.windows(3).position(|window| {
+        window[0] == Instruction::Push(vec![])
+            && window[1] == Instruction::Op(OP_IF)
+            && window[2] == Instruction::Push(ENVELOPE_TAG.to_vec())
+    })?;
+
+    // Collect the chunks until OP_ENDIF.
+    let mut payload_bytes = Vec::<u8>::new();
+    for instruction in instructions[start + 3..].iter() {
+        match instruction {
+            Instruction::Push(data) => payload_bytes.extend(data),
+            Instruction::Op(OP_ENDIF) => return Some(payload_bytes),
+            Instruction::Op(_) => return None,
+        }
+    }
+
+    // Envelope is never closed.
+    None
+}
+
+/// Splits payload bytes into push chunks.
+/// A single-byte chunk is avoided since it would be pushed as a small-number opcode.
+fn chunks(payload_bytes: &[u8]) -> Vec<Vec<u8>> {
+    let mut chunks: Vec<Vec<u8>> = payload_bytes
+        .chunks(MAX_ENVELOPE_CHUNK_SIZE)
+        .map(|chunk| chunk.to_vec())
+        .collect();
+
+    // Borrow a byte from the previous chunk if the last chunk is a single byte.
+    if chunks.len() > 1 && chunks[chunks.len() - 1].len() == 1 {
+        let len = chunks.len();
+        if let Some(byte) = chunks[len - 2].pop() {
+            chunks[len - 1].insert(0, byte);
+        }
+    }
+
+    chunks
+}
+
+/// Walks a script into instructions.
+/// Returns `None` if a push runs past the end of the script.
+fn instructions(script: &[u8]) -> Option<Vec<Instruction>> {
+    let mut instructions = Vec::<Instruction>::new();
+    let mut cursor = 0;
+
+    while cursor < script.len() {
+        let opcode = script[cursor];
+        cursor += 1;
+
+        // Determine the push length, if any.
+        let push_len = match opcode {
+            OP_FALSE => Some(0),
+            0x01..=0x4b => Some(opcode as usize),
+            OP_PUSHDATA1 => {
+                let len = *script.get(cursor)? as usize;
+                cursor += 1;
+                Some(len)
+            }
+            OP_PUSHDATA2 => {
+                let len = u16::from_le_bytes(script.get(cursor..cursor + 2)?.try_into().ok()?);
+                cursor += 2;
+                Some(len as usize)
+            }
+            OP_PUSHDATA4 => {
+                let len = u32::from_le_bytes(script.get(cursor..cursor + 4)?.try_into().ok()?);
+                cursor += 4;
+                Some(len as usize)
+            }
+            _ => None,
+        };
+
+        let instruction = match push_len {
+            Some(len) => {
+                let data = script.get(cursor..cursor + len)?.to_vec();
+                cursor += len;
+                Instruction::Push(data)
+            }
+            None => match opcode {
+                // Minimal small-number pushes.
+                OP_1NEGATE => Instruction::Push(vec![0x81]),
+                OP_1..=OP_16 => Instruction::Push(vec![opcode - OP_1 + 1]),
+                _ => Instruction::Op(opcode),
+            },
+        };
+
+        instructions.push(instruction);
+    }
+
+    Some(instructions)
+}
@@ -0,0 +1,422 @@
+use super::envelope::{envelope, parse_envelope};
+use crate::{
+    constructive::{
+        calldata::element::CallElement,
+        cpe::{
+            cpe::CompactPayloadEncoding,
+            decode_error::{error::CPEDecodingError, payload_error::PayloadCPEDecodingError},
+        },
+        entity::account::Account,
+        entry::{combinator::combinator::Combinator, decode_ctx::EntryDecodeCtx, entry::Entry},
+        txn::txholder::TxHolder,
+        valtype::{maybe_common::common_table::CommonTable, short_val::ShortVal},
+    },
+    transmutive::{
+        hash::{Hash, HashTag},
+        musig::keyagg::MusigKeyAggCtx,
+        secp::{
+            authenticable::AuthSighash,
+            schnorr::{self, SchnorrSigningMode},
+        },
+    },
+};
+use async_trait::async_trait;
+use bit_vec::BitVec;
+use secp::Point;
+
+/// Current payload version.
+pub const PAYLOAD_VERSION: u8 = 1;
+
+/// Byte length of the payload header: version, common table version, state root, session id and payload auth signature.
+pub const PAYLOAD_HEADER_LEN: usize = 1 + 1 + 32 + 32 + 64;
+
+/// `Payload` is the session output carried in the Payload TXO's reveal.
+/// It commits to the ops price, the ordered entries and the aggregated payload auth signature.
+/// It also commits to the state root the entries are applied on top of.
+///
+/// Header (bytes):
+/// `version (1) | common table version (1) | state root (32) | session id (32) | payload auth signature (64)`
+///
+/// Body (CPE bits, zero-padded to a byte boundary):
+/// `ops price | number of entries | entry..entry`
+#[derive(Clone)]
+pub struct Payload {
+    version: u8,
+    // Common table the `MaybeCommon` values are encoded with.
+    common_table: CommonTable,
+    // State root prior to the entries.
+    state_root: [u8; 32],
+    session_id: [u8; 32],
+    ops_price: u32,
+    entries: Vec<Entry>,
+    // New registrations derived from the entries.
+    new_accounts: Vec<Account>,
+    new_contracts: Vec<[u8; 32]>,
+    payload_auth_sig: [u8; 64],
+}
+
+impl Payload {
+    /// Creates a new `Payload`.
+    /// Returns `None` if there are no entries.
+    pub fn new(
+        session_id: [u8; 32],
+        ops_price: u32,
+        common_table: &CommonTable,
+        state_root: [u8; 32],
+        entries: Vec<Entry>,
+        payload_auth_sig: [u8; 64],
+    ) -> Option<Payload> {
+        if entries.is_empty() {
+            return None;
+        }
+
+        let (new_accounts, new_contracts) = registrations(&entries);
+
+        let payload = Payload {
+            version: PAYLOAD_VERSION,
+            common_table: common_table.to_owned(),
+            state_root,
+            session_id,
+            ops_price,
+            entries,
+            new_accounts,
+            new_contracts,
+            payload_auth_sig,
+        };
+
+        Some(payload)
+    }
+
+    /// Returns the payload version.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the common table the payload is encoded with.
+    pub fn common_table(&self) -> CommonTable {
+        self.common_table.clone()
+    }
+
+    /// Returns the state root the entries are applied on top of.
+    pub fn state_root(&self) -> [u8; 32] {
+        self.state_root
+    }
+
+    /// Returns the session id.
+    pub fn session_id(&self) -> [u8; 32] {
+        self.session_id
+    }
+
+    /// Returns the ops price.
+    pub fn ops_price(&self) -> u32 {
+        self.ops_price
+    }
+
+    /// Returns the ordered entries.
+    pub fn entries(&self) -> Vec<Entry> {
+        self.entries.clone()
+    }
+
+    /// Returns the accounts registered by this payload, in order of appearance.
+    pub fn new_accounts(&self) -> Vec<Account> {
+        self.new_accounts.clone()
+    }
+
+    /// Returns the contract ids registered by this payload, in order of appearance.
+    pub fn new_contracts(&self) -> Vec<[u8; 32]> {
+        self.new_contracts.clone()
+    }
+
+    /// Returns the keys of the accounts sending the entries, in order of appearance.
+    pub fn msg_senders(&self) -> Vec<Point> {
+        let mut msg_senders = Vec::<Point>::new();
+        for entry in self.entries.iter() {
+            let key = entry.account().key();
+            if !msg_senders.contains(&key) {
+                msg_senders.push(key);
+            }
+        }
+        msg_senders
+    }
+
+    /// Returns the aggregated payload auth signature.
+    pub fn payload_auth_sig(&self) -> [u8; 64] {
+        self.payload_auth_sig
+    }
+
+    /// Returns the message signed by the payload auth signature.
+    pub fn auth_msg(&self) -> [u8; 32] {
+        Payload::auth_msg_from_parts(
+            self.session_id,
+            self.ops_price,
+            &self.common_table,
+            self.state_root,
+            &self.entries,
+        )
+    }
+
+    /// Verifies the payload auth signature, aggregated by msg.senders and the operator.
+    pub fn verify_auth_sig(&self, operator_key: Point) -> bool {
+        let mut keys = self.msg_senders();
+        keys.push(operator_key);
+
+        let key_agg_ctx = match MusigKeyAggCtx::new(&keys, None) {
+            Some(ctx) => ctx,
+            None => return false,
+        };
+
+        schnorr::verify_xonly(
+            key_agg_ctx.agg_key().serialize_xonly(),
+            self.auth_msg(),
+            self.payload_auth_sig,
+            SchnorrSigningMode::BIP340,
+        )
+    }
+
+    /// Returns the message signed by the payload auth signature, from the parts of a payload.
+    /// Commits to every header field, the ops price and the entry sighashes by index,
+    /// so that none of them can be altered once msg.senders sign.
+    pub fn auth_msg_from_parts(
+        session_id: [u8; 32],
+        ops_price: u32,
+        common_table: &CommonTable,
+        state_root: [u8; 32],
+        entries: &[Entry],
+    ) -> [u8; 32] {
+        let mut preimage = Vec::<u8>::new();
+
+        // Header
+        preimage.push(PAYLOAD_VERSION);
+        preimage.push(common_table.version());
+        preimage.extend(state_root);
+        preimage.extend(session_id);
+
+        // Ops price
+        preimage.extend(ops_price.to_le_bytes());
+
+        // Entries
+        for (index, entry) in entries.iter().enumerate() {
+            let entry_sighash = entry.auth_sighash();
+            preimage.extend((index as u32).to_le_bytes());
+            preimage.extend(entry_sighash);
+        }
+
+        preimage.hash(Some(HashTag::PayloadAuth))
+    }
+
+    /// Serializes the payload into its header and padded CPE body.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        let mut bytes = Vec::<u8>::new();
+
+        // Header.
+        bytes.push(self.version);
+        bytes.push(self.common_table.version());
+        bytes.extend(self.state_root);
+        bytes.extend(self.session_id);
+        bytes.extend(self.payload_auth_sig);
+
+        // Body. `BitVec::to_bytes` pads the last byte with zeros.
+        bytes.extend(self.encode_cpe()?.to_bytes());
+
+        Some(bytes)
+    }
+
+    /// Returns the payload wrapped in an envelope for the Payload TXO reveal script.
+    pub fn envelope(&self) -> Option<Vec<u8>> {
+        Some(envelope(&self.to_bytes()?))
+    }
+
+    /// Decodes a `Payload` from an envelope in the Payload TXO reveal script.
+    pub async fn decode_envelope(
+        script: &[u8],
+        txholder: &mut TxHolder,
+        ctx: &EntryDecodeCtx,
+    ) -> Result<Payload, CPEDecodingError> {
+        let bytes = parse_envelope(script).ok_or(CPEDecodingError::PayloadCPEDecodingError(
+            PayloadCPEDecodingError::EnvelopeNotFound,
+        ))?;
+
+        Self::decode(&bytes, txholder, ctx).await
+    }
+
+    /// Decodes a `Payload` from its header and padded CPE body.
+    pub async fn decode(
+        bytes: &[u8],
+        txholder: &mut TxHolder,
+        ctx: &EntryDecodeCtx,
+    ) -> Result<Payload, CPEDecodingError> {
+        // Decode the version.
+        let version = *bytes
+            .first()
+            .ok_or(CPEDecodingError::PayloadCPEDecodingError(
+                PayloadCPEDecodingError::FailedToCollectHeader,
+            ))?;
+
+        // Make sure the version is supported.
+        if version != PAYLOAD_VERSION {
+            return Err(CPEDecodingError::PayloadCPEDecodingError(
+                PayloadCPEDecodingError::UnsupportedVersion(version),
+            ));
+        }
+
+        // Make sure the header is present.
+        if bytes.len() < PAYLOAD_HEADER_LEN {
+            return Err(CPEDecodingError::PayloadCPEDecodingError(
+                PayloadCPEDecodingError::FailedToCollectHeader,
+            ));
+        }
+
+        // Decode the common table version.
+        let common_table_version = bytes[1];
+
+        // Make sure the common table is scheduled and active.
+        let common_table = CommonTable::scheduled(common_table_version).ok_or(
+            CPEDecodingError::PayloadCPEDecodingError(PayloadCPEDecodingError::UnknownCommonTable(
+                common_table_version,
+            )),
+        )?;
+        // Activation is checked at the payload reveal height, so that every node agrees.
+        if !common_table.is_active(ctx.bitcoin_height() as u64) {
+            return Err(CPEDecodingError::PayloadCPEDecodingError(
+                PayloadCPEDecodingError::InactiveCommonTable(common_table_version),
+            ));
+        }
+
+        // Decode the state root.
+        let state_root: [u8; 32] = bytes[2..34].try_into().map_err(|_| {
+            CPEDecodingError::PayloadCPEDecodingError(
+                PayloadCPEDecodingError::FailedToCollectHeader,
+            )
+        })?;
+
+        // Decode the session id.
+        let session_id: [u8; 32] = bytes[34..66].try_into().map_err(|_| {
+            CPEDecodingError::PayloadCPEDecodingError(
+                PayloadCPEDecodingError::FailedToCollectHeader,
+            )
+        })?;
+
+        // Decode the payload auth signature.
+        let payload_auth_sig: [u8; 64] =
+            bytes[66..PAYLOAD_HEADER_LEN].try_into().map_err(|_| {
+                CPEDecodingError::PayloadCPEDecodingError(
+                    PayloadCPEDecodingError::FailedToCollectHeader,
+                )
+            })?;
+
+        // Decode the body.
+        let body = BitVec::from_bytes(&bytes[PAYLOAD_HEADER_LEN..]);
+        let mut bit_stream = body.iter();
+
+        // Decode the ops price.
+        let ops_price = ShortVal::decode_cpe(&mut bit_stream)?.value();
+
+        // Decode the number of entries.
+        let num_entries = ShortVal::decode_cpe(&mut bit_stream)?.value();
+        if num_entries == 0 {
+            return Err(CPEDecodingError::PayloadCPEDecodingError(
+                PayloadCPEDecodingError::NoEntries,
+            ));
+        }
+
+        // Decode the entries.
+        let mut entries = Vec::<Entry>::new();
+        for _ in 0..num_entries {
+            entries.push(Entry::decode_cpe(&mut bit_stream, txholder, ctx, &common_table).await?);
+        }
+
+        // Remaining bits can only be the zero padding of the last byte.
+        let padding: Vec<bool> = bit_stream.collect();
+        if padding.len() >= 8 {
+            return Err(CPEDecodingError::PayloadCPEDecodingError(
+                PayloadCPEDecodingError::TrailingBits,
+            ));
+        }
+        if padding.iter().any(|bit| *bit) {
+            return Err(CPEDecodingError::PayloadCPEDecodingError(
+                PayloadCPEDecodingError::NonZeroPadding,
+            ));
+        }
+
+        // Derive the new registrations.
+        let (new_accounts, new_contracts) = registrations(&entries);
+
+        // Construct the `Payload`.
+        let payload = Payload {
+            version,
+            common_table,
+            state_root,
+            session_id,
+            ops_price,
+            entries,
+            new_accounts,
+            new_contracts,
+            payload_auth_sig,
+        };
+
+        // Return the `Payload`.
+        Ok(payload)
+    }
+}
+
+/// Collects the unregistered accounts and deployed contract ids referenced by the entries.
+fn registrations(entries: &Vec<Entry>) -> (Vec<Account>, Vec<[u8; 32]>) {
+    let mut new_accounts = Vec::<Account>::new();
+    let mut new_contracts = Vec::<[u8; 32]>::new();
+
+    let mut insert_account = |account: Account| {
+        if account.rank().is_none() && !new_accounts.contains(&account) {
+            new_accounts.push(account);
+        }
+    };
+
+    for entry in entries.iter() {
+        insert_account(entry.account());
+
+        match entry.main_combinator() {
+            Some(Combinator::Move(r#move)) => insert_account(r#move.to()),
+            Some(Combinator::BatchMove(batch_move)) => {
+                for to in batch_move.recipients() {
+                    insert_account(to);
+                }
+            }
+            Some(Combinator::Call(call)) => {
+                for element in call.calldata() {
+                    if let CallElement::Account(account) = element {
+                        insert_account(account);
+                    }
+                }
+            }
+            Some(Combinator::Deploy(deploy)) => {
+                let contract_id = deploy.contract_id();
+                if !new_contracts.contains(&contract_id) {
+                    new_contracts.push(contract_id);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    (new_accounts, new_contracts)
+}
+
+#[async_trait]
+impl CompactPayloadEncoding for Payload {
+    fn encode_cpe(&self) -> Option<BitVec> {
+        let mut bits = BitVec::new();
+
+        // Extend the ops price.
+        bits.extend(ShortVal::new(self.ops_price).encode_cpe()?);
+
+        // Extend the number of entries.
+        bits.extend(ShortVal::new(self.entries.len() as u32).encode_cpe()?);
+
+        // Extend the entries.
+        for entry in self.entries.iter() {
+            bits.extend(entry.encode_cpe_with_table(&self.common_table)?);
+        }
+
+        // Return the bits.
+        Some(bits)
+    }
+}
@@ -54,6 +54,10 @@ impl TapLeaf {
     pub fn tap_script(&self) -> Vec<u8> {
         self.tap_script.clone()
     }
+
+    pub fn leaf_version(&self) -> u8 {
+        self.leaf_version
+    }
 }
 
 #[derive(Clone)]
@@ -126,6 +130,13 @@ impl TapRoot {
         }
     }
 
+    pub fn key_and_script_path_weighted(inner_key: Point, leaves: Vec<(u32, TapLeaf)>) -> TapRoot {
+        TapRoot {
+            inner_key,
+            tree: Some(TapTree::new_weighted(leaves)),
+        }
+    }
+
     pub fn key_path_only(inner_key: Point) -> TapRoot {
         TapRoot {
             inner_key,
@@ -149,6 +160,14 @@ impl TapRoot {
         }
     }
 
+    pub fn script_path_only_weighted(leaves: Vec<(u32, TapLeaf)>) -> TapRoot {
+        let inner_key = Point::from_slice(&POINT_WITH_UNKNOWN_DISCRETE_LOGARITHM).unwrap();
+        TapRoot {
+            inner_key,
+            tree: Some(TapTree::new_weighted(leaves)),
+        }
+    }
+
     pub fn inner_key(&self) -> Point {
         self.inner_key
     }
@@ -203,16 +222,22 @@ impl TapRoot {
     }
 
     pub fn control_block(&self, index: usize) -> Option<ControlBlock> {
-        let path = match &self.tree {
-            Some(tree) => tree.path(index),
+        let (leaf, path) = match &self.tree {
+            Some(tree) => (tree.leaves().get(index)?.to_owned(), tree.path(index)),
             None => return None,
         };
 
         let inner_key = self.inner_key();
         let parity: bool = self.tweaked_key_parity()?;
 
-        Some(ControlBlock::new(inner_key, parity, path))
+        Some(ControlBlock::new_version(
+            inner_key,
+            parity,
+            leaf.leaf_version(),
+            path,
+        ))
     }
+
     pub fn tree(&self) -> Option<TapTree> {
         self.tree.clone()
     }
@@ -221,6 +246,8 @@ impl TapRoot {
 #[derive(Clone)]
 pub struct TapTree {
     leaves: Vec<TapLeaf>,
+    // Leaf weights, if the tree is Huffman-weighted rather than balanced.
+    weights: Option<Vec<u32>>,
     tap_branch: [u8; 32],
 }
 
@@ -235,10 +262,35 @@ impl TapTree {
 
         TapTree {
             leaves: leaves.clone(),
+            weights: None,
+            tap_branch,
+        }
+    }
+
+    /// Builds a Huffman tree from leaves weighted by their spending likelihood,
+    /// so that more likely leaves sit closer to the root and have shorter merkle paths.
+    pub fn new_weighted(weighted_leaves: Vec<(u32, TapLeaf)>) -> TapTree {
+        let weights: Vec<u32> = weighted_leaves.iter().map(|(weight, _)| *weight).collect();
+        let leaves: Vec<TapLeaf> = weighted_leaves.into_iter().map(|(_, leaf)| leaf).collect();
+
+        let uppermost_branch = weighted_tree_builder(&leaves, &weights, None).0;
+
+        let tap_branch = match &uppermost_branch {
+            Branch::Leaf(leaf) => leaf.hash(),
+            Branch::Branch(branch) => branch.hash(),
+        };
+
+        TapTree {
+            leaves,
+            weights: Some(weights),
             tap_branch,
         }
     }
 
+    pub fn weights(&self) -> Option<Vec<u32>> {
+        self.weights.clone()
+    }
+
     pub fn leaves(&self) -> Vec<TapLeaf> {
         self.leaves.clone()
     }
@@ -250,12 +302,26 @@ impl TapTree {
     pub fn path(&self, index: usize) -> Vec<u8> {
         // Given leaf index return the merkle path
 
-        let path_vec = match tree_builder(&self.leaves, Some(index)).1 {
+        let path = match &self.weights {
+            Some(weights) => weighted_tree_builder(&self.leaves, weights, Some(index)).1,
+            None => tree_builder(&self.leaves, Some(index)).1,
+        };
+
+        let path_vec = match path {
             Some(vec) => vec,
             None => panic!(),
         };
         path_vec
     }
+
+    /// Returns the leaf indexes in depth-first order, e.g. for BIP-371 `tap_tree` fields.
+    pub fn depth_first_order(&self) -> Vec<usize> {
+        match &self.weights {
+            Some(weights) => weighted_tree_builder(&self.leaves, weights, None).2,
+            // Balanced trees pair leaves in order, so leaf order is already depth-first.
+            None => (0..self.leaves.len()).collect(),
+        }
+    }
 }
 
 // tree_builder returns given a vector of leaves, the tree root,
@@ -379,6 +445,89 @@ pub fn tree_builder(leaves: &Vec<TapLeaf>, index: Option<usize>) -> (Branch, Opt
     }
 }
 
+// weighted_tree_builder returns given a vector of leaves and their weights, the Huffman tree root,
+// optionally a merkle path corresponding to some leaf, and the leaf indexes in depth-first order
+pub fn weighted_tree_builder(
+    leaves: &Vec<TapLeaf>,
+    weights: &Vec<u32>,
+    index: Option<usize>,
+) -> (Branch, Option<Vec<u8>>, Vec<usize>) {
+    if leaves.len() == 0 {
+        panic!("TapTree must be initialized with at least one TapLeaf.");
+    }
+    assert_eq!(leaves.len(), weights.len());
+
+    let mut path: Vec<u8> = Vec::<u8>::new();
+
+    // Pending nodes as (weight, insertion sequence, branch, leaf indexes in depth-first order)
+    let mut nodes: Vec<(u64, usize, Branch, Vec<usize>)> = leaves
+        .iter()
+        .zip(weights.iter())
+        .enumerate()
+        .map(|(i, (leaf, weight))| (*weight as u64, i, leaf.into_branch(), vec![i]))
+        .collect();
+
+    let mut sequence: usize = leaves.len();
+
+    while nodes.len() > 1 {
+        // Pop the two lightest nodes, ties broken by insertion order
+        let first = pop_lightest(&mut nodes);
+        let second = pop_lightest(&mut nodes);
+
+        let first_bytes = match &first.2 {
+            Branch::Leaf(leaf) => leaf.hash(),
+            Branch::Branch(branch) => branch.hash(),
+        };
+
+        let second_bytes = match &second.2 {
+            Branch::Leaf(leaf) => leaf.hash(),
+            Branch::Branch(branch) => branch.hash(),
+        };
+
+        // Lookup match?
+        if let Some(index) = index {
+            if first.3.contains(&index) {
+                path.extend(&second_bytes);
+            } else if second.3.contains(&index) {
+                path.extend(&first_bytes);
+            }
+        }
+
+        let new_branch: TapBranch = TapBranch::new(first.2, second.2);
+
+        let mut order = first.3;
+        order.extend(second.3);
+
+        nodes.push((
+            first.0 + second.0,
+            sequence,
+            new_branch.into_branch(),
+            order,
+        ));
+        sequence += 1;
+    }
+
+    let (_, _, branch, order) = nodes.remove(0);
+
+    match &index {
+        Some(_) => (branch, Some(path), order),
+        None => (branch, None, order),
+    }
+}
+
+fn pop_lightest(
+    nodes: &mut Vec<(u64, usize, Branch, Vec<usize>)>,
+) -> (u64, usize, Branch, Vec<usize>) {
+    let position = nodes
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, (weight, sequence, _, _))| (*weight, *sequence))
+        .map(|(position, _)| position)
+        .unwrap();
+
+    nodes.remove(position)
+}
+
 pub struct ControlBlock {
     inner_key: Point,
     parity: bool,
@@ -396,6 +545,20 @@ impl ControlBlock {
         }
     }
 
+    pub fn new_version(
+        inner_key: Point,
+        parity: bool,
+        leaf_version: u8,
+        path: Vec<u8>,
+    ) -> ControlBlock {
+        ControlBlock {
+            inner_key,
+            parity,
+            leaf_version,
+            path,
+        }
+    }
+
     pub fn to_vec(&self) -> Vec<u8> {
         let mut vec: Vec<u8> = Vec::<u8>::new();
 
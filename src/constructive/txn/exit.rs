@@ -0,0 +1,228 @@
+use super::{
+    sighash::{sweep_sighash, sweep_witness},
+    vtxo_tree::ANCHOR_SPK,
+};
+use crate::{
+    constructive::{
+        taproot::{TapRoot, P2TR},
+        txo::vtxo::VTXO,
+    },
+    inscriptive::baked::EXPIRY_BLOCKS,
+    transmutive::secp::schnorr::{self, SchnorrSigningMode},
+};
+use bitcoin::{
+    absolute, consensus::encode::serialize_hex, transaction, Amount, OutPoint, ScriptBuf, Sequence,
+    Transaction, TxIn, TxOut, Witness,
+};
+use secp::Point;
+use serde_json::{json, Value};
+
+/// Unilateral exit package of a `VTXO`.
+///
+/// Contains the pre-signed virtual tree branch from the projector down to the `VTXO`,
+/// followed by the sweep spending the `VTXO` from its exit path `(Self after 3 months)`.
+/// Branch transactions carry no fees, and are bumped through their anchors with `cpfp`.
+#[derive(Clone)]
+pub struct ExitPackage {
+    vtxo: VTXO,
+    branch: Vec<Transaction>,
+    sweep: Transaction,
+}
+
+impl ExitPackage {
+    /// Creates a new `ExitPackage` from a `VTXO` and its stored virtual tree branch.
+    /// The sweep pays the `VTXO` value minus the fee to `sweep_spk`, signed with the account secret key.
+    /// Returns `None` if the branch does not lead to the `VTXO` or is not signed.
+    pub fn new(
+        vtxo: &VTXO,
+        branch: &Vec<Transaction>,
+        secret_key: [u8; 32],
+        sweep_spk: Vec<u8>,
+        sweep_fee: u64,
+    ) -> Option<ExitPackage> {
+        let mut package = ExitPackage::new_unsigned(vtxo, branch, sweep_spk, sweep_fee)?;
+
+        // Sign the sweep from the exit path.
+        let prevouts = vec![package.vtxo_txout()?];
+        let sighash = sweep_sighash(vtxo, &package.sweep, 0, &prevouts).ok()?;
+        let sig = schnorr::sign(secret_key, sighash, SchnorrSigningMode::BIP340)?;
+        package.sweep.input[0].witness = sweep_witness(vtxo, sig)?;
+
+        Some(package)
+    }
+
+    /// Creates a new `ExitPackage` with an unsigned sweep, to be signed externally, e.g. through a PSBT.
+    /// Returns `None` if the branch does not lead to the `VTXO` or is not signed.
+    pub fn new_unsigned(
+        vtxo: &VTXO,
+        branch: &Vec<Transaction>,
+        sweep_spk: Vec<u8>,
+        sweep_fee: u64,
+    ) -> Option<ExitPackage> {
+        let outpoint = vtxo.outpoint()?;
+        let value = vtxo.value()?;
+
+        // #1 Validate the branch.
+        let leaf_tx = branch.last()?;
+        if leaf_tx.compute_txid() != outpoint.txid {
+            return None;
+        }
+
+        // The leaf output must be the VTXO.
+        let vtxo_txout = leaf_tx.output.get(outpoint.vout as usize)?.to_owned();
+        if vtxo_txout.script_pubkey.as_bytes() != vtxo.spk()?.as_slice()
+            || vtxo_txout.value.to_sat() != value
+        {
+            return None;
+        }
+
+        // Each transaction must spend its predecessor and be signed.
+        for (index, tx) in branch.iter().enumerate() {
+            let input = tx.input.get(0)?;
+            if input.witness.is_empty() {
+                return None;
+            }
+            if index > 0 && input.previous_output.txid != branch[index - 1].compute_txid() {
+                return None;
+            }
+        }
+
+        // #2 Construct the sweep.
+        let sweep = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: outpoint,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::from_consensus(EXPIRY_BLOCKS),
+                witness: Witness::new(),
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(value.checked_sub(sweep_fee)?),
+                script_pubkey: ScriptBuf::from(sweep_spk),
+            }],
+        };
+
+        let package = ExitPackage {
+            vtxo: vtxo.to_owned(),
+            branch: branch.to_owned(),
+            sweep,
+        };
+
+        Some(package)
+    }
+
+    /// Returns the `VTXO` being exited.
+    pub fn vtxo(&self) -> VTXO {
+        self.vtxo.clone()
+    }
+
+    /// Returns the virtual tree branch, root first.
+    pub fn branch(&self) -> Vec<Transaction> {
+        self.branch.clone()
+    }
+
+    /// Returns the timelocked sweep to the account.
+    pub fn sweep(&self) -> Transaction {
+        self.sweep.clone()
+    }
+
+    /// Returns whether the sweep is signed.
+    pub fn is_signed(&self) -> bool {
+        !self.sweep.input[0].witness.is_empty()
+    }
+
+    /// Replaces the sweep with its externally signed counterpart.
+    /// The signed sweep must be the same transaction, carrying a witness.
+    pub fn set_sweep(&mut self, signed_sweep: &Transaction) -> bool {
+        if signed_sweep.compute_txid() != self.sweep.compute_txid() {
+            return false;
+        }
+
+        match signed_sweep.input.get(0) {
+            Some(input) if !input.witness.is_empty() => {
+                self.sweep = signed_sweep.to_owned();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the `VTXO` output spent by the sweep.
+    pub fn vtxo_txout(&self) -> Option<TxOut> {
+        let txout = TxOut {
+            value: Amount::from_sat(self.vtxo.value()?),
+            script_pubkey: ScriptBuf::from(self.vtxo.spk()?),
+        };
+
+        Some(txout)
+    }
+
+    /// Returns the ordered set of transactions to broadcast.
+    /// The sweep becomes valid `EXPIRY_BLOCKS` after the leaf transaction confirms.
+    pub fn txs(&self) -> Vec<Transaction> {
+        let mut txs = self.branch.clone();
+        txs.push(self.sweep.clone());
+        txs
+    }
+
+    /// Returns a JSON representation of the package with raw transaction hexes.
+    pub fn json(&self) -> Value {
+        json!({
+            "vtxo": self.vtxo.json(),
+            "branch": self.branch.iter().map(|tx| serialize_hex(tx)).collect::<Vec<String>>(),
+            "sweep": serialize_hex(&self.sweep),
+            "sweep_csv_blocks": EXPIRY_BLOCKS,
+        })
+    }
+
+    /// Constructs an unsigned CPFP child bumping a branch transaction through its anchor.
+    /// The child spends the anchor and a funding output, and pays the change to `change_spk`.
+    pub fn cpfp(
+        parent: &Transaction,
+        funding_outpoint: OutPoint,
+        funding_txout: &TxOut,
+        change_spk: Vec<u8>,
+        fee: u64,
+    ) -> Option<Transaction> {
+        // Locate the anchor.
+        let anchor_vout = parent
+            .output
+            .iter()
+            .position(|txout| txout.script_pubkey.as_bytes() == ANCHOR_SPK)?;
+
+        let anchor_value = parent.output[anchor_vout].value.to_sat();
+        let change_value = (funding_txout.value.to_sat() + anchor_value).checked_sub(fee)?;
+
+        let child = Transaction {
+            // Anchor spends are v3 to join the parent package.
+            version: transaction::Version(3),
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![
+                TxIn {
+                    previous_output: OutPoint::new(parent.compute_txid(), anchor_vout as u32),
+                    script_sig: ScriptBuf::new(),
+                    sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                    witness: Witness::new(),
+                },
+                TxIn {
+                    previous_output: funding_outpoint,
+                    script_sig: ScriptBuf::new(),
+                    sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                    witness: Witness::new(),
+                },
+            ],
+            output: vec![TxOut {
+                value: Amount::from_sat(change_value),
+                script_pubkey: ScriptBuf::from(change_spk),
+            }],
+        };
+
+        Some(child)
+    }
+}
+
+/// Returns the script pubkey of the account's `Self` output, a key-path only P2TR.
+pub fn self_spk(account_key: Point) -> Option<Vec<u8>> {
+    TapRoot::key_path_only(account_key).spk()
+}
@@ -1,2 +1,10 @@
 pub mod txholder;
 pub mod ext;
+pub mod pool;
+pub mod pool_error;
+pub mod vtxo_tree;
+pub mod exit;
+pub mod sighash;
+pub mod sighash_error;
+pub mod psbt;
+pub mod psbt_error;
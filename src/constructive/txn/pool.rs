@@ -0,0 +1,364 @@
+use super::{
+    pool_error::PoolTxError,
+    sighash::{key_path_witness, sweep_witness},
+    txholder::MAX_EXTRA_INS,
+};
+use crate::{
+    constructive::{
+        payload::payload::Payload,
+        taproot::P2TR,
+        txo::{lift::Lift, payload::PayloadTXO, projector::Projector},
+    },
+    inscriptive::{baked::EXPIRY_BLOCKS, set::utxo_set::UTXO_SET},
+    operative::session::ccontext::CSessionCtx,
+};
+use bitcoin::{
+    absolute, transaction, Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness,
+};
+use secp::Point;
+
+/// Value of a single `Connector` projected by the `Connector Projector`.
+pub const CONNECTOR_VALUE: u64 = 450;
+
+/// Minimum value of the `Payload` output.
+pub const MIN_PAYLOAD_VALUE: u64 = 330;
+
+/// Builds the `Pool Transaction` of a finalized coordinator session.
+///
+/// Inputs: `Payload`, expired projectors (extra inputs), `Lift`s.
+/// Outputs: `Payload`, `VTXO Projector`, `Connector Projector`.
+pub struct PoolTxBuilder {
+    // Previous payload output.
+    prev_payload_outpoint: OutPoint,
+    prev_payload_txout: TxOut,
+    prev_payload_witness: Option<Witness>,
+    // Expired projectors swept by the operator.
+    expired_projectors: Vec<(OutPoint, TxOut, Witness)>,
+    // Lifts spent by the session, along with their aggregated signatures.
+    lifts: Vec<(Lift, [u8; 64])>,
+    // Session outputs.
+    payload_txo: PayloadTXO,
+    vtxo_projector: Projector,
+    connector_projector: Projector,
+    num_connectors: usize,
+}
+
+impl PoolTxBuilder {
+    /// Creates a new `PoolTxBuilder` from a finalized session and the previous pool transaction.
+    /// The new payload carries the header the session fixed upon lock.
+    /// Its output is gated by the given operator hashlocks.
+    pub fn new(
+        session: &CSessionCtx,
+        prev_pool_tx: &Transaction,
+        hashlocks: &Vec<[u8; 32]>,
+    ) -> Result<PoolTxBuilder, PoolTxError> {
+        let operator_key = session
+            .operator_key()
+            .ok_or(PoolTxError::OperatorKeyNotFound)?;
+        let payload = session.payload().ok_or(PoolTxError::PayloadNotReady)?;
+        let vtxo_projector = session
+            .vtxo_projector()
+            .ok_or(PoolTxError::VTXOProjectorNotFound)?;
+        let connector_projector = session
+            .connector_projector()
+            .ok_or(PoolTxError::ConnectorProjectorNotFound)?;
+        let lifts = session
+            .lift_prevtxo_sigs()
+            .ok_or(PoolTxError::LiftSigsNotReady)?;
+
+        PoolTxBuilder::from_parts(
+            prev_pool_tx,
+            &payload,
+            operator_key,
+            hashlocks,
+            vtxo_projector,
+            connector_projector,
+            lifts,
+            session.num_connectors(),
+        )
+    }
+
+    /// Creates a new `PoolTxBuilder` from the parts of a finalized session and the previous pool transaction.
+    pub fn from_parts(
+        prev_pool_tx: &Transaction,
+        payload: &Payload,
+        operator_key: Point,
+        hashlocks: &Vec<[u8; 32]>,
+        vtxo_projector: Projector,
+        connector_projector: Projector,
+        lifts: Vec<(Lift, [u8; 64])>,
+        num_connectors: usize,
+    ) -> Result<PoolTxBuilder, PoolTxError> {
+        // Payload output is the first output of the previous pool transaction.
+        let prev_payload_txout = prev_pool_tx
+            .output
+            .get(0)
+            .ok_or(PoolTxError::PrevPayloadOutputNotFound)?
+            .to_owned();
+        let prev_payload_outpoint = OutPoint::new(prev_pool_tx.compute_txid(), 0);
+
+        let payload_txo = PayloadTXO::new(payload, operator_key, hashlocks)
+            .ok_or(PoolTxError::InvalidHashlocks)?;
+
+        // Lifts must carry their outpoints and values.
+        for (lift, _) in lifts.iter() {
+            if lift.outpoint().is_none() || lift.value().is_none() {
+                return Err(PoolTxError::InvalidLift);
+            }
+        }
+
+        let builder = PoolTxBuilder {
+            prev_payload_outpoint,
+            prev_payload_txout,
+            prev_payload_witness: None,
+            expired_projectors: Vec::new(),
+            lifts,
+            payload_txo,
+            vtxo_projector,
+            connector_projector,
+            num_connectors,
+        };
+
+        Ok(builder)
+    }
+
+    /// Reveals the previous payload by spending the previous payload output from its operator path,
+    /// unlocking its hashlocks with the given preimages.
+    pub fn reveal_prev_payload(
+        &mut self,
+        prev_payload_txo: &PayloadTXO,
+        operator_sig: [u8; 64],
+        preimages: &Vec<Vec<u8>>,
+    ) -> Result<(), PoolTxError> {
+        // Make sure the previous payload matches the previous payload output.
+        let spk = prev_payload_txo
+            .spk()
+            .ok_or(PoolTxError::TaprootConstructionError)?;
+        if self.prev_payload_txout.script_pubkey.as_bytes() != spk.as_slice() {
+            return Err(PoolTxError::PrevPayloadMismatch);
+        }
+
+        let witness = prev_payload_txo
+            .operator_witness(operator_sig, preimages)
+            .ok_or(PoolTxError::InvalidPreimages)?;
+        self.prev_payload_witness = Some(witness);
+
+        Ok(())
+    }
+
+    /// Adds an expired projector to be swept by the operator as an extra input.
+    pub fn add_expired_projector(
+        &mut self,
+        outpoint: OutPoint,
+        txout: TxOut,
+        projector: &Projector,
+        operator_sig: [u8; 64],
+    ) -> Result<(), PoolTxError> {
+        if self.expired_projectors.len() >= MAX_EXTRA_INS as usize {
+            return Err(PoolTxError::TooManyExtraInputs);
+        }
+
+        // Make sure the projector matches the output.
+        let spk = projector
+            .spk()
+            .ok_or(PoolTxError::TaprootConstructionError)?;
+        if txout.script_pubkey.as_bytes() != spk.as_slice() {
+            return Err(PoolTxError::ExpiredProjectorMismatch);
+        }
+
+        let witness =
+            sweep_witness(projector, operator_sig).ok_or(PoolTxError::TaprootConstructionError)?;
+        self.expired_projectors.push((outpoint, txout, witness));
+
+        Ok(())
+    }
+
+    /// Returns the new payload output, to be revealed by the next pool transaction.
+    pub fn payload_txo(&self) -> PayloadTXO {
+        self.payload_txo.clone()
+    }
+
+    /// Returns the number of extra inputs.
+    pub fn extra_in(&self) -> u8 {
+        self.expired_projectors.len() as u8
+    }
+
+    /// Returns the total value of the `VTXO Projector`, i.e. the sum of lifted values.
+    pub fn vtxo_projector_value(&self) -> u64 {
+        self.lifts
+            .iter()
+            .map(|(lift, _)| lift.value().unwrap_or(0))
+            .sum()
+    }
+
+    /// Returns the total value of the `Connector Projector`.
+    pub fn connector_projector_value(&self) -> u64 {
+        self.num_connectors as u64 * CONNECTOR_VALUE
+    }
+
+    /// Builds the signed `Pool Transaction`.
+    /// The operator liquidity carried by the previous payload and the swept projectors
+    /// is forwarded to the new payload output, minus the fee.
+    pub fn build(&self, fee: u64) -> Result<Transaction, PoolTxError> {
+        let prev_payload_witness = self
+            .prev_payload_witness
+            .clone()
+            .ok_or(PoolTxError::PrevPayloadNotRevealed)?;
+
+        // #1 Inputs.
+        let mut input = Vec::<TxIn>::new();
+        let mut input_value = self.prev_payload_txout.value.to_sat();
+
+        // Payload input.
+        input.push(TxIn {
+            previous_output: self.prev_payload_outpoint,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: prev_payload_witness,
+        });
+
+        // Expired projector inputs are swept after the expiry timelock.
+        for (outpoint, txout, witness) in self.expired_projectors.iter() {
+            input_value += txout.value.to_sat();
+            input.push(TxIn {
+                previous_output: outpoint.to_owned(),
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::from_consensus(EXPIRY_BLOCKS),
+                witness: witness.to_owned(),
+            });
+        }
+
+        // Lift inputs are spent from the collaborative key path.
+        for (lift, sig) in self.lifts.iter() {
+            let outpoint = lift.outpoint().ok_or(PoolTxError::InvalidLift)?;
+            input_value += lift.value().ok_or(PoolTxError::InvalidLift)?;
+            input.push(TxIn {
+                previous_output: outpoint,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: key_path_witness(sig.to_owned()),
+            });
+        }
+
+        // #2 Output values.
+        let vtxo_projector_value = self.vtxo_projector_value();
+        let connector_projector_value = self.connector_projector_value();
+        let payload_value = input_value
+            .checked_sub(vtxo_projector_value + connector_projector_value + fee)
+            .ok_or(PoolTxError::InsufficientFunds)?;
+        if payload_value < MIN_PAYLOAD_VALUE {
+            return Err(PoolTxError::InsufficientFunds);
+        }
+
+        // #3 Outputs.
+        let payload_spk = self
+            .payload_txo
+            .spk()
+            .ok_or(PoolTxError::TaprootConstructionError)?;
+        let vtxo_projector_spk = self
+            .vtxo_projector
+            .spk()
+            .ok_or(PoolTxError::TaprootConstructionError)?;
+        let connector_projector_spk = self
+            .connector_projector
+            .spk()
+            .ok_or(PoolTxError::TaprootConstructionError)?;
+
+        let output = vec![
+            TxOut {
+                value: Amount::from_sat(payload_value),
+                script_pubkey: ScriptBuf::from(payload_spk),
+            },
+            TxOut {
+                value: Amount::from_sat(vtxo_projector_value),
+                script_pubkey: ScriptBuf::from(vtxo_projector_spk),
+            },
+            TxOut {
+                value: Amount::from_sat(connector_projector_value),
+                script_pubkey: ScriptBuf::from(connector_projector_spk),
+            },
+        ];
+
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input,
+            output,
+        };
+
+        Ok(tx)
+    }
+
+    /// Builds the signed `Pool Transaction` and verifies it against the UTXO set.
+    pub async fn build_and_verify(
+        &self,
+        fee: u64,
+        utxo_set: &UTXO_SET,
+    ) -> Result<Transaction, PoolTxError> {
+        let tx = self.build(fee)?;
+        self.verify(&tx, utxo_set).await?;
+        Ok(tx)
+    }
+
+    /// Verifies a `Pool Transaction` against the UTXO set.
+    /// Every input must spend an unspent output matching the expected prevout,
+    /// carry a witness, and the inputs must cover the outputs.
+    pub async fn verify(&self, tx: &Transaction, utxo_set: &UTXO_SET) -> Result<(), PoolTxError> {
+        // Expected prevouts in input order.
+        let mut expected = Vec::<(OutPoint, TxOut)>::new();
+        expected.push((self.prev_payload_outpoint, self.prev_payload_txout.clone()));
+        for (outpoint, txout, _) in self.expired_projectors.iter() {
+            expected.push((outpoint.to_owned(), txout.to_owned()));
+        }
+        for (lift, _) in self.lifts.iter() {
+            let outpoint = lift.outpoint().ok_or(PoolTxError::InvalidLift)?;
+            let value = lift.value().ok_or(PoolTxError::InvalidLift)?;
+            let spk = lift.spk().ok_or(PoolTxError::TaprootConstructionError)?;
+            expected.push((
+                outpoint,
+                TxOut {
+                    value: Amount::from_sat(value),
+                    script_pubkey: ScriptBuf::from(spk),
+                },
+            ));
+        }
+
+        if tx.input.len() != expected.len() || tx.output.len() != 3 {
+            return Err(PoolTxError::InvalidOutputLayout);
+        }
+
+        let mut input_value: u64 = 0;
+        {
+            let _utxo_set = utxo_set.lock().await;
+            for (index, (txin, (outpoint, txout))) in tx.input.iter().zip(expected).enumerate() {
+                let index = index as u32;
+
+                // The input must spend the expected outpoint.
+                if txin.previous_output != outpoint {
+                    return Err(PoolTxError::PrevoutMismatch(index));
+                }
+
+                // The prevout must be unspent and match the expected output.
+                let utxo = _utxo_set
+                    .txout_by_outpoint(&outpoint)
+                    .ok_or(PoolTxError::PrevoutNotFound(index))?;
+                if utxo != txout {
+                    return Err(PoolTxError::PrevoutMismatch(index));
+                }
+
+                if txin.witness.is_empty() {
+                    return Err(PoolTxError::MissingWitness(index));
+                }
+
+                input_value += utxo.value.to_sat();
+            }
+        }
+
+        let output_value: u64 = tx.output.iter().map(|txout| txout.value.to_sat()).sum();
+        if output_value > input_value {
+            return Err(PoolTxError::InsufficientFunds);
+        }
+
+        Ok(())
+    }
+}
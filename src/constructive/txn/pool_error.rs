@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// Error type for building and verifying a `Pool Transaction`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PoolTxError {
+    // Previous pool transaction has no payload output.
+    PrevPayloadOutputNotFound,
+    // Previous payload does not match the previous payload output.
+    PrevPayloadMismatch,
+    // Previous payload is not revealed.
+    PrevPayloadNotRevealed,
+    // Session payload is not ready, i.e. the payload auth signature is not aggregated.
+    PayloadNotReady,
+    // Payload output hashlocks are missing.
+    InvalidHashlocks,
+    // Preimages do not unlock the previous payload hashlocks.
+    InvalidPreimages,
+    // Session has no operator key.
+    OperatorKeyNotFound,
+    // Session has no VTXO projector.
+    VTXOProjectorNotFound,
+    // Session has no connector projector.
+    ConnectorProjectorNotFound,
+    // Lift signatures are not fully aggregated.
+    LiftSigsNotReady,
+    // Lift carries no outpoint or value.
+    InvalidLift,
+    // Expired projector does not match its output.
+    ExpiredProjectorMismatch,
+    // Too many extra inputs.
+    TooManyExtraInputs,
+    // Inputs do not cover the outputs and the fee.
+    InsufficientFunds,
+    // Failed to construct a taproot output.
+    TaprootConstructionError,
+    // Input prevout is not in the UTXO set.
+    PrevoutNotFound(u32),
+    // Input prevout does not match the expected output.
+    PrevoutMismatch(u32),
+    // Input carries no witness.
+    MissingWitness(u32),
+    // Output layout is invalid.
+    InvalidOutputLayout,
+}
@@ -0,0 +1,297 @@
+use super::{exit::ExitPackage, psbt_error::PsbtError};
+use crate::{
+    constructive::{
+        taproot::{TapLeaf, TapRoot, P2TR},
+        txo::lift::Lift,
+    },
+    inscriptive::baked::EXPIRY_BLOCKS,
+    transmutive::codec::base64,
+};
+use bitcoin::{
+    absolute,
+    hashes::Hash,
+    key::XOnlyPublicKey,
+    psbt::{Input, Output, Psbt},
+    taproot::{ControlBlock, LeafVersion, TapLeafHash, TapNodeHash, TapTree, TaprootBuilder},
+    transaction, Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness,
+};
+use secp::Point;
+
+/// Constructs a PSBT funding a `Lift` address from external wallet outputs.
+///
+/// The lift output comes first, followed by the optional change output.
+/// The lift output carries its internal key and tap tree, so that signers can verify the address.
+pub fn lift_funding_psbt(
+    lift: &Lift,
+    value: u64,
+    funding: &Vec<(OutPoint, TxOut)>,
+    change: Option<TxOut>,
+) -> Result<Psbt, PsbtError> {
+    if funding.is_empty() {
+        return Err(PsbtError::NoFundingInputs);
+    }
+
+    // The inputs must cover the outputs.
+    let input_value: u64 = funding.iter().map(|(_, txout)| txout.value.to_sat()).sum();
+    let change_value = change
+        .as_ref()
+        .map(|txout| txout.value.to_sat())
+        .unwrap_or(0);
+    if input_value < value + change_value {
+        return Err(PsbtError::InsufficientFunds);
+    }
+
+    let taproot = lift.taproot().ok_or(PsbtError::TaprootConstructionError)?;
+    let lift_spk = taproot.spk().ok_or(PsbtError::TaprootConstructionError)?;
+
+    let mut output = vec![TxOut {
+        value: Amount::from_sat(value),
+        script_pubkey: ScriptBuf::from(lift_spk),
+    }];
+    if let Some(change) = change {
+        output.push(change);
+    }
+
+    let tx = Transaction {
+        version: transaction::Version::TWO,
+        lock_time: absolute::LockTime::ZERO,
+        input: funding
+            .iter()
+            .map(|(outpoint, _)| TxIn {
+                previous_output: outpoint.to_owned(),
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::new(),
+            })
+            .collect(),
+        output,
+    };
+
+    let mut psbt = Psbt::from_unsigned_tx(tx).map_err(|_| PsbtError::PsbtConstructionError)?;
+
+    // Funding inputs are external; only their prevouts are known.
+    for (input, (_, txout)) in psbt.inputs.iter_mut().zip(funding.iter()) {
+        input.witness_utxo = Some(txout.to_owned());
+    }
+
+    set_output_tap_fields(&mut psbt.outputs[0], &taproot)?;
+
+    Ok(psbt)
+}
+
+/// Constructs a PSBT spending a `Lift` from its timelocked self-recovery path `(Self after 3 months)`.
+pub fn lift_recovery_psbt(lift: &Lift, sweep_spk: Vec<u8>, fee: u64) -> Result<Psbt, PsbtError> {
+    let outpoint = lift.outpoint().ok_or(PsbtError::InvalidLift)?;
+    let value = lift.value().ok_or(PsbtError::InvalidLift)?;
+
+    let taproot = lift.taproot().ok_or(PsbtError::TaprootConstructionError)?;
+    let lift_txout = TxOut {
+        value: Amount::from_sat(value),
+        script_pubkey: ScriptBuf::from(taproot.spk().ok_or(PsbtError::TaprootConstructionError)?),
+    };
+
+    let tx = Transaction {
+        version: transaction::Version::TWO,
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: outpoint,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::from_consensus(EXPIRY_BLOCKS),
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::from_sat(value.checked_sub(fee).ok_or(PsbtError::InsufficientFunds)?),
+            script_pubkey: ScriptBuf::from(sweep_spk),
+        }],
+    };
+
+    let mut psbt = Psbt::from_unsigned_tx(tx).map_err(|_| PsbtError::PsbtConstructionError)?;
+    psbt.inputs[0].witness_utxo = Some(lift_txout);
+    set_input_tap_fields(&mut psbt.inputs[0], &taproot)?;
+
+    Ok(psbt)
+}
+
+/// Constructs a PSBT of the sweep of a unilateral exit package.
+///
+/// The virtual tree branch is pre-signed and broadcast as is; only the sweep
+/// spending the `VTXO` from its exit path `(Self after 3 months)` needs the account signature.
+/// If the package sweep is already signed, the input is exported finalized.
+pub fn exit_psbt(package: &ExitPackage) -> Result<Psbt, PsbtError> {
+    let vtxo = package.vtxo();
+    let taproot = vtxo.taproot().ok_or(PsbtError::TaprootConstructionError)?;
+    let vtxo_txout = package
+        .vtxo_txout()
+        .ok_or(PsbtError::TaprootConstructionError)?;
+
+    // Strip the witness, if any, from the unsigned transaction.
+    let sweep = package.sweep();
+    let mut unsigned_sweep = sweep.clone();
+    unsigned_sweep.input[0].witness = Witness::new();
+
+    let mut psbt =
+        Psbt::from_unsigned_tx(unsigned_sweep).map_err(|_| PsbtError::PsbtConstructionError)?;
+    psbt.inputs[0].witness_utxo = Some(vtxo_txout);
+
+    match package.is_signed() {
+        true => psbt.inputs[0].final_script_witness = Some(sweep.input[0].witness.clone()),
+        false => set_input_tap_fields(&mut psbt.inputs[0], &taproot)?,
+    }
+
+    Ok(psbt)
+}
+
+/// Encodes a PSBT into base64.
+pub fn export_psbt(psbt: &Psbt) -> String {
+    base64::encode(&psbt.serialize())
+}
+
+/// Decodes a base64 encoded, possibly partially signed PSBT.
+pub fn import_psbt(encoded: &str) -> Result<Psbt, PsbtError> {
+    let bytes = base64::decode(encoded).ok_or(PsbtError::Base64DecodingError)?;
+    Psbt::deserialize(&bytes).map_err(|_| PsbtError::PsbtDecodingError)
+}
+
+/// Merges the signatures of an imported PSBT into a PSBT of the same transaction.
+pub fn combine_psbt(psbt: &mut Psbt, imported: Psbt) -> Result<(), PsbtError> {
+    if psbt.unsigned_tx.compute_txid() != imported.unsigned_tx.compute_txid() {
+        return Err(PsbtError::UnsignedTxMismatch);
+    }
+
+    psbt.combine(imported)
+        .map_err(|_| PsbtError::UnsignedTxMismatch)
+}
+
+/// Finalizes the signed taproot inputs of a PSBT, and returns the number of inputs finalized.
+///
+/// A key-path signature takes precedence over script-path signatures.
+/// A script-path signature is finalized together with its leaf script and control block.
+/// Inputs that are already finalized or carry no signatures are left untouched.
+pub fn finalize_psbt(psbt: &mut Psbt) -> Result<usize, PsbtError> {
+    let mut finalized = 0;
+
+    for (index, input) in psbt.inputs.iter_mut().enumerate() {
+        if input.final_script_witness.is_some() {
+            continue;
+        }
+
+        let witness = match input.tap_key_sig {
+            // Key path.
+            Some(sig) => Witness::from_slice(&[sig.to_vec()]),
+            // Script path.
+            None => {
+                let ((_, leaf_hash), sig) = match input.tap_script_sigs.iter().next() {
+                    Some(entry) => entry,
+                    None => continue,
+                };
+
+                let (control_block, (script, _)) = input
+                    .tap_scripts
+                    .iter()
+                    .find(|(_, (script, leaf_version))| {
+                        TapLeafHash::from_script(script, *leaf_version) == *leaf_hash
+                    })
+                    .ok_or(PsbtError::UnknownLeafScript(index as u32))?;
+
+                Witness::from_slice(&[sig.to_vec(), script.to_bytes(), control_block.serialize()])
+            }
+        };
+
+        // Clear everything but the UTXO and final fields (BIP-174 finalizer).
+        *input = Input {
+            witness_utxo: input.witness_utxo.clone(),
+            non_witness_utxo: input.non_witness_utxo.clone(),
+            final_script_witness: Some(witness),
+            unknown: input.unknown.clone(),
+            ..Default::default()
+        };
+
+        finalized += 1;
+    }
+
+    Ok(finalized)
+}
+
+/// Extracts the signed transaction from a fully finalized PSBT.
+pub fn extract_tx(psbt: &Psbt) -> Result<Transaction, PsbtError> {
+    let mut tx = psbt.unsigned_tx.clone();
+
+    for (index, (txin, input)) in tx.input.iter_mut().zip(psbt.inputs.iter()).enumerate() {
+        txin.witness = input
+            .final_script_witness
+            .clone()
+            .ok_or(PsbtError::InputNotFinalized(index as u32))?;
+        if let Some(script_sig) = &input.final_script_sig {
+            txin.script_sig = script_sig.to_owned();
+        }
+    }
+
+    Ok(tx)
+}
+
+/// Sets the taproot fields of an input: internal key, merkle root and leaf scripts.
+fn set_input_tap_fields(input: &mut Input, taproot: &TapRoot) -> Result<(), PsbtError> {
+    input.tap_internal_key = Some(xonly(taproot.inner_key())?);
+
+    if let Some(tree) = taproot.tree() {
+        input.tap_merkle_root = Some(TapNodeHash::from_byte_array(taproot.tap_branch()));
+
+        for (index, leaf) in tree.leaves().iter().enumerate() {
+            let control_block = taproot
+                .control_block(index)
+                .ok_or(PsbtError::TaprootConstructionError)?;
+            let control_block = ControlBlock::decode(&control_block.to_vec())
+                .map_err(|_| PsbtError::FieldConversionError)?;
+
+            input.tap_scripts.insert(
+                control_block,
+                (ScriptBuf::from(leaf.tap_script()), leaf_version(leaf)?),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Sets the taproot fields of an output: internal key and tap tree.
+fn set_output_tap_fields(output: &mut Output, taproot: &TapRoot) -> Result<(), PsbtError> {
+    output.tap_internal_key = Some(xonly(taproot.inner_key())?);
+
+    if let Some(tree) = taproot.tree() {
+        // Leaves are added in depth-first order, with depths derived from their merkle paths.
+        let leaves = tree.leaves();
+        let mut builder = TaprootBuilder::new();
+        for index in tree.depth_first_order() {
+            let leaf = &leaves[index];
+            let depth = (tree.path(index).len() / 32) as u8;
+            builder = builder
+                .add_leaf_with_ver(
+                    depth,
+                    ScriptBuf::from(leaf.tap_script()),
+                    leaf_version(leaf)?,
+                )
+                .map_err(|_| PsbtError::FieldConversionError)?;
+        }
+        let tap_tree = TapTree::try_from(builder).map_err(|_| PsbtError::FieldConversionError)?;
+
+        // The tap tree must commit to the same merkle root.
+        if tap_tree.root_hash().to_byte_array() != taproot.tap_branch() {
+            return Err(PsbtError::FieldConversionError);
+        }
+
+        output.tap_tree = Some(tap_tree);
+    }
+
+    Ok(())
+}
+
+/// Returns the leaf version of a tap leaf.
+fn leaf_version(leaf: &TapLeaf) -> Result<LeafVersion, PsbtError> {
+    LeafVersion::from_consensus(leaf.leaf_version()).map_err(|_| PsbtError::FieldConversionError)
+}
+
+/// Converts a point into its x-only public key.
+fn xonly(point: Point) -> Result<XOnlyPublicKey, PsbtError> {
+    XOnlyPublicKey::from_slice(&point.serialize_xonly())
+        .map_err(|_| PsbtError::FieldConversionError)
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Error type for exporting and importing PSBTs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PsbtError {
+    // Lift carries no outpoint or value.
+    InvalidLift,
+    // No funding inputs are given.
+    NoFundingInputs,
+    // Inputs do not cover the outputs and the fee.
+    InsufficientFunds,
+    // Failed to construct a taproot output.
+    TaprootConstructionError,
+    // Failed to convert a key or script into its PSBT field.
+    FieldConversionError,
+    // Failed to construct the PSBT from the unsigned transaction.
+    PsbtConstructionError,
+    // PSBT is not base64 encoded.
+    Base64DecodingError,
+    // PSBT failed to deserialize.
+    PsbtDecodingError,
+    // Imported PSBT is of a different transaction.
+    UnsignedTxMismatch,
+    // Input carries no signature.
+    InputNotSigned(u32),
+    // Input signature does not match any of its leaf scripts.
+    UnknownLeafScript(u32),
+    // Input is not finalized.
+    InputNotFinalized(u32),
+}
@@ -0,0 +1,168 @@
+use super::sighash_error::SighashError;
+use crate::{
+    constructive::taproot::{TapRoot, P2TR},
+    inscriptive::set::utxo_set::UTXO_SET,
+    transmutive::secp::schnorr::{self, SchnorrSigningMode},
+};
+use bitcoin::{
+    hashes::Hash,
+    sighash::{Prevouts, SighashCache, TapSighashType},
+    taproot::{LeafVersion, TapLeafHash},
+    ScriptBuf, Transaction, TxOut, Witness,
+};
+
+/// Leaf index of the sweep path of single-leaf outputs, i.e. `Lift`, `VTXO` and the projectors.
+pub const SWEEP_PATH_INDEX: usize = 0;
+
+/// Collects the prevouts of a transaction from the UTXO set, in input order.
+pub async fn prevouts(tx: &Transaction, utxo_set: &UTXO_SET) -> Result<Vec<TxOut>, SighashError> {
+    let _utxo_set = utxo_set.lock().await;
+
+    let mut prevouts = Vec::<TxOut>::new();
+    for (index, txin) in tx.input.iter().enumerate() {
+        let txout = _utxo_set
+            .txout_by_outpoint(&txin.previous_output)
+            .ok_or(SighashError::PrevoutNotFound(index as u32))?;
+        prevouts.push(txout);
+    }
+
+    Ok(prevouts)
+}
+
+/// Computes the BIP-341 key-path sighash of an input, committing to all prevouts.
+pub fn key_path_sighash(
+    tx: &Transaction,
+    input_index: usize,
+    prevouts: &Vec<TxOut>,
+) -> Result<[u8; 32], SighashError> {
+    check_bounds(tx, input_index, prevouts)?;
+
+    let sighash = SighashCache::new(tx)
+        .taproot_key_spend_signature_hash(
+            input_index,
+            &Prevouts::All(prevouts.as_slice()),
+            TapSighashType::Default,
+        )
+        .map_err(|_| SighashError::SighashComputationError)?;
+
+    Ok(sighash.to_byte_array())
+}
+
+/// Computes the BIP-341 script-path sighash of an input spending the given tapscript.
+pub fn script_path_sighash(
+    tx: &Transaction,
+    input_index: usize,
+    prevouts: &Vec<TxOut>,
+    tap_script: &Vec<u8>,
+) -> Result<[u8; 32], SighashError> {
+    check_bounds(tx, input_index, prevouts)?;
+
+    let leaf_hash = TapLeafHash::from_script(
+        &ScriptBuf::from(tap_script.to_owned()),
+        LeafVersion::TapScript,
+    );
+
+    let sighash = SighashCache::new(tx)
+        .taproot_script_spend_signature_hash(
+            input_index,
+            &Prevouts::All(prevouts.as_slice()),
+            leaf_hash,
+            TapSighashType::Default,
+        )
+        .map_err(|_| SighashError::SighashComputationError)?;
+
+    Ok(sighash.to_byte_array())
+}
+
+/// Computes the sighash of an input spending a TXO from its sweep path.
+pub fn sweep_sighash<T: P2TR>(
+    txo: &T,
+    tx: &Transaction,
+    input_index: usize,
+    prevouts: &Vec<TxOut>,
+) -> Result<[u8; 32], SighashError> {
+    let tap_script = sweep_script(txo)?;
+    script_path_sighash(tx, input_index, prevouts, &tap_script)
+}
+
+/// Returns the witness of a key-path spend, carrying the aggregated MuSig signature.
+pub fn key_path_witness(agg_sig: [u8; 64]) -> Witness {
+    Witness::from_slice(&[agg_sig.to_vec()])
+}
+
+/// Returns the witness of a script-path spend from the leaf at the given index.
+/// `stack` holds the items consumed by the tapscript, bottom first.
+pub fn script_path_witness(
+    taproot: &TapRoot,
+    leaf_index: usize,
+    stack: &Vec<Vec<u8>>,
+) -> Option<Witness> {
+    let leaf = taproot.tree()?.leaves().get(leaf_index)?.to_owned();
+    let control_block = taproot.control_block(leaf_index)?;
+
+    let mut witness = stack.to_owned();
+    witness.push(leaf.tap_script());
+    witness.push(control_block.to_vec());
+
+    Some(Witness::from_slice(&witness))
+}
+
+/// Returns the witness of a TXO spent from its CSV sweep path with a single signature.
+/// The spending input must carry the sweep path relative timelock as its sequence.
+pub fn sweep_witness<T: P2TR>(txo: &T, sig: [u8; 64]) -> Option<Witness> {
+    let taproot = txo.taproot()?;
+    script_path_witness(&taproot, SWEEP_PATH_INDEX, &vec![sig.to_vec()])
+}
+
+/// Verifies a key-path signature of an input spending a TXO against its tweaked key.
+pub fn verify_key_path<T: P2TR>(
+    txo: &T,
+    tx: &Transaction,
+    input_index: usize,
+    prevouts: &Vec<TxOut>,
+    sig: [u8; 64],
+) -> Result<bool, SighashError> {
+    let tweaked_key = txo
+        .taproot()
+        .and_then(|taproot| taproot.tweaked_key())
+        .ok_or(SighashError::TaprootConstructionError)?;
+    let sighash = key_path_sighash(tx, input_index, prevouts)?;
+
+    Ok(schnorr::verify_xonly(
+        tweaked_key.serialize_xonly(),
+        sighash,
+        sig,
+        SchnorrSigningMode::BIP340,
+    ))
+}
+
+/// Returns the sweep path tapscript of a TXO.
+fn sweep_script<T: P2TR>(txo: &T) -> Result<Vec<u8>, SighashError> {
+    let taproot = txo
+        .taproot()
+        .ok_or(SighashError::TaprootConstructionError)?;
+    let tree = taproot.tree().ok_or(SighashError::NoScriptPath)?;
+    let leaf = tree
+        .leaves()
+        .get(SWEEP_PATH_INDEX)
+        .ok_or(SighashError::NoScriptPath)?
+        .to_owned();
+
+    Ok(leaf.tap_script())
+}
+
+/// Makes sure the input index is in bounds and each input has a prevout.
+fn check_bounds(
+    tx: &Transaction,
+    input_index: usize,
+    prevouts: &Vec<TxOut>,
+) -> Result<(), SighashError> {
+    if prevouts.len() != tx.input.len() {
+        return Err(SighashError::PrevoutsLengthMismatch);
+    }
+    if input_index >= tx.input.len() {
+        return Err(SighashError::InputIndexOutOfBounds(input_index as u32));
+    }
+
+    Ok(())
+}
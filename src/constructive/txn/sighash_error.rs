@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Error type for computing taproot sighashes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SighashError {
+    // Input prevout is not in the UTXO set.
+    PrevoutNotFound(u32),
+    // Number of prevouts does not match the number of inputs.
+    PrevoutsLengthMismatch,
+    // Input index is out of bounds.
+    InputIndexOutOfBounds(u32),
+    // Output has no script path to spend from.
+    NoScriptPath,
+    // Failed to construct a taproot output.
+    TaprootConstructionError,
+    // Sighash computation failed.
+    SighashComputationError,
+}
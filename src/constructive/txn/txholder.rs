@@ -1,6 +1,6 @@
 use crate::{constructive::valtype::atomic_val::AtomicVal, inscriptive::set::utxo_set::UTXO_SET};
 use bitcoin::{OutPoint, Transaction, TxOut};
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 /// Default number of inputs: `Payload`.
 const DEFAULT_NUM_INS: u32 = 1;
@@ -9,11 +9,13 @@ const DEFAULT_NUM_INS: u32 = 1;
 const DEFAULT_NUM_OUTS: u32 = 3;
 
 /// Maximum 8 extra inputs are allowed.
-const MAX_EXTRA_INS: u8 = 8;
+pub const MAX_EXTRA_INS: u8 = 8;
 
 /// A holder for a transaction, its extra inputs, and its input and output iterators.
 pub struct TxHolder {
     utxo_set: UTXO_SET,
+    /// Prevouts that are no longer in the UTXO set, e.g. of an already synced transaction.
+    prevouts: HashMap<OutPoint, TxOut>,
     /// The transaction.
     tx: Transaction,
     /// The number of extra inputs.
@@ -40,6 +42,7 @@ impl TxHolder {
 
         let tx_holder = TxHolder {
             utxo_set: Arc::clone(&utxo_set),
+            prevouts: HashMap::new(),
             tx,
             extra_in,
             iterator_in,
@@ -49,6 +52,12 @@ impl TxHolder {
         Some(tx_holder)
     }
 
+    /// Sets the prevouts to resolve inputs with, ahead of the UTXO set.
+    pub fn with_prevouts(mut self, prevouts: Vec<(OutPoint, TxOut)>) -> TxHolder {
+        self.prevouts = prevouts.into_iter().collect();
+        self
+    }
+
     /// Returns the transaction.
     pub fn tx(&self) -> Transaction {
         self.tx.clone()
@@ -103,6 +112,11 @@ impl TxHolder {
             .get(current_iter)
             .map(|input| input.previous_output)?;
 
+        // Retrieve the TXOUT from the prevouts, if given.
+        if let Some(txout) = self.prevouts.get(&outpoint) {
+            return Some((outpoint, txout.to_owned()));
+        }
+
         // Retrieve the TXOUT from the UTXO set.
         let txout = {
             let _utxo_set = self.utxo_set.lock().await;
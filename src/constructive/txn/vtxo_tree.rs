@@ -0,0 +1,392 @@
+use crate::{
+    constructive::{
+        taproot::{TapRoot, P2TR},
+        txo::{projector::Projector, vtxo::VTXO},
+    },
+    transmutive::musig::keyagg::MusigKeyAggCtx,
+};
+use bitcoin::{
+    absolute, transaction, Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness,
+};
+use secp::Point;
+use serde::{Deserialize, Serialize};
+
+/// Default number of children per tree node.
+pub const DEFAULT_RADIX: u8 = 4;
+
+/// Minimum number of children per tree node.
+pub const MIN_RADIX: u8 = 2;
+
+/// Maximum number of children per tree node.
+pub const MAX_RADIX: u8 = 16;
+
+/// Pay-to-anchor script pubkey attached to every tree transaction for fee bumping.
+pub const ANCHOR_SPK: [u8; 4] = [0x51, 0x02, 0x4e, 0x73];
+
+/// Tree transactions are v3 so that their zero-value anchors can be spent by a CPFP child.
+const TREE_TX_VERSION: i32 = 3;
+
+/// An output of a tree node, either a child node or a `VTXO` leaf.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum VTXOTreeOutput {
+    Node(usize),
+    Leaf(usize),
+}
+
+/// A single virtual transaction of the `VTXO` tree.
+#[derive(Clone)]
+pub struct VTXOTreeNode {
+    // The virtual transaction.
+    tx: Transaction,
+    // Parent node index and output index, or `None` if the node spends the projector.
+    parent: Option<(usize, u32)>,
+    // Outputs of the node in order, excluding the anchor.
+    outputs: Vec<VTXOTreeOutput>,
+    // Keys signing for the node input.
+    keys: Vec<Point>,
+    // Depth of the node, root being zero.
+    depth: u32,
+    // Aggregated signature for the node input.
+    sig: Option<[u8; 64]>,
+}
+
+impl VTXOTreeNode {
+    /// Returns the unsigned virtual transaction.
+    pub fn unsigned_tx(&self) -> Transaction {
+        self.tx.clone()
+    }
+
+    /// Returns the virtual transaction with its input witness, if signed.
+    pub fn signed_tx(&self) -> Option<Transaction> {
+        let mut tx = self.tx.clone();
+        tx.input[0].witness = Witness::from_slice(&[self.sig?.to_vec()]);
+        Some(tx)
+    }
+
+    /// Returns the parent node index and output index.
+    pub fn parent(&self) -> Option<(usize, u32)> {
+        self.parent
+    }
+
+    /// Returns the node outputs, excluding the anchor.
+    pub fn outputs(&self) -> Vec<VTXOTreeOutput> {
+        self.outputs.clone()
+    }
+
+    /// Returns the keys signing for the node input.
+    pub fn keys(&self) -> Vec<Point> {
+        self.keys.clone()
+    }
+
+    /// Returns the depth of the node.
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+}
+
+/// Virtual transaction tree projecting `VTXO`s from the `VTXO Projector`.
+///
+/// The root spends the projector from its reveal path, and each node splits its input
+/// into up to `radix` outputs, each either a child node or a `VTXO` leaf:
+///
+/// ```text
+///                      ┌────────────────┐
+///                      │ VTXO Projector │
+///                      └────────────────┘
+///                               │
+///                          ┌─────────┐
+///                          │  Root   │
+///                          └─────────┘
+///                       ⋰       ┊       ⋱
+///              ┌─────────┐            ┌─────────┐
+///              │  Node   │     ┄      │  Node   │
+///              └─────────┘            └─────────┘
+///              ⋰        ⋱              ⋰        ⋱
+///         ┌──────┐   ┌──────┐     ┌──────┐   ┌──────┐
+///         │ VTXO │ ┄ │ VTXO │     │ VTXO │ ┄ │ VTXO │
+///         └──────┘   └──────┘     └──────┘   └──────┘
+/// ```
+///
+/// Internal outputs are key-path only, locked to `(msg.senders[] + Operator)`
+/// of the `VTXO`s beneath them.
+#[derive(Clone)]
+pub struct VTXOTree {
+    radix: u8,
+    projector: Projector,
+    projector_outpoint: OutPoint,
+    vtxos: Vec<VTXO>,
+    nodes: Vec<VTXOTreeNode>,
+}
+
+impl VTXOTree {
+    /// Constructs a new `VTXOTree` under the `VTXO Projector` output.
+    /// `VTXO`s are ordered by account key and value to keep the tree deterministic.
+    /// Returns `None` if the radix is out of bounds, there are no `VTXO`s,
+    /// or a `VTXO` carries no value.
+    pub fn new(
+        projector: &Projector,
+        projector_outpoint: OutPoint,
+        vtxos: &Vec<VTXO>,
+        radix: u8,
+    ) -> Option<VTXOTree> {
+        if radix < MIN_RADIX || radix > MAX_RADIX || vtxos.is_empty() {
+            return None;
+        }
+
+        // Every VTXO must carry a value.
+        if vtxos.iter().any(|vtxo| vtxo.value().is_none()) {
+            return None;
+        }
+
+        // Sort the VTXOs.
+        let mut vtxos = vtxos.to_owned();
+        vtxos.sort_by(|a, b| {
+            a.account_key()
+                .serialize()
+                .cmp(&b.account_key().serialize())
+                .then(a.value().cmp(&b.value()))
+        });
+
+        let mut tree = VTXOTree {
+            radix,
+            projector: projector.to_owned(),
+            projector_outpoint,
+            vtxos,
+            nodes: Vec::new(),
+        };
+
+        // Build the tree from the root.
+        let leaves: Vec<usize> = (0..tree.vtxos.len()).collect();
+        tree.build_node(&leaves, projector_outpoint, None, projector.keys(), 0)?;
+
+        Some(tree)
+    }
+
+    /// Recursively builds a node spending the given outpoint. Returns the node index.
+    fn build_node(
+        &mut self,
+        leaves: &[usize],
+        prevout: OutPoint,
+        parent: Option<(usize, u32)>,
+        keys: Vec<Point>,
+        depth: u32,
+    ) -> Option<usize> {
+        // Split the leaves into up to `radix` contiguous groups.
+        let groups = split(leaves, self.radix as usize);
+
+        // Construct the outputs.
+        let mut output = Vec::<TxOut>::new();
+        for group in groups.iter() {
+            let value: u64 = group.iter().filter_map(|i| self.vtxos[*i].value()).sum();
+            let spk = match group.len() {
+                1 => self.vtxos[group[0]].spk()?,
+                _ => self.branch_taproot(group)?.spk()?,
+            };
+            output.push(TxOut {
+                value: Amount::from_sat(value),
+                script_pubkey: ScriptBuf::from(spk),
+            });
+        }
+
+        // Anchor output for fee bumping.
+        output.push(TxOut {
+            value: Amount::ZERO,
+            script_pubkey: ScriptBuf::from(ANCHOR_SPK.to_vec()),
+        });
+
+        let tx = Transaction {
+            version: transaction::Version(TREE_TX_VERSION),
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: prevout,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::new(),
+            }],
+            output,
+        };
+        let txid = tx.compute_txid();
+
+        // Insert the node before its children.
+        let index = self.nodes.len();
+        self.nodes.push(VTXOTreeNode {
+            tx,
+            parent,
+            outputs: Vec::new(),
+            keys,
+            depth,
+            sig: None,
+        });
+
+        // Build the children.
+        let mut outputs = Vec::<VTXOTreeOutput>::new();
+        for (vout, group) in groups.iter().enumerate() {
+            match group.len() {
+                1 => outputs.push(VTXOTreeOutput::Leaf(group[0])),
+                _ => {
+                    let keys = self.branch_keys(group);
+                    let child = self.build_node(
+                        group,
+                        OutPoint::new(txid, vout as u32),
+                        Some((index, vout as u32)),
+                        keys,
+                        depth + 1,
+                    )?;
+                    outputs.push(VTXOTreeOutput::Node(child));
+                }
+            }
+        }
+        self.nodes[index].outputs = outputs;
+
+        Some(index)
+    }
+
+    /// Returns the keys of a branch: the distinct account keys beneath it plus the operator key.
+    fn branch_keys(&self, group: &[usize]) -> Vec<Point> {
+        let mut keys = Vec::<Point>::new();
+        for i in group.iter() {
+            let key = self.vtxos[*i].account_key();
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        keys.push(self.projector.operator_key());
+
+        keys
+    }
+
+    /// Returns the key-path only taproot of a branch output.
+    fn branch_taproot(&self, group: &[usize]) -> Option<TapRoot> {
+        let key_agg_ctx = MusigKeyAggCtx::new(&self.branch_keys(group), None)?;
+        Some(TapRoot::key_path_only(key_agg_ctx.agg_inner_key()))
+    }
+
+    /// Returns the radix of the tree.
+    pub fn radix(&self) -> u8 {
+        self.radix
+    }
+
+    /// Returns the projector outpoint the tree descends from.
+    pub fn projector_outpoint(&self) -> OutPoint {
+        self.projector_outpoint
+    }
+
+    /// Returns the ordered `VTXO`s of the tree.
+    pub fn vtxos(&self) -> Vec<VTXO> {
+        self.vtxos.clone()
+    }
+
+    /// Returns the tree nodes, root first.
+    pub fn nodes(&self) -> Vec<VTXOTreeNode> {
+        self.nodes.clone()
+    }
+
+    /// Returns the number of virtual transactions in the tree.
+    pub fn size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the depth of the tree, i.e. the number of transactions on the longest exit path.
+    pub fn depth(&self) -> u32 {
+        self.nodes
+            .iter()
+            .map(|node| node.depth + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the key aggregation context for signing a node input.
+    /// The root spends the projector from its reveal path; other nodes spend key-path only branches.
+    pub fn key_agg_ctx(&self, node_index: usize) -> Option<MusigKeyAggCtx> {
+        let node = self.nodes.get(node_index)?;
+        match node.parent {
+            None => self.projector.key_agg_ctx(),
+            Some(_) => MusigKeyAggCtx::new(&node.keys, None),
+        }
+    }
+
+    /// Sets the aggregated signature of a node input.
+    pub fn set_sig(&mut self, node_index: usize, sig: [u8; 64]) -> bool {
+        match self.nodes.get_mut(node_index) {
+            Some(node) => {
+                node.sig = Some(sig);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether every node is signed.
+    pub fn is_signed(&self) -> bool {
+        self.nodes.iter().all(|node| node.sig.is_some())
+    }
+
+    /// Returns the node index and output index holding a `VTXO` leaf.
+    pub fn leaf_position(&self, vtxo: &VTXO) -> Option<(usize, u32)> {
+        let leaf = self.vtxos.iter().position(|v| v == vtxo)?;
+        self.nodes.iter().enumerate().find_map(|(index, node)| {
+            node.outputs
+                .iter()
+                .enumerate()
+                .find_map(|(vout, output)| match output {
+                    VTXOTreeOutput::Leaf(i) if *i == leaf => Some((index, vout as u32)),
+                    _ => None,
+                })
+        })
+    }
+
+    /// Returns the outpoint of a `VTXO` leaf in the tree.
+    pub fn leaf_outpoint(&self, vtxo: &VTXO) -> Option<OutPoint> {
+        let (index, vout) = self.leaf_position(vtxo)?;
+        Some(OutPoint::new(self.nodes[index].tx.compute_txid(), vout))
+    }
+
+    /// Returns the node indexes from the root down to the node holding the `VTXO` leaf.
+    pub fn branch(&self, vtxo: &VTXO) -> Option<Vec<usize>> {
+        let (mut index, _) = self.leaf_position(vtxo)?;
+
+        let mut branch = vec![index];
+        while let Some((parent, _)) = self.nodes[index].parent {
+            branch.push(parent);
+            index = parent;
+        }
+        branch.reverse();
+
+        Some(branch)
+    }
+
+    /// Returns the signed unilateral exit path of a `VTXO`, root first.
+    /// Returns `None` if the `VTXO` is not in the tree or a node on its path is not signed.
+    pub fn exit_path(&self, vtxo: &VTXO) -> Option<Vec<Transaction>> {
+        self.branch(vtxo)?
+            .iter()
+            .map(|index| self.nodes[*index].signed_tx())
+            .collect()
+    }
+
+    /// Returns the signed unilateral exit paths of all `VTXO`s of an account.
+    pub fn exit_paths(&self, account_key: Point) -> Option<Vec<(VTXO, Vec<Transaction>)>> {
+        self.vtxos
+            .iter()
+            .filter(|vtxo| vtxo.account_key() == account_key)
+            .map(|vtxo| Some((vtxo.to_owned(), self.exit_path(vtxo)?)))
+            .collect()
+    }
+}
+
+/// Splits leaves into up to `radix` contiguous groups of near-equal size.
+fn split(leaves: &[usize], radix: usize) -> Vec<Vec<usize>> {
+    let num_groups = leaves.len().min(radix);
+    let base = leaves.len() / num_groups;
+    let extra = leaves.len() % num_groups;
+
+    let mut groups = Vec::<Vec<usize>>::new();
+    let mut cursor = 0;
+    for i in 0..num_groups {
+        let len = base + if i < extra { 1 } else { 0 };
+        groups.push(leaves[cursor..cursor + len].to_vec());
+        cursor += len;
+    }
+
+    groups
+}
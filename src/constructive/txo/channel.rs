@@ -0,0 +1,285 @@
+use crate::constructive::taproot::{TapLeaf, TapRoot, P2TR};
+use crate::transmutive::codec::csv::{CSVEncode, CSVFlag};
+use crate::transmutive::musig::keyagg::MusigKeyAggCtx;
+use bitcoin::OutPoint;
+use secp::Point;
+use serde::{Deserialize, Serialize};
+
+type Bytes = Vec<u8>;
+
+/// Number of degrading periods, and thus the maximum number of states, of a `Channel`.
+pub const CHANNEL_PERIODS: u8 = 128;
+
+/// Relative timelock of the first channel state, in days.
+pub const CHANNEL_START_DAYS: u8 = 141;
+
+/// Relative timelock of the last channel state, in days.
+pub const CHANNEL_END_DAYS: u8 = CHANNEL_START_DAYS - CHANNEL_PERIODS + 1;
+
+/// A state of a `Channel`, splitting the channel value between `Self` and `Operator`.
+///
+/// State `n` spends the channel from the TapLeaf `n`, after `142 - n` days.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChannelState {
+    number: u8,
+    self_value: u64,
+    operator_value: u64,
+}
+
+impl ChannelState {
+    /// Creates a new channel state. Returns `None` if the state number is out of range.
+    pub fn new(number: u8, self_value: u64, operator_value: u64) -> Option<ChannelState> {
+        if number == 0 || number > CHANNEL_PERIODS {
+            return None;
+        }
+
+        Some(ChannelState {
+            number,
+            self_value,
+            operator_value,
+        })
+    }
+
+    /// Returns the state number, starting from 1.
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    /// Returns the sats value of `Self` in this state.
+    pub fn self_value(&self) -> u64 {
+        self.self_value
+    }
+
+    /// Returns the sats value of `Operator` in this state.
+    pub fn operator_value(&self) -> u64 {
+        self.operator_value
+    }
+
+    /// Returns the relative timelock of this state in days.
+    pub fn days(&self) -> u8 {
+        CHANNEL_START_DAYS - (self.number - 1)
+    }
+
+    /// Returns the relative timelock flag of this state.
+    pub fn csv_flag(&self) -> CSVFlag {
+        CSVFlag::Days(self.days())
+    }
+
+    /// Returns the nSequence an input spending this state must carry.
+    pub fn n_sequence(&self) -> u32 {
+        let bytes = Bytes::n_sequence(self.csv_flag());
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+
+    /// Returns the index of the TapLeaf this state spends from.
+    pub fn leaf_index(&self) -> usize {
+        (self.number - 1) as usize
+    }
+}
+
+/// Revocation data of a channel state overwritten by a newer one.
+///
+/// Degrading timelocks need no revocation secrets; the newer state becomes valid
+/// `precedence` blocks before the revoked one and therefore overwrites it.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChannelRevocation {
+    revoked_state: u8,
+    revoked_sequence: u32,
+    precedence: u32,
+}
+
+impl ChannelRevocation {
+    /// Returns the number of the revoked state.
+    pub fn revoked_state(&self) -> u8 {
+        self.revoked_state
+    }
+
+    /// Returns the nSequence of the revoked state.
+    pub fn revoked_sequence(&self) -> u32 {
+        self.revoked_sequence
+    }
+
+    /// Returns the number of blocks by which the newer state precedes the revoked one.
+    pub fn precedence(&self) -> u32 {
+        self.precedence
+    }
+
+    /// Returns whether the given state is revoked by this revocation.
+    pub fn revokes(&self, state: &ChannelState) -> bool {
+        state.number() <= self.revoked_state
+    }
+}
+
+/// Channel is a virtual transaction output that turns its parent `VTXO` into a 2-of-2
+/// state channel between `Self` and `Operator`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Channel {
+    account: Point,
+    operator: Point,
+    outpoint: Option<OutPoint>,
+    value: Option<u64>,
+    state: Option<ChannelState>,
+}
+
+impl Channel {
+    /// Creates a new channel with no state.
+    pub fn new(
+        account: Point,
+        operator: Point,
+        outpoint: Option<OutPoint>,
+        value: Option<u64>,
+    ) -> Channel {
+        Channel {
+            account,
+            operator,
+            outpoint,
+            value,
+            state: None,
+        }
+    }
+
+    /// Serializes the channel into a vector of bytes.
+    pub fn serialize(&self) -> Vec<u8> {
+        match serde_json::to_vec(self) {
+            Ok(bytes) => bytes,
+            Err(_) => vec![],
+        }
+    }
+
+    /// Returns the account key of the channel.
+    pub fn account_key(&self) -> Point {
+        self.account
+    }
+
+    /// Returns the operator key of the channel.
+    pub fn operator_key(&self) -> Point {
+        self.operator
+    }
+
+    /// Returns the outpoint of the channel.
+    pub fn outpoint(&self) -> Option<OutPoint> {
+        self.outpoint
+    }
+
+    /// Returns the sats value of the channel.
+    pub fn value(&self) -> Option<u64> {
+        self.value
+    }
+
+    /// Returns the latest state of the channel.
+    pub fn state(&self) -> Option<ChannelState> {
+        self.state
+    }
+
+    /// Returns the number of states left before the channel is exhausted.
+    pub fn states_left(&self) -> u8 {
+        match self.state {
+            Some(state) => CHANNEL_PERIODS - state.number(),
+            None => CHANNEL_PERIODS,
+        }
+    }
+
+    /// Returns the keys of the channel.
+    pub fn keys(&self) -> Vec<Point> {
+        let mut keys = Vec::<Point>::new();
+
+        keys.push(self.account_key());
+        keys.push(self.operator_key());
+
+        keys
+    }
+
+    /// Returns the aggregated key of the channel, used in every state leaf.
+    pub fn agg_key(&self) -> Option<Point> {
+        let key_agg_ctx = self.key_agg_ctx()?;
+        let agg_key = key_agg_ctx.agg_inner_key();
+
+        Some(agg_key)
+    }
+
+    /// Returns the key aggregation context for signing channel states.
+    /// Channel states are spent from the script path, hence the context is untweaked.
+    pub fn key_agg_ctx(&self) -> Option<MusigKeyAggCtx> {
+        let keys = self.keys();
+        let key_agg_ctx = MusigKeyAggCtx::new(&keys, None)?;
+
+        Some(key_agg_ctx)
+    }
+
+    /// Returns the TapLeaf of the given state: `(Self + Operator) after 142 - n days`.
+    pub fn state_leaf(&self, state: &ChannelState) -> Option<TapLeaf> {
+        let agg_key = self.agg_key()?;
+
+        let mut state_path_script = Vec::<u8>::new();
+        state_path_script.extend(Bytes::csv_script(state.csv_flag())); // Relative Timelock
+        state_path_script.push(0x20); // OP_PUSHDATA_32
+        state_path_script.extend(agg_key.serialize_xonly()); // (Self + Operator) 32-bytes
+        state_path_script.push(0xac); // OP_CHECKSIG
+
+        Some(TapLeaf::new(state_path_script))
+    }
+
+    /// Produces the next channel state along with the revocation data of the current one.
+    /// Returns `None` if the channel is exhausted or the values exceed the channel value.
+    pub fn next_state(
+        &self,
+        self_value: u64,
+        operator_value: u64,
+    ) -> Option<(ChannelState, Option<ChannelRevocation>)> {
+        let total = self_value.checked_add(operator_value)?;
+        if let Some(value) = self.value {
+            if total > value {
+                return None;
+            }
+        }
+
+        let number = match self.state {
+            Some(state) => state.number().checked_add(1)?,
+            None => 1,
+        };
+        let next = ChannelState::new(number, self_value, operator_value)?;
+
+        let revocation = match self.state {
+            Some(current) => Some(ChannelRevocation {
+                revoked_state: current.number(),
+                revoked_sequence: current.n_sequence(),
+                precedence: current.n_sequence() - next.n_sequence(),
+            }),
+            None => None,
+        };
+
+        Some((next, revocation))
+    }
+
+    /// Advances the channel to the given state. The state must directly follow the current one.
+    pub fn update(&mut self, state: ChannelState) -> bool {
+        let expected = match self.state {
+            Some(current) => current.number() + 1,
+            None => 1,
+        };
+
+        if state.number() != expected {
+            return false;
+        }
+
+        self.state = Some(state);
+        true
+    }
+}
+
+impl P2TR for Channel {
+    fn taproot(&self) -> Option<TapRoot> {
+        //// State Paths: (Self + Operator) after 141 days .. (Self + Operator) after 14 days
+        let mut state_paths = Vec::<TapLeaf>::new();
+        for number in 1..=CHANNEL_PERIODS {
+            let state = ChannelState::new(number, 0, 0)?;
+            state_paths.push(self.state_leaf(&state)?);
+        }
+
+        Some(TapRoot::script_path_only_multi(state_paths))
+    }
+
+    fn spk(&self) -> Option<Bytes> {
+        self.taproot()?.spk()
+    }
+}
@@ -1,4 +1,6 @@
+pub mod channel;
 pub mod connector;
 pub mod lift;
+pub mod payload;
 pub mod projector;
 pub mod vtxo;
@@ -0,0 +1,197 @@
+use crate::constructive::payload::payload::Payload;
+use crate::constructive::taproot::{TapLeaf, TapRoot, P2TR};
+use crate::transmutive::codec::csv::{CSVEncode, CSVFlag};
+use crate::transmutive::musig::keyagg::MusigKeyAggCtx;
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::Witness;
+use secp::Point;
+use serde::{Deserialize, Serialize};
+
+type Bytes = Vec<u8>;
+
+/// Leaf index of the operator path.
+pub const OPERATOR_PATH_INDEX: usize = 0;
+
+/// Leaf index of the msg.senders fallback path.
+pub const SENDERS_PATH_INDEX: usize = 1;
+
+/// Payload is a bare transaction output contained in each pool transaction.
+///
+/// The operator spends it with the hashlock preimages in the next pool transaction,
+/// revealing the payload envelope committed in the operator path.
+/// If the operator does not, msg.senders can reclaim it after one week.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PayloadTXO {
+    msg_senders: Vec<Point>,
+    operator: Point,
+    hashlocks: Vec<[u8; 32]>,
+    envelope: Bytes,
+}
+
+impl PayloadTXO {
+    /// Creates a new `PayloadTXO` committing to the payload envelope.
+    /// Returns `None` if no hashlocks are given.
+    pub fn new(
+        payload: &Payload,
+        operator: Point,
+        hashlocks: &Vec<[u8; 32]>,
+    ) -> Option<PayloadTXO> {
+        if hashlocks.is_empty() {
+            return None;
+        }
+
+        let payload_txo = PayloadTXO {
+            msg_senders: payload.msg_senders(),
+            operator,
+            hashlocks: hashlocks.to_owned(),
+            envelope: payload.envelope()?,
+        };
+
+        Some(payload_txo)
+    }
+
+    /// Serializes the payload TXO into a vector of bytes.
+    pub fn serialize(&self) -> Vec<u8> {
+        match serde_json::to_vec(self) {
+            Ok(bytes) => bytes,
+            Err(_) => vec![],
+        }
+    }
+
+    /// Returns the keys of msg.senders.
+    pub fn msg_senders(&self) -> Vec<Point> {
+        self.msg_senders.clone()
+    }
+
+    /// Returns the operator key.
+    pub fn operator_key(&self) -> Point {
+        self.operator
+    }
+
+    /// Returns the SHA256 hashlocks of the operator path.
+    pub fn hashlocks(&self) -> Vec<[u8; 32]> {
+        self.hashlocks.clone()
+    }
+
+    /// Returns the payload envelope.
+    pub fn envelope(&self) -> Bytes {
+        self.envelope.clone()
+    }
+
+    /// Returns the aggregated key of msg.senders, or the key itself if there is a single sender.
+    pub fn senders_agg_key(&self) -> Option<Point> {
+        if let [sender_key] = self.msg_senders.as_slice() {
+            return Some(*sender_key);
+        }
+
+        let key_agg_ctx = self.senders_key_agg_ctx()?;
+        let agg_key = key_agg_ctx.agg_inner_key();
+
+        Some(agg_key)
+    }
+
+    /// Returns the key aggregation context of msg.senders.
+    /// The fallback is spent from the script path, hence the context is untweaked.
+    /// Returns `None` if there are fewer than two senders.
+    pub fn senders_key_agg_ctx(&self) -> Option<MusigKeyAggCtx> {
+        MusigKeyAggCtx::new(&self.msg_senders, None)
+    }
+
+    /// Returns the operator path: `(Operator with hashlocks)`, followed by the payload envelope.
+    pub fn operator_path(&self) -> TapLeaf {
+        let mut operator_path_script = Vec::<u8>::new();
+        for hashlock in self.hashlocks.iter() {
+            operator_path_script.push(0xa8); // OP_SHA256
+            operator_path_script.push(0x20); // OP_PUSHDATA_32
+            operator_path_script.extend(hashlock); // Hashlock 32-bytes
+            operator_path_script.push(0x88); // OP_EQUALVERIFY
+        }
+        operator_path_script.push(0x20); // OP_PUSHDATA_32
+        operator_path_script.extend(self.operator_key().serialize_xonly()); // Operator Key 32-bytes
+        operator_path_script.push(0xac); // OP_CHECKSIG
+        operator_path_script.extend(self.envelope.clone()); // Payload envelope
+
+        TapLeaf::new(operator_path_script)
+    }
+
+    /// Returns the fallback path: `(msg.senders[] after 1 week)`.
+    pub fn senders_path(&self) -> Option<TapLeaf> {
+        let senders_agg_key = self.senders_agg_key()?;
+
+        let mut senders_path_script = Vec::<u8>::new();
+        senders_path_script.extend(Bytes::csv_script(CSVFlag::CSVWeek)); // Relative Timelock
+        senders_path_script.push(0x20); // OP_PUSHDATA_32
+        senders_path_script.extend(senders_agg_key.serialize_xonly()); // msg.senders Key 32-bytes
+        senders_path_script.push(0xac); // OP_CHECKSIG
+
+        Some(TapLeaf::new(senders_path_script))
+    }
+
+    /// Returns the nSequence of the fallback spend.
+    pub fn senders_sequence(&self) -> u32 {
+        let bytes = Bytes::n_sequence(CSVFlag::CSVWeek);
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+
+    /// Returns the witness spending from the operator path.
+    /// Preimages are given in hashlock order. Returns `None` if a preimage does not match.
+    pub fn operator_witness(
+        &self,
+        operator_sig: [u8; 64],
+        preimages: &Vec<Bytes>,
+    ) -> Option<Witness> {
+        if preimages.len() != self.hashlocks.len() {
+            return None;
+        }
+
+        for (preimage, hashlock) in preimages.iter().zip(self.hashlocks.iter()) {
+            if sha256::Hash::hash(preimage).to_byte_array() != *hashlock {
+                return None;
+            }
+        }
+
+        let taproot = self.taproot()?;
+        let control_block = taproot.control_block(OPERATOR_PATH_INDEX)?;
+
+        // The first hashlock is checked first, hence its preimage goes on top of the stack.
+        let mut witness = Vec::<Bytes>::new();
+        witness.push(operator_sig.to_vec());
+        witness.extend(preimages.iter().rev().cloned());
+        witness.push(self.operator_path().tap_script());
+        witness.push(control_block.to_vec());
+
+        Some(Witness::from_slice(&witness))
+    }
+
+    /// Returns the witness spending from the fallback path with the aggregated msg.senders signature.
+    /// The spending input must carry `senders_sequence`.
+    pub fn senders_witness(&self, senders_sig: [u8; 64]) -> Option<Witness> {
+        let taproot = self.taproot()?;
+        let control_block = taproot.control_block(SENDERS_PATH_INDEX)?;
+
+        Some(Witness::from_slice(&[
+            senders_sig.to_vec(),
+            self.senders_path()?.tap_script(),
+            control_block.to_vec(),
+        ]))
+    }
+}
+
+impl P2TR for PayloadTXO {
+    fn taproot(&self) -> Option<TapRoot> {
+        //// Operator Path: (Operator with hashlocks)
+        let operator_path = self.operator_path();
+
+        //// Fallback Path: (msg.senders[] after 1 week)
+        let senders_path = self.senders_path()?;
+
+        Some(TapRoot::script_path_only_multi(vec![
+            operator_path,
+            senders_path,
+        ]))
+    }
+
+    fn spk(&self) -> Option<Bytes> {
+        self.taproot()?.spk()
+    }
+}
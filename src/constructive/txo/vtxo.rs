@@ -5,6 +5,7 @@ use crate::transmutive::secp::into::IntoScalar;
 use bitcoin::OutPoint;
 use secp::Point;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 type Bytes = Vec<u8>;
 
@@ -79,6 +80,50 @@ impl VTXO {
         self.at_bitcoin_height
     }
 
+    /// Returns a JSON representation of the VTXO.
+    pub fn json(&self) -> Value {
+        // Construct the VTXO JSON object
+        let mut obj = Map::new();
+
+        // Add account key
+        obj.insert(
+            "account".to_string(),
+            Value::String(hex::encode(self.account.serialize_xonly())),
+        );
+
+        // Add operator key
+        obj.insert(
+            "operator".to_string(),
+            Value::String(hex::encode(self.operator.serialize_xonly())),
+        );
+
+        // Add outpoint if present
+        match &self.outpoint {
+            Some(outpoint) => {
+                obj.insert("outpoint".to_string(), Value::String(outpoint.to_string()))
+            }
+            None => obj.insert("outpoint".to_string(), Value::Null),
+        };
+
+        // Add value if present
+        match self.value {
+            Some(value) => obj.insert("value".to_string(), Value::Number(value.into())),
+            None => obj.insert("value".to_string(), Value::Null),
+        };
+
+        // Add Bitcoin height if present
+        match self.at_bitcoin_height {
+            Some(height) => obj.insert(
+                "at_bitcoin_height".to_string(),
+                Value::Number(height.into()),
+            ),
+            None => obj.insert("at_bitcoin_height".to_string(), Value::Null),
+        };
+
+        // Return the VTXO JSON object
+        Value::Object(obj)
+    }
+
     /// Returns the keys of the VTXO.
     pub fn keys(&self) -> Vec<Point> {
         let mut keys = Vec::<Point>::new();
@@ -1,3 +1,4 @@
+use super::common_table::CommonTable;
 use crate::constructive::cpe::{
     cpe::CompactPayloadEncoding,
     decode_error::{
@@ -23,146 +24,20 @@ pub struct CommonLongVal {
 }
 
 impl CommonLongVal {
-    /// Creates a new `CommonLongVal` from a `u64` integer.
+    /// Creates a new `CommonLongVal` from a `u64` integer, using the genesis table.
     pub fn new(value: u64) -> Option<CommonLongVal> {
-        // Get the index.
-        let index = match value {
-            50 => 0,
-            100 => 1,
-            200 => 2,
-            250 => 3,
-            500 => 4,
-            550 => 5,
-            600 => 6,
-            750 => 7,
-            800 => 8,
-            900 => 9,
-            999 => 10,
-            1_000 => 11,
-            1_500 => 12,
-            2_000 => 13,
-            2_500 => 14,
-            3_000 => 15,
-            4_000 => 16,
-            5_000 => 17,
-            5_500 => 18,
-            6_000 => 19,
-            7_000 => 20,
-            7_500 => 21,
-            8_000 => 22,
-            9_000 => 23,
-            9_900 => 24,
-            10_000 => 25,
-            15_000 => 26,
-            20_000 => 27,
-            25_000 => 28,
-            30_000 => 29,
-            35_000 => 30,
-            40_000 => 31,
-            50_000 => 32,
-            55_000 => 33,
-            60_000 => 34,
-            70_000 => 35,
-            75_000 => 36,
-            80_000 => 37,
-            90_000 => 38,
-            99_900 => 39,
-            100_000 => 40,
-            150_000 => 41,
-            200_000 => 42,
-            250_000 => 43,
-            300_000 => 44,
-            350_000 => 45,
-            400_000 => 46,
-            500_000 => 47,
-            550_000 => 48,
-            600_000 => 49,
-            700_000 => 50,
-            750_000 => 51,
-            800_000 => 52,
-            900_000 => 53,
-            1_000_000 => 54,
-            2_000_000 => 55,
-            2_500_000 => 56,
-            5_000_000 => 57,
-            5_500_000 => 58,
-            10_000_000 => 59,
-            25_000_000 => 60,
-            50_000_000 => 61,
-            75_000_000 => 62,
-            100_000_000 => 63,
-            150_000_000 => 64,
-            200_000_000 => 65,
-            250_000_000 => 66,
-            300_000_000 => 67,
-            350_000_000 => 68,
-            400_000_000 => 69,
-            500_000_000 => 70,
-            550_000_000 => 71,
-            600_000_000 => 72,
-            700_000_000 => 73,
-            750_000_000 => 74,
-            800_000_000 => 75,
-            900_000_000 => 76,
-            1_000_000_000 => 77,
-            2_000_000_000 => 78,
-            2_500_000_000 => 79,
-            5_000_000_000 => 80,
-            5_500_000_000 => 81,
-            10_000_000_000 => 82,
-            25_000_000_000 => 83,
-            50_000_000_000 => 84,
-            75_000_000_000 => 85,
-            100_000_000_000 => 86,
-            150_000_000_000 => 87,
-            200_000_000_000 => 88,
-            250_000_000_000 => 89,
-            300_000_000_000 => 90,
-            350_000_000_000 => 91,
-            400_000_000_000 => 92,
-            500_000_000_000 => 93,
-            550_000_000_000 => 94,
-            600_000_000_000 => 95,
-            700_000_000_000 => 96,
-            750_000_000_000 => 97,
-            800_000_000_000 => 98,
-            900_000_000_000 => 99,
-            1_000_000_000_000 => 100,
-            2_000_000_000_000 => 101,
-            2_500_000_000_000 => 102,
-            5_000_000_000_000 => 103,
-            5_500_000_000_000 => 104,
-            10_000_000_000_000 => 105,
-            25_000_000_000_000 => 106,
-            50_000_000_000_000 => 107,
-            75_000_000_000_000 => 108,
-            100_000_000_000_000 => 109,
-            150_000_000_000_000 => 110,
-            200_000_000_000_000 => 111,
-            250_000_000_000_000 => 112,
-            300_000_000_000_000 => 113,
-            350_000_000_000_000 => 114,
-            400_000_000_000_000 => 115,
-            500_000_000_000_000 => 116,
-            550_000_000_000_000 => 117,
-            600_000_000_000_000 => 118,
-            700_000_000_000_000 => 119,
-            750_000_000_000_000 => 120,
-            800_000_000_000_000 => 121,
-            900_000_000_000_000 => 122,
-            1_000_000_000_000_000 => 123,
-            2_000_000_000_000_000 => 124,
-            2_500_000_000_000_000 => 125,
-            5_000_000_000_000_000 => 126,
-            10_000_000_000_000_000 => 127,
+        Self::new_with_table(value, &CommonTable::genesis())
+    }
 
-            _ => return None, // Invalid value
-        };
+    /// Creates a new `CommonLongVal` from a `u64` integer, using the given table.
+    pub fn new_with_table(value: u64, common_table: &CommonTable) -> Option<CommonLongVal> {
+        // Get the index.
+        let index = common_table.long_index(value)?;
 
-        // Create a new `CommonVal`.
+        // Create a new `CommonLongVal`.
         let common_long_val = CommonLongVal { value, index };
 
-        // Return the `CommonVal`.
+        // Return the `CommonLongVal`.
         Some(common_long_val)
     }
 
@@ -176,9 +51,17 @@ impl CommonLongVal {
         self.index
     }
 
-    /// Compact payload encoding for `CommonLongVal`.
-    /// Decodes a common u64 integer from a 7-bit `bit_vec::Iter`.
+    /// Compact payload decoding for `CommonLongVal`, using the genesis table.
     pub fn decode_cpe(bit_stream: &mut bit_vec::Iter<'_>) -> Result<Self, CPEDecodingError> {
+        Self::decode_cpe_with_table(bit_stream, &CommonTable::genesis())
+    }
+
+    /// Compact payload decoding for `CommonLongVal`, using the given table.
+    /// Decodes a common u64 integer from a 7-bit `bit_vec::Iter`.
+    pub fn decode_cpe_with_table(
+        bit_stream: &mut bit_vec::Iter<'_>,
+        common_table: &CommonTable,
+    ) -> Result<Self, CPEDecodingError> {
         // Initialize empty bitvec.
         let mut bits = BitVec::new();
 
@@ -205,144 +88,15 @@ impl CommonLongVal {
             ),
         )?;
 
-        // Convert the 6-bit index to a CommonInt variant and its u32 value
-        let value: u64 = match index {
-            0 => 50,
-            1 => 100,
-            2 => 200,
-            3 => 250,
-            4 => 500,
-            5 => 550,
-            6 => 600,
-            7 => 750,
-            8 => 800,
-            9 => 900,
-            10 => 999,
-            11 => 1_000,
-            12 => 1_500,
-            13 => 2_000,
-            14 => 2_500,
-            15 => 3_000,
-            16 => 4_000,
-            17 => 5_000,
-            18 => 5_500,
-            19 => 6_000,
-            20 => 7_000,
-            21 => 7_500,
-            22 => 8_000,
-            23 => 9_000,
-            24 => 9_900,
-            25 => 10_000,
-            26 => 15_000,
-            27 => 20_000,
-            28 => 25_000,
-            29 => 30_000,
-            30 => 35_000,
-            31 => 40_000,
-            32 => 50_000,
-            33 => 55_000,
-            34 => 60_000,
-            35 => 70_000,
-            36 => 75_000,
-            37 => 80_000,
-            38 => 90_000,
-            39 => 99_900,
-            40 => 100_000,
-            41 => 150_000,
-            42 => 200_000,
-            43 => 250_000,
-            44 => 300_000,
-            45 => 350_000,
-            46 => 400_000,
-            47 => 500_000,
-            48 => 550_000,
-            49 => 600_000,
-            50 => 700_000,
-            51 => 750_000,
-            52 => 800_000,
-            53 => 900_000,
-            54 => 1_000_000,
-            55 => 2_000_000,
-            56 => 2_500_000,
-            57 => 5_000_000,
-            58 => 5_500_000,
-            59 => 10_000_000,
-            60 => 25_000_000,
-            61 => 50_000_000,
-            62 => 75_000_000,
-            63 => 100_000_000,
-            64 => 150_000_000,
-            65 => 200_000_000,
-            66 => 250_000_000,
-            67 => 300_000_000,
-            68 => 350_000_000,
-            69 => 400_000_000,
-            70 => 500_000_000,
-            71 => 550_000_000,
-            72 => 600_000_000,
-            73 => 700_000_000,
-            74 => 750_000_000,
-            75 => 800_000_000,
-            76 => 900_000_000,
-            77 => 1_000_000_000,
-            78 => 2_000_000_000,
-            79 => 2_500_000_000,
-            80 => 5_000_000_000,
-            81 => 5_500_000_000,
-            82 => 10_000_000_000,
-            83 => 25_000_000_000,
-            84 => 50_000_000_000,
-            85 => 75_000_000_000,
-            86 => 100_000_000_000,
-            87 => 150_000_000_000,
-            88 => 200_000_000_000,
-            89 => 250_000_000_000,
-            90 => 300_000_000_000,
-            91 => 350_000_000_000,
-            92 => 400_000_000_000,
-            93 => 500_000_000_000,
-            94 => 550_000_000_000,
-            95 => 600_000_000_000,
-            96 => 700_000_000_000,
-            97 => 750_000_000_000,
-            98 => 800_000_000_000,
-            99 => 900_000_000_000,
-            100 => 1_000_000_000_000,
-            101 => 2_000_000_000_000,
-            102 => 2_500_000_000_000,
-            103 => 5_000_000_000_000,
-            104 => 5_500_000_000_000,
-            105 => 10_000_000_000_000,
-            106 => 25_000_000_000_000,
-            107 => 50_000_000_000_000,
-            108 => 75_000_000_000_000,
-            109 => 100_000_000_000_000,
-            110 => 150_000_000_000_000,
-            111 => 200_000_000_000_000,
-            112 => 250_000_000_000_000,
-            113 => 300_000_000_000_000,
-            114 => 350_000_000_000_000,
-            115 => 400_000_000_000_000,
-            116 => 500_000_000_000_000,
-            117 => 550_000_000_000_000,
-            118 => 600_000_000_000_000,
-            119 => 700_000_000_000_000,
-            120 => 750_000_000_000_000,
-            121 => 800_000_000_000_000,
-            122 => 900_000_000_000_000,
-            123 => 1_000_000_000_000_000,
-            124 => 2_000_000_000_000_000,
-            125 => 2_500_000_000_000_000,
-            126 => 5_000_000_000_000_000,
-            127 => 10_000_000_000_000_000,
-            _ => {
-                return Err(CPEDecodingError::MaybeCommonCPEDecodingError(
+        // Look up the value at the index in the table.
+        let value =
+            common_table
+                .long_value(index)
+                .ok_or(CPEDecodingError::MaybeCommonCPEDecodingError(
                     MaybeCommonCPEDecodingError::CommonLongValCPEDecodingError(
                         CommonLongValCPEDecodingError::UncommonInteger,
                     ),
-                ))
-            }
-        };
+                ))?;
 
         // Create a new `CommonLongVal`.
         let common_long_val = CommonLongVal { value, index };
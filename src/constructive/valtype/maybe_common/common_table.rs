@@ -0,0 +1,516 @@
+use crate::constructive::{
+    calldata::element::CallElement,
+    cpe::cpe::CompactPayloadEncoding,
+    entry::{combinator::combinator::Combinator, entry::Entry},
+    valtype::{long_val::LongVal, short_val::ShortVal},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Number of values in a common short table. Indexed by 6 bits.
+pub const COMMON_SHORT_TABLE_LEN: usize = 64;
+
+/// Number of values in a common long table. Indexed by 7 bits.
+pub const COMMON_LONG_TABLE_LEN: usize = 128;
+
+/// Version of the genesis common table.
+pub const GENESIS_COMMON_TABLE_VERSION: u8 = 0;
+
+/// Common short values of the genesis table.
+const GENESIS_COMMON_SHORT_VALUES: [u32; COMMON_SHORT_TABLE_LEN] = [
+    50,
+    100,
+    200,
+    250,
+    500,
+    550,
+    600,
+    750,
+    800,
+    900,
+    999,
+    1_000,
+    1_500,
+    2_000,
+    2_500,
+    3_000,
+    4_000,
+    5_000,
+    5_500,
+    6_000,
+    7_000,
+    7_500,
+    8_000,
+    9_000,
+    9_900,
+    10_000,
+    15_000,
+    20_000,
+    25_000,
+    30_000,
+    35_000,
+    40_000,
+    50_000,
+    55_000,
+    60_000,
+    70_000,
+    75_000,
+    80_000,
+    90_000,
+    99_900,
+    100_000,
+    150_000,
+    200_000,
+    250_000,
+    300_000,
+    350_000,
+    400_000,
+    500_000,
+    550_000,
+    600_000,
+    700_000,
+    750_000,
+    800_000,
+    900_000,
+    1_000_000,
+    2_000_000,
+    2_500_000,
+    5_000_000,
+    5_500_000,
+    10_000_000,
+    25_000_000,
+    50_000_000,
+    75_000_000,
+    100_000_000,
+];
+
+/// Common long values of the genesis table.
+const GENESIS_COMMON_LONG_VALUES: [u64; COMMON_LONG_TABLE_LEN] = [
+    50,
+    100,
+    200,
+    250,
+    500,
+    550,
+    600,
+    750,
+    800,
+    900,
+    999,
+    1_000,
+    1_500,
+    2_000,
+    2_500,
+    3_000,
+    4_000,
+    5_000,
+    5_500,
+    6_000,
+    7_000,
+    7_500,
+    8_000,
+    9_000,
+    9_900,
+    10_000,
+    15_000,
+    20_000,
+    25_000,
+    30_000,
+    35_000,
+    40_000,
+    50_000,
+    55_000,
+    60_000,
+    70_000,
+    75_000,
+    80_000,
+    90_000,
+    99_900,
+    100_000,
+    150_000,
+    200_000,
+    250_000,
+    300_000,
+    350_000,
+    400_000,
+    500_000,
+    550_000,
+    600_000,
+    700_000,
+    750_000,
+    800_000,
+    900_000,
+    1_000_000,
+    2_000_000,
+    2_500_000,
+    5_000_000,
+    5_500_000,
+    10_000_000,
+    25_000_000,
+    50_000_000,
+    75_000_000,
+    100_000_000,
+    150_000_000,
+    200_000_000,
+    250_000_000,
+    300_000_000,
+    350_000_000,
+    400_000_000,
+    500_000_000,
+    550_000_000,
+    600_000_000,
+    700_000_000,
+    750_000_000,
+    800_000_000,
+    900_000_000,
+    1_000_000_000,
+    2_000_000_000,
+    2_500_000_000,
+    5_000_000_000,
+    5_500_000_000,
+    10_000_000_000,
+    25_000_000_000,
+    50_000_000_000,
+    75_000_000_000,
+    100_000_000_000,
+    150_000_000_000,
+    200_000_000_000,
+    250_000_000_000,
+    300_000_000_000,
+    350_000_000_000,
+    400_000_000_000,
+    500_000_000_000,
+    550_000_000_000,
+    600_000_000_000,
+    700_000_000_000,
+    750_000_000_000,
+    800_000_000_000,
+    900_000_000_000,
+    1_000_000_000_000,
+    2_000_000_000_000,
+    2_500_000_000_000,
+    5_000_000_000_000,
+    5_500_000_000_000,
+    10_000_000_000_000,
+    25_000_000_000_000,
+    50_000_000_000_000,
+    75_000_000_000_000,
+    100_000_000_000_000,
+    150_000_000_000_000,
+    200_000_000_000_000,
+    250_000_000_000_000,
+    300_000_000_000_000,
+    350_000_000_000_000,
+    400_000_000_000_000,
+    500_000_000_000_000,
+    550_000_000_000_000,
+    600_000_000_000_000,
+    700_000_000_000_000,
+    750_000_000_000_000,
+    800_000_000_000_000,
+    900_000_000_000_000,
+    1_000_000_000_000_000,
+    2_000_000_000_000_000,
+    2_500_000_000_000_000,
+    5_000_000_000_000_000,
+    10_000_000_000_000_000,
+];
+
+/// `CommonTable` is a versioned lookup table of common values.
+///
+/// Payloads select the table their `MaybeCommon` values are encoded with.
+/// A table can only be selected by payloads revealed at or above its activation Bitcoin height.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommonTable {
+    version: u8,
+    activation_height: u64,
+    short_values: Vec<u32>,
+    long_values: Vec<u64>,
+}
+
+impl CommonTable {
+    /// Creates a new `CommonTable`.
+    /// Returns `None` if the tables are not of the expected lengths, or contain duplicates.
+    pub fn new(
+        version: u8,
+        activation_height: u64,
+        short_values: Vec<u32>,
+        long_values: Vec<u64>,
+    ) -> Option<CommonTable> {
+        if short_values.len() != COMMON_SHORT_TABLE_LEN
+            || long_values.len() != COMMON_LONG_TABLE_LEN
+        {
+            return None;
+        }
+
+        // Values must be unique to be mapped to a single index.
+        for (index, value) in short_values.iter().enumerate() {
+            if short_values[..index].contains(value) {
+                return None;
+            }
+        }
+        for (index, value) in long_values.iter().enumerate() {
+            if long_values[..index].contains(value) {
+                return None;
+            }
+        }
+
+        Some(CommonTable {
+            version,
+            activation_height,
+            short_values,
+            long_values,
+        })
+    }
+
+    /// Returns the genesis table.
+    pub fn genesis() -> CommonTable {
+        CommonTable {
+            version: GENESIS_COMMON_TABLE_VERSION,
+            activation_height: 0,
+            short_values: GENESIS_COMMON_SHORT_VALUES.to_vec(),
+            long_values: GENESIS_COMMON_LONG_VALUES.to_vec(),
+        }
+    }
+
+    /// Returns the scheduled table with the given version.
+    pub fn scheduled(version: u8) -> Option<CommonTable> {
+        scheduled_tables()
+            .into_iter()
+            .find(|table| table.version == version)
+    }
+
+    /// Returns the latest scheduled table active at the given Bitcoin height.
+    pub fn latest(height: u64) -> CommonTable {
+        scheduled_tables()
+            .into_iter()
+            .filter(|table| table.is_active(height))
+            .max_by_key(|table| table.version)
+            .unwrap_or_else(CommonTable::genesis)
+    }
+
+    /// Proposes a table that minimizes the bits spent on the recorded values.
+    ///
+    /// Values are ranked by the bits they would save as common values. Unfilled slots
+    /// are padded with genesis values, so that the table stays complete.
+    pub fn propose(
+        version: u8,
+        activation_height: u64,
+        stats: &CommonValueStats,
+    ) -> Option<CommonTable> {
+        let short_values = propose_values(
+            &stats.short_counts,
+            |value| short_saved_bits(*value),
+            &GENESIS_COMMON_SHORT_VALUES,
+        );
+
+        let long_values = propose_values(
+            &stats.long_counts,
+            |value| long_saved_bits(*value),
+            &GENESIS_COMMON_LONG_VALUES,
+        );
+
+        CommonTable::new(version, activation_height, short_values, long_values)
+    }
+
+    /// Returns the table version.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the Bitcoin height from which the table can be selected.
+    pub fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    /// Returns whether the table is active at the given Bitcoin height.
+    pub fn is_active(&self, height: u64) -> bool {
+        height >= self.activation_height
+    }
+
+    /// Returns the common short values.
+    pub fn short_values(&self) -> Vec<u32> {
+        self.short_values.clone()
+    }
+
+    /// Returns the common long values.
+    pub fn long_values(&self) -> Vec<u64> {
+        self.long_values.clone()
+    }
+
+    /// Returns the index of a common short value.
+    pub fn short_index(&self, value: u32) -> Option<u8> {
+        self.short_values
+            .iter()
+            .position(|common_value| *common_value == value)
+            .map(|index| index as u8)
+    }
+
+    /// Returns the common short value at the given index.
+    pub fn short_value(&self, index: u8) -> Option<u32> {
+        self.short_values.get(index as usize).copied()
+    }
+
+    /// Returns the index of a common long value.
+    pub fn long_index(&self, value: u64) -> Option<u8> {
+        self.long_values
+            .iter()
+            .position(|common_value| *common_value == value)
+            .map(|index| index as u8)
+    }
+
+    /// Returns the common long value at the given index.
+    pub fn long_value(&self, index: u8) -> Option<u64> {
+        self.long_values.get(index as usize).copied()
+    }
+}
+
+/// Returns the scheduled tables, in version order.
+///
+/// Table upgrades are appended here along with their activation Bitcoin heights.
+/// Tables are never removed, so that older payloads remain decodable.
+pub fn scheduled_tables() -> Vec<CommonTable> {
+    vec![CommonTable::genesis()]
+}
+
+/// Occurrence counts of the values encoded as `MaybeCommon` in synced entries.
+#[derive(Debug, Clone, Default)]
+pub struct CommonValueStats {
+    short_counts: HashMap<u32, u64>,
+    long_counts: HashMap<u64, u64>,
+}
+
+impl CommonValueStats {
+    /// Creates an empty `CommonValueStats`.
+    pub fn new() -> CommonValueStats {
+        CommonValueStats::default()
+    }
+
+    /// Records a short value.
+    pub fn insert_short(&mut self, value: u32) {
+        *self.short_counts.entry(value).or_insert(0) += 1;
+    }
+
+    /// Records a long value.
+    pub fn insert_long(&mut self, value: u64) {
+        // Long values beyond the u32 range are never encoded as common.
+        if value > u32::MAX as u64 {
+            return;
+        }
+
+        *self.long_counts.entry(value).or_insert(0) += 1;
+    }
+
+    /// Records the `MaybeCommon` values of an entry.
+    pub fn insert_entry(&mut self, entry: &Entry) {
+        match entry.main_combinator() {
+            Some(Combinator::Move(r#move)) => self.insert_short(r#move.amount()),
+            Some(Combinator::BatchMove(batch_move)) => {
+                for (_, amount) in batch_move.payments() {
+                    self.insert_short(amount);
+                }
+            }
+            Some(Combinator::Add(add)) => self.insert_short(add.amount()),
+            Some(Combinator::Sub(sub)) => self.insert_short(sub.amount()),
+            Some(Combinator::Swapout(swapout)) => self.insert_short(swapout.amount()),
+            Some(Combinator::Call(call)) => {
+                for element in call.calldata() {
+                    match element {
+                        CallElement::U32(short_val) => self.insert_short(short_val.value()),
+                        CallElement::Payable(short_val) => self.insert_short(short_val.value()),
+                        CallElement::U64(long_val) => self.insert_long(long_val.value()),
+                        _ => (),
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Returns the number of recorded short value occurrences.
+    pub fn short_count(&self) -> u64 {
+        self.short_counts.values().sum()
+    }
+
+    /// Returns the number of recorded long value occurrences.
+    pub fn long_count(&self) -> u64 {
+        self.long_counts.values().sum()
+    }
+
+    /// Returns the number of bits the given table saves on the recorded values,
+    /// compared to encoding them all as uncommon.
+    pub fn saved_bits(&self, table: &CommonTable) -> u64 {
+        let short_saved: u64 = self
+            .short_counts
+            .iter()
+            .filter(|(value, _)| table.short_index(**value).is_some())
+            .map(|(value, count)| count * short_saved_bits(*value))
+            .sum();
+
+        let long_saved: u64 = self
+            .long_counts
+            .iter()
+            .filter(|(value, _)| table.long_index(**value).is_some())
+            .map(|(value, count)| count * long_saved_bits(*value))
+            .sum();
+
+        short_saved + long_saved
+    }
+}
+
+/// Bits saved by encoding a short value as common rather than uncommon.
+fn short_saved_bits(value: u32) -> u64 {
+    let uncommon_bits = ShortVal::new(value)
+        .encode_cpe()
+        .map(|bits| bits.len())
+        .unwrap_or(0);
+
+    // Common short values are encoded in 6 bits.
+    uncommon_bits.saturating_sub(6) as u64
+}
+
+/// Bits saved by encoding a long value as common rather than uncommon.
+fn long_saved_bits(value: u64) -> u64 {
+    let uncommon_bits = LongVal::new(value)
+        .encode_cpe()
+        .map(|bits| bits.len())
+        .unwrap_or(0);
+
+    // Common long values are encoded in 7 bits.
+    uncommon_bits.saturating_sub(7) as u64
+}
+
+/// Picks the values saving the most bits, padded with the fallback values.
+/// Returned values are sorted in ascending order.
+fn propose_values<T, F>(counts: &HashMap<T, u64>, saved_bits: F, fallback: &[T]) -> Vec<T>
+where
+    T: Copy + Ord + std::hash::Hash,
+    F: Fn(&T) -> u64,
+{
+    let mut candidates: Vec<(T, u64)> = counts
+        .iter()
+        .map(|(value, count)| (*value, count * saved_bits(value)))
+        .filter(|(_, saved)| *saved > 0)
+        .collect();
+
+    // Most saving first, ties broken by the smaller value.
+    candidates.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut values: Vec<T> = candidates
+        .into_iter()
+        .take(fallback.len())
+        .map(|(value, _)| value)
+        .collect();
+
+    for value in fallback.iter() {
+        if values.len() == fallback.len() {
+            break;
+        }
+        if !values.contains(value) {
+            values.push(*value);
+        }
+    }
+
+    values.sort();
+    values
+}
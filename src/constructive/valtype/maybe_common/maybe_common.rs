@@ -1,4 +1,6 @@
-use super::{common_long::CommonLongVal, common_short::CommonShortVal};
+use super::{
+    common_long::CommonLongVal, common_short::CommonShortVal, common_table::CommonTable,
+};
 use crate::constructive::cpe::{
     cpe::CompactPayloadEncoding,
     decode_error::{error::CPEDecodingError, valtype_error::MaybeCommonCPEDecodingError},
@@ -49,12 +51,17 @@ impl<T> MaybeCommon<T>
 where
     T: Commonable + CompactPayloadEncoding + Clone + From<ShortVal> + From<LongVal>,
 {
-    /// Creates a new `MaybeCommon` for `ShortVal` or `LongVal`.
+    /// Creates a new `MaybeCommon` for `ShortVal` or `LongVal`, using the genesis table.
     pub fn new(val: T) -> Self {
+        Self::new_with_table(val, &CommonTable::genesis())
+    }
+
+    /// Creates a new `MaybeCommon` for `ShortVal` or `LongVal`, using the given table.
+    pub fn new_with_table(val: T, common_table: &CommonTable) -> Self {
         match val.maybe_common_value() {
             MaybeCommonValue::Short(short_val) => {
                 // Check if the value is common.
-                match CommonShortVal::new(short_val.value()) {
+                match CommonShortVal::new_with_table(short_val.value(), common_table) {
                     Some(common_val) => MaybeCommon::Common(CommonVal::CommonShort(common_val)),
                     None => MaybeCommon::Uncommon(val),
                 }
@@ -64,7 +71,7 @@ where
                 match long_val.value() <= u32::MAX as u64 {
                     true => {
                         // Check if the value is common.
-                        match CommonLongVal::new(long_val.value()) {
+                        match CommonLongVal::new_with_table(long_val.value(), common_table) {
                             Some(common_val) => {
                                 MaybeCommon::Common(CommonVal::CommonLong(common_val))
                             }
@@ -101,8 +108,16 @@ where
         }
     }
 
-    /// Decodes a `MaybeCommon` from a bit stream.
+    /// Decodes a `MaybeCommon` from a bit stream, using the genesis table.
     pub fn decode_cpe(bit_stream: &mut bit_vec::Iter<'_>) -> Result<Self, CPEDecodingError> {
+        Self::decode_cpe_with_table(bit_stream, &CommonTable::genesis())
+    }
+
+    /// Decodes a `MaybeCommon` from a bit stream, using the given table.
+    pub fn decode_cpe_with_table(
+        bit_stream: &mut bit_vec::Iter<'_>,
+        common_table: &CommonTable,
+    ) -> Result<Self, CPEDecodingError> {
         // Check if the value is common.
         let is_common = bit_stream
             .next()
@@ -118,7 +133,8 @@ where
                 match T::maybe_common_value_type() {
                     MaybeCommonValueType::Short => {
                         // Decode common short value from 6 bits.
-                        let common_short_val = CommonShortVal::decode_cpe(bit_stream)?;
+                        let common_short_val =
+                            CommonShortVal::decode_cpe_with_table(bit_stream, common_table)?;
 
                         // Return the common short value.
                         Ok(MaybeCommon::Common(CommonVal::CommonShort(
@@ -127,7 +143,8 @@ where
                     }
                     MaybeCommonValueType::Long => {
                         // Decode common long value from 7 bits.
-                        let common_long_val = CommonLongVal::decode_cpe(bit_stream)?;
+                        let common_long_val =
+                            CommonLongVal::decode_cpe_with_table(bit_stream, common_table)?;
 
                         // Return the common long value.
                         Ok(MaybeCommon::Common(CommonVal::CommonLong(common_long_val)))
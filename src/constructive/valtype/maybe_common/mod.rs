@@ -1,3 +1,4 @@
 pub mod common_long;
 pub mod common_short;
+pub mod common_table;
 pub mod maybe_common;
@@ -0,0 +1,153 @@
+use crate::{
+    constructive::calldata::element_type::CallElementType,
+    executive::program::method::method_type::MethodType,
+};
+
+/// A contract declaration; compiles into a `Program`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractDecl {
+    /// The contract (program) name.
+    pub name: String,
+    /// The storage maps.
+    pub storage: Vec<StorageDecl>,
+    /// The functions; each compiles into a `ProgramMethod`.
+    pub functions: Vec<FunctionDecl>,
+}
+
+/// A `storage name: map<K, V>;` declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageDecl {
+    /// The map name.
+    pub name: String,
+    /// The key type.
+    pub key_type: CallElementType,
+    /// The value type.
+    pub value_type: CallElementType,
+}
+
+/// A function declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionDecl {
+    /// The function (method) name.
+    pub name: String,
+    /// The method type.
+    pub method_type: MethodType,
+    /// The typed parameters, in calldata order.
+    pub params: Vec<(String, CallElementType)>,
+    /// The return types.
+    pub returns: Vec<CallElementType>,
+    /// The function body.
+    pub body: Vec<Stmt>,
+}
+
+/// A statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stmt {
+    // `let name: ty = value;`
+    Let {
+        name: String,
+        ty: CallElementType,
+        value: Expr,
+    },
+    // `name = value;`
+    Assign {
+        name: String,
+        value: Expr,
+    },
+    // `map[key] = value;`
+    MapAssign {
+        map: String,
+        key: Expr,
+        value: Expr,
+    },
+    // `if cond { .. } else { .. }`
+    If {
+        cond: Expr,
+        then_body: Vec<Stmt>,
+        else_body: Vec<Stmt>,
+    },
+    // `return a, b;`
+    Return(Vec<Expr>),
+    // `require(cond);`
+    Require(Expr),
+    // `fail;`
+    Fail,
+}
+
+/// An expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    // Decimal integer literal.
+    Int(u64),
+    // Hex byte array literal.
+    Bytes(Vec<u8>),
+    // Boolean literal.
+    Bool(bool),
+    // Local variable or parameter.
+    Var(String),
+    // `map[key]`
+    MapRead {
+        map: String,
+        key: Box<Expr>,
+    },
+    // `!expr`
+    Not(Box<Expr>),
+    // `lhs op rhs`
+    Binary {
+        op: BinaryOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+    // `expr as ty`
+    Cast {
+        expr: Box<Expr>,
+        ty: CallElementType,
+    },
+}
+
+/// A binary operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    /// Returns the source symbol of the operator.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Mod => "%",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+        }
+    }
+
+    /// Returns whether the operator is arithmetic.
+    pub fn is_arithmetic(&self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod
+        )
+    }
+}
@@ -0,0 +1,739 @@
+use super::{
+    ast::{BinaryOp, ContractDecl, Expr, FunctionDecl, Stmt, StorageDecl},
+    lang_error::{CodegenError, LangError},
+    lexer::tokenize,
+    parser::Parser,
+};
+use crate::{
+    constructive::calldata::element_type::CallElementType,
+    executive::{
+        opcode::{
+            op::{
+                altstack::{op_fromaltstack::OP_FROMALTSTACK, op_toaltstack::OP_TOALTSTACK},
+                arithmetic::{
+                    op_add::OP_ADD, op_booland::OP_BOOLAND, op_boolor::OP_BOOLOR, op_div::OP_DIV,
+                    op_greaterthan::OP_GREATERTHAN, op_greaterthanorequal::OP_GREATERTHANOREQUAL,
+                    op_lessthan::OP_LESSTHAN, op_lessthanorequal::OP_LESSTHANOREQUAL,
+                    op_mul::OP_MUL, op_not::OP_NOT, op_numequal::OP_NUMEQUAL,
+                    op_numnotequal::OP_NUMNOTEQUAL, op_sub::OP_SUB,
+                },
+                bitwise::op_equal::OP_EQUAL,
+                flow::{
+                    op_else::OP_ELSE, op_endif::OP_ENDIF, op_fail::OP_FAIL, op_if::OP_IF,
+                    op_returnall::OP_RETURNALL, op_verify::OP_VERIFY,
+                },
+                memory::{op_free::OP_MFREE, op_mread::OP_MREAD, op_mwrite::OP_MWRITE},
+                push::{
+                    op_10::OP_10, op_11::OP_11, op_12::OP_12, op_13::OP_13, op_14::OP_14,
+                    op_15::OP_15, op_16::OP_16, op_2::OP_2, op_3::OP_3, op_4::OP_4, op_5::OP_5,
+                    op_6::OP_6, op_7::OP_7, op_8::OP_8, op_9::OP_9, op_false::OP_FALSE,
+                    op_pushdata::OP_PUSHDATA, op_true::OP_TRUE,
+                },
+                splice::{op_cat::OP_CAT, op_size::OP_SIZE},
+                stack::{op_drop::OP_DROP, op_dup::OP_DUP},
+                storage::{op_sfree::OP_SFREE, op_sread::OP_SREAD, op_swrite::OP_SWRITE},
+            },
+            opcode::Opcode,
+            optimizer::optimizer::ScriptOptimizer,
+        },
+        program::{
+            method::{
+                limits::MAX_METHOD_OPCODE_COUNT, method::ProgramMethod, method_type::MethodType,
+            },
+            program::Program,
+        },
+        stack::limits::MAX_KEY_LENGTH,
+    },
+};
+
+/// Compiles contract source into a `Program`.
+pub fn compile_contract(source: &str) -> Result<Program, LangError> {
+    // Tokenize the source.
+    let tokens = tokenize(source).map_err(|e| LangError::LexError(e))?;
+
+    // Parse the contract declaration.
+    let contract = Parser::new(tokens)
+        .parse_contract()
+        .map_err(|e| LangError::ParseError(e))?;
+
+    // Generate the program.
+    compile_contract_decl(&contract).map_err(|e| LangError::CodegenError(e))
+}
+
+/// Compiles a parsed contract declaration into a `Program`.
+pub fn compile_contract_decl(contract: &ContractDecl) -> Result<Program, CodegenError> {
+    // Validate the storage maps.
+    // Each map is keyed by a one-byte prefix (map index + 1), so that the prefix is never 0x00.
+    if contract.storage.len() > u8::MAX as usize {
+        return Err(CodegenError::TooManyStorageMaps);
+    }
+    for (index, map) in contract.storage.iter().enumerate() {
+        if contract.storage[..index]
+            .iter()
+            .any(|other| other.name == map.name)
+        {
+            return Err(CodegenError::DuplicateStorageMap(map.name.clone()));
+        }
+        match fixed_length(map.key_type) {
+            Some(length) if length < MAX_KEY_LENGTH as usize => {}
+            _ => return Err(CodegenError::InvalidStorageKeyType(map.key_type)),
+        }
+    }
+
+    // Compile the methods.
+    let mut methods = Vec::<ProgramMethod>::with_capacity(contract.functions.len());
+    for function in contract.functions.iter() {
+        let script = FunctionCodegen::new(&contract.storage, function).compile()?;
+
+        let call_element_types = function.params.iter().map(|(_, ty)| *ty).collect();
+
+        let method = ProgramMethod::new(
+            function.name.clone(),
+            function.method_type,
+            call_element_types,
+            script,
+        )
+        .map_err(|e| CodegenError::MethodConstructionError(function.name.clone(), e))?;
+
+        methods.push(method);
+    }
+
+    Program::new(contract.name.clone(), methods)
+        .map_err(|e| CodegenError::ProgramConstructionError(e))
+}
+
+/// A local variable or parameter kept in contract memory.
+struct Local {
+    name: String,
+    ty: CallElementType,
+    slot: u8,
+}
+
+/// Generates the script of a single function.
+///
+/// Locals live in contract memory under one-byte keys (slot + 1), so that the main stack is
+/// empty between statements and `OP_RETURNALL` returns only the return values.
+struct FunctionCodegen<'a> {
+    storage: &'a [StorageDecl],
+    function: &'a FunctionDecl,
+    scopes: Vec<Vec<Local>>,
+    next_slot: usize,
+    script: Vec<Opcode>,
+}
+
+impl<'a> FunctionCodegen<'a> {
+    fn new(storage: &'a [StorageDecl], function: &'a FunctionDecl) -> Self {
+        Self {
+            storage,
+            function,
+            scopes: vec![vec![]],
+            next_slot: 0,
+            script: Vec::new(),
+        }
+    }
+
+    fn compile(mut self) -> Result<Vec<Opcode>, CodegenError> {
+        // Move the parameters into memory. The last parameter is on top of the stack.
+        let function = self.function;
+        let mut slots = Vec::<u8>::with_capacity(function.params.len());
+        for (name, ty) in function.params.iter() {
+            slots.push(self.declare(name, *ty)?);
+        }
+        for slot in slots.into_iter().rev() {
+            self.write_local(slot)?;
+        }
+
+        // Compile the body.
+        self.compile_block(&function.body)?;
+
+        // Close the method if it does not terminate on its own.
+        if !terminates(&function.body) {
+            if !function.returns.is_empty() {
+                return Err(CodegenError::MissingReturn(function.name.clone()));
+            }
+            self.emit(Opcode::OP_RETURNALL(OP_RETURNALL));
+        }
+
+        // Optimize the script. Short scripts are padded up to the minimum opcode count.
+        let (script, _) = self
+            .script
+            .optimize()
+            .map_err(|e| CodegenError::OpcodeCompileError(function.name.clone(), e))?;
+
+        // Check the opcode count.
+        if script.len() > MAX_METHOD_OPCODE_COUNT {
+            return Err(CodegenError::MethodOpcodeCountExceeded(
+                function.name.clone(),
+                script.len(),
+            ));
+        }
+
+        Ok(script)
+    }
+
+    fn emit(&mut self, opcode: Opcode) {
+        self.script.push(opcode);
+    }
+
+    fn declare(&mut self, name: &str, ty: CallElementType) -> Result<u8, CodegenError> {
+        if self.lookup(name).is_some() {
+            return Err(CodegenError::DuplicateVariable(name.to_string()));
+        }
+        // Slot + 1 must fit in the one-byte memory key.
+        if self.next_slot >= u8::MAX as usize {
+            return Err(CodegenError::TooManyLocals(self.function.name.clone()));
+        }
+        let slot = self.next_slot as u8;
+        self.next_slot += 1;
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(Local {
+                name: name.to_string(),
+                ty,
+                slot,
+            });
+        }
+        Ok(slot)
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Local> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter())
+            .find(|local| local.name == name)
+    }
+
+    fn storage_map(&self, name: &str) -> Result<(u8, &'a StorageDecl), CodegenError> {
+        let storage: &'a [StorageDecl] = self.storage;
+        storage
+            .iter()
+            .enumerate()
+            .find(|(_, map)| map.name == name)
+            .map(|(index, map)| (index as u8, map))
+            .ok_or(CodegenError::UnknownStorageMap(name.to_string()))
+    }
+
+    fn compile_block(&mut self, stmts: &[Stmt]) -> Result<(), CodegenError> {
+        for stmt in stmts {
+            self.compile_stmt(stmt)?;
+        }
+        Ok(())
+    }
+
+    fn compile_scoped_block(&mut self, stmts: &[Stmt]) -> Result<(), CodegenError> {
+        self.scopes.push(vec![]);
+        self.compile_block(stmts)?;
+        self.scopes.pop();
+        Ok(())
+    }
+
+    fn compile_stmt(&mut self, stmt: &Stmt) -> Result<(), CodegenError> {
+        match stmt {
+            Stmt::Let { name, ty, value } => {
+                let found = self.compile_expr(value, Some(*ty))?;
+                expect_type(found, *ty)?;
+                let slot = self.declare(name, *ty)?;
+                self.write_local(slot)
+            }
+            Stmt::Assign { name, value } => {
+                let (ty, slot) = match self.lookup(name) {
+                    Some(local) => (local.ty, local.slot),
+                    None => return Err(CodegenError::UnknownVariable(name.clone())),
+                };
+                let found = self.compile_expr(value, Some(ty))?;
+                expect_type(found, ty)?;
+                self.write_local(slot)
+            }
+            Stmt::MapAssign { map, key, value } => {
+                if self.function.method_type == MethodType::ReadOnly {
+                    return Err(CodegenError::StorageWriteInReadOnlyMethod(
+                        self.function.name.clone(),
+                    ));
+                }
+                let (_, decl) = self.storage_map(map)?;
+                let found = self.compile_expr(value, Some(decl.value_type))?;
+                expect_type(found, decl.value_type)?;
+
+                // Generate the storage key in a scratch script, as it is emitted in both branches.
+                let key_script = self.scratch(|codegen| codegen.storage_key(map, key))?;
+                self.write_storage(&key_script);
+                Ok(())
+            }
+            Stmt::If {
+                cond,
+                then_body,
+                else_body,
+            } => {
+                let found = self.compile_expr(cond, Some(CallElementType::Bool))?;
+                expect_type(found, CallElementType::Bool)?;
+                self.emit(Opcode::OP_IF(OP_IF));
+                self.compile_scoped_block(then_body)?;
+                if !else_body.is_empty() {
+                    self.emit(Opcode::OP_ELSE(OP_ELSE));
+                    self.compile_scoped_block(else_body)?;
+                }
+                self.emit(Opcode::OP_ENDIF(OP_ENDIF));
+                Ok(())
+            }
+            Stmt::Return(values) => {
+                let function = self.function;
+                let returns = &function.returns;
+                if values.len() != returns.len() {
+                    return Err(CodegenError::ReturnArityMismatch {
+                        expected: returns.len(),
+                        found: values.len(),
+                    });
+                }
+                // Push in reverse, so that the first return value is popped first.
+                for (value, ty) in values.iter().zip(returns.iter()).rev() {
+                    let found = self.compile_expr(value, Some(*ty))?;
+                    expect_type(found, *ty)?;
+                }
+                self.emit(Opcode::OP_RETURNALL(OP_RETURNALL));
+                Ok(())
+            }
+            Stmt::Require(cond) => {
+                let found = self.compile_expr(cond, Some(CallElementType::Bool))?;
+                expect_type(found, CallElementType::Bool)?;
+                self.emit(Opcode::OP_VERIFY(OP_VERIFY));
+                Ok(())
+            }
+            Stmt::Fail => {
+                self.emit(Opcode::OP_FAIL(OP_FAIL));
+                Ok(())
+            }
+        }
+    }
+
+    /// Runs `f` against an empty script and returns what it emitted.
+    fn scratch<F>(&mut self, f: F) -> Result<Vec<Opcode>, CodegenError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), CodegenError>,
+    {
+        let saved = std::mem::take(&mut self.script);
+        let result = f(self);
+        let emitted = std::mem::replace(&mut self.script, saved);
+        result.map(|_| emitted)
+    }
+
+    /// Writes the value on top of the stack to the local slot, or frees the slot if the value is empty.
+    fn write_local(&mut self, slot: u8) -> Result<(), CodegenError> {
+        let key = push_bytes(&slot_key(slot))?;
+        self.emit(Opcode::OP_SIZE(OP_SIZE));
+        self.emit(Opcode::OP_IF(OP_IF));
+        self.emit(key.clone());
+        self.emit(Opcode::OP_MWRITE(OP_MWRITE));
+        self.emit(Opcode::OP_DROP(OP_DROP));
+        self.emit(Opcode::OP_ELSE(OP_ELSE));
+        self.emit(Opcode::OP_DROP(OP_DROP));
+        self.emit(key);
+        self.emit(Opcode::OP_MFREE(OP_MFREE));
+        self.emit(Opcode::OP_DROP(OP_DROP));
+        self.emit(Opcode::OP_ENDIF(OP_ENDIF));
+        Ok(())
+    }
+
+    /// Writes the value on top of the stack to storage, or frees the key if the value is empty.
+    fn write_storage(&mut self, key_script: &[Opcode]) {
+        self.emit(Opcode::OP_SIZE(OP_SIZE));
+        self.emit(Opcode::OP_IF(OP_IF));
+        self.script.extend(key_script.iter().cloned());
+        self.emit(Opcode::OP_SWRITE(OP_SWRITE));
+        self.emit(Opcode::OP_DROP(OP_DROP));
+        self.emit(Opcode::OP_ELSE(OP_ELSE));
+        self.emit(Opcode::OP_DROP(OP_DROP));
+        self.script.extend(key_script.iter().cloned());
+        self.emit(Opcode::OP_SFREE(OP_SFREE));
+        self.emit(Opcode::OP_DROP(OP_DROP));
+        self.emit(Opcode::OP_ENDIF(OP_ENDIF));
+    }
+
+    /// Pushes the storage key for `map[key]`: the map prefix followed by the key bytes.
+    fn storage_key(&mut self, map: &str, key: &Expr) -> Result<(), CodegenError> {
+        let (index, decl) = self.storage_map(map)?;
+        self.emit(push_bytes(&[index + 1])?);
+        let found = self.compile_expr(key, Some(decl.key_type))?;
+        expect_type(found, decl.key_type)?;
+        // Normalize fixed-width numbers to their minimal encoding.
+        if is_numeric(decl.key_type) {
+            self.emit(Opcode::OP_FALSE(OP_FALSE));
+            self.emit(Opcode::OP_ADD(OP_ADD));
+            self.emit(Opcode::OP_VERIFY(OP_VERIFY));
+        }
+        self.emit(Opcode::OP_CAT(OP_CAT));
+        Ok(())
+    }
+
+    /// Infers the type of an expression without emitting code.
+    /// Returns `None` for expressions made of untyped integer literals only.
+    fn infer(&self, expr: &Expr) -> Result<Option<CallElementType>, CodegenError> {
+        match expr {
+            Expr::Int(_) => Ok(None),
+            Expr::Bytes(bytes) => Ok(Some(bytes_type(bytes.len()))),
+            Expr::Bool(_) => Ok(Some(CallElementType::Bool)),
+            Expr::Var(name) => match self.lookup(name) {
+                Some(local) => Ok(Some(local.ty)),
+                None => Err(CodegenError::UnknownVariable(name.clone())),
+            },
+            Expr::MapRead { map, .. } => Ok(Some(self.storage_map(map)?.1.value_type)),
+            Expr::Not(_) => Ok(Some(CallElementType::Bool)),
+            Expr::Binary { op, lhs, rhs } if op.is_arithmetic() => {
+                Ok(self.infer(lhs)?.or(self.infer(rhs)?))
+            }
+            Expr::Binary { .. } => Ok(Some(CallElementType::Bool)),
+            Expr::Cast { ty, .. } => Ok(Some(*ty)),
+        }
+    }
+
+    /// Compiles an expression, pushing exactly one item. Returns the expression type.
+    /// `expected` is used to type integer literals.
+    fn compile_expr(
+        &mut self,
+        expr: &Expr,
+        expected: Option<CallElementType>,
+    ) -> Result<CallElementType, CodegenError> {
+        match expr {
+            Expr::Int(value) => {
+                let ty = match expected {
+                    Some(ty) if is_numeric(ty) => ty,
+                    Some(ty) => {
+                        return Err(CodegenError::TypeMismatch {
+                            expected: ty,
+                            found: CallElementType::U64,
+                        })
+                    }
+                    None => CallElementType::U64,
+                };
+                if *value >> 1 >> (bit_width(ty) - 1) != 0 {
+                    return Err(CodegenError::LiteralOutOfRange(*value, ty));
+                }
+                self.emit(push_uint(*value as u128)?);
+                Ok(ty)
+            }
+            Expr::Bytes(bytes) => {
+                self.emit(push_bytes(bytes)?);
+                Ok(bytes_type(bytes.len()))
+            }
+            Expr::Bool(true) => {
+                self.emit(Opcode::OP_TRUE(OP_TRUE));
+                Ok(CallElementType::Bool)
+            }
+            Expr::Bool(false) => {
+                self.emit(Opcode::OP_FALSE(OP_FALSE));
+                Ok(CallElementType::Bool)
+            }
+            Expr::Var(name) => {
+                let (ty, slot) = match self.lookup(name) {
+                    Some(local) => (local.ty, local.slot),
+                    None => return Err(CodegenError::UnknownVariable(name.clone())),
+                };
+                self.emit(push_bytes(&slot_key(slot))?);
+                self.emit(Opcode::OP_MREAD(OP_MREAD));
+                Ok(ty)
+            }
+            Expr::MapRead { map, key } => {
+                let value_type = self.storage_map(map)?.1.value_type;
+                self.storage_key(map, key)?;
+                self.emit(Opcode::OP_SREAD(OP_SREAD));
+                Ok(value_type)
+            }
+            Expr::Not(inner) => {
+                let found = self.compile_expr(inner, Some(CallElementType::Bool))?;
+                expect_type(found, CallElementType::Bool)?;
+                self.emit(Opcode::OP_NOT(OP_NOT));
+                Ok(CallElementType::Bool)
+            }
+            Expr::Binary { op, lhs, rhs } => self.compile_binary(*op, lhs, rhs, expected),
+            Expr::Cast { expr, ty } => self.compile_cast(expr, *ty),
+        }
+    }
+
+    fn compile_binary(
+        &mut self,
+        op: BinaryOp,
+        lhs: &Expr,
+        rhs: &Expr,
+        expected: Option<CallElementType>,
+    ) -> Result<CallElementType, CodegenError> {
+        match op {
+            BinaryOp::And | BinaryOp::Or => {
+                for operand in [lhs, rhs] {
+                    let found = self.compile_expr(operand, Some(CallElementType::Bool))?;
+                    expect_type(found, CallElementType::Bool)?;
+                }
+                self.emit(match op {
+                    BinaryOp::And => Opcode::OP_BOOLAND(OP_BOOLAND),
+                    _ => Opcode::OP_BOOLOR(OP_BOOLOR),
+                });
+                Ok(CallElementType::Bool)
+            }
+            BinaryOp::Eq | BinaryOp::Ne => {
+                let ty = self
+                    .infer(lhs)?
+                    .or(self.infer(rhs)?)
+                    .unwrap_or(CallElementType::U64);
+                let lhs_type = self.compile_expr(lhs, Some(ty))?;
+                let rhs_type = self.compile_expr(rhs, Some(ty))?;
+                if !comparable(lhs_type, rhs_type) {
+                    return Err(CodegenError::TypeMismatch {
+                        expected: lhs_type,
+                        found: rhs_type,
+                    });
+                }
+                // Fixed-width numbers are compared numerically, everything else bytewise.
+                match (is_numeric(ty), op) {
+                    (true, BinaryOp::Eq) => self.emit(Opcode::OP_NUMEQUAL(OP_NUMEQUAL)),
+                    (true, _) => self.emit(Opcode::OP_NUMNOTEQUAL(OP_NUMNOTEQUAL)),
+                    (false, BinaryOp::Eq) => self.emit(Opcode::OP_EQUAL(OP_EQUAL)),
+                    (false, _) => {
+                        self.emit(Opcode::OP_EQUAL(OP_EQUAL));
+                        self.emit(Opcode::OP_NOT(OP_NOT));
+                    }
+                }
+                Ok(CallElementType::Bool)
+            }
+            _ => {
+                // Arithmetic and ordering operators share a common numeric operand type.
+                let ty = match self.infer(lhs)?.or(self.infer(rhs)?) {
+                    Some(ty) => ty,
+                    None => match expected {
+                        Some(ty) if op.is_arithmetic() && is_numeric(ty) => ty,
+                        _ => CallElementType::U64,
+                    },
+                };
+                if !is_numeric(ty) {
+                    return Err(CodegenError::InvalidOperandType(
+                        op.symbol().to_string(),
+                        ty,
+                    ));
+                }
+
+                // `OP_SUB` and `OP_DIV` take their left operand from the top of the stack.
+                let operands = match op {
+                    BinaryOp::Sub | BinaryOp::Div | BinaryOp::Mod => [rhs, lhs],
+                    _ => [lhs, rhs],
+                };
+                for operand in operands {
+                    let found = self.compile_expr(operand, Some(ty))?;
+                    expect_type(found, ty)?;
+                }
+
+                match op {
+                    BinaryOp::Add | BinaryOp::Mul => {
+                        self.emit(match op {
+                            BinaryOp::Add => Opcode::OP_ADD(OP_ADD),
+                            _ => Opcode::OP_MUL(OP_MUL),
+                        });
+                        self.emit(Opcode::OP_VERIFY(OP_VERIFY));
+                        self.range_check(ty)?;
+                        Ok(ty)
+                    }
+                    BinaryOp::Sub => {
+                        self.emit(Opcode::OP_SUB(OP_SUB));
+                        self.emit(Opcode::OP_VERIFY(OP_VERIFY));
+                        Ok(ty)
+                    }
+                    BinaryOp::Div => {
+                        // Keep the quotient, drop the modulo.
+                        self.emit(Opcode::OP_DIV(OP_DIV));
+                        self.emit(Opcode::OP_VERIFY(OP_VERIFY));
+                        self.emit(Opcode::OP_TOALTSTACK(OP_TOALTSTACK));
+                        self.emit(Opcode::OP_DROP(OP_DROP));
+                        self.emit(Opcode::OP_FROMALTSTACK(OP_FROMALTSTACK));
+                        Ok(ty)
+                    }
+                    BinaryOp::Mod => {
+                        // Keep the modulo, drop the quotient.
+                        self.emit(Opcode::OP_DIV(OP_DIV));
+                        self.emit(Opcode::OP_VERIFY(OP_VERIFY));
+                        self.emit(Opcode::OP_DROP(OP_DROP));
+                        Ok(ty)
+                    }
+                    _ => {
+                        self.emit(match op {
+                            BinaryOp::Lt => Opcode::OP_LESSTHAN(OP_LESSTHAN),
+                            BinaryOp::Le => Opcode::OP_LESSTHANOREQUAL(OP_LESSTHANOREQUAL),
+                            BinaryOp::Gt => Opcode::OP_GREATERTHAN(OP_GREATERTHAN),
+                            _ => Opcode::OP_GREATERTHANOREQUAL(OP_GREATERTHANOREQUAL),
+                        });
+                        Ok(CallElementType::Bool)
+                    }
+                }
+            }
+        }
+    }
+
+    fn compile_cast(
+        &mut self,
+        expr: &Expr,
+        to: CallElementType,
+    ) -> Result<CallElementType, CodegenError> {
+        // Untyped literals are typed by the cast target.
+        let hint = match self.infer(expr)? {
+            None if is_numeric(to) => Some(to),
+            inferred => inferred,
+        };
+        let from = self.compile_expr(expr, hint)?;
+
+        if from == to {
+            return Ok(to);
+        }
+
+        match (from, to) {
+            // Narrowing numeric casts are range checked.
+            (from, to) if is_numeric(from) && is_numeric(to) => {
+                if bit_width(to) < bit_width(from) {
+                    self.range_check(to)?;
+                }
+                Ok(to)
+            }
+            // Any fixed-length byte array widens to varbytes.
+            (CallElementType::Bytes(_), CallElementType::Varbytes) => Ok(to),
+            // Varbytes narrow to a fixed length by checking the size.
+            (CallElementType::Varbytes, CallElementType::Bytes(index)) => {
+                self.emit(Opcode::OP_SIZE(OP_SIZE));
+                self.emit(push_uint(index as u128 + 1)?);
+                self.emit(Opcode::OP_NUMEQUAL(OP_NUMEQUAL));
+                self.emit(Opcode::OP_VERIFY(OP_VERIFY));
+                Ok(to)
+            }
+            _ => Err(CodegenError::InvalidCast { from, to }),
+        }
+    }
+
+    /// Fails the execution unless the number on top of the stack fits in the type.
+    fn range_check(&mut self, ty: CallElementType) -> Result<(), CodegenError> {
+        let bound_bytes = {
+            let mut bytes = vec![0x00; (bit_width(ty) / 8) as usize];
+            bytes.push(0x01);
+            bytes
+        };
+        self.emit(Opcode::OP_DUP(OP_DUP));
+        self.emit(push_bytes(&bound_bytes)?);
+        self.emit(Opcode::OP_LESSTHAN(OP_LESSTHAN));
+        self.emit(Opcode::OP_VERIFY(OP_VERIFY));
+        Ok(())
+    }
+}
+
+/// Returns whether the statements end the execution on every path.
+fn terminates(stmts: &[Stmt]) -> bool {
+    match stmts.last() {
+        Some(Stmt::Return(_)) | Some(Stmt::Fail) => true,
+        Some(Stmt::If {
+            then_body,
+            else_body,
+            ..
+        }) => terminates(then_body) && terminates(else_body),
+        _ => false,
+    }
+}
+
+/// The memory key of a local slot.
+fn slot_key(slot: u8) -> Vec<u8> {
+    vec![slot + 1]
+}
+
+fn is_numeric(ty: CallElementType) -> bool {
+    matches!(
+        ty,
+        CallElementType::U8
+            | CallElementType::U16
+            | CallElementType::U32
+            | CallElementType::U64
+            | CallElementType::Payable
+    )
+}
+
+fn bit_width(ty: CallElementType) -> u32 {
+    match ty {
+        CallElementType::U8 => 8,
+        CallElementType::U16 => 16,
+        CallElementType::U32 | CallElementType::Payable => 32,
+        _ => 64,
+    }
+}
+
+/// Returns the byte length of types with a known encoded length.
+fn fixed_length(ty: CallElementType) -> Option<usize> {
+    match ty {
+        CallElementType::U8 => Some(1),
+        CallElementType::U16 => Some(2),
+        CallElementType::U32 | CallElementType::Payable => Some(4),
+        CallElementType::U64 => Some(8),
+        CallElementType::Bool => Some(1),
+        CallElementType::Account | CallElementType::Contract => Some(32),
+        CallElementType::Bytes(index) => Some(index as usize + 1),
+        CallElementType::Varbytes => None,
+    }
+}
+
+fn bytes_type(length: usize) -> CallElementType {
+    match length {
+        1..=256 => CallElementType::Bytes((length - 1) as u8),
+        _ => CallElementType::Varbytes,
+    }
+}
+
+/// Checks that a value of type `found` can be stored where `expected` is required.
+fn expect_type(found: CallElementType, expected: CallElementType) -> Result<(), CodegenError> {
+    match (found, expected) {
+        (found, expected) if found == expected => Ok(()),
+        (CallElementType::Bytes(_), CallElementType::Varbytes) => Ok(()),
+        // Account and contract ids are 32-byte arrays.
+        (CallElementType::Bytes(31), CallElementType::Account | CallElementType::Contract) => {
+            Ok(())
+        }
+        _ => Err(CodegenError::TypeMismatch { expected, found }),
+    }
+}
+
+/// Checks that two types can be compared for equality.
+fn comparable(a: CallElementType, b: CallElementType) -> bool {
+    let bytes_like = |ty: CallElementType| {
+        matches!(
+            ty,
+            CallElementType::Bytes(_)
+                | CallElementType::Varbytes
+                | CallElementType::Account
+                | CallElementType::Contract
+        )
+    };
+    a == b || (bytes_like(a) && bytes_like(b))
+}
+
+/// Returns the minimal push opcode for an unsigned integer.
+fn push_uint(value: u128) -> Result<Opcode, CodegenError> {
+    let mut bytes = value.to_le_bytes().to_vec();
+    while bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+    push_bytes(&bytes)
+}
+
+/// Returns the minimal push opcode for the given bytes.
+fn push_bytes(bytes: &[u8]) -> Result<Opcode, CodegenError> {
+    let opcode = match bytes {
+        [] => Opcode::OP_FALSE(OP_FALSE),
+        // A single zero byte has no minimal push; `OP_FALSE` pushes an empty item.
+        [0] => return Err(CodegenError::UnpushableBytesLiteral),
+        [1] => Opcode::OP_TRUE(OP_TRUE),
+        [2] => Opcode::OP_2(OP_2),
+        [3] => Opcode::OP_3(OP_3),
+        [4] => Opcode::OP_4(OP_4),
+        [5] => Opcode::OP_5(OP_5),
+        [6] => Opcode::OP_6(OP_6),
+        [7] => Opcode::OP_7(OP_7),
+        [8] => Opcode::OP_8(OP_8),
+        [9] => Opcode::OP_9(OP_9),
+        [10] => Opcode::OP_10(OP_10),
+        [11] => Opcode::OP_11(OP_11),
+        [12] => Opcode::OP_12(OP_12),
+        [13] => Opcode::OP_13(OP_13),
+        [14] => Opcode::OP_14(OP_14),
+        [15] => Opcode::OP_15(OP_15),
+        [16] => Opcode::OP_16(OP_16),
+        _ => Opcode::OP_PUSHDATA(OP_PUSHDATA(bytes.to_vec())),
+    };
+    Ok(opcode)
+}
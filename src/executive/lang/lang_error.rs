@@ -0,0 +1,215 @@
+use crate::{
+    constructive::calldata::element_type::CallElementType,
+    executive::{
+        opcode::compiler::compiler_error::OpcodeCompileError,
+        program::{
+            method::method_error::MethodConstructionError, program_error::ProgramConstructionError,
+        },
+    },
+};
+use std::fmt;
+
+/// The error that occurs when tokenizing the contract source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LexError {
+    /// Unexpected character at the given line.
+    UnexpectedCharacter(usize, char),
+    /// Integer literal does not fit in 64 bits.
+    IntegerLiteralOverflow(usize),
+    /// Hex literal is empty or has an odd number of digits.
+    InvalidHexLiteral(usize),
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexError::UnexpectedCharacter(line, c) => {
+                write!(f, "Unexpected character '{}' at line {}", c, line)
+            }
+            LexError::IntegerLiteralOverflow(line) => {
+                write!(f, "Integer literal overflows u64 at line {}", line)
+            }
+            LexError::InvalidHexLiteral(line) => {
+                write!(f, "Invalid hex literal at line {}", line)
+            }
+        }
+    }
+}
+
+/// The error that occurs when parsing the token stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Unexpected token at the given line.
+    UnexpectedToken {
+        line: usize,
+        found: String,
+        expected: String,
+    },
+    /// Input ended while more tokens were expected.
+    UnexpectedEndOfInput(String),
+    /// Unknown type name at the given line.
+    UnknownType(usize, String),
+    /// Fixed byte length is out of the 1-256 range.
+    InvalidBytesLength(usize, u64),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedToken {
+                line,
+                found,
+                expected,
+            } => write!(
+                f,
+                "Unexpected token '{}' at line {}, expected {}",
+                found, line, expected
+            ),
+            ParseError::UnexpectedEndOfInput(expected) => {
+                write!(f, "Unexpected end of input, expected {}", expected)
+            }
+            ParseError::UnknownType(line, name) => {
+                write!(f, "Unknown type '{}' at line {}", name, line)
+            }
+            ParseError::InvalidBytesLength(line, length) => {
+                write!(f, "Invalid bytes length {} at line {}", length, line)
+            }
+        }
+    }
+}
+
+/// The error that occurs when type checking and generating the method scripts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodegenError {
+    /// Variable is not declared in scope.
+    UnknownVariable(String),
+    /// Storage map is not declared.
+    UnknownStorageMap(String),
+    /// Variable is already declared in scope.
+    DuplicateVariable(String),
+    /// Storage map is declared more than once.
+    DuplicateStorageMap(String),
+    /// Too many storage maps to fit in a one-byte key prefix.
+    TooManyStorageMaps,
+    /// Too many locals to fit in a one-byte memory key.
+    TooManyLocals(String),
+    /// Type mismatch.
+    TypeMismatch {
+        expected: CallElementType,
+        found: CallElementType,
+    },
+    /// Operator is not defined for the operand type.
+    InvalidOperandType(String, CallElementType),
+    /// Integer literal does not fit in the target type.
+    LiteralOutOfRange(u64, CallElementType),
+    /// Byte literal cannot be pushed as a minimal push.
+    UnpushableBytesLiteral,
+    /// Cast between the two types is not supported.
+    InvalidCast {
+        from: CallElementType,
+        to: CallElementType,
+    },
+    /// Storage map key type is not fixed-length or too long.
+    InvalidStorageKeyType(CallElementType),
+    /// Storage write in a read-only method.
+    StorageWriteInReadOnlyMethod(String),
+    /// Return value count does not match the signature.
+    ReturnArityMismatch { expected: usize, found: usize },
+    /// Method with return values does not end with a return.
+    MissingReturn(String),
+    /// Opcode compile error while optimizing the method script.
+    OpcodeCompileError(String, OpcodeCompileError),
+    /// Method script exceeds `MAX_METHOD_OPCODE_COUNT`.
+    MethodOpcodeCountExceeded(String, usize),
+    /// Method construction error.
+    MethodConstructionError(String, MethodConstructionError),
+    /// Program construction error.
+    ProgramConstructionError(ProgramConstructionError),
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodegenError::UnknownVariable(name) => write!(f, "Unknown variable '{}'", name),
+            CodegenError::UnknownStorageMap(name) => write!(f, "Unknown storage map '{}'", name),
+            CodegenError::DuplicateVariable(name) => {
+                write!(f, "Variable '{}' is already declared", name)
+            }
+            CodegenError::DuplicateStorageMap(name) => {
+                write!(f, "Storage map '{}' is already declared", name)
+            }
+            CodegenError::TooManyStorageMaps => write!(f, "Too many storage maps"),
+            CodegenError::TooManyLocals(method) => {
+                write!(f, "Too many locals in method '{}'", method)
+            }
+            CodegenError::TypeMismatch { expected, found } => {
+                write!(f, "Type mismatch: expected {}, found {}", expected, found)
+            }
+            CodegenError::InvalidOperandType(op, ty) => {
+                write!(f, "Operator '{}' is not defined for {}", op, ty)
+            }
+            CodegenError::LiteralOutOfRange(value, ty) => {
+                write!(f, "Literal {} is out of range for {}", value, ty)
+            }
+            CodegenError::UnpushableBytesLiteral => {
+                write!(f, "Byte literal 0x00 cannot be pushed")
+            }
+            CodegenError::InvalidCast { from, to } => {
+                write!(f, "Cannot cast {} to {}", from, to)
+            }
+            CodegenError::InvalidStorageKeyType(ty) => {
+                write!(f, "Invalid storage key type {}", ty)
+            }
+            CodegenError::StorageWriteInReadOnlyMethod(method) => {
+                write!(f, "Storage write in read-only method '{}'", method)
+            }
+            CodegenError::ReturnArityMismatch { expected, found } => {
+                write!(
+                    f,
+                    "Return arity mismatch: expected {}, found {}",
+                    expected, found
+                )
+            }
+            CodegenError::MissingReturn(method) => {
+                write!(f, "Method '{}' is missing a return", method)
+            }
+            CodegenError::OpcodeCompileError(method, err) => {
+                write!(f, "Method '{}' opcode compile error: {}", method, err)
+            }
+            CodegenError::MethodOpcodeCountExceeded(method, count) => {
+                write!(
+                    f,
+                    "Method '{}' compiles to {} opcodes, exceeding the limit",
+                    method, count
+                )
+            }
+            CodegenError::MethodConstructionError(method, err) => {
+                write!(f, "Method '{}' construction error: {}", method, err)
+            }
+            CodegenError::ProgramConstructionError(err) => {
+                write!(f, "Program construction error: {}", err)
+            }
+        }
+    }
+}
+
+/// The error that occurs when compiling contract source into a `Program`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LangError {
+    /// Lexing error.
+    LexError(LexError),
+    /// Parsing error.
+    ParseError(ParseError),
+    /// Code generation error.
+    CodegenError(CodegenError),
+}
+
+impl fmt::Display for LangError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LangError::LexError(err) => write!(f, "Lex error: {}", err),
+            LangError::ParseError(err) => write!(f, "Parse error: {}", err),
+            LangError::CodegenError(err) => write!(f, "Codegen error: {}", err),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use super::{
+    lang_error::LexError,
+    token::{SpannedToken, Token},
+};
+
+/// Tokenizes the contract source.
+pub fn tokenize(source: &str) -> Result<Vec<SpannedToken>, LexError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::<SpannedToken>::new();
+    let mut line: usize = 1;
+    let mut i: usize = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        // Skip whitespace.
+        if c.is_whitespace() {
+            if c == '\n' {
+                line += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        // Skip line comments.
+        if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+
+        // Identifiers and keywords.
+        if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let token = Token::keyword(&word).unwrap_or(Token::Ident(word));
+            tokens.push(SpannedToken { token, line });
+            continue;
+        }
+
+        // Hex byte array literals.
+        if c == '0' && matches!(chars.get(i + 1), Some('x') | Some('X')) {
+            i += 2;
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_hexdigit() {
+                i += 1;
+            }
+            let digits: String = chars[start..i].iter().collect();
+            if digits.is_empty() || digits.len() % 2 != 0 {
+                return Err(LexError::InvalidHexLiteral(line));
+            }
+            let bytes = hex::decode(&digits).map_err(|_| LexError::InvalidHexLiteral(line))?;
+            tokens.push(SpannedToken {
+                token: Token::Hex(bytes),
+                line,
+            });
+            continue;
+        }
+
+        // Decimal integer literals.
+        if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            let digits: String = chars[start..i].iter().collect();
+            let value = digits
+                .parse::<u64>()
+                .map_err(|_| LexError::IntegerLiteralOverflow(line))?;
+            tokens.push(SpannedToken {
+                token: Token::Int(value),
+                line,
+            });
+            continue;
+        }
+
+        // Two-character operators.
+        let next = chars.get(i + 1).copied();
+        let double = match (c, next) {
+            ('-', Some('>')) => Some(Token::Arrow),
+            ('=', Some('=')) => Some(Token::EqEq),
+            ('!', Some('=')) => Some(Token::NotEq),
+            ('<', Some('=')) => Some(Token::Le),
+            ('>', Some('=')) => Some(Token::Ge),
+            ('&', Some('&')) => Some(Token::AndAnd),
+            ('|', Some('|')) => Some(Token::OrOr),
+            _ => None,
+        };
+        if let Some(token) = double {
+            tokens.push(SpannedToken { token, line });
+            i += 2;
+            continue;
+        }
+
+        // Single-character tokens.
+        let token = match c {
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ',' => Token::Comma,
+            ';' => Token::Semicolon,
+            ':' => Token::Colon,
+            '=' => Token::Assign,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '%' => Token::Percent,
+            '<' => Token::Lt,
+            '>' => Token::Gt,
+            '!' => Token::Bang,
+            _ => return Err(LexError::UnexpectedCharacter(line, c)),
+        };
+        tokens.push(SpannedToken { token, line });
+        i += 1;
+    }
+
+    Ok(tokens)
+}
@@ -0,0 +1,6 @@
+pub mod ast;
+pub mod compiler;
+pub mod lang_error;
+pub mod lexer;
+pub mod parser;
+pub mod token;
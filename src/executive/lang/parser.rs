@@ -0,0 +1,456 @@
+use super::{
+    ast::{BinaryOp, ContractDecl, Expr, FunctionDecl, Stmt, StorageDecl},
+    lang_error::ParseError,
+    token::{SpannedToken, Token},
+};
+use crate::{
+    constructive::calldata::element_type::CallElementType,
+    executive::program::method::method_type::MethodType,
+};
+
+/// A recursive descent parser for the contract language.
+pub struct Parser {
+    tokens: Vec<SpannedToken>,
+    position: usize,
+}
+
+impl Parser {
+    /// Creates a new parser over the given token stream.
+    pub fn new(tokens: Vec<SpannedToken>) -> Self {
+        Self {
+            tokens,
+            position: 0,
+        }
+    }
+
+    /// Parses a single contract declaration spanning the whole input.
+    pub fn parse_contract(&mut self) -> Result<ContractDecl, ParseError> {
+        self.expect(Token::Contract)?;
+        let name = self.expect_ident()?;
+        self.expect(Token::LBrace)?;
+
+        let mut storage = Vec::<StorageDecl>::new();
+        let mut functions = Vec::<FunctionDecl>::new();
+
+        loop {
+            match self.peek() {
+                Some(Token::RBrace) => {
+                    self.advance();
+                    break;
+                }
+                Some(Token::Storage) => storage.push(self.parse_storage()?),
+                Some(Token::Callable) | Some(Token::ReadOnly) | Some(Token::Internal) => {
+                    functions.push(self.parse_function()?)
+                }
+                _ => return Err(self.unexpected("storage or function declaration")),
+            }
+        }
+
+        // Make sure nothing trails the contract.
+        if self.peek().is_some() {
+            return Err(self.unexpected("end of input"));
+        }
+
+        Ok(ContractDecl {
+            name,
+            storage,
+            functions,
+        })
+    }
+
+    fn parse_storage(&mut self) -> Result<StorageDecl, ParseError> {
+        self.expect(Token::Storage)?;
+        let name = self.expect_ident()?;
+        self.expect(Token::Colon)?;
+        self.expect(Token::Map)?;
+        self.expect(Token::Lt)?;
+        let key_type = self.parse_type()?;
+        self.expect(Token::Comma)?;
+        let value_type = self.parse_type()?;
+        self.expect(Token::Gt)?;
+        self.expect(Token::Semicolon)?;
+
+        Ok(StorageDecl {
+            name,
+            key_type,
+            value_type,
+        })
+    }
+
+    fn parse_function(&mut self) -> Result<FunctionDecl, ParseError> {
+        let method_type = match self.advance() {
+            Some(Token::Callable) => MethodType::Callable,
+            Some(Token::ReadOnly) => MethodType::ReadOnly,
+            _ => MethodType::Internal,
+        };
+        self.expect(Token::Fn)?;
+        let name = self.expect_ident()?;
+
+        // Parameters.
+        self.expect(Token::LParen)?;
+        let mut params = Vec::<(String, CallElementType)>::new();
+        if self.peek() != Some(&Token::RParen) {
+            loop {
+                let param_name = self.expect_ident()?;
+                self.expect(Token::Colon)?;
+                let param_type = self.parse_type()?;
+                params.push((param_name, param_type));
+                if !self.eat(&Token::Comma) {
+                    break;
+                }
+            }
+        }
+        self.expect(Token::RParen)?;
+
+        // Return types; either a single type or a parenthesized list.
+        let mut returns = Vec::<CallElementType>::new();
+        if self.eat(&Token::Arrow) {
+            if self.eat(&Token::LParen) {
+                loop {
+                    returns.push(self.parse_type()?);
+                    if !self.eat(&Token::Comma) {
+                        break;
+                    }
+                }
+                self.expect(Token::RParen)?;
+            } else {
+                returns.push(self.parse_type()?);
+            }
+        }
+
+        let body = self.parse_block()?;
+
+        Ok(FunctionDecl {
+            name,
+            method_type,
+            params,
+            returns,
+            body,
+        })
+    }
+
+    fn parse_type(&mut self) -> Result<CallElementType, ParseError> {
+        let line = self.line();
+        // `contract` doubles as a keyword and a type name.
+        if self.eat(&Token::Contract) {
+            return Ok(CallElementType::Contract);
+        }
+        let name = self.expect_ident()?;
+        match name.as_str() {
+            "u8" => Ok(CallElementType::U8),
+            "u16" => Ok(CallElementType::U16),
+            "u32" => Ok(CallElementType::U32),
+            "u64" => Ok(CallElementType::U64),
+            "bool" => Ok(CallElementType::Bool),
+            "account" => Ok(CallElementType::Account),
+            "varbytes" => Ok(CallElementType::Varbytes),
+            "payable" => Ok(CallElementType::Payable),
+            "bytes" => {
+                self.expect(Token::Lt)?;
+                let length = match self.peek() {
+                    Some(Token::Int(length)) => *length,
+                    _ => return Err(self.unexpected("byte length")),
+                };
+                self.advance();
+                self.expect(Token::Gt)?;
+                // Byte length is the inner value + 1.
+                match length {
+                    1..=256 => Ok(CallElementType::Bytes((length - 1) as u8)),
+                    _ => Err(ParseError::InvalidBytesLength(line, length)),
+                }
+            }
+            _ => Err(ParseError::UnknownType(line, name)),
+        }
+    }
+
+    fn parse_block(&mut self) -> Result<Vec<Stmt>, ParseError> {
+        self.expect(Token::LBrace)?;
+        let mut stmts = Vec::<Stmt>::new();
+        while !self.eat(&Token::RBrace) {
+            stmts.push(self.parse_stmt()?);
+        }
+        Ok(stmts)
+    }
+
+    fn parse_stmt(&mut self) -> Result<Stmt, ParseError> {
+        match self.peek() {
+            Some(Token::Let) => {
+                self.advance();
+                let name = self.expect_ident()?;
+                self.expect(Token::Colon)?;
+                let ty = self.parse_type()?;
+                self.expect(Token::Assign)?;
+                let value = self.parse_expr()?;
+                self.expect(Token::Semicolon)?;
+                Ok(Stmt::Let { name, ty, value })
+            }
+            Some(Token::If) => {
+                self.advance();
+                let cond = self.parse_expr()?;
+                let then_body = self.parse_block()?;
+                let else_body = match self.eat(&Token::Else) {
+                    // `else if` chains nest into the else body.
+                    true if self.peek() == Some(&Token::If) => vec![self.parse_stmt()?],
+                    true => self.parse_block()?,
+                    false => vec![],
+                };
+                Ok(Stmt::If {
+                    cond,
+                    then_body,
+                    else_body,
+                })
+            }
+            Some(Token::Return) => {
+                self.advance();
+                let mut values = Vec::<Expr>::new();
+                if !self.eat(&Token::Semicolon) {
+                    loop {
+                        values.push(self.parse_expr()?);
+                        if !self.eat(&Token::Comma) {
+                            break;
+                        }
+                    }
+                    self.expect(Token::Semicolon)?;
+                }
+                Ok(Stmt::Return(values))
+            }
+            Some(Token::Require) => {
+                self.advance();
+                self.expect(Token::LParen)?;
+                let cond = self.parse_expr()?;
+                self.expect(Token::RParen)?;
+                self.expect(Token::Semicolon)?;
+                Ok(Stmt::Require(cond))
+            }
+            Some(Token::Fail) => {
+                self.advance();
+                self.expect(Token::Semicolon)?;
+                Ok(Stmt::Fail)
+            }
+            Some(Token::Ident(_)) => {
+                let name = self.expect_ident()?;
+                if self.eat(&Token::LBracket) {
+                    let key = self.parse_expr()?;
+                    self.expect(Token::RBracket)?;
+                    self.expect(Token::Assign)?;
+                    let value = self.parse_expr()?;
+                    self.expect(Token::Semicolon)?;
+                    Ok(Stmt::MapAssign {
+                        map: name,
+                        key,
+                        value,
+                    })
+                } else {
+                    self.expect(Token::Assign)?;
+                    let value = self.parse_expr()?;
+                    self.expect(Token::Semicolon)?;
+                    Ok(Stmt::Assign { name, value })
+                }
+            }
+            _ => Err(self.unexpected("statement")),
+        }
+    }
+
+    /// Parses an expression.
+    pub fn parse_expr(&mut self) -> Result<Expr, ParseError> {
+        self.parse_or()
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_and()?;
+        while self.eat(&Token::OrOr) {
+            let rhs = self.parse_and()?;
+            lhs = binary(BinaryOp::Or, lhs, rhs);
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_equality()?;
+        while self.eat(&Token::AndAnd) {
+            let rhs = self.parse_equality()?;
+            lhs = binary(BinaryOp::And, lhs, rhs);
+        }
+        Ok(lhs)
+    }
+
+    fn parse_equality(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_comparison()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::EqEq) => BinaryOp::Eq,
+                Some(Token::NotEq) => BinaryOp::Ne,
+                _ => return Ok(lhs),
+            };
+            self.advance();
+            let rhs = self.parse_comparison()?;
+            lhs = binary(op, lhs, rhs);
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_additive()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Lt) => BinaryOp::Lt,
+                Some(Token::Le) => BinaryOp::Le,
+                Some(Token::Gt) => BinaryOp::Gt,
+                Some(Token::Ge) => BinaryOp::Ge,
+                _ => return Ok(lhs),
+            };
+            self.advance();
+            let rhs = self.parse_additive()?;
+            lhs = binary(op, lhs, rhs);
+        }
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => BinaryOp::Add,
+                Some(Token::Minus) => BinaryOp::Sub,
+                _ => return Ok(lhs),
+            };
+            self.advance();
+            let rhs = self.parse_multiplicative()?;
+            lhs = binary(op, lhs, rhs);
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => BinaryOp::Mul,
+                Some(Token::Slash) => BinaryOp::Div,
+                Some(Token::Percent) => BinaryOp::Mod,
+                _ => return Ok(lhs),
+            };
+            self.advance();
+            let rhs = self.parse_unary()?;
+            lhs = binary(op, lhs, rhs);
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+        if self.eat(&Token::Bang) {
+            let expr = self.parse_unary()?;
+            return Ok(Expr::Not(Box::new(expr)));
+        }
+        self.parse_cast()
+    }
+
+    fn parse_cast(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.parse_primary()?;
+        while self.eat(&Token::As) {
+            let ty = self.parse_type()?;
+            expr = Expr::Cast {
+                expr: Box::new(expr),
+                ty,
+            };
+        }
+        Ok(expr)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ParseError> {
+        let token = match self.peek() {
+            Some(token) => token.clone(),
+            None => return Err(self.unexpected("expression")),
+        };
+        self.advance();
+        match token {
+            Token::Int(value) => Ok(Expr::Int(value)),
+            Token::Hex(bytes) => Ok(Expr::Bytes(bytes)),
+            Token::True => Ok(Expr::Bool(true)),
+            Token::False => Ok(Expr::Bool(false)),
+            Token::LParen => {
+                let expr = self.parse_expr()?;
+                self.expect(Token::RParen)?;
+                Ok(expr)
+            }
+            Token::Ident(name) => {
+                if self.eat(&Token::LBracket) {
+                    let key = self.parse_expr()?;
+                    self.expect(Token::RBracket)?;
+                    Ok(Expr::MapRead {
+                        map: name,
+                        key: Box::new(key),
+                    })
+                } else {
+                    Ok(Expr::Var(name))
+                }
+            }
+            _ => {
+                // Step back to report the offending token.
+                self.position -= 1;
+                Err(self.unexpected("expression"))
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|t| &t.token)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).map(|t| t.token.clone());
+        if token.is_some() {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), ParseError> {
+        if self.eat(&token) {
+            return Ok(());
+        }
+        Err(self.unexpected(&format!("'{}'", token)))
+    }
+
+    fn expect_ident(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Some(Token::Ident(name)) => {
+                let name = name.clone();
+                self.position += 1;
+                Ok(name)
+            }
+            _ => Err(self.unexpected("identifier")),
+        }
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .or(self.tokens.last())
+            .map(|t| t.line)
+            .unwrap_or(1)
+    }
+
+    fn unexpected(&self, expected: &str) -> ParseError {
+        match self.tokens.get(self.position) {
+            Some(spanned) => ParseError::UnexpectedToken {
+                line: spanned.line,
+                found: spanned.token.to_string(),
+                expected: expected.to_string(),
+            },
+            None => ParseError::UnexpectedEndOfInput(expected.to_string()),
+        }
+    }
+}
+
+fn binary(op: BinaryOp, lhs: Expr, rhs: Expr) -> Expr {
+    Expr::Binary {
+        op,
+        lhs: Box::new(lhs),
+        rhs: Box::new(rhs),
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+
+/// A lexical token of the contract language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    // Identifier, such as a variable, function or type name.
+    Ident(String),
+    // Decimal integer literal.
+    Int(u64),
+    // Hex byte array literal (`0x..`).
+    Hex(Vec<u8>),
+    // Keywords.
+    Contract,
+    Storage,
+    Map,
+    Fn,
+    Callable,
+    ReadOnly,
+    Internal,
+    Let,
+    If,
+    Else,
+    Return,
+    Require,
+    Fail,
+    True,
+    False,
+    As,
+    // Delimiters.
+    LBrace,
+    RBrace,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Semicolon,
+    Colon,
+    Arrow,
+    // Operators.
+    Assign,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    EqEq,
+    NotEq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    AndAnd,
+    OrOr,
+    Bang,
+}
+
+impl Token {
+    /// Returns the keyword token for the given word, if any.
+    pub fn keyword(word: &str) -> Option<Token> {
+        match word {
+            "contract" => Some(Token::Contract),
+            "storage" => Some(Token::Storage),
+            "map" => Some(Token::Map),
+            "fn" => Some(Token::Fn),
+            "callable" => Some(Token::Callable),
+            "readonly" => Some(Token::ReadOnly),
+            "internal" => Some(Token::Internal),
+            "let" => Some(Token::Let),
+            "if" => Some(Token::If),
+            "else" => Some(Token::Else),
+            "return" => Some(Token::Return),
+            "require" => Some(Token::Require),
+            "fail" => Some(Token::Fail),
+            "true" => Some(Token::True),
+            "false" => Some(Token::False),
+            "as" => Some(Token::As),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "{}", name),
+            Token::Int(value) => write!(f, "{}", value),
+            Token::Hex(bytes) => write!(f, "0x{}", hex::encode(bytes)),
+            Token::Contract => write!(f, "contract"),
+            Token::Storage => write!(f, "storage"),
+            Token::Map => write!(f, "map"),
+            Token::Fn => write!(f, "fn"),
+            Token::Callable => write!(f, "callable"),
+            Token::ReadOnly => write!(f, "readonly"),
+            Token::Internal => write!(f, "internal"),
+            Token::Let => write!(f, "let"),
+            Token::If => write!(f, "if"),
+            Token::Else => write!(f, "else"),
+            Token::Return => write!(f, "return"),
+            Token::Require => write!(f, "require"),
+            Token::Fail => write!(f, "fail"),
+            Token::True => write!(f, "true"),
+            Token::False => write!(f, "false"),
+            Token::As => write!(f, "as"),
+            Token::LBrace => write!(f, "{{"),
+            Token::RBrace => write!(f, "}}"),
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+            Token::LBracket => write!(f, "["),
+            Token::RBracket => write!(f, "]"),
+            Token::Comma => write!(f, ","),
+            Token::Semicolon => write!(f, ";"),
+            Token::Colon => write!(f, ":"),
+            Token::Arrow => write!(f, "->"),
+            Token::Assign => write!(f, "="),
+            Token::Plus => write!(f, "+"),
+            Token::Minus => write!(f, "-"),
+            Token::Star => write!(f, "*"),
+            Token::Slash => write!(f, "/"),
+            Token::Percent => write!(f, "%"),
+            Token::EqEq => write!(f, "=="),
+            Token::NotEq => write!(f, "!="),
+            Token::Lt => write!(f, "<"),
+            Token::Le => write!(f, "<="),
+            Token::Gt => write!(f, ">"),
+            Token::Ge => write!(f, ">="),
+            Token::AndAnd => write!(f, "&&"),
+            Token::OrOr => write!(f, "||"),
+            Token::Bang => write!(f, "!"),
+        }
+    }
+}
+
+/// A token paired with the source line it was read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpannedToken {
+    /// The token.
+    pub token: Token,
+    /// The 1-based source line.
+    pub line: usize,
+}
@@ -1,3 +1,4 @@
+pub mod lang;
 pub mod opcode;
 pub mod program;
 pub mod stack;
@@ -34,6 +34,7 @@ use crate::executive::opcode::op::bitwise::op_invert::OP_INVERT;
 use crate::executive::opcode::op::bitwise::op_or::OP_OR;
 use crate::executive::opcode::op::bitwise::op_reverse::OP_REVERSE;
 use crate::executive::opcode::op::bitwise::op_xor::OP_XOR;
+use crate::executive::opcode::op::call::op_trycall::OP_TRYCALL;
 use crate::executive::opcode::op::flow::op_else::OP_ELSE;
 use crate::executive::opcode::op::flow::op_endif::OP_ENDIF;
 use crate::executive::opcode::op::flow::op_fail::OP_FAIL;
@@ -52,6 +53,9 @@ use crate::executive::opcode::op::digest::op_ripemd160::OP_RIPEMD160;
 use crate::executive::opcode::op::digest::op_sha1::OP_SHA1;
 use crate::executive::opcode::op::digest::op_sha256::OP_SHA256;
 use crate::executive::opcode::op::digest::op_taggedhash::OP_TAGGEDHASH;
+use crate::executive::opcode::op::memory::op_free::OP_MFREE;
+use crate::executive::opcode::op::memory::op_mread::OP_MREAD;
+use crate::executive::opcode::op::memory::op_mwrite::OP_MWRITE;
 use crate::executive::opcode::op::push::op_10::OP_10;
 use crate::executive::opcode::op::push::op_11::OP_11;
 use crate::executive::opcode::op::push::op_12::OP_12;
@@ -108,6 +112,9 @@ use crate::executive::opcode::op::stack::op_roll::OP_ROLL;
 use crate::executive::opcode::op::stack::op_rot::OP_ROT;
 use crate::executive::opcode::op::stack::op_swap::OP_SWAP;
 use crate::executive::opcode::op::stack::op_tuck::OP_TUCK;
+use crate::executive::opcode::op::storage::op_sfree::OP_SFREE;
+use crate::executive::opcode::op::storage::op_sread::OP_SREAD;
+use crate::executive::opcode::op::storage::op_swrite::OP_SWRITE;
 use crate::executive::opcode::opcode::Opcode;
 
 /// A trait for compiling and decompiling an opcode.
@@ -245,6 +252,16 @@ impl OpcodeCompiler for Opcode {
             Opcode::OP_CHECKSCHNORRSIGBIP340(_) => Ok(OP_CHECKSCHNORRSIGBIP340::bytecode()),
             Opcode::OP_CHECKBLSSIGSINGLE(_) => Ok(OP_CHECKBLSSIGSINGLE::bytecode()),
             Opcode::OP_CHECKBLSSIGAGG(_) => Ok(OP_CHECKBLSSIGAGG::bytecode()),
+            // Memory
+            Opcode::OP_MWRITE(_) => Ok(OP_MWRITE::bytecode()),
+            Opcode::OP_MREAD(_) => Ok(OP_MREAD::bytecode()),
+            Opcode::OP_MFREE(_) => Ok(OP_MFREE::bytecode()),
+            // Storage
+            Opcode::OP_SWRITE(_) => Ok(OP_SWRITE::bytecode()),
+            Opcode::OP_SREAD(_) => Ok(OP_SREAD::bytecode()),
+            Opcode::OP_SFREE(_) => Ok(OP_SFREE::bytecode()),
+            // Call
+            Opcode::OP_TRYCALL(_) => Ok(OP_TRYCALL::bytecode()),
         }
     }
 
@@ -459,6 +476,16 @@ impl OpcodeCompiler for Opcode {
             0xb6 => Ok(Opcode::OP_CHECKSCHNORRSIGBIP340(OP_CHECKSCHNORRSIGBIP340)),
             0xb7 => Ok(Opcode::OP_CHECKBLSSIGSINGLE(OP_CHECKBLSSIGSINGLE)),
             0xb8 => Ok(Opcode::OP_CHECKBLSSIGAGG(OP_CHECKBLSSIGAGG)),
+            // Memory
+            0xbc => Ok(Opcode::OP_MWRITE(OP_MWRITE)),
+            0xbd => Ok(Opcode::OP_MREAD(OP_MREAD)),
+            0xbe => Ok(Opcode::OP_MFREE(OP_MFREE)),
+            // Storage
+            0xbf => Ok(Opcode::OP_SWRITE(OP_SWRITE)),
+            0xc0 => Ok(Opcode::OP_SREAD(OP_SREAD)),
+            0xc1 => Ok(Opcode::OP_SFREE(OP_SFREE)),
+            // Call
+            0xc2 => Ok(Opcode::OP_TRYCALL(OP_TRYCALL)),
             // Undefined
             _ => Err(OpcodeDecompileError::UndefinedOpcodeError),
         }
@@ -1,4 +1,5 @@
 pub mod compiler;
 pub mod op;
 pub mod opcode;
+pub mod optimizer;
 pub mod ops;
@@ -0,0 +1,123 @@
+use crate::executive::{
+    opcode::ops::OP_TRYCALL_OPS,
+    stack::{
+        limits::MAX_CALL_DEPTH,
+        stack_error::StackError,
+        stack_holder::StackHolder,
+        stack_item::StackItem,
+        stack_uint::{SafeConverter, StackItemUintExt},
+    },
+};
+
+/// Calls a method, and reverts its state changes on failure instead of failing the caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub struct OP_TRYCALL;
+
+impl OP_TRYCALL {
+    /// Executes the try-call opcode.
+    ///
+    /// The `inner_call` executes the called method against the stack holder with the
+    /// contract id, method index and call arguments. Both internal and cross-contract
+    /// calls go through it, and record their storage writes, balance moves and events
+    /// into the same stack holder so that they can be reverted together.
+    pub fn execute<F>(stack_holder: &mut StackHolder, inner_call: F) -> Result<(), StackError>
+    where
+        F: FnOnce(
+            &mut StackHolder,
+            [u8; 32],
+            u8,
+            Vec<StackItem>,
+        ) -> Result<Vec<StackItem>, StackError>,
+    {
+        // If this is not the active execution, return immediately.
+        if !stack_holder.active_execution() {
+            return Ok(());
+        }
+
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_TRYCALL_OPS)?;
+
+        // Check the call depth limit before popping the call arguments, leaving the stack intact.
+        if stack_holder.call_depth() >= MAX_CALL_DEPTH {
+            return Err(StackError::CallDepthLimitExceeded);
+        }
+
+        // Pop the contract id.
+        let contract_id: [u8; 32] = stack_holder
+            .pop()?
+            .bytes()
+            .try_into()
+            .map_err(|_| StackError::InvalidContractIdBytes)?;
+
+        // Pop the method index.
+        let method_index = stack_holder
+            .pop()?
+            .to_stack_uint()
+            .ok_or(StackError::StackUintConversionError)?
+            .to_u32()
+            .ok_or(StackError::StackUintConversionError)?;
+
+        // Method index must fit in a single byte.
+        let method_index: u8 = method_index
+            .try_into()
+            .map_err(|_| StackError::InvalidMethodIndex)?;
+
+        // Pop the number of call arguments.
+        let args_count = stack_holder
+            .pop()?
+            .to_stack_uint()
+            .ok_or(StackError::StackUintConversionError)?
+            .to_usize()
+            .ok_or(StackError::StackUintConversionError)?;
+
+        // Collect the call arguments.
+        let mut args = Vec::<StackItem>::with_capacity(args_count);
+        for _ in 0..args_count {
+            args.push(stack_holder.pop()?);
+        }
+
+        // Take a savepoint before entering the inner call.
+        let savepoint = stack_holder.savepoint();
+
+        // Enter the inner call.
+        stack_holder.enter_call()?;
+
+        // Execute the inner call.
+        let result = inner_call(stack_holder, contract_id, method_index, args);
+
+        match result {
+            Ok(return_items) => {
+                // Exit the inner call.
+                stack_holder.exit_call();
+
+                // Push the returned items to the main stack.
+                for item in return_items {
+                    stack_holder.push(item)?;
+                }
+
+                // Push True to the main stack.
+                stack_holder.push(StackItem::true_item())?;
+            }
+            Err(error) => {
+                // Spent ops cannot be reverted, so running out of ops fails the caller too.
+                if !error.is_revertible() {
+                    return Err(error);
+                }
+
+                // Roll back to the savepoint. This also restores the call depth.
+                stack_holder.revert_to_savepoint(savepoint);
+
+                // Push False (an empty stack item) to the main stack.
+                stack_holder.push(StackItem::false_item())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the bytecode for the `OP_TRYCALL` opcode (0xc2).
+    pub fn bytecode() -> Vec<u8> {
+        vec![0xc2]
+    }
+}
@@ -8,12 +8,12 @@ use crate::executive::{
     },
 };
 
-/// The `OP_MSWEEP` opcode.
-#[derive(Debug, Clone, Copy)]
+/// The `OP_MFREE` opcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
-pub struct OP_MSWEEP;
+pub struct OP_MFREE;
 
-impl OP_MSWEEP {
+impl OP_MFREE {
     pub fn execute(stack_holder: &mut StackHolder) -> Result<(), StackError> {
         // If this is not the active execution, return immediately.
         if !stack_holder.active_execution() {
@@ -31,8 +31,8 @@ impl OP_MSWEEP {
         // Get contract memory.
         let memory = stack_holder.memory_mut();
 
-        // Write to memory.
-        let sweep_result_item = match memory.remove(&key.bytes().to_vec()) {
+        // Free from memory.
+        let free_result_item = match memory.remove(&key.bytes().to_vec()) {
             // If the key already exists, push true value.
             Some(_) => StackItem::new(vec![0x01]),
             // If the key does not exist, push false value (empty vector).
@@ -43,8 +43,13 @@ impl OP_MSWEEP {
         stack_holder.increment_ops(OP_MFREE_OPS)?;
 
         // Push result to stack.
-        stack_holder.push(sweep_result_item)?;
+        stack_holder.push(free_result_item)?;
 
         Ok(())
     }
+
+    /// Returns the bytecode for the `OP_MFREE` opcode (0xbe).
+    pub fn bytecode() -> Vec<u8> {
+        vec![0xbe]
+    }
 }
@@ -9,7 +9,7 @@ use crate::executive::{
 };
 
 /// The `OP_MREAD` opcode.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
 pub struct OP_MREAD;
 
@@ -47,4 +47,9 @@ impl OP_MREAD {
 
         Ok(())
     }
+
+    /// Returns the bytecode for the `OP_MREAD` opcode (0xbd).
+    pub fn bytecode() -> Vec<u8> {
+        vec![0xbd]
+    }
 }
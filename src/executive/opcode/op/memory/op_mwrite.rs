@@ -9,7 +9,7 @@ use crate::executive::{
 };
 
 /// The `OP_MWRITE` opcode.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
 pub struct OP_MWRITE;
 
@@ -69,4 +69,9 @@ impl OP_MWRITE {
 
         Ok(())
     }
+
+    /// Returns the bytecode for the `OP_MWRITE` opcode (0xbc).
+    pub fn bytecode() -> Vec<u8> {
+        vec![0xbc]
+    }
 }
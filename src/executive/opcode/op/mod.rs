@@ -1,6 +1,7 @@
 pub mod altstack;
 pub mod arithmetic;
 pub mod bitwise;
+pub mod call;
 pub mod digest;
 pub mod flow;
 pub mod memory;
@@ -10,3 +11,4 @@ pub mod secp;
 pub mod signature;
 pub mod splice;
 pub mod stack;
+pub mod storage;
@@ -0,0 +1,3 @@
+pub mod op_sfree;
+pub mod op_sread;
+pub mod op_swrite;
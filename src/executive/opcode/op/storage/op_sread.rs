@@ -0,0 +1,52 @@
+use crate::executive::{
+    opcode::ops::OP_SREAD_OPS,
+    stack::{
+        limits::{MAX_KEY_LENGTH, MIN_KEY_LENGTH},
+        stack_error::StackError,
+        stack_holder::StackHolder,
+        stack_item::StackItem,
+    },
+};
+
+/// The `OP_SREAD` opcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub struct OP_SREAD;
+
+impl OP_SREAD {
+    pub fn execute(stack_holder: &mut StackHolder) -> Result<(), StackError> {
+        // If this is not the active execution, return immediately.
+        if !stack_holder.active_execution() {
+            return Ok(());
+        }
+
+        // Pop key
+        let key = stack_holder.pop()?;
+
+        // Make sure key is within the valid length range (1 to 40 bytes).
+        if key.len() < MIN_KEY_LENGTH || key.len() > MAX_KEY_LENGTH {
+            return Err(StackError::InvalidStorageKeyLength(key.len() as u8));
+        }
+
+        // Read from storage.
+        let value = match stack_holder.read_storage(key.bytes()) {
+            // If the value exists, push value.
+            Some(value) => StackItem::new(value),
+            // If the value does not exist, push false value (empty vector).
+            None => StackItem::new(vec![]),
+        };
+
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_SREAD_OPS)?;
+
+        // Push result to stack.
+        stack_holder.push(value)?;
+
+        Ok(())
+    }
+
+    /// Returns the bytecode for the `OP_SREAD` opcode (0xc0).
+    pub fn bytecode() -> Vec<u8> {
+        vec![0xc0]
+    }
+}
@@ -0,0 +1,62 @@
+use crate::executive::{
+    opcode::ops::OP_SWRITE_OPS,
+    stack::{
+        limits::{MAX_KEY_LENGTH, MIN_KEY_LENGTH, MIN_VALUE_LENGTH},
+        stack_error::StackError,
+        stack_holder::StackHolder,
+        stack_item::StackItem,
+    },
+};
+
+/// The `OP_SWRITE` opcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub struct OP_SWRITE;
+
+impl OP_SWRITE {
+    pub fn execute(stack_holder: &mut StackHolder) -> Result<(), StackError> {
+        // If this is not the active execution, return immediately.
+        if !stack_holder.active_execution() {
+            return Ok(());
+        }
+
+        // Pop key
+        let key = stack_holder.pop()?;
+
+        // Make sure key is within the valid length range (1 to 40 bytes).
+        if key.len() < MIN_KEY_LENGTH || key.len() > MAX_KEY_LENGTH {
+            return Err(StackError::InvalidStorageKeyLength(key.len() as u8));
+        }
+
+        // Pop value
+        let value = stack_holder.pop()?;
+
+        // Make sure value is not empty.
+        // NOTE: The maximum length of the value is bound by the stack item size limit.
+        if value.len() < MIN_VALUE_LENGTH {
+            return Err(StackError::InvalidStorageValueLength(value.len() as u8));
+        }
+
+        // Write to storage.
+        let insertion_result_item =
+            match stack_holder.write_storage(key.bytes().to_vec(), value.bytes().to_vec()) {
+                // If the key already exists, push true value.
+                true => StackItem::new(vec![0x01]),
+                // If the key does not exist, push false value (empty vector).
+                false => StackItem::new(vec![]),
+            };
+
+        // Increment the ops counter.
+        stack_holder.increment_ops(OP_SWRITE_OPS)?;
+
+        // Push result to stack.
+        stack_holder.push(insertion_result_item)?;
+
+        Ok(())
+    }
+
+    /// Returns the bytecode for the `OP_SWRITE` opcode (0xbf).
+    pub fn bytecode() -> Vec<u8> {
+        vec![0xbf]
+    }
+}
@@ -16,6 +16,7 @@ use super::op::{
         op_and::OP_AND, op_equal::OP_EQUAL, op_equalverify::OP_EQUALVERIFY, op_invert::OP_INVERT,
         op_or::OP_OR, op_reverse::OP_REVERSE, op_xor::OP_XOR,
     },
+    call::op_trycall::OP_TRYCALL,
     digest::{
         op_blake2bvar::OP_BLAKE2BVAR, op_blake2svar::OP_BLAKE2SVAR, op_hash160::OP_HASH160,
         op_hash256::OP_HASH256, op_ripemd160::OP_RIPEMD160, op_sha1::OP_SHA1, op_sha256::OP_SHA256,
@@ -26,6 +27,7 @@ use super::op::{
         op_notif::OP_NOTIF, op_returnall::OP_RETURNALL, op_returnerr::OP_RETURNERR,
         op_returnsome::OP_RETURNSOME, op_verify::OP_VERIFY,
     },
+    memory::{op_free::OP_MFREE, op_mread::OP_MREAD, op_mwrite::OP_MWRITE},
     push::{
         op_10::OP_10, op_11::OP_11, op_12::OP_12, op_13::OP_13, op_14::OP_14, op_15::OP_15,
         op_16::OP_16, op_2::OP_2, op_3::OP_3, op_4::OP_4, op_5::OP_5, op_6::OP_6, op_7::OP_7,
@@ -54,6 +56,7 @@ use super::op::{
         op_ifdup::OP_IFDUP, op_nip::OP_NIP, op_over::OP_OVER, op_pick::OP_PICK, op_roll::OP_ROLL,
         op_rot::OP_ROT, op_swap::OP_SWAP, op_tuck::OP_TUCK,
     },
+    storage::{op_sfree::OP_SFREE, op_sread::OP_SREAD, op_swrite::OP_SWRITE},
 };
 use std::fmt::{self, Display};
 
@@ -179,6 +182,16 @@ pub enum Opcode {
     OP_CHECKSCHNORRSIGBIP340(OP_CHECKSCHNORRSIGBIP340),
     OP_CHECKBLSSIGSINGLE(OP_CHECKBLSSIGSINGLE),
     OP_CHECKBLSSIGAGG(OP_CHECKBLSSIGAGG),
+    // Memory
+    OP_MWRITE(OP_MWRITE),
+    OP_MREAD(OP_MREAD),
+    OP_MFREE(OP_MFREE),
+    // Storage
+    OP_SWRITE(OP_SWRITE),
+    OP_SREAD(OP_SREAD),
+    OP_SFREE(OP_SFREE),
+    // Call
+    OP_TRYCALL(OP_TRYCALL),
 }
 
 impl Display for Opcode {
@@ -305,6 +318,16 @@ impl Display for Opcode {
             Opcode::OP_CHECKSCHNORRSIGBIP340(_) => write!(f, "OP_CHECKSCHNORRSIGBIP340"),
             Opcode::OP_CHECKBLSSIGSINGLE(_) => write!(f, "OP_CHECKBLSSIGSINGLE"),
             Opcode::OP_CHECKBLSSIGAGG(_) => write!(f, "OP_CHECKBLSSIGAGG"),
+            // Memory
+            Opcode::OP_MWRITE(_) => write!(f, "OP_MWRITE"),
+            Opcode::OP_MREAD(_) => write!(f, "OP_MREAD"),
+            Opcode::OP_MFREE(_) => write!(f, "OP_MFREE"),
+            // Storage
+            Opcode::OP_SWRITE(_) => write!(f, "OP_SWRITE"),
+            Opcode::OP_SREAD(_) => write!(f, "OP_SREAD"),
+            Opcode::OP_SFREE(_) => write!(f, "OP_SFREE"),
+            // Call
+            Opcode::OP_TRYCALL(_) => write!(f, "OP_TRYCALL"),
         }
     }
 }
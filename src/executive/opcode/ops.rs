@@ -102,3 +102,11 @@ pub const OP_WITHIN_OPS: u32 = 1;
 pub const OP_MREAD_OPS: u32 = 5;
 pub const OP_MWRITE_OPS: u32 = 5;
 pub const OP_MFREE_OPS: u32 = 1;
+
+// Storage
+pub const OP_SREAD_OPS: u32 = 50;
+pub const OP_SWRITE_OPS: u32 = 50;
+pub const OP_SFREE_OPS: u32 = 1;
+
+// Call
+pub const OP_TRYCALL_OPS: u32 = 10;
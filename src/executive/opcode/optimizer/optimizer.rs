@@ -0,0 +1,343 @@
+use crate::executive::{
+    opcode::{
+        compiler::{compiler::OpcodeCompiler, compiler_error::OpcodeCompileError},
+        op::{
+            arithmetic::{op_1add::OP_1ADD, op_numequalverify::OP_NUMEQUALVERIFY},
+            bitwise::op_equalverify::OP_EQUALVERIFY,
+            flow::{op_if::OP_IF, op_nop::OP_NOP, op_notif::OP_NOTIF, op_verify::OP_VERIFY},
+            stack::{op_2drop::OP_2DROP, op_drop::OP_DROP},
+        },
+        opcode::Opcode,
+        ops::{
+            OP_1ADD_OPS, OP_2DROP_OPS, OP_ADD_OPS, OP_DROP_OPS, OP_DUP_OPS, OP_ELSE_OPS,
+            OP_ENDIF_OPS, OP_EQUALVERIFY_OPS, OP_EQUAL_OPS, OP_FROMALTSTACK_OPS, OP_IF_OPS,
+            OP_NOP_OPS, OP_NOTIF_OPS, OP_NOT_OPS, OP_NUMEQUALVERIFY_OPS, OP_NUMEQUAL_OPS,
+            OP_TOALTSTACK_OPS, OP_VERIFY_OPS,
+        },
+    },
+    program::method::limits::MIN_METHOD_OPCODE_COUNT,
+};
+
+/// The outcome of an optimization pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptimizationReport {
+    /// The number of rewrites applied.
+    rewrites: usize,
+    /// The opcode count before and after.
+    opcodes_before: usize,
+    opcodes_after: usize,
+    /// The compiled byte length before and after.
+    bytes_before: usize,
+    bytes_after: usize,
+    /// The ops saved on the executed path.
+    ops_saved: u32,
+}
+
+impl OptimizationReport {
+    /// Returns the number of rewrites applied.
+    pub fn rewrites(&self) -> usize {
+        self.rewrites
+    }
+
+    /// Returns the opcode count before the optimization.
+    pub fn opcodes_before(&self) -> usize {
+        self.opcodes_before
+    }
+
+    /// Returns the opcode count after the optimization.
+    pub fn opcodes_after(&self) -> usize {
+        self.opcodes_after
+    }
+
+    /// Returns the compiled byte length before the optimization.
+    pub fn bytes_before(&self) -> usize {
+        self.bytes_before
+    }
+
+    /// Returns the compiled byte length after the optimization.
+    pub fn bytes_after(&self) -> usize {
+        self.bytes_after
+    }
+
+    /// Returns the number of bytes saved.
+    pub fn bytes_saved(&self) -> usize {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+
+    /// Returns the ops saved on the executed path.
+    /// Ops of opcodes skipped by inactive branches are not counted, except for the flow opcodes.
+    pub fn ops_saved(&self) -> u32 {
+        self.ops_saved
+    }
+}
+
+/// A trait for peephole optimizing a method script.
+pub trait ScriptOptimizer {
+    /// Applies semantics-preserving rewrites until none applies, and reports the savings.
+    ///
+    /// Rewrites preserve the outcome of every successful execution. Scripts shorter than
+    /// `MIN_METHOD_OPCODE_COUNT` after the pass are padded with `OP_NOP`.
+    fn optimize(&self) -> Result<(Vec<Opcode>, OptimizationReport), OpcodeCompileError>;
+}
+
+impl ScriptOptimizer for Vec<Opcode> {
+    fn optimize(&self) -> Result<(Vec<Opcode>, OptimizationReport), OpcodeCompileError> {
+        let bytes_before = compiled_len(self)?;
+
+        let mut script = self.clone();
+        let mut rewrites: usize = 0;
+        let mut ops_saved: u32 = 0;
+
+        // Apply rewrites until a fixpoint is reached.
+        loop {
+            let mut changed = false;
+            let mut optimized = Vec::<Opcode>::with_capacity(script.len());
+            let mut index = 0;
+
+            while index < script.len() {
+                match rewrite_at(&script, index) {
+                    Some(rewrite) => {
+                        optimized.extend(rewrite.replacement);
+                        index += rewrite.consumed;
+                        ops_saved += rewrite.ops_saved;
+                        rewrites += 1;
+                        changed = true;
+                    }
+                    None => {
+                        optimized.push(script[index].clone());
+                        index += 1;
+                    }
+                }
+            }
+
+            script = optimized;
+
+            if !changed {
+                break;
+            }
+        }
+
+        // Keep the script above the minimum opcode count.
+        while script.len() < MIN_METHOD_OPCODE_COUNT {
+            script.push(Opcode::OP_NOP(OP_NOP));
+        }
+
+        let report = OptimizationReport {
+            rewrites,
+            opcodes_before: self.len(),
+            opcodes_after: script.len(),
+            bytes_before,
+            bytes_after: compiled_len(&script)?,
+            ops_saved,
+        };
+
+        Ok((script, report))
+    }
+}
+
+/// A single rewrite: replaces `consumed` opcodes with `replacement`.
+struct Rewrite {
+    consumed: usize,
+    replacement: Vec<Opcode>,
+    ops_saved: u32,
+}
+
+impl Rewrite {
+    fn new(consumed: usize, replacement: Vec<Opcode>, ops_saved: u32) -> Option<Self> {
+        Some(Self {
+            consumed,
+            replacement,
+            ops_saved,
+        })
+    }
+}
+
+/// Returns the rewrite that applies at the given index, if any.
+fn rewrite_at(script: &[Opcode], index: usize) -> Option<Rewrite> {
+    let window = &script[index..];
+
+    // Constant conditions.
+    if let Some(rewrite) = eliminate_constant_branch(script, index) {
+        return Some(rewrite);
+    }
+
+    match window {
+        // Padding is redundant; it is re-added at the end of the pass if needed.
+        [Opcode::OP_NOP(_), ..] => Rewrite::new(1, vec![], OP_NOP_OPS),
+        // Duplicating and dropping is a no-op.
+        [Opcode::OP_DUP(_), Opcode::OP_DROP(_), ..] => {
+            Rewrite::new(2, vec![], OP_DUP_OPS + OP_DROP_OPS)
+        }
+        // Round-tripping through the alt stack is a no-op.
+        [Opcode::OP_TOALTSTACK(_), Opcode::OP_FROMALTSTACK(_), ..] => {
+            Rewrite::new(2, vec![], OP_TOALTSTACK_OPS + OP_FROMALTSTACK_OPS)
+        }
+        // Pushing and dropping is a no-op.
+        [push, Opcode::OP_DROP(_), ..] if push_ops(push).is_some() => {
+            Rewrite::new(2, vec![], push_ops(push)? + OP_DROP_OPS)
+        }
+        // Verifying a constant true is a no-op. `OP_VERIFY` requires exactly 0x01.
+        [Opcode::OP_TRUE(_), Opcode::OP_VERIFY(_), ..] => {
+            Rewrite::new(2, vec![], push_ops(&window[0])? + OP_VERIFY_OPS)
+        }
+        // Two drops fold into one.
+        [Opcode::OP_DROP(_), Opcode::OP_DROP(_), ..] => Rewrite::new(
+            2,
+            vec![Opcode::OP_2DROP(OP_2DROP)],
+            (2 * OP_DROP_OPS).saturating_sub(OP_2DROP_OPS),
+        ),
+        // Equality checks followed by a verify fold into their verifying form.
+        [Opcode::OP_EQUAL(_), Opcode::OP_VERIFY(_), ..] => Rewrite::new(
+            2,
+            vec![Opcode::OP_EQUALVERIFY(OP_EQUALVERIFY)],
+            (OP_EQUAL_OPS + OP_VERIFY_OPS).saturating_sub(OP_EQUALVERIFY_OPS),
+        ),
+        [Opcode::OP_NUMEQUAL(_), Opcode::OP_VERIFY(_), ..] => Rewrite::new(
+            2,
+            vec![Opcode::OP_NUMEQUALVERIFY(OP_NUMEQUALVERIFY)],
+            (OP_NUMEQUAL_OPS + OP_VERIFY_OPS).saturating_sub(OP_NUMEQUALVERIFY_OPS),
+        ),
+        // Adding one folds into `OP_1ADD`. Both push a success flag, but leave different
+        // items behind on overflow, so the flag must be verified.
+        [Opcode::OP_TRUE(_), Opcode::OP_ADD(_), Opcode::OP_VERIFY(_), ..] => Rewrite::new(
+            3,
+            vec![Opcode::OP_1ADD(OP_1ADD), Opcode::OP_VERIFY(OP_VERIFY)],
+            (push_ops(&window[0])? + OP_ADD_OPS).saturating_sub(OP_1ADD_OPS),
+        ),
+        // Negated conditions fold into the opposite branch opcode.
+        [Opcode::OP_NOT(_), Opcode::OP_IF(_), ..] => {
+            Rewrite::new(2, vec![Opcode::OP_NOTIF(OP_NOTIF)], OP_NOT_OPS)
+        }
+        [Opcode::OP_NOT(_), Opcode::OP_NOTIF(_), ..] => {
+            Rewrite::new(2, vec![Opcode::OP_IF(OP_IF)], OP_NOT_OPS)
+        }
+        // Empty branches only consume the condition.
+        [Opcode::OP_IF(_) | Opcode::OP_NOTIF(_), Opcode::OP_ENDIF(_), ..] => Rewrite::new(
+            2,
+            vec![Opcode::OP_DROP(OP_DROP)],
+            (OP_IF_OPS + OP_ENDIF_OPS).saturating_sub(OP_DROP_OPS),
+        ),
+        [Opcode::OP_IF(_) | Opcode::OP_NOTIF(_), Opcode::OP_ELSE(_), Opcode::OP_ENDIF(_), ..] => {
+            Rewrite::new(
+                3,
+                vec![Opcode::OP_DROP(OP_DROP)],
+                (OP_IF_OPS + OP_ELSE_OPS + OP_ENDIF_OPS).saturating_sub(OP_DROP_OPS),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Replaces `<constant> OP_IF a [OP_ELSE b] OP_ENDIF` with the branch that is taken.
+fn eliminate_constant_branch(script: &[Opcode], index: usize) -> Option<Rewrite> {
+    let condition = constant_condition(script.get(index)?)?;
+
+    let (taken_if_true, branch_ops) = match script.get(index + 1)? {
+        Opcode::OP_IF(_) => (condition, OP_IF_OPS),
+        Opcode::OP_NOTIF(_) => (!condition, OP_NOTIF_OPS),
+        _ => return None,
+    };
+
+    let (else_index, endif_index) = match_branch(script, index + 1)?;
+
+    let then_body = &script[index + 2..else_index.unwrap_or(endif_index)];
+    let else_body = match else_index {
+        Some(else_index) => &script[else_index + 1..endif_index],
+        None => &[],
+    };
+
+    let (taken, skipped) = match taken_if_true {
+        true => (then_body, else_body),
+        false => (else_body, then_body),
+    };
+
+    // Flow opcodes in the skipped branch are counted even though they do not execute.
+    let skipped_flow_ops: u32 = skipped
+        .iter()
+        .map(|opcode| match opcode {
+            Opcode::OP_IF(_) => OP_IF_OPS,
+            Opcode::OP_NOTIF(_) => OP_NOTIF_OPS,
+            Opcode::OP_ELSE(_) => OP_ELSE_OPS,
+            Opcode::OP_ENDIF(_) => OP_ENDIF_OPS,
+            _ => 0,
+        })
+        .sum();
+
+    let else_ops = match else_index {
+        Some(_) => OP_ELSE_OPS,
+        None => 0,
+    };
+
+    Rewrite::new(
+        endif_index + 1 - index,
+        taken.to_vec(),
+        push_ops(&script[index])? + branch_ops + else_ops + OP_ENDIF_OPS + skipped_flow_ops,
+    )
+}
+
+/// Returns the matching `OP_ELSE` (if any) and `OP_ENDIF` indexes of the branch opening at `if_index`.
+fn match_branch(script: &[Opcode], if_index: usize) -> Option<(Option<usize>, usize)> {
+    let mut depth: usize = 0;
+    let mut else_index: Option<usize> = None;
+
+    for (index, opcode) in script.iter().enumerate().skip(if_index + 1) {
+        match opcode {
+            Opcode::OP_IF(_) | Opcode::OP_NOTIF(_) => depth += 1,
+            Opcode::OP_ELSE(_) if depth == 0 => {
+                // Only one `OP_ELSE` is allowed per branch.
+                if else_index.is_some() {
+                    return None;
+                }
+                else_index = Some(index);
+            }
+            Opcode::OP_ENDIF(_) if depth == 0 => return Some((else_index, index)),
+            Opcode::OP_ENDIF(_) => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Returns the truthiness of a constant push, as evaluated by `OP_IF`.
+fn constant_condition(opcode: &Opcode) -> Option<bool> {
+    match opcode {
+        Opcode::OP_FALSE(_) => Some(false),
+        Opcode::OP_PUSHDATA(op_pushdata) => Some(!op_pushdata.0.is_empty()),
+        _ => push_ops(opcode).map(|_| true),
+    }
+}
+
+/// Returns the ops of a data push, or `None` if the opcode is not a data push.
+fn push_ops(opcode: &Opcode) -> Option<u32> {
+    match opcode {
+        Opcode::OP_PUSHDATA(op_pushdata) => Some(1 + op_pushdata.0.len() as u32),
+        Opcode::OP_FALSE(_)
+        | Opcode::OP_TRUE(_)
+        | Opcode::OP_2(_)
+        | Opcode::OP_3(_)
+        | Opcode::OP_4(_)
+        | Opcode::OP_5(_)
+        | Opcode::OP_6(_)
+        | Opcode::OP_7(_)
+        | Opcode::OP_8(_)
+        | Opcode::OP_9(_)
+        | Opcode::OP_10(_)
+        | Opcode::OP_11(_)
+        | Opcode::OP_12(_)
+        | Opcode::OP_13(_)
+        | Opcode::OP_14(_)
+        | Opcode::OP_15(_)
+        | Opcode::OP_16(_) => Some(1),
+        _ => None,
+    }
+}
+
+/// Returns the compiled byte length of the script.
+fn compiled_len(script: &[Opcode]) -> Result<usize, OpcodeCompileError> {
+    let mut len = 0;
+    for opcode in script.iter() {
+        len += opcode.compile()?.len();
+    }
+    Ok(len)
+}
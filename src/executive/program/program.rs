@@ -68,6 +68,15 @@ impl Program {
         &self.methods
     }
 
+    /// Returns the number of callable methods.
+    /// Callable methods are ordered first, so they occupy the lowest method indexes.
+    pub fn callable_methods_len(&self) -> usize {
+        self.methods
+            .iter()
+            .filter(|method| method.method_type() == MethodType::Callable)
+            .count()
+    }
+
     /// Returns the method index by given method name.
     pub fn index_by_method_name(&self, method_name: &str) -> Option<usize> {
         self.methods
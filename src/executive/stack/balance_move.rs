@@ -0,0 +1,32 @@
+/// A balance move recorded during execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceMove {
+    // The account or contract the value is moved from.
+    from: [u8; 32],
+    // The account or contract the value is moved to.
+    to: [u8; 32],
+    // The amount moved.
+    amount: u32,
+}
+
+impl BalanceMove {
+    /// Creates a new balance move.
+    pub fn new(from: [u8; 32], to: [u8; 32], amount: u32) -> Self {
+        Self { from, to, amount }
+    }
+
+    /// Returns the source of the move.
+    pub fn from(&self) -> [u8; 32] {
+        self.from
+    }
+
+    /// Returns the destination of the move.
+    pub fn to(&self) -> [u8; 32] {
+        self.to
+    }
+
+    /// Returns the amount moved.
+    pub fn amount(&self) -> u32 {
+        self.amount
+    }
+}
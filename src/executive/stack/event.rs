@@ -0,0 +1,25 @@
+/// An event emitted during execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    // The contract that emitted the event.
+    contract_id: [u8; 32],
+    // The event payload.
+    data: Vec<u8>,
+}
+
+impl Event {
+    /// Creates a new event.
+    pub fn new(contract_id: [u8; 32], data: Vec<u8>) -> Self {
+        Self { contract_id, data }
+    }
+
+    /// Returns the id of the contract that emitted the event.
+    pub fn contract_id(&self) -> [u8; 32] {
+        self.contract_id
+    }
+
+    /// Returns the event payload.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
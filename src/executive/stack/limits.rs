@@ -18,3 +18,6 @@ pub const MAX_CONTRACT_MEMORY_SIZE: u32 = 65_536;
 
 // Ops upper bound.
 pub const OPS_LIMIT: u32 = 100_000;
+
+/// The maximum depth of nested calls.
+pub const MAX_CALL_DEPTH: u8 = 16;
@@ -1,5 +1,8 @@
+pub mod balance_move;
+pub mod event;
 pub mod flow;
 pub mod limits;
+pub mod savepoint;
 pub mod stack;
 pub mod stack_error;
 pub mod stack_holder;
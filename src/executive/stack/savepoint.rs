@@ -0,0 +1,34 @@
+use super::{flow::flow_encounter::FlowEncounter, stack::Stack};
+use std::collections::HashMap;
+
+/// A snapshot of the revertible execution state of a `StackHolder`.
+///
+/// Ops counters are deliberately not covered; ops spent by a reverted call stay spent.
+#[derive(Debug, Clone)]
+pub struct Savepoint {
+    // Main stack.
+    pub(super) main_stack: Stack,
+    // Alt stack.
+    pub(super) alt_stack: Stack,
+    // Contract memory.
+    pub(super) memory: HashMap<Vec<u8>, Vec<u8>>,
+    // Contract memory size.
+    pub(super) memory_size: u32,
+    // Contract storage overlay.
+    pub(super) storage_overlay: HashMap<Vec<u8>, Option<Vec<u8>>>,
+    // Number of balance moves recorded at the time of the savepoint.
+    pub(super) balance_moves_len: usize,
+    // Number of events emitted at the time of the savepoint.
+    pub(super) events_len: usize,
+    // Flow encounters.
+    pub(super) flow_encounters: Vec<FlowEncounter>,
+    // Call depth.
+    pub(super) call_depth: u8,
+}
+
+impl Savepoint {
+    /// Returns the call depth the savepoint was taken at.
+    pub fn call_depth(&self) -> u8 {
+        self.call_depth
+    }
+}
@@ -62,4 +62,21 @@ pub enum StackError {
     InvalidBLSMessageBytes,
     // Invalid BLS signature bytes.
     InvalidBLSSignatureBytes,
+    // Invalid contract id bytes.
+    InvalidContractIdBytes,
+    // Invalid method index.
+    InvalidMethodIndex,
+    // Call depth limit exceeded.
+    CallDepthLimitExceeded,
+}
+
+impl StackError {
+    /// Returns whether a failed call with this error can be reverted to a savepoint.
+    /// Running out of ops is never recoverable, since spent ops are not reverted.
+    pub fn is_revertible(&self) -> bool {
+        !matches!(
+            self,
+            StackError::InternalOpsBudgetExceeded | StackError::ExternalOpsLimitExceeded
+        )
+    }
 }
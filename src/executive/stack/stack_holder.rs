@@ -1,6 +1,9 @@
 use super::{
+    balance_move::BalanceMove,
+    event::Event,
     flow::{flow_encounter::FlowEncounter, flow_status::FlowStatus},
-    limits::OPS_LIMIT,
+    limits::{MAX_CALL_DEPTH, OPS_LIMIT},
+    savepoint::Savepoint,
     stack::Stack,
     stack_error::StackError,
     stack_item::StackItem,
@@ -22,6 +25,17 @@ pub struct StackHolder<'a> {
     memory: HashMap<Vec<u8>, Vec<u8>>,
     // Contract memory size.
     memory_size: u32,
+    // Contract storage, as stored before the execution.
+    storage: HashMap<Vec<u8>, Vec<u8>>,
+    // Contract storage overlay backed by the stored contract storage.
+    // Storage writes and frees are buffered here until the execution succeeds.
+    storage_overlay: HashMap<Vec<u8>, Option<Vec<u8>>>,
+    // Balance moves recorded during execution.
+    balance_moves: Vec<BalanceMove>,
+    // Events emitted during execution.
+    events: Vec<Event>,
+    // Depth of nested calls.
+    call_depth: u8,
     // Ops budget.
     ops_budget: u32,
     // Internal ops counter.
@@ -68,6 +82,11 @@ impl<'a> StackHolder<'a> {
             alt_stack: Stack::new(),
             memory: HashMap::new(),
             memory_size: 0,
+            storage: HashMap::new(),
+            storage_overlay: HashMap::new(),
+            balance_moves: Vec::<BalanceMove>::new(),
+            events: Vec::<Event>::new(),
+            call_depth: 0,
             ops_budget,
             internal_ops_counter,
             external_ops_counter,
@@ -177,6 +196,113 @@ impl<'a> StackHolder<'a> {
         self.memory_size = new_size;
     }
 
+    /// Loads the stored contract storage backing the storage overlay.
+    pub fn load_storage(&mut self, storage: HashMap<Vec<u8>, Vec<u8>>) {
+        self.storage = storage;
+    }
+
+    /// Returns the contract storage overlay, i.e. the storage changes made so far.
+    /// Freed keys map to `None`.
+    pub fn storage_overlay(&self) -> &HashMap<Vec<u8>, Option<Vec<u8>>> {
+        &self.storage_overlay
+    }
+
+    /// Reads a storage value, from the overlay if changed, otherwise from the stored storage.
+    pub fn read_storage(&self, key: &[u8]) -> Option<Vec<u8>> {
+        match self.storage_overlay.get(key) {
+            Some(value) => value.clone(),
+            None => self.storage.get(key).cloned(),
+        }
+    }
+
+    /// Writes a storage value to the overlay.
+    /// Returns whether the key already existed.
+    pub fn write_storage(&mut self, key: Vec<u8>, value: Vec<u8>) -> bool {
+        let exists = self.read_storage(&key).is_some();
+        self.storage_overlay.insert(key, Some(value));
+        exists
+    }
+
+    /// Frees a storage value in the overlay.
+    /// Returns whether the key existed.
+    pub fn free_storage(&mut self, key: Vec<u8>) -> bool {
+        let exists = self.read_storage(&key).is_some();
+        if exists {
+            self.storage_overlay.insert(key, None);
+        }
+        exists
+    }
+
+    /// Returns the balance moves recorded so far.
+    pub fn balance_moves(&self) -> &Vec<BalanceMove> {
+        &self.balance_moves
+    }
+
+    /// Records a balance move.
+    pub fn record_balance_move(&mut self, balance_move: BalanceMove) {
+        self.balance_moves.push(balance_move);
+    }
+
+    /// Returns the events emitted so far.
+    pub fn events(&self) -> &Vec<Event> {
+        &self.events
+    }
+
+    /// Emits an event.
+    pub fn emit_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Returns the current call depth.
+    pub fn call_depth(&self) -> u8 {
+        self.call_depth
+    }
+
+    /// Enters a nested call.
+    pub fn enter_call(&mut self) -> Result<(), StackError> {
+        if self.call_depth >= MAX_CALL_DEPTH {
+            return Err(StackError::CallDepthLimitExceeded);
+        }
+
+        self.call_depth += 1;
+
+        Ok(())
+    }
+
+    /// Exits a nested call.
+    pub fn exit_call(&mut self) {
+        self.call_depth = self.call_depth.saturating_sub(1);
+    }
+
+    /// Takes a savepoint of the revertible execution state.
+    pub fn savepoint(&self) -> Savepoint {
+        Savepoint {
+            main_stack: self.main_stack.clone(),
+            alt_stack: self.alt_stack.clone(),
+            memory: self.memory.clone(),
+            memory_size: self.memory_size,
+            storage_overlay: self.storage_overlay.clone(),
+            balance_moves_len: self.balance_moves.len(),
+            events_len: self.events.len(),
+            flow_encounters: self.flow_encounters.clone(),
+            call_depth: self.call_depth,
+        }
+    }
+
+    /// Rolls the execution state back to the given savepoint.
+    /// Balance moves and events recorded after the savepoint are discarded.
+    pub fn revert_to_savepoint(&mut self, savepoint: Savepoint) {
+        self.main_stack = savepoint.main_stack;
+        self.alt_stack = savepoint.alt_stack;
+        self.memory = savepoint.memory;
+        self.memory_size = savepoint.memory_size;
+        self.storage_overlay = savepoint.storage_overlay;
+        self.balance_moves.truncate(savepoint.balance_moves_len);
+        self.events.truncate(savepoint.events_len);
+        self.flow_encounters = savepoint.flow_encounters;
+        self.call_depth = savepoint.call_depth;
+    }
+
     /// Returns the main stack.
     pub fn stack(&mut self) -> &mut Stack {
         &mut self.main_stack
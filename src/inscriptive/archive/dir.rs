@@ -0,0 +1,340 @@
+use crate::{
+    inscriptive::{balance::dir::BALANCE_DIRECTORY, registery::registery::REGISTERY},
+    operative::Chain,
+};
+use secp::Point;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Guarded archive directory.
+#[allow(non_camel_case_types)]
+pub type ARCHIVE_DIRECTORY = Arc<Mutex<ArchiveDirectory>>;
+
+/// Archived value key: a one-byte prefix followed by the 32-byte account key or contract id.
+#[allow(non_camel_case_types)]
+type VALUE_KEY = [u8; 33];
+
+/// Key prefix of account balances in storage.
+const ACCOUNT_BALANCE_PREFIX: u8 = 0x00;
+
+/// Key prefix of contract balances in storage.
+const CONTRACT_BALANCE_PREFIX: u8 = 0x01;
+
+/// Key prefix of account ranks in storage.
+const ACCOUNT_RANK_PREFIX: u8 = 0x02;
+
+/// Key prefix of contract ranks in storage.
+const CONTRACT_RANK_PREFIX: u8 = 0x03;
+
+/// Directory for the historical balances and ranks of accounts and contracts by rollup height.
+///
+/// Only the values that changed at a rollup height are stored, keyed by the value key followed
+/// by the rollup height, so that the value at any archived height is the latest version at or below it.
+/// The first archived height, and any height archived after skipped ones, holds a full snapshot
+/// of the state. A zero value means the balance was emptied, or the entity was not registered.
+pub struct ArchiveDirectory {
+    // In-memory latest archived values, zero values excluded.
+    latest: HashMap<VALUE_KEY, u64>,
+    // In-storage db for storing the versioned values.
+    versions_db: sled::Db,
+    // In-storage db for storing the value keys changed at each rollup height.
+    heights_db: sled::Db,
+}
+
+impl ArchiveDirectory {
+    pub fn new(chain: Chain) -> Option<ARCHIVE_DIRECTORY> {
+        // Open the versions db.
+        let versions_db = {
+            let path = format!("{}/{}/{}", "db", chain.to_string(), "archive/versions");
+            sled::open(path).ok()?
+        };
+
+        // Open the heights db.
+        let heights_db = {
+            let path = format!("{}/{}/{}", "db", chain.to_string(), "archive/heights");
+            sled::open(path).ok()?
+        };
+
+        let mut latest = HashMap::<VALUE_KEY, u64>::new();
+
+        // Versions are ordered by rollup height, so the last version of each value key wins.
+        for lookup in versions_db.iter() {
+            if let Ok((key, val)) = lookup {
+                if key.len() != 41 {
+                    return None;
+                }
+
+                let value_key: VALUE_KEY = (&key[..33]).try_into().ok()?;
+                let value = u64::from_be_bytes(val.as_ref().try_into().ok()?);
+
+                match value {
+                    0 => latest.remove(&value_key),
+                    _ => latest.insert(value_key, value),
+                };
+            }
+        }
+
+        let archive_dir = ArchiveDirectory {
+            latest,
+            versions_db,
+            heights_db,
+        };
+
+        Some(Arc::new(Mutex::new(archive_dir)))
+    }
+
+    /// Creates an empty archive directory that is not persisted, for tests.
+    pub fn new_temporary() -> Option<ARCHIVE_DIRECTORY> {
+        let archive_dir = ArchiveDirectory {
+            latest: HashMap::new(),
+            versions_db: sled::Config::new().temporary(true).open().ok()?,
+            heights_db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(archive_dir)))
+    }
+
+    /// Returns the first and the last archived rollup heights.
+    pub fn archived_heights(&self) -> Option<(u64, u64)> {
+        let (first, _) = self.heights_db.first().ok()??;
+        let (last, _) = self.heights_db.last().ok()??;
+
+        Some((
+            u64::from_be_bytes(first.as_ref().try_into().ok()?),
+            u64::from_be_bytes(last.as_ref().try_into().ok()?),
+        ))
+    }
+
+    /// Returns the balance of the given account key at the given rollup height.
+    /// Returns `None` if the height is not archived.
+    pub fn account_balance_at(&self, account_key: Point, rollup_height: u64) -> Option<u64> {
+        let value_key = value_key(ACCOUNT_BALANCE_PREFIX, account_key.serialize_xonly());
+        self.value_at(value_key, rollup_height)
+    }
+
+    /// Returns the balance of the given contract id at the given rollup height.
+    /// Returns `None` if the height is not archived.
+    pub fn contract_balance_at(&self, contract_id: [u8; 32], rollup_height: u64) -> Option<u64> {
+        let value_key = value_key(CONTRACT_BALANCE_PREFIX, contract_id);
+        self.value_at(value_key, rollup_height)
+    }
+
+    /// Returns the rank of the given account key at the given rollup height.
+    /// Returns `None` if the height is not archived, or the account was not registered at it.
+    pub fn account_rank_at(&self, account_key: Point, rollup_height: u64) -> Option<u32> {
+        let value_key = value_key(ACCOUNT_RANK_PREFIX, account_key.serialize_xonly());
+        match self.value_at(value_key, rollup_height)? {
+            0 => None,
+            rank => Some(rank as u32),
+        }
+    }
+
+    /// Returns the rank of the given contract id at the given rollup height.
+    /// Returns `None` if the height is not archived, or the contract was not registered at it.
+    pub fn contract_rank_at(&self, contract_id: [u8; 32], rollup_height: u64) -> Option<u32> {
+        let value_key = value_key(CONTRACT_RANK_PREFIX, contract_id);
+        match self.value_at(value_key, rollup_height)? {
+            0 => None,
+            rank => Some(rank as u32),
+        }
+    }
+
+    /// Archives the current balances and ranks at the given rollup height.
+    ///
+    /// Heights must be archived in increasing order. If the given height does not follow
+    /// the last archived one, a full snapshot is recorded at it, and the skipped heights are not archived.
+    pub async fn archive_state(
+        &mut self,
+        rollup_height: u64,
+        balance_dir: &BALANCE_DIRECTORY,
+        registery: &REGISTERY,
+    ) -> bool {
+        // Record a full snapshot if heights were skipped.
+        let snapshot = match self.archived_heights() {
+            Some((_, last_height)) if rollup_height <= last_height => return false,
+            Some((_, last_height)) => last_height + 1 != rollup_height,
+            None => true,
+        };
+
+        let current = current_values(balance_dir, registery).await;
+
+        // Collect the values that changed since the last archived height.
+        let mut changes = Vec::<(VALUE_KEY, u64)>::new();
+        for (value_key, value) in current.iter() {
+            if snapshot || self.latest.get(value_key) != Some(value) {
+                changes.push((*value_key, *value));
+            }
+        }
+        for value_key in self.latest.keys() {
+            if !current.contains_key(value_key) {
+                changes.push((*value_key, 0));
+            }
+        }
+
+        // Update in-storage.
+        let mut batch = sled::Batch::default();
+        let mut changed_keys = Vec::<u8>::new();
+        for (value_key, value) in changes.iter() {
+            batch.insert(
+                version_key(*value_key, rollup_height).to_vec(),
+                value.to_be_bytes().to_vec(),
+            );
+            changed_keys.extend(value_key);
+        }
+
+        if self.versions_db.apply_batch(batch).is_err() {
+            return false;
+        }
+
+        if self
+            .heights_db
+            .insert(rollup_height.to_be_bytes(), changed_keys)
+            .is_err()
+        {
+            return false;
+        }
+
+        // Update in-memory.
+        self.latest = current;
+
+        true
+    }
+
+    /// Removes the values archived at the given rollup height, which must be the last archived one.
+    pub fn remove_height(&mut self, rollup_height: u64) -> bool {
+        match self.archived_heights() {
+            Some((_, last_height)) if last_height == rollup_height => (),
+            _ => return false,
+        }
+
+        let changed_keys: Vec<VALUE_KEY> = match self.heights_db.get(rollup_height.to_be_bytes()) {
+            Ok(Some(val)) => val
+                .chunks_exact(33)
+                .filter_map(|chunk| chunk.try_into().ok())
+                .collect(),
+            _ => return false,
+        };
+
+        // Remove in-storage.
+        let mut batch = sled::Batch::default();
+        for value_key in changed_keys.iter() {
+            batch.remove(version_key(*value_key, rollup_height).to_vec());
+        }
+
+        if self.versions_db.apply_batch(batch).is_err() {
+            return false;
+        }
+
+        if self.heights_db.remove(rollup_height.to_be_bytes()).is_err() {
+            return false;
+        }
+
+        // Remove in-memory, falling back to the prior versions.
+        for value_key in changed_keys {
+            let prior_value = match rollup_height.checked_sub(1) {
+                Some(prior_height) => self.version_at(value_key, prior_height),
+                None => 0,
+            };
+
+            match prior_value {
+                0 => self.latest.remove(&value_key),
+                _ => self.latest.insert(value_key, prior_value),
+            };
+        }
+
+        true
+    }
+
+    /// Returns the value of the given value key at the given rollup height, if the height is archived.
+    fn value_at(&self, value_key: VALUE_KEY, rollup_height: u64) -> Option<u64> {
+        // Skipped heights are not archived.
+        if !self
+            .heights_db
+            .contains_key(rollup_height.to_be_bytes())
+            .ok()?
+        {
+            return None;
+        }
+
+        Some(self.version_at(value_key, rollup_height))
+    }
+
+    /// Returns the latest version of the given value key at or below the given rollup height.
+    fn version_at(&self, value_key: VALUE_KEY, rollup_height: u64) -> u64 {
+        let lookup = self
+            .versions_db
+            .range(version_key(value_key, 0)..=version_key(value_key, rollup_height))
+            .next_back();
+
+        match lookup {
+            Some(Ok((_, val))) => match val.as_ref().try_into() {
+                Ok(bytes) => u64::from_be_bytes(bytes),
+                Err(_) => 0,
+            },
+            _ => 0,
+        }
+    }
+}
+
+/// Returns the value key of the given prefix and the 32-byte account key or contract id.
+fn value_key(prefix: u8, id: [u8; 32]) -> VALUE_KEY {
+    let mut value_key = [prefix; 33];
+    value_key[1..].copy_from_slice(&id);
+    value_key
+}
+
+/// Returns the storage key of a value key versioned at the given rollup height.
+fn version_key(value_key: VALUE_KEY, rollup_height: u64) -> [u8; 41] {
+    let mut version_key = [0x00; 41];
+    version_key[..33].copy_from_slice(&value_key);
+    version_key[33..].copy_from_slice(&rollup_height.to_be_bytes());
+    version_key
+}
+
+/// Returns the current non-zero balances and ranks by their value keys.
+async fn current_values(
+    balance_dir: &BALANCE_DIRECTORY,
+    registery: &REGISTERY,
+) -> HashMap<VALUE_KEY, u64> {
+    let mut values = HashMap::<VALUE_KEY, u64>::new();
+
+    {
+        let _balance_dir = balance_dir.lock().await;
+        for (account_key, balance) in _balance_dir.account_balances() {
+            values.insert(value_key(ACCOUNT_BALANCE_PREFIX, account_key), balance);
+        }
+        for (contract_id, balance) in _balance_dir.contract_balances() {
+            values.insert(value_key(CONTRACT_BALANCE_PREFIX, contract_id), balance);
+        }
+    }
+
+    let (account_registery, contract_registery) = {
+        let _registery = registery.lock().await;
+        (
+            _registery.account_registery(),
+            _registery.contract_registery(),
+        )
+    };
+
+    {
+        let _account_registery = account_registery.lock().await;
+        for account in _account_registery.accounts() {
+            if let Some(rank) = account.rank() {
+                let account_key = account.key().serialize_xonly();
+                values.insert(value_key(ACCOUNT_RANK_PREFIX, account_key), rank as u64);
+            }
+        }
+    }
+
+    {
+        let _contract_registery = contract_registery.lock().await;
+        for contract in _contract_registery.contracts() {
+            if let Some(rank) = contract.rank() {
+                let contract_id = contract.contract_id();
+                values.insert(value_key(CONTRACT_RANK_PREFIX, contract_id), rank as u64);
+            }
+        }
+    }
+
+    values
+}
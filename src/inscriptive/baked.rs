@@ -37,3 +37,9 @@ pub const INITIAL_OPERATOR_SET: [[u8; 32]; 3] = [
 // Sync start heights
 pub const SIGNET_SYNC_START_HEIGHT: u64 = 244_066;
 pub const MAINNET_SYNC_START_HEIGHT: u64 = 888_116;
+
+// Number of blocks after which VTXOs and projectors expire (three months).
+pub const EXPIRY_BLOCKS: u32 = 12_960;
+
+// Number of most recent blocks the syncer can roll back on a reorg.
+pub const MAX_REORG_DEPTH: u64 = 144;
@@ -0,0 +1,361 @@
+use crate::{
+    constructive::{
+        calldata::element::CallElement,
+        entry::{combinator::combinator::Combinator, entry::Entry},
+    },
+    operative::Chain,
+};
+use secp::Point;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Guarded balance directory.
+#[allow(non_camel_case_types)]
+pub type BALANCE_DIRECTORY = Arc<Mutex<BalanceDirectory>>;
+
+/// Key prefix of account balances in storage.
+const ACCOUNT_BALANCE_PREFIX: u8 = 0x00;
+
+/// Key prefix of contract balances in storage.
+const CONTRACT_BALANCE_PREFIX: u8 = 0x01;
+
+/// Account and contract balances prior to a set of entries, keyed by account keys and contract ids.
+pub type PriorBalances = (Vec<([u8; 32], u64)>, Vec<([u8; 32], u64)>);
+
+/// Balance updates of a set of entries, not yet committed.
+#[derive(Default)]
+struct BalanceUpdates {
+    // Updated account balances, keyed by account keys.
+    accounts: HashMap<[u8; 32], u64>,
+    // Updated contract balances, keyed by contract ids.
+    contracts: HashMap<[u8; 32], u64>,
+}
+
+/// Directory for the rollup balances of accounts and contracts.
+pub struct BalanceDirectory {
+    // In-memory account balances, keyed by account keys.
+    account_balances: HashMap<[u8; 32], u64>,
+    // In-memory contract balances, keyed by contract ids.
+    contract_balances: HashMap<[u8; 32], u64>,
+    // In-storage db.
+    db: sled::Db,
+}
+
+impl BalanceDirectory {
+    pub fn new(chain: Chain) -> Option<BALANCE_DIRECTORY> {
+        let path = format!("{}/{}/{}", "db", chain.to_string(), "dir/balance");
+        let db = sled::open(path).ok()?;
+
+        let mut account_balances = HashMap::<[u8; 32], u64>::new();
+        let mut contract_balances = HashMap::<[u8; 32], u64>::new();
+
+        for lookup in db.iter() {
+            if let Ok((key, val)) = lookup {
+                if key.len() != 33 {
+                    return None;
+                }
+
+                let id: [u8; 32] = (&key[1..]).try_into().ok()?;
+                let balance = u64::from_be_bytes(val.as_ref().try_into().ok()?);
+
+                match key[0] {
+                    ACCOUNT_BALANCE_PREFIX => account_balances.insert(id, balance),
+                    CONTRACT_BALANCE_PREFIX => contract_balances.insert(id, balance),
+                    _ => return None,
+                };
+            }
+        }
+
+        let balance_dir = BalanceDirectory {
+            account_balances,
+            contract_balances,
+            db,
+        };
+
+        Some(Arc::new(Mutex::new(balance_dir)))
+    }
+
+    /// Creates an empty balance directory that is not persisted, for tests.
+    pub fn new_temporary() -> Option<BALANCE_DIRECTORY> {
+        let balance_dir = BalanceDirectory {
+            account_balances: HashMap::new(),
+            contract_balances: HashMap::new(),
+            db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(balance_dir)))
+    }
+
+    /// Returns the balance of the given account key.
+    pub fn account_balance(&self, account_key: Point) -> u64 {
+        self.account_balances
+            .get(&account_key.serialize_xonly())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the balance of the given contract id.
+    pub fn contract_balance(&self, contract_id: [u8; 32]) -> u64 {
+        self.contract_balances
+            .get(&contract_id)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns all non-zero account balances, keyed by account keys.
+    pub fn account_balances(&self) -> HashMap<[u8; 32], u64> {
+        self.account_balances.clone()
+    }
+
+    /// Returns all non-zero contract balances, keyed by contract ids.
+    pub fn contract_balances(&self) -> HashMap<[u8; 32], u64> {
+        self.contract_balances.clone()
+    }
+
+    /// Returns whether the entries can be applied, in order, to the current balances.
+    pub fn validate_entries(&self, entries: &Vec<Entry>) -> bool {
+        self.balance_updates(entries).is_some()
+    }
+
+    /// Applies the entries of a session to the balances in a single batch.
+    ///
+    /// Liftups, recharges, revives and claims credit the entry account, while moves, calls, deploys and swapouts spend from it.
+    /// Either all entries are applied, or none is; returns `false` if any entry overdraws a balance.
+    pub fn apply_entries(&mut self, entries: &Vec<Entry>) -> bool {
+        match self.balance_updates(entries) {
+            Some(updates) => self.write_updates(updates),
+            None => false,
+        }
+    }
+
+    /// Returns the current account and contract balances that the entries would update.
+    /// These are the balances to restore in order to revert the entries.
+    pub fn prior_balances(&self, entries: &Vec<Entry>) -> Option<PriorBalances> {
+        let updates = self.balance_updates(entries)?;
+
+        let accounts = updates
+            .accounts
+            .keys()
+            .map(|key| (*key, self.account_balances.get(key).copied().unwrap_or(0)))
+            .collect();
+        let contracts = updates
+            .contracts
+            .keys()
+            .map(|id| (*id, self.contract_balances.get(id).copied().unwrap_or(0)))
+            .collect();
+
+        Some((accounts, contracts))
+    }
+
+    /// Restores the given account and contract balances in a single batch.
+    pub fn restore_balances(&mut self, prior_balances: &PriorBalances) -> bool {
+        let (accounts, contracts) = prior_balances;
+
+        let updates = BalanceUpdates {
+            accounts: accounts.iter().copied().collect(),
+            contracts: contracts.iter().copied().collect(),
+        };
+
+        self.write_updates(updates)
+    }
+
+    /// Writes the balance updates in-storage atomically, then in-memory.
+    fn write_updates(&mut self, updates: BalanceUpdates) -> bool {
+        // Update in-storage atomically.
+        let mut batch = sled::Batch::default();
+        for (prefix, balances) in [
+            (ACCOUNT_BALANCE_PREFIX, &updates.accounts),
+            (CONTRACT_BALANCE_PREFIX, &updates.contracts),
+        ] {
+            for (id, balance) in balances.iter() {
+                let mut key = vec![prefix];
+                key.extend(id);
+
+                match balance {
+                    0 => batch.remove(key),
+                    _ => batch.insert(key, balance.to_be_bytes().to_vec()),
+                }
+            }
+        }
+
+        if self.db.apply_batch(batch).is_err() {
+            return false;
+        }
+
+        // Update in-memory.
+        for (balances, updated) in [
+            (&mut self.account_balances, updates.accounts),
+            (&mut self.contract_balances, updates.contracts),
+        ] {
+            for (id, balance) in updated {
+                match balance {
+                    0 => balances.remove(&id),
+                    _ => balances.insert(id, balance),
+                };
+            }
+        }
+
+        true
+    }
+
+    /// Computes the balance updates of the entries, applied in order.
+    /// Returns `None` if any entry overdraws a balance.
+    fn balance_updates(&self, entries: &Vec<Entry>) -> Option<BalanceUpdates> {
+        let mut updates = BalanceUpdates::default();
+
+        for entry in entries.iter() {
+            self.apply_entry(&mut updates, entry)?;
+        }
+
+        Some(updates)
+    }
+
+    /// Applies a single entry on top of the given updates.
+    fn apply_entry(&self, updates: &mut BalanceUpdates, entry: &Entry) -> Option<()> {
+        let account_key = entry.account().key().serialize_xonly();
+
+        // Credit the lifted value.
+        if let Some(liftup) = entry.liftup() {
+            for lift in liftup.lifts() {
+                self.credit_account(updates, account_key, lift.value()?)?;
+            }
+        }
+
+        // Credit the recharged value.
+        if let Some(recharge) = entry.recharge() {
+            for vtxo in recharge.vtxos() {
+                self.credit_account(updates, account_key, vtxo.value()?)?;
+            }
+        }
+
+        match entry.main_combinator() {
+            Some(Combinator::Move(r#move)) => {
+                let amount = r#move.amount() as u64;
+                self.debit_account(updates, r#move.from().key().serialize_xonly(), amount)?;
+                self.credit_account(updates, r#move.to().key().serialize_xonly(), amount)?;
+            }
+            Some(Combinator::BatchMove(batch_move)) => {
+                // Load the touched balances, then apply the payments all at once.
+                let mut touched = vec![batch_move.from()];
+                touched.extend(batch_move.recipients());
+                for account in touched {
+                    let key = account.key().serialize_xonly();
+                    let balance = self.updated_account_balance(updates, key);
+                    updates.accounts.insert(key, balance);
+                }
+
+                if !batch_move.apply(&mut updates.accounts) {
+                    return None;
+                }
+            }
+            Some(Combinator::Call(call)) => {
+                // Payables move from the caller to the contract.
+                let mut amount: u64 = 0;
+                for element in call.calldata() {
+                    if let CallElement::Payable(payable) = element {
+                        amount = amount.checked_add(payable.value() as u64)?;
+                    }
+                }
+
+                if amount > 0 {
+                    let contract_id = call.contract().contract_id();
+                    self.debit_account(updates, call.from().key().serialize_xonly(), amount)?;
+
+                    let balance = self.updated_contract_balance(updates, contract_id);
+                    updates
+                        .contracts
+                        .insert(contract_id, balance.checked_add(amount)?);
+                }
+            }
+            Some(Combinator::Deploy(deploy)) => {
+                // The balance seed moves from the deployer to the contract.
+                let amount = deploy.balance_seed() as u64;
+                if amount > 0 {
+                    let contract_id = deploy.contract_id();
+                    self.debit_account(updates, deploy.deployer().key().serialize_xonly(), amount)?;
+
+                    let balance = self.updated_contract_balance(updates, contract_id);
+                    updates
+                        .contracts
+                        .insert(contract_id, balance.checked_add(amount)?);
+                }
+            }
+            Some(Combinator::Swapout(swapout)) => {
+                let key = swapout.account().key().serialize_xonly();
+                self.debit_account(updates, key, swapout.amount() as u64)?;
+            }
+            Some(Combinator::Revive(revive)) => {
+                let key = revive.account().key().serialize_xonly();
+                self.credit_account(updates, key, revive.value())?;
+            }
+            Some(Combinator::Claim(claim)) => {
+                let key = claim.account().key().serialize_xonly();
+                self.credit_account(updates, key, claim.value())?;
+            }
+            // Liquidity is tracked by the LP directory, not by the rollup balances.
+            Some(Combinator::Add(_)) | Some(Combinator::Sub(_)) => {}
+            // Liftups and recharges are credited above, as they belong to the uppermost left branch.
+            Some(Combinator::Liftup(_)) | Some(Combinator::Recharge(_)) => {}
+            // Reserved fails the entry.
+            Some(Combinator::Reserved(_)) => return None,
+            // Nop entries carry no main combinator.
+            None => {}
+        }
+
+        Some(())
+    }
+
+    /// Returns the account balance with the updates applied.
+    fn updated_account_balance(&self, updates: &BalanceUpdates, account_key: [u8; 32]) -> u64 {
+        match updates.accounts.get(&account_key) {
+            Some(balance) => *balance,
+            None => self
+                .account_balances
+                .get(&account_key)
+                .copied()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Returns the contract balance with the updates applied.
+    fn updated_contract_balance(&self, updates: &BalanceUpdates, contract_id: [u8; 32]) -> u64 {
+        match updates.contracts.get(&contract_id) {
+            Some(balance) => *balance,
+            None => self
+                .contract_balances
+                .get(&contract_id)
+                .copied()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Credits an account on top of the given updates.
+    fn credit_account(
+        &self,
+        updates: &mut BalanceUpdates,
+        account_key: [u8; 32],
+        amount: u64,
+    ) -> Option<()> {
+        let balance = self.updated_account_balance(updates, account_key);
+        updates
+            .accounts
+            .insert(account_key, balance.checked_add(amount)?);
+
+        Some(())
+    }
+
+    /// Debits an account on top of the given updates.
+    fn debit_account(
+        &self,
+        updates: &mut BalanceUpdates,
+        account_key: [u8; 32],
+        amount: u64,
+    ) -> Option<()> {
+        let balance = self.updated_account_balance(updates, account_key);
+        updates
+            .accounts
+            .insert(account_key, balance.checked_sub(amount)?);
+
+        Some(())
+    }
+}
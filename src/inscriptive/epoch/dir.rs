@@ -51,6 +51,16 @@ impl EpochDirectory {
         Some(Arc::new(Mutex::new(epoch_dir)))
     }
 
+    /// Creates an empty epoch directory that is not persisted, for tests.
+    pub fn new_temporary() -> Option<EPOCH_DIRECTORY> {
+        let epoch_dir = EpochDirectory {
+            epochs: HashMap::new(),
+            db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(epoch_dir)))
+    }
+
     pub fn insert_epoch(&mut self, epoch: &Epoch) -> bool {
         let height = epoch.height();
 
@@ -1,4 +1,6 @@
+pub mod archive;
 pub mod baked;
+pub mod balance;
 pub mod blacklist;
 pub mod epoch;
 pub mod lp;
@@ -6,4 +8,7 @@ pub mod registery;
 pub mod repo;
 pub mod rollup;
 pub mod set;
+pub mod state;
+pub mod storage;
+pub mod undo;
 pub mod wallet;
@@ -109,6 +109,18 @@ impl AccountRegistery {
         Some(Arc::new(Mutex::new(registery)))
     }
 
+    /// Creates an empty account registery that is not persisted, for tests.
+    pub fn new_temporary() -> Option<ACCOUNT_REGISTERY> {
+        let registery = AccountRegistery {
+            accounts: HashMap::new(),
+            accounts_db: sled::Config::new().temporary(true).open().ok()?,
+            call_counters: HashMap::new(),
+            call_counters_db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(registery)))
+    }
+
     /// Sorts the call counters.
     fn sort_call_counters(
         call_counters: &HashMap<REGISTERY_INDEX, CALL_COUNTER>,
@@ -228,6 +240,11 @@ impl AccountRegistery {
         self.accounts.get(&rank).map(|account| account.to_owned())
     }
 
+    /// Returns all registered accounts.
+    pub fn accounts(&self) -> Vec<Account> {
+        self.accounts.values().cloned().collect()
+    }
+
     /// Returns the current registery index height.
     pub fn registery_index_height(&self) -> u32 {
         self.accounts.keys().max().unwrap_or(&0).to_owned()
@@ -292,6 +309,62 @@ impl AccountRegistery {
         true
     }
 
+    /// Removes the given account from the registery.
+    fn remove_account(&mut self, key: Point) -> bool {
+        // Find the account by key.
+        let (rank, registery_index) = match self
+            .accounts
+            .iter()
+            .find(|(_, account)| account.key() == key)
+        {
+            Some((rank, account)) => match account.registery_index() {
+                Some(registery_index) => (rank.to_owned(), registery_index),
+                None => return false,
+            },
+            None => return false,
+        };
+
+        // Remove from the in-memory accounts list.
+        self.accounts.remove(&rank);
+
+        // Remove from the in-storage accounts db.
+        if let Err(_) = self.accounts_db.remove(&key.serialize_xonly()) {
+            return false;
+        }
+
+        // Remove from the in-memory call counters list.
+        self.call_counters.remove(&registery_index);
+
+        // Remove from the in-storage call counters db.
+        if let Err(_) = self.call_counters_db.remove(&registery_index.to_le_bytes()) {
+            return false;
+        }
+
+        true
+    }
+
+    // Decrements the call counter for the given account.
+    fn decrement_call_counter(&mut self, registery_index: u32, decrement_by: u64) -> bool {
+        // Update the call counter in-memory, and return the new call counter.
+        let new_call_counter = match self.call_counters.get_mut(&registery_index) {
+            Some(counter) => {
+                *counter = counter.saturating_sub(decrement_by);
+                *counter
+            }
+            None => return false,
+        };
+
+        // Update the call counter in-storage.
+        if let Err(_) = self.call_counters_db.insert(
+            &registery_index.to_le_bytes(),
+            new_call_counter.to_le_bytes().to_vec(),
+        ) {
+            return false;
+        }
+
+        true
+    }
+
     /// Updates the registery in a single batch operation.
     /// This is the only public operation that can be used to write/update the contract registery.
     pub fn batch_update(
@@ -350,4 +423,42 @@ impl AccountRegistery {
 
         true
     }
+
+    /// Reverts a `batch_update` in a single batch operation.
+    /// Only used to roll back the registery when a synced block is reorged out.
+    pub fn batch_revert(
+        &mut self,
+        // List of accounts registered by the reverted update.
+        accounts_to_deregister: Vec<Point>,
+        // List of accounts called by the reverted update and the number of times that they were called.
+        accounts_called: HashMap<Account, u64>,
+    ) -> bool {
+        // Decrement the call counter for the given accounts.
+        for (account, num_times_called) in accounts_called {
+            // Get the registery index of the account.
+            let registery_index = match account.registery_index() {
+                Some(index) => index,
+                None => return false,
+            };
+
+            // Decrement the call counter.
+            if !self.decrement_call_counter(registery_index, num_times_called) {
+                return false;
+            }
+        }
+
+        // Deregister the accounts.
+        for account_key in accounts_to_deregister {
+            if !self.remove_account(account_key) {
+                return false;
+            }
+        }
+
+        // Update the accounts ranks.
+        if !self.rank_accounts() {
+            return false;
+        }
+
+        true
+    }
 }
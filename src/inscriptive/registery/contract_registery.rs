@@ -108,6 +108,18 @@ impl ContractRegistery {
         Some(Arc::new(Mutex::new(registery)))
     }
 
+    /// Creates an empty contract registery that is not persisted, for tests.
+    pub fn new_temporary() -> Option<CONTRACT_REGISTERY> {
+        let registery = ContractRegistery {
+            contracts: HashMap::new(),
+            contracts_db: sled::Config::new().temporary(true).open().ok()?,
+            call_counters: HashMap::new(),
+            call_counters_db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(registery)))
+    }
+
     /// Sorts the call counters.
     fn sort_call_counters(
         call_counters: &HashMap<REGISTERY_INDEX, CALL_COUNTER>,
@@ -206,6 +218,11 @@ impl ContractRegistery {
             .map(|contract| contract.to_owned())
     }
 
+    /// Returns all registered contracts.
+    pub fn contracts(&self) -> Vec<Contract> {
+        self.contracts.values().cloned().collect()
+    }
+
     /// Returns the current registery index height.
     pub fn registery_index_height(&self) -> u32 {
         self.contracts.keys().max().unwrap_or(&0).to_owned()
@@ -267,6 +284,59 @@ impl ContractRegistery {
         true
     }
 
+    /// Removes the given contract from the registery.
+    fn remove_contract(&mut self, contract_id: [u8; 32]) -> bool {
+        // Find the contract by contract id.
+        let (rank, registery_index) = match self
+            .contracts
+            .iter()
+            .find(|(_, contract)| contract.contract_id() == contract_id)
+        {
+            Some((rank, contract)) => (rank.to_owned(), contract.registery_index()),
+            None => return false,
+        };
+
+        // Remove from the in-memory contracts list.
+        self.contracts.remove(&rank);
+
+        // Remove from the in-storage contracts db.
+        if let Err(_) = self.contracts_db.remove(&contract_id) {
+            return false;
+        }
+
+        // Remove from the in-memory call counters list.
+        self.call_counters.remove(&registery_index);
+
+        // Remove from the in-storage call counters db.
+        if let Err(_) = self.call_counters_db.remove(&registery_index.to_le_bytes()) {
+            return false;
+        }
+
+        true
+    }
+
+    // Decrements the call counter for the given contract.
+    fn decrement_call_counter(&mut self, registery_index: u32, decrement_by: u64) -> bool {
+        // Update the call counter in-memory, and return the new call counter.
+        let new_call_counter = match self.call_counters.get_mut(&registery_index) {
+            Some(counter) => {
+                *counter = counter.saturating_sub(decrement_by);
+                *counter
+            }
+            None => return false,
+        };
+
+        // Update the call counter in-storage.
+        if let Err(_) = self.call_counters_db.insert(
+            &registery_index.to_le_bytes(),
+            new_call_counter.to_le_bytes().to_vec(),
+        ) {
+            return false;
+        }
+
+        true
+    }
+
     /// Updates the registery in a single batch operation.
     /// This is the only public operation that can be used to write/update the contract registery.
     pub fn batch_update(
@@ -315,4 +385,35 @@ impl ContractRegistery {
 
         true
     }
+
+    /// Reverts a `batch_update` in a single batch operation.
+    /// Only used to roll back the registery when a synced block is reorged out.
+    pub fn batch_revert(
+        &mut self,
+        // List of contract IDs registered by the reverted update.
+        contracts_to_deregister: Vec<[u8; 32]>,
+        // List of contracts called by the reverted update and the number of times that they were called.
+        contracts_called: HashMap<Contract, u64>,
+    ) -> bool {
+        // Decrement the call counter for the given contracts.
+        for (contract, num_times_called) in contracts_called {
+            if !self.decrement_call_counter(contract.registery_index(), num_times_called) {
+                return false;
+            }
+        }
+
+        // Deregister the contracts.
+        for contract_id in contracts_to_deregister {
+            if !self.remove_contract(contract_id) {
+                return false;
+            }
+        }
+
+        // Update the contracts ranks.
+        if !self.rank_contracts() {
+            return false;
+        }
+
+        true
+    }
 }
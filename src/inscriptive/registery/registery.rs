@@ -28,6 +28,16 @@ impl Registery {
         Some(Arc::new(Mutex::new(registery)))
     }
 
+    /// Creates an empty registery that is not persisted, for tests.
+    pub fn new_temporary() -> Option<REGISTERY> {
+        let registery = Registery {
+            account_registery: AccountRegistery::new_temporary()?,
+            contract_registery: ContractRegistery::new_temporary()?,
+        };
+
+        Some(Arc::new(Mutex::new(registery)))
+    }
+
     pub fn account_registery(&self) -> ACCOUNT_REGISTERY {
         Arc::clone(&self.account_registery)
     }
@@ -0,0 +1,111 @@
+use crate::{
+    executive::program::{compiler::compiler::ProgramCompiler, program::Program},
+    operative::Chain,
+};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Guarded program repository.
+#[allow(non_camel_case_types)]
+pub type PROGRAM_REPO = Arc<Mutex<ProgramRepo>>;
+
+/// Contract id of a deployed program.
+type ContractId = [u8; 32];
+
+/// Repository for storing deployed contract programs.
+pub struct ProgramRepo {
+    // In-memory list of programs by contract id.
+    programs: HashMap<ContractId, Program>,
+    // In-storage db for storing the program bytecodes.
+    programs_db: sled::Db,
+}
+
+impl ProgramRepo {
+    pub fn new(chain: Chain) -> Option<PROGRAM_REPO> {
+        // Open the programs db.
+        let programs_db = {
+            let path = format!("{}/{}/{}", "db", chain.to_string(), "repo/program");
+            sled::open(path).ok()?
+        };
+
+        // Initialize the in-memory list of programs.
+        let mut programs = HashMap::<ContractId, Program>::new();
+
+        // Collect the programs from db.
+        for lookup in programs_db.iter() {
+            if let Ok((key, val)) = lookup {
+                // Key is the 32-byte contract id.
+                let contract_id: ContractId = key.as_ref().try_into().ok()?;
+
+                // Value is the program bytecode.
+                let program = Program::decompile(&mut val.iter().copied()).ok()?;
+
+                // Insert into the in-memory programs list.
+                programs.insert(contract_id, program);
+            }
+        }
+
+        // Construct the program repository.
+        let program_repo = ProgramRepo {
+            programs,
+            programs_db,
+        };
+
+        Some(Arc::new(Mutex::new(program_repo)))
+    }
+
+    /// Creates an empty program repository that is not persisted, for tests.
+    pub fn new_temporary() -> Option<PROGRAM_REPO> {
+        let program_repo = ProgramRepo {
+            programs: HashMap::new(),
+            programs_db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(program_repo)))
+    }
+
+    /// Returns the program of a given contract id.
+    pub fn program_by_contract_id(&self, contract_id: [u8; 32]) -> Option<Program> {
+        self.programs.get(&contract_id).cloned()
+    }
+
+    /// Returns true if a program is stored for the given contract id.
+    pub fn is_stored(&self, contract_id: [u8; 32]) -> bool {
+        self.programs.contains_key(&contract_id)
+    }
+
+    /// Inserts a program for a given contract id.
+    pub fn insert_program(&mut self, contract_id: [u8; 32], program: &Program) -> bool {
+        // Check if the program is already stored.
+        if self.is_stored(contract_id) {
+            return false;
+        }
+
+        // Compile the program into bytecode.
+        let bytecode = match program.compile() {
+            Ok(bytecode) => bytecode,
+            Err(_) => return false,
+        };
+
+        // Insert in-db.
+        if let Err(_) = self.programs_db.insert(&contract_id, bytecode) {
+            return false;
+        }
+
+        // Insert in-memory.
+        self.programs.insert(contract_id, program.to_owned());
+
+        true
+    }
+
+    /// Removes the program of a given contract id.
+    pub fn remove_program(&mut self, contract_id: [u8; 32]) -> bool {
+        // Remove in-db.
+        if let Err(_) = self.programs_db.remove(&contract_id) {
+            return false;
+        }
+
+        // Remove in-memory.
+        self.programs.remove(&contract_id).is_some()
+    }
+}
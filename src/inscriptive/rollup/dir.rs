@@ -1,3 +1,4 @@
+use super::pool_tip::PoolTip;
 use crate::operative::Chain;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -13,6 +14,8 @@ pub struct RollupDirectory {
     bitcoin_sync_height: u64,
     // Rollup sync height.
     rollup_sync_height: u64,
+    // Latest synced pool transaction.
+    pool_tip: Option<PoolTip>,
     // In-storage db.
     db: sled::Db,
 }
@@ -36,16 +39,36 @@ impl RollupDirectory {
             .and_then(|val| val.as_ref().try_into().ok().map(u64::from_be_bytes))
             .unwrap_or(0);
 
+        let pool_tip: Option<PoolTip> = db
+            .get(b"pool_tip")
+            .ok()
+            .flatten()
+            .and_then(|val| serde_json::from_slice(&val).ok());
+
         let rollup_dir = RollupDirectory {
             synced: false,
             bitcoin_sync_height,
             rollup_sync_height,
+            pool_tip,
             db,
         };
 
         Some(Arc::new(Mutex::new(rollup_dir)))
     }
 
+    /// Creates an empty rollup directory that is not persisted, for tests.
+    pub fn new_temporary() -> Option<ROLLUP_DIRECTORY> {
+        let rollup_dir = RollupDirectory {
+            synced: false,
+            bitcoin_sync_height: 0,
+            rollup_sync_height: 0,
+            pool_tip: None,
+            db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(rollup_dir)))
+    }
+
     pub fn set_synced(&mut self, synced: bool) {
         self.synced = synced;
     }
@@ -85,4 +108,21 @@ impl RollupDirectory {
             .db
             .insert(b"rollup_sync_height", height.to_be_bytes().to_vec());
     }
+
+    /// Returns the latest synced pool transaction.
+    pub fn pool_tip(&self) -> Option<PoolTip> {
+        self.pool_tip.clone()
+    }
+
+    /// Sets the latest synced pool transaction.
+    pub fn set_pool_tip(&mut self, pool_tip: Option<PoolTip>) {
+        // Update in-db.
+        let _ = match &pool_tip {
+            Some(pool_tip) => self.db.insert(b"pool_tip", pool_tip.serialize()),
+            None => self.db.remove(b"pool_tip"),
+        };
+
+        // Update in-memory.
+        self.pool_tip = pool_tip;
+    }
 }
@@ -0,0 +1,52 @@
+use bitcoin::{OutPoint, Transaction, TxOut};
+use serde::{Deserialize, Serialize};
+
+/// The latest synced `Pool Transaction`, whose payload is yet to be revealed by the next one.
+///
+/// The prevouts of its inputs are kept since they are spent from the UTXO set by the time
+/// the payload is revealed, and are needed to decode the payload's `Liftup`s.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PoolTip {
+    tx: Transaction,
+    prevouts: Vec<(OutPoint, TxOut)>,
+    // Bitcoin height the transaction is confirmed at.
+    bitcoin_height: u64,
+}
+
+impl PoolTip {
+    /// Creates a new `PoolTip`.
+    pub fn new(tx: Transaction, prevouts: Vec<(OutPoint, TxOut)>, bitcoin_height: u64) -> PoolTip {
+        PoolTip {
+            tx,
+            prevouts,
+            bitcoin_height,
+        }
+    }
+
+    /// Returns the pool transaction.
+    pub fn tx(&self) -> Transaction {
+        self.tx.clone()
+    }
+
+    /// Returns the prevouts of the pool transaction inputs.
+    pub fn prevouts(&self) -> Vec<(OutPoint, TxOut)> {
+        self.prevouts.clone()
+    }
+
+    /// Returns the Bitcoin height the pool transaction is confirmed at.
+    pub fn bitcoin_height(&self) -> u64 {
+        self.bitcoin_height
+    }
+
+    /// Returns the outpoint of the payload output, spent by the next pool transaction.
+    pub fn payload_outpoint(&self) -> OutPoint {
+        OutPoint::new(self.tx.compute_txid(), 0)
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        match serde_json::to_vec(self) {
+            Ok(bytes) => bytes,
+            Err(_) => vec![],
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod swept_set;
 pub mod utxo_set;
 pub mod vtxo_set;
 pub mod set;
\ No newline at end of file
@@ -1,4 +1,5 @@
 use super::{
+    swept_set::{SweptSet, SWEPT_SET},
     utxo_set::{UTXOSet, UTXO_SET},
     vtxo_set::{VTXOSet, VTXO_SET},
 };
@@ -10,10 +11,11 @@ use tokio::sync::Mutex;
 #[allow(non_camel_case_types)]
 pub type COIN_SET = Arc<Mutex<CoinSet>>;
 
-/// Set for storing bare and virtual coins (UTXOs and VTXOs).
+/// Set for storing bare and virtual coins (UTXOs and VTXOs), and swept VTXOs.
 pub struct CoinSet {
     utxo_set: UTXO_SET,
     vtxo_set: VTXO_SET,
+    swept_set: SWEPT_SET,
 }
 
 impl CoinSet {
@@ -25,7 +27,25 @@ impl CoinSet {
         // Construct vtxo set.
         let vtxo_set = VTXOSet::new(chain)?;
 
-        let set = CoinSet { utxo_set, vtxo_set };
+        // Construct swept set.
+        let swept_set = SweptSet::new(chain)?;
+
+        let set = CoinSet {
+            utxo_set,
+            vtxo_set,
+            swept_set,
+        };
+
+        Some(Arc::new(Mutex::new(set)))
+    }
+
+    /// Creates an empty coin set that is not persisted, for tests.
+    pub fn new_temporary() -> Option<COIN_SET> {
+        let set = CoinSet {
+            utxo_set: UTXOSet::new_temporary()?,
+            vtxo_set: VTXOSet::new_temporary()?,
+            swept_set: SweptSet::new_temporary()?,
+        };
 
         Some(Arc::new(Mutex::new(set)))
     }
@@ -39,4 +59,9 @@ impl CoinSet {
     pub fn vtxo_set(&self) -> VTXO_SET {
         Arc::clone(&self.vtxo_set)
     }
+
+    /// Returns the swept set.
+    pub fn swept_set(&self) -> SWEPT_SET {
+        Arc::clone(&self.swept_set)
+    }
 }
@@ -0,0 +1,147 @@
+use crate::{
+    constructive::{txn::ext::OutpointExt, txo::vtxo::VTXO},
+    operative::Chain,
+};
+use secp::Point;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Owner key of a swept VTXO.
+type AccountKey = Point;
+
+/// Guarded swept set.
+#[allow(non_camel_case_types)]
+pub type SWEPT_SET = Arc<Mutex<SweptSet>>;
+
+/// A lookup struct for storing the VTXOs swept by the operator along with their expired projectors.
+/// Swept VTXOs remain claimable by their accounts until claimed.
+pub struct SweptSet {
+    // In-memory swept set.
+    vtxos: HashMap<AccountKey, Vec<VTXO>>,
+    // In-storage swept set.
+    vtxos_db: sled::Db,
+}
+
+impl SweptSet {
+    /// Creates the SweptSet instance.
+    pub fn new(chain: Chain) -> Option<SWEPT_SET> {
+        // Collect swept VTXOs from db.
+        let vtxos_path = format!("{}/{}/{}", "db", chain.to_string(), "set/swept");
+        let vtxos_db = sled::open(vtxos_path).ok()?;
+
+        let mut swept_vtxos = HashMap::<AccountKey, Vec<VTXO>>::new();
+
+        // Load swept VTXOs from db.
+        for lookup in vtxos_db.iter() {
+            if let Ok((_, val)) = lookup {
+                // Deserialize VTXO.
+                let vtxo = serde_json::from_slice::<VTXO>(&val).ok()?;
+
+                // Insert VTXO to the account's swept set.
+                swept_vtxos
+                    .entry(vtxo.account_key())
+                    .or_default()
+                    .push(vtxo);
+            }
+        }
+
+        // Construct SweptSet instance.
+        let swept_set = SweptSet {
+            vtxos: swept_vtxos,
+            vtxos_db,
+        };
+
+        // Return the SweptSet instance.
+        Some(Arc::new(Mutex::new(swept_set)))
+    }
+
+    /// Creates an empty swept set that is not persisted, for tests.
+    pub fn new_temporary() -> Option<SWEPT_SET> {
+        let swept_set = SweptSet {
+            vtxos: HashMap::new(),
+            vtxos_db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(swept_set)))
+    }
+
+    /// Returns the swept VTXOs of a given account key.
+    pub fn swept_vtxos_by_account_key(&self, account_key: &Point) -> Vec<VTXO> {
+        match self.vtxos.get(account_key) {
+            Some(vtxos) => vtxos.clone(),
+            None => vec![],
+        }
+    }
+
+    /// Returns whether the given VTXO is in the swept set.
+    pub fn is_swept(&self, vtxo: &VTXO) -> bool {
+        match self.vtxos.get(&vtxo.account_key()) {
+            Some(vtxos) => vtxos.contains(vtxo),
+            None => false,
+        }
+    }
+
+    /// Inserts a swept VTXO to the swept set.
+    pub fn insert_vtxo(&mut self, vtxo: &VTXO) -> bool {
+        // Get VTXO's outpoint.
+        let vtxo_outpoint = match vtxo.outpoint() {
+            Some(outpoint) => outpoint,
+            None => return false,
+        };
+
+        // Return the account's swept set.
+        let account_swept_set = self.vtxos.entry(vtxo.account_key()).or_default();
+
+        // Check if the VTXO already exists.
+        if account_swept_set
+            .iter()
+            .any(|swept_vtxo| swept_vtxo.outpoint() == Some(vtxo_outpoint))
+        {
+            return false;
+        }
+
+        // Insert VTXO to the in-memory set.
+        account_swept_set.push(vtxo.to_owned());
+
+        // Insert VTXO to the in-storage set.
+        match self
+            .vtxos_db
+            .insert(&vtxo_outpoint.bytes_36(), vtxo.serialize())
+        {
+            Ok(_) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// Removes a swept VTXO from the swept set.
+    pub fn remove_vtxo(&mut self, vtxo: &VTXO) -> bool {
+        // Get VTXO's outpoint.
+        let vtxo_outpoint = match vtxo.outpoint() {
+            Some(outpoint) => outpoint,
+            None => return false,
+        };
+
+        // Return the account's swept set.
+        let account_swept_set = match self.vtxos.get_mut(&vtxo.account_key()) {
+            Some(set) => set,
+            None => return false,
+        };
+
+        // Make sure the VTXO exists.
+        if !account_swept_set
+            .iter()
+            .any(|swept_vtxo| swept_vtxo.outpoint() == Some(vtxo_outpoint))
+        {
+            return false;
+        }
+
+        // Remove VTXO from the in-memory set.
+        account_swept_set.retain(|swept_vtxo| swept_vtxo.outpoint() != Some(vtxo_outpoint));
+
+        // Remove VTXO from the in-storage set.
+        match self.vtxos_db.remove(&vtxo_outpoint.bytes_36()) {
+            Ok(_) => true,
+            Err(_) => false,
+        }
+    }
+}
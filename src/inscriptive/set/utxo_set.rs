@@ -58,6 +58,16 @@ impl UTXOSet {
         Some(Arc::new(Mutex::new(utxoset)))
     }
 
+    /// Creates an empty UTXO set that is not persisted, for tests.
+    pub fn new_temporary() -> Option<UTXO_SET> {
+        let utxoset = UTXOSet {
+            utxos: HashMap::new(),
+            utxos_db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(utxoset)))
+    }
+
     /// Returns the number of utxos in the set.
     pub fn num_utxos(&self) -> usize {
         self.utxos.len()
@@ -68,21 +78,26 @@ impl UTXOSet {
         self.utxos.get(outpoint).cloned()
     }
 
-    /// Inserts a txout into the set.
-    pub fn insert_txout(&mut self, outpoint: &OutPoint, txout: &TxOut) {
+    /// Inserts a txout into the set, returning whether it was newly inserted.
+    pub fn insert_txout(&mut self, outpoint: &OutPoint, txout: &TxOut) -> bool {
         // Insert txout into the in-memory set.
         if let None = self.utxos.insert(outpoint.clone(), txout.clone()) {
             // Insert txout into the in-storage set.
             let _ = self.utxos_db.insert(&outpoint.bytes_36(), txout.bytes());
+            return true;
         }
+
+        false
     }
 
-    /// Removes a txout from the set.
-    pub fn remove_txout(&mut self, outpoint: &OutPoint) {
+    /// Removes a txout from the set, returning the removed txout.
+    pub fn remove_txout(&mut self, outpoint: &OutPoint) -> Option<TxOut> {
         // Remove txout from the in-memory set.
-        if let Some(_) = self.utxos.remove(outpoint) {
-            // Remove txout from the in-storage set.
-            let _ = self.utxos_db.remove(&outpoint.bytes_36());
-        }
+        let txout = self.utxos.remove(outpoint)?;
+
+        // Remove txout from the in-storage set.
+        let _ = self.utxos_db.remove(&outpoint.bytes_36());
+
+        Some(txout)
     }
 }
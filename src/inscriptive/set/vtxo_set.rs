@@ -65,6 +65,16 @@ impl VTXOSet {
         Some(Arc::new(Mutex::new(vtxoset)))
     }
 
+    /// Creates an empty VTXO set that is not persisted, for tests.
+    pub fn new_temporary() -> Option<VTXO_SET> {
+        let vtxoset = VTXOSet {
+            vtxos: HashMap::new(),
+            vtxos_db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(vtxoset)))
+    }
+
     /// Returns the VTXO set of a given account.
     pub fn vtxo_set_by_account(&self, account: &Account) -> Option<Vec<VTXO>> {
         let account_key = account.key();
@@ -77,6 +87,11 @@ impl VTXOSet {
         Some(account_vtxo_set.clone())
     }
 
+    /// Returns all VTXOs in the set.
+    pub fn vtxos(&self) -> Vec<VTXO> {
+        self.vtxos.values().flatten().cloned().collect()
+    }
+
     /// Inserts a VTXO to the VTXO set.
     pub fn insert_vtxo(&mut self, vtxo: &VTXO) -> bool {
         // Get VTXO's account key.
@@ -0,0 +1,113 @@
+use super::tree::StateTree;
+use crate::{
+    inscriptive::{
+        balance::dir::BALANCE_DIRECTORY, registery::registery::REGISTERY, set::vtxo_set::VTXO_SET,
+        storage::dir::STORAGE_DIRECTORY,
+    },
+    operative::Chain,
+    transmutive::smt::smt::EMPTY_ROOT,
+};
+use std::{collections::BTreeMap, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Guarded state directory.
+#[allow(non_camel_case_types)]
+pub type STATE_DIRECTORY = Arc<Mutex<StateDirectory>>;
+
+/// Directory for the state roots by rollup height.
+pub struct StateDirectory {
+    // In-memory state roots by rollup height.
+    state_roots: BTreeMap<u64, [u8; 32]>,
+    // In-storage db.
+    db: sled::Db,
+}
+
+impl StateDirectory {
+    pub fn new(chain: Chain) -> Option<STATE_DIRECTORY> {
+        let path = format!("{}/{}/{}", "db", chain.to_string(), "dir/state");
+        let db = sled::open(path).ok()?;
+
+        let mut state_roots = BTreeMap::<u64, [u8; 32]>::new();
+
+        for lookup in db.iter() {
+            if let Ok((key, val)) = lookup {
+                let rollup_height = u64::from_be_bytes(key.as_ref().try_into().ok()?);
+                let state_root: [u8; 32] = val.as_ref().try_into().ok()?;
+
+                state_roots.insert(rollup_height, state_root);
+            }
+        }
+
+        let state_dir = StateDirectory { state_roots, db };
+
+        Some(Arc::new(Mutex::new(state_dir)))
+    }
+
+    /// Creates an empty state directory that is not persisted, for tests.
+    pub fn new_temporary() -> Option<STATE_DIRECTORY> {
+        let state_dir = StateDirectory {
+            state_roots: BTreeMap::new(),
+            db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(state_dir)))
+    }
+
+    /// Returns the state root at the given rollup height.
+    pub fn state_root(&self, rollup_height: u64) -> Option<[u8; 32]> {
+        self.state_roots.get(&rollup_height).copied()
+    }
+
+    /// Returns the latest state root, or the empty root if none is recorded yet.
+    pub fn latest_state_root(&self) -> [u8; 32] {
+        match self.state_roots.last_key_value() {
+            Some((_, state_root)) => *state_root,
+            None => EMPTY_ROOT,
+        }
+    }
+
+    /// Records the state root at the given rollup height.
+    pub fn insert_state_root(&mut self, rollup_height: u64, state_root: [u8; 32]) -> bool {
+        // Update in-storage.
+        if self
+            .db
+            .insert(rollup_height.to_be_bytes(), state_root.to_vec())
+            .is_err()
+        {
+            return false;
+        }
+
+        // Update in-memory.
+        self.state_roots.insert(rollup_height, state_root);
+
+        true
+    }
+
+    /// Removes the state root at the given rollup height.
+    pub fn remove_state_root(&mut self, rollup_height: u64) -> Option<[u8; 32]> {
+        // Remove in-storage.
+        let _ = self.db.remove(rollup_height.to_be_bytes());
+
+        // Remove in-memory.
+        self.state_roots.remove(&rollup_height)
+    }
+
+    /// Computes the state root from the current state, and records it at the given rollup height.
+    pub async fn commit_state_root(
+        &mut self,
+        rollup_height: u64,
+        balance_dir: &BALANCE_DIRECTORY,
+        registery: &REGISTERY,
+        storage_dir: &STORAGE_DIRECTORY,
+        vtxo_set: &VTXO_SET,
+    ) -> Option<[u8; 32]> {
+        let state_root = StateTree::from_state(balance_dir, registery, storage_dir, vtxo_set)
+            .await?
+            .root();
+
+        match self.insert_state_root(rollup_height, state_root) {
+            true => Some(state_root),
+            false => None,
+        }
+    }
+}
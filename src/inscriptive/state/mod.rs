@@ -0,0 +1,2 @@
+pub mod dir;
+pub mod tree;
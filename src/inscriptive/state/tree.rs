@@ -0,0 +1,218 @@
+use crate::{
+    constructive::txn::ext::OutpointExt,
+    inscriptive::{
+        balance::dir::BALANCE_DIRECTORY, registery::registery::REGISTERY, set::vtxo_set::VTXO_SET,
+        storage::dir::STORAGE_DIRECTORY,
+    },
+    transmutive::{
+        hash::{Hash, HashTag},
+        smt::{proof::SMTProof, smt::SparseMerkleTree},
+    },
+};
+use bitcoin::OutPoint;
+use secp::Point;
+
+/// A slot of the global rollup state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StateKey {
+    // Rollup balance of an account.
+    AccountBalance(Point),
+    // Registery index of an account.
+    AccountRegistery(Point),
+    // Rollup balance of a contract.
+    ContractBalance([u8; 32]),
+    // Registery index of a contract.
+    ContractRegistery([u8; 32]),
+    // Storage slot of a contract.
+    ContractStorage([u8; 32], Vec<u8>),
+    // A VTXO in the VTXO set.
+    VTXO(OutPoint),
+}
+
+impl StateKey {
+    /// Returns the tree key of the slot.
+    pub fn key(&self) -> [u8; 32] {
+        let mut preimage = Vec::<u8>::new();
+
+        match self {
+            StateKey::AccountBalance(account_key) => {
+                preimage.push(0x00);
+                preimage.extend(account_key.serialize_xonly());
+            }
+            StateKey::AccountRegistery(account_key) => {
+                preimage.push(0x01);
+                preimage.extend(account_key.serialize_xonly());
+            }
+            StateKey::ContractBalance(contract_id) => {
+                preimage.push(0x02);
+                preimage.extend(contract_id);
+            }
+            StateKey::ContractRegistery(contract_id) => {
+                preimage.push(0x03);
+                preimage.extend(contract_id);
+            }
+            StateKey::ContractStorage(contract_id, storage_key) => {
+                preimage.push(0x04);
+                preimage.extend(contract_id);
+                preimage.extend(storage_key);
+            }
+            StateKey::VTXO(outpoint) => {
+                preimage.push(0x05);
+                preimage.extend(outpoint.bytes_36());
+            }
+        }
+
+        preimage.hash(Some(HashTag::StateKey))
+    }
+}
+
+/// Returns the tree value hash of a state value.
+pub fn state_value_hash(value: &[u8]) -> [u8; 32] {
+    value.hash(Some(HashTag::StateValue))
+}
+
+/// Sparse Merkle tree committing to the global rollup state.
+///
+/// Values are the little-endian balances and registery indexes, the raw contract storage values,
+/// and the VTXO owner key followed by the VTXO value.
+#[derive(Clone, Default, Debug)]
+pub struct StateTree {
+    smt: SparseMerkleTree,
+}
+
+impl StateTree {
+    /// Creates an empty state tree.
+    pub fn new() -> StateTree {
+        StateTree {
+            smt: SparseMerkleTree::new(),
+        }
+    }
+
+    /// Builds the state tree from the balance directory, the registery, the storage directory and the VTXO set.
+    pub async fn from_state(
+        balance_dir: &BALANCE_DIRECTORY,
+        registery: &REGISTERY,
+        storage_dir: &STORAGE_DIRECTORY,
+        vtxo_set: &VTXO_SET,
+    ) -> Option<StateTree> {
+        let mut tree = StateTree::new();
+
+        // Balances.
+        {
+            let _balance_dir = balance_dir.lock().await;
+
+            for (account_key, balance) in _balance_dir.account_balances() {
+                let mut xonly = vec![0x02];
+                xonly.extend(account_key);
+                let account_key = Point::from_slice(&xonly).ok()?;
+
+                tree.insert(
+                    &StateKey::AccountBalance(account_key),
+                    &balance.to_le_bytes(),
+                );
+            }
+
+            for (contract_id, balance) in _balance_dir.contract_balances() {
+                tree.insert(
+                    &StateKey::ContractBalance(contract_id),
+                    &balance.to_le_bytes(),
+                );
+            }
+        }
+
+        // Registery.
+        let (account_registery, contract_registery) = {
+            let _registery = registery.lock().await;
+            (
+                _registery.account_registery(),
+                _registery.contract_registery(),
+            )
+        };
+
+        {
+            let _account_registery = account_registery.lock().await;
+
+            for account in _account_registery.accounts() {
+                tree.insert(
+                    &StateKey::AccountRegistery(account.key()),
+                    &account.registery_index()?.to_le_bytes(),
+                );
+            }
+        }
+
+        {
+            let _contract_registery = contract_registery.lock().await;
+
+            for contract in _contract_registery.contracts() {
+                tree.insert(
+                    &StateKey::ContractRegistery(contract.contract_id()),
+                    &contract.registery_index().to_le_bytes(),
+                );
+            }
+        }
+
+        // Contract storage.
+        {
+            let _storage_dir = storage_dir.lock().await;
+
+            for (contract_id, storage) in _storage_dir.storages() {
+                for (storage_key, value) in storage {
+                    tree.insert(&StateKey::ContractStorage(contract_id, storage_key), &value);
+                }
+            }
+        }
+
+        // VTXO set.
+        {
+            let _vtxo_set = vtxo_set.lock().await;
+
+            for vtxo in _vtxo_set.vtxos() {
+                let mut value = vtxo.account_key().serialize_xonly().to_vec();
+                value.extend(vtxo.value()?.to_le_bytes());
+
+                tree.insert(&StateKey::VTXO(vtxo.outpoint()?), &value);
+            }
+        }
+
+        Some(tree)
+    }
+
+    /// Inserts or updates a state value.
+    pub fn insert(&mut self, state_key: &StateKey, value: &[u8]) {
+        self.smt.insert(state_key.key(), state_value_hash(value));
+    }
+
+    /// Removes a state value.
+    pub fn remove(&mut self, state_key: &StateKey) {
+        self.smt.remove(state_key.key());
+    }
+
+    /// Returns the state root.
+    pub fn root(&self) -> [u8; 32] {
+        self.smt.root()
+    }
+
+    /// Returns the inclusion or exclusion proof of a state slot.
+    pub fn proof(&self, state_key: &StateKey) -> SMTProof {
+        self.smt.proof(state_key.key())
+    }
+}
+
+/// Verifies that the state slot holds the value under the state root.
+pub fn verify_state_inclusion(
+    state_root: [u8; 32],
+    proof: &SMTProof,
+    state_key: &StateKey,
+    value: &[u8],
+) -> bool {
+    proof.verify_inclusion(state_root, state_key.key(), state_value_hash(value))
+}
+
+/// Verifies that the state slot is empty under the state root.
+pub fn verify_state_exclusion(
+    state_root: [u8; 32],
+    proof: &SMTProof,
+    state_key: &StateKey,
+) -> bool {
+    proof.verify_exclusion(state_root, state_key.key())
+}
@@ -0,0 +1,158 @@
+use crate::operative::Chain;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Guarded storage directory.
+#[allow(non_camel_case_types)]
+pub type STORAGE_DIRECTORY = Arc<Mutex<StorageDirectory>>;
+
+/// Contract id of a deployed contract.
+type ContractId = [u8; 32];
+
+/// Directory for the storage of deployed contracts.
+///
+/// Storage values are keyed by the contract id followed by the storage key.
+pub struct StorageDirectory {
+    // In-memory storage by contract id.
+    storages: HashMap<ContractId, HashMap<Vec<u8>, Vec<u8>>>,
+    // In-storage db.
+    db: sled::Db,
+}
+
+impl StorageDirectory {
+    pub fn new(chain: Chain) -> Option<STORAGE_DIRECTORY> {
+        let path = format!("{}/{}/{}", "db", chain.to_string(), "dir/storage");
+        let db = sled::open(path).ok()?;
+
+        let mut storages = HashMap::<ContractId, HashMap<Vec<u8>, Vec<u8>>>::new();
+
+        for lookup in db.iter() {
+            if let Ok((key, val)) = lookup {
+                if key.len() <= 32 {
+                    return None;
+                }
+
+                let contract_id: ContractId = key[..32].try_into().ok()?;
+
+                storages
+                    .entry(contract_id)
+                    .or_default()
+                    .insert(key[32..].to_vec(), val.to_vec());
+            }
+        }
+
+        let storage_dir = StorageDirectory { storages, db };
+
+        Some(Arc::new(Mutex::new(storage_dir)))
+    }
+
+    /// Creates an empty storage directory that is not persisted, for tests.
+    pub fn new_temporary() -> Option<STORAGE_DIRECTORY> {
+        let storage_dir = StorageDirectory {
+            storages: HashMap::new(),
+            db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(storage_dir)))
+    }
+
+    /// Returns the storage of the given contract id.
+    pub fn storage(&self, contract_id: [u8; 32]) -> HashMap<Vec<u8>, Vec<u8>> {
+        self.storages.get(&contract_id).cloned().unwrap_or_default()
+    }
+
+    /// Returns the storage of all contracts, keyed by contract ids.
+    pub fn storages(&self) -> HashMap<[u8; 32], HashMap<Vec<u8>, Vec<u8>>> {
+        self.storages.clone()
+    }
+
+    /// Returns the storage value of the given contract id and key.
+    pub fn value(&self, contract_id: [u8; 32], key: &[u8]) -> Option<Vec<u8>> {
+        self.storages.get(&contract_id)?.get(key).cloned()
+    }
+
+    /// Seeds the storage of a newly deployed contract.
+    /// Returns `false` if the contract already has storage.
+    pub fn seed_storage(&mut self, contract_id: [u8; 32], seed: &[(Vec<u8>, Vec<u8>)]) -> bool {
+        if self.storages.contains_key(&contract_id) {
+            return false;
+        }
+
+        // Update in-storage.
+        let mut batch = sled::Batch::default();
+        for (key, value) in seed.iter() {
+            batch.insert(storage_key(contract_id, key), value.to_owned());
+        }
+
+        if self.db.apply_batch(batch).is_err() {
+            return false;
+        }
+
+        // Update in-memory.
+        if !seed.is_empty() {
+            self.storages
+                .insert(contract_id, seed.iter().cloned().collect());
+        }
+
+        true
+    }
+
+    /// Writes back the storage changes of a succeeded execution.
+    /// Freed keys map to `None`.
+    pub fn update_storage(
+        &mut self,
+        contract_id: [u8; 32],
+        changes: &HashMap<Vec<u8>, Option<Vec<u8>>>,
+    ) -> bool {
+        // Update in-storage.
+        let mut batch = sled::Batch::default();
+        for (key, value) in changes.iter() {
+            match value {
+                Some(value) => batch.insert(storage_key(contract_id, key), value.to_owned()),
+                None => batch.remove(storage_key(contract_id, key)),
+            }
+        }
+
+        if self.db.apply_batch(batch).is_err() {
+            return false;
+        }
+
+        // Update in-memory.
+        let storage = self.storages.entry(contract_id).or_default();
+        for (key, value) in changes.iter() {
+            match value {
+                Some(value) => storage.insert(key.to_owned(), value.to_owned()),
+                None => storage.remove(key),
+            };
+        }
+
+        if storage.is_empty() {
+            self.storages.remove(&contract_id);
+        }
+
+        true
+    }
+
+    /// Removes the storage of the given contract id.
+    pub fn remove_storage(&mut self, contract_id: [u8; 32]) -> bool {
+        let storage = match self.storages.remove(&contract_id) {
+            Some(storage) => storage,
+            None => return true,
+        };
+
+        // Remove in-storage.
+        let mut batch = sled::Batch::default();
+        for key in storage.keys() {
+            batch.remove(storage_key(contract_id, key));
+        }
+
+        self.db.apply_batch(batch).is_ok()
+    }
+}
+
+/// Returns the storage key of the given contract id and key.
+fn storage_key(contract_id: [u8; 32], key: &[u8]) -> Vec<u8> {
+    let mut storage_key = contract_id.to_vec();
+    storage_key.extend(key);
+    storage_key
+}
@@ -0,0 +1,100 @@
+use super::journal::UndoJournal;
+use crate::{inscriptive::baked, operative::Chain};
+use std::{collections::BTreeMap, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Guarded undo directory.
+#[allow(non_camel_case_types)]
+pub type UNDO_DIRECTORY = Arc<Mutex<UndoDirectory>>;
+
+/// Directory for the processed block hashes and their undo journals by Bitcoin height.
+///
+/// Only the most recent `MAX_REORG_DEPTH` blocks are kept, which bounds how deep a reorg can be rolled back.
+pub struct UndoDirectory {
+    // In-memory undo journals by Bitcoin height.
+    journals: BTreeMap<u64, UndoJournal>,
+    // In-storage db.
+    db: sled::Db,
+}
+
+impl UndoDirectory {
+    pub fn new(chain: Chain) -> Option<UNDO_DIRECTORY> {
+        let path = format!("{}/{}/{}", "db", chain.to_string(), "dir/undo");
+        let db = sled::open(path).ok()?;
+
+        let mut journals = BTreeMap::<u64, UndoJournal>::new();
+
+        for lookup in db.iter() {
+            if let Ok((key, val)) = lookup {
+                let height = u64::from_be_bytes(key.as_ref().try_into().ok()?);
+                let journal: UndoJournal = serde_json::from_slice(&val).ok()?;
+
+                journals.insert(height, journal);
+            }
+        }
+
+        let undo_dir = UndoDirectory { journals, db };
+
+        Some(Arc::new(Mutex::new(undo_dir)))
+    }
+
+    /// Creates an empty undo directory that is not persisted, for tests.
+    pub fn new_temporary() -> Option<UNDO_DIRECTORY> {
+        let undo_dir = UndoDirectory {
+            journals: BTreeMap::new(),
+            db: sled::Config::new().temporary(true).open().ok()?,
+        };
+
+        Some(Arc::new(Mutex::new(undo_dir)))
+    }
+
+    /// Returns the hash of the block processed at the given height.
+    pub fn block_hash(&self, height: u64) -> Option<[u8; 32]> {
+        self.journals
+            .get(&height)
+            .map(|journal| journal.block_hash())
+    }
+
+    /// Returns the undo journal of the block processed at the given height.
+    pub fn journal(&self, height: u64) -> Option<UndoJournal> {
+        self.journals.get(&height).cloned()
+    }
+
+    /// Records the undo journal of the block processed at the given height,
+    /// pruning the journals that fall out of the reorg depth.
+    pub fn insert_journal(&mut self, height: u64, journal: &UndoJournal) -> bool {
+        // Update in-storage.
+        if self
+            .db
+            .insert(height.to_be_bytes(), journal.serialize())
+            .is_err()
+        {
+            return false;
+        }
+
+        // Update in-memory.
+        self.journals.insert(height, journal.to_owned());
+
+        // Prune stale journals.
+        let stale_heights: Vec<u64> = self
+            .journals
+            .range(..height.saturating_sub(baked::MAX_REORG_DEPTH - 1))
+            .map(|(height, _)| *height)
+            .collect();
+
+        for stale_height in stale_heights {
+            self.remove_journal(stale_height);
+        }
+
+        true
+    }
+
+    /// Removes the undo journal of the block processed at the given height.
+    pub fn remove_journal(&mut self, height: u64) -> Option<UndoJournal> {
+        // Remove in-storage.
+        let _ = self.db.remove(height.to_be_bytes());
+
+        // Remove in-memory.
+        self.journals.remove(&height)
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{
+    constructive::{
+        entity::{account::Account, contract::Contract},
+        txo::{lift::Lift, vtxo::VTXO},
+    },
+    inscriptive::{
+        archive::dir::ARCHIVE_DIRECTORY,
+        balance::dir::{PriorBalances, BALANCE_DIRECTORY},
+        registery::registery::REGISTERY,
+        repo::repo::PROGRAM_REPO,
+        rollup::{dir::ROLLUP_DIRECTORY, pool_tip::PoolTip},
+        set::set::COIN_SET,
+        state::dir::STATE_DIRECTORY,
+        storage::dir::STORAGE_DIRECTORY,
+        wallet::wallet::WALLET,
+    },
+};
+use bitcoin::{OutPoint, TxOut};
+use secp::Point;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A single store mutation made while syncing a block.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum UndoOp {
+    // A UTXO was inserted into the UTXO set.
+    UTXOInserted(OutPoint),
+    // A UTXO was removed from the UTXO set.
+    UTXORemoved(OutPoint, TxOut),
+    // A lift was inserted into the lift wallet.
+    LiftInserted(Lift),
+    // A lift was removed from the lift wallet.
+    LiftRemoved(Lift),
+    // A VTXO was inserted into the VTXO set.
+    VTXOInserted(VTXO),
+    // A VTXO was removed from the VTXO set.
+    VTXORemoved(VTXO),
+    // A VTXO was moved from the VTXO set to the swept set.
+    VTXOSwept(VTXO),
+    // A swept VTXO was claimed from the swept set.
+    SweptVTXOClaimed(VTXO),
+    // Accounts were registered, and registered accounts were called the given number of times.
+    AccountsRegistered(Vec<Point>, Vec<(Account, u64)>),
+    // Contracts were registered, and registered contracts were called the given number of times.
+    ContractsRegistered(Vec<[u8; 32]>, Vec<(Contract, u64)>),
+    // Balances were updated from the given prior balances.
+    BalancesUpdated(PriorBalances),
+    // The program of the given deployed contract was stored.
+    ProgramInserted([u8; 32]),
+    // The storage of the given deployed contract was seeded.
+    StorageSeeded([u8; 32]),
+    // A state root was recorded at the given rollup height.
+    StateRootInserted(u64),
+    // The rollup sync height was advanced from the given prior height.
+    RollupSyncHeightSet(u64),
+    // The pool tip was replaced, from the given prior pool tip.
+    PoolTipSet(Option<PoolTip>),
+    // The state was archived at the given rollup height.
+    StateArchived(u64),
+}
+
+/// Journal of the store mutations made while syncing a block, in the order they were made.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct UndoJournal {
+    // Hash of the synced block.
+    block_hash: [u8; 32],
+    // Mutations made while syncing the block.
+    ops: Vec<UndoOp>,
+}
+
+impl UndoJournal {
+    /// Creates an empty journal for the block.
+    pub fn new(block_hash: [u8; 32]) -> UndoJournal {
+        UndoJournal {
+            block_hash,
+            ops: Vec::new(),
+        }
+    }
+
+    /// Returns the hash of the synced block.
+    pub fn block_hash(&self) -> [u8; 32] {
+        self.block_hash
+    }
+
+    /// Returns the recorded mutations.
+    pub fn ops(&self) -> Vec<UndoOp> {
+        self.ops.clone()
+    }
+
+    /// Records a mutation.
+    pub fn record(&mut self, op: UndoOp) {
+        self.ops.push(op);
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        match serde_json::to_vec(self) {
+            Ok(bytes) => bytes,
+            Err(_) => vec![],
+        }
+    }
+
+    /// Reverts the recorded mutations in reverse order.
+    pub async fn revert(
+        &self,
+        coin_set: &COIN_SET,
+        wallet: Option<&WALLET>,
+        registery: &REGISTERY,
+        program_repo: &PROGRAM_REPO,
+        balance_dir: &BALANCE_DIRECTORY,
+        storage_dir: &STORAGE_DIRECTORY,
+        state_dir: &STATE_DIRECTORY,
+        rollup_dir: &ROLLUP_DIRECTORY,
+        archive_dir: Option<&ARCHIVE_DIRECTORY>,
+    ) {
+        let (utxo_set, vtxo_set, swept_set) = {
+            let _coin_set = coin_set.lock().await;
+            (
+                _coin_set.utxo_set(),
+                _coin_set.vtxo_set(),
+                _coin_set.swept_set(),
+            )
+        };
+
+        let (account_registery, contract_registery) = {
+            let _registery = registery.lock().await;
+            (
+                _registery.account_registery(),
+                _registery.contract_registery(),
+            )
+        };
+
+        let lift_wallet = match wallet {
+            Some(wallet) => {
+                let _wallet = wallet.lock().await;
+                Some(_wallet.lift_wallet())
+            }
+            None => None,
+        };
+
+        for op in self.ops.iter().rev() {
+            match op {
+                UndoOp::UTXOInserted(outpoint) => {
+                    let mut _utxo_set = utxo_set.lock().await;
+                    _utxo_set.remove_txout(outpoint);
+                }
+                UndoOp::UTXORemoved(outpoint, txout) => {
+                    let mut _utxo_set = utxo_set.lock().await;
+                    _utxo_set.insert_txout(outpoint, txout);
+                }
+                UndoOp::LiftInserted(lift) => {
+                    if let Some(lift_wallet) = &lift_wallet {
+                        let mut _lift_wallet = lift_wallet.lock().await;
+                        _lift_wallet.remove_lift(lift);
+                    }
+                }
+                UndoOp::LiftRemoved(lift) => {
+                    if let Some(lift_wallet) = &lift_wallet {
+                        let mut _lift_wallet = lift_wallet.lock().await;
+                        _lift_wallet.insert_lift(lift);
+                    }
+                }
+                UndoOp::VTXOInserted(vtxo) => {
+                    let mut _vtxo_set = vtxo_set.lock().await;
+                    _vtxo_set.remove_vtxo(vtxo);
+                }
+                UndoOp::VTXORemoved(vtxo) => {
+                    let mut _vtxo_set = vtxo_set.lock().await;
+                    _vtxo_set.insert_vtxo(vtxo);
+                }
+                UndoOp::VTXOSwept(vtxo) => {
+                    {
+                        let mut _swept_set = swept_set.lock().await;
+                        _swept_set.remove_vtxo(vtxo);
+                    }
+                    let mut _vtxo_set = vtxo_set.lock().await;
+                    _vtxo_set.insert_vtxo(vtxo);
+                }
+                UndoOp::SweptVTXOClaimed(vtxo) => {
+                    let mut _swept_set = swept_set.lock().await;
+                    _swept_set.insert_vtxo(vtxo);
+                }
+                UndoOp::AccountsRegistered(account_keys, accounts_called) => {
+                    let mut _account_registery = account_registery.lock().await;
+                    _account_registery.batch_revert(
+                        account_keys.to_owned(),
+                        accounts_called.iter().copied().collect::<HashMap<_, _>>(),
+                    );
+                }
+                UndoOp::ContractsRegistered(contract_ids, contracts_called) => {
+                    let mut _contract_registery = contract_registery.lock().await;
+                    _contract_registery.batch_revert(
+                        contract_ids.to_owned(),
+                        contracts_called.iter().copied().collect::<HashMap<_, _>>(),
+                    );
+                }
+                UndoOp::BalancesUpdated(prior_balances) => {
+                    let mut _balance_dir = balance_dir.lock().await;
+                    _balance_dir.restore_balances(prior_balances);
+                }
+                UndoOp::ProgramInserted(contract_id) => {
+                    let mut _program_repo = program_repo.lock().await;
+                    _program_repo.remove_program(*contract_id);
+                }
+                UndoOp::StorageSeeded(contract_id) => {
+                    let mut _storage_dir = storage_dir.lock().await;
+                    _storage_dir.remove_storage(*contract_id);
+                }
+                UndoOp::StateRootInserted(rollup_height) => {
+                    let mut _state_dir = state_dir.lock().await;
+                    _state_dir.remove_state_root(*rollup_height);
+                }
+                UndoOp::RollupSyncHeightSet(rollup_height) => {
+                    let mut _rollup_dir = rollup_dir.lock().await;
+                    _rollup_dir.set_rollup_sync_height(*rollup_height);
+                }
+                UndoOp::PoolTipSet(pool_tip) => {
+                    let mut _rollup_dir = rollup_dir.lock().await;
+                    _rollup_dir.set_pool_tip(pool_tip.to_owned());
+                }
+                UndoOp::StateArchived(rollup_height) => {
+                    if let Some(archive_dir) = archive_dir {
+                        let mut _archive_dir = archive_dir.lock().await;
+                        _archive_dir.remove_height(*rollup_height);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -43,6 +43,18 @@ impl LiftWallet {
         Some(Arc::new(Mutex::new(lift_wallet)))
     }
 
+    /// Creates an empty lift wallet that is not persisted, for modes that run without a wallet.
+    pub fn new_temporary() -> Option<LIFT_WALLET> {
+        let lifts_db = sled::Config::new().temporary(true).open().ok()?;
+
+        let lift_wallet = LiftWallet {
+            lifts: Vec::new(),
+            lifts_db,
+        };
+
+        Some(Arc::new(Mutex::new(lift_wallet)))
+    }
+
     pub fn lifts(&self) -> Vec<Lift> {
         self.lifts.clone()
     }
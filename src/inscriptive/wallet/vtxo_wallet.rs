@@ -2,7 +2,8 @@ use crate::{
     constructive::{txn::ext::OutpointExt, txo::vtxo::VTXO},
     operative::Chain,
 };
-use std::sync::Arc;
+use bitcoin::{OutPoint, Transaction};
+use std::{collections::HashMap, sync::Arc};
 use tokio::sync::Mutex;
 
 /// Guarded VTXO wallet.
@@ -15,6 +16,10 @@ pub struct VTXOWallet {
     vtxos: Vec<VTXO>,
     // In-storage VTXO set.
     vtxos_db: sled::Db,
+    // In memory virtual tree branches by VTXO outpoint.
+    branches: HashMap<OutPoint, Vec<Transaction>>,
+    // In-storage virtual tree branches.
+    branches_db: sled::Db,
 }
 
 impl VTXOWallet {
@@ -33,9 +38,26 @@ impl VTXOWallet {
             }
         }
 
+        // Collect virtual tree branches from db.
+
+        let branches_path = format!("{}/{}/{}", "db", chain.to_string(), "wallet/vtxo_branch");
+        let branches_db = sled::open(branches_path).ok()?;
+
+        let mut branches = HashMap::<OutPoint, Vec<Transaction>>::new();
+
+        for lookup in branches_db.iter() {
+            if let Ok((key, val)) = lookup {
+                let outpoint = OutPoint::from_bytes36(key.as_ref().try_into().ok()?)?;
+                let branch: Vec<Transaction> = serde_json::from_slice(&val).ok()?;
+                branches.insert(outpoint, branch);
+            }
+        }
+
         let wallet = VTXOWallet {
             vtxos: vtxo_set,
             vtxos_db,
+            branches,
+            branches_db,
         };
 
         Some(Arc::new(Mutex::new(wallet)))
@@ -94,10 +116,46 @@ impl VTXOWallet {
         // Remove in-memory.
         self.vtxos.remove(index);
 
+        // Remove the virtual tree branch, if any.
+        if self.branches.remove(&outpoint).is_some() {
+            if let Err(_) = self.branches_db.remove(&outpoint.bytes_36()) {
+                return false;
+            }
+        }
+
         // Remove in-db.
         match self.vtxos_db.remove(&outpoint.bytes_36()) {
             Ok(_) => return true,
             Err(_) => return false,
         }
     }
+
+    /// Returns the stored virtual tree branch of a VTXO, root first.
+    pub fn branch(&self, vtxo: &VTXO) -> Option<Vec<Transaction>> {
+        self.branches.get(&vtxo.outpoint()?).cloned()
+    }
+
+    /// Stores the signed virtual tree branch of a VTXO, root first.
+    pub fn insert_branch(&mut self, vtxo: &VTXO, branch: &Vec<Transaction>) -> bool {
+        let outpoint = match vtxo.outpoint() {
+            Some(outpoint) => outpoint,
+            None => return false,
+        };
+
+        // Serialize the branch.
+        let branch_bytes = match serde_json::to_vec(branch) {
+            Ok(bytes) => bytes,
+            Err(_) => return false,
+        };
+
+        // Insert in-db.
+        if let Err(_) = self.branches_db.insert(&outpoint.bytes_36(), branch_bytes) {
+            return false;
+        }
+
+        // Insert in-memory.
+        self.branches.insert(outpoint, branch.to_owned());
+
+        true
+    }
 }
@@ -17,7 +17,7 @@ fn main() {
         eprintln!(
             "{}",
             format!(
-                "Usage: {} <network> <mode> <rpc-url> <rpc-user> <rpc-password>",
+                "Usage: {} <network> <mode> <rpc-url> <rpc-user> <rpc-password> [--archive]",
                 args[0]
             )
             .red()
@@ -49,6 +49,16 @@ fn main() {
     // RPC args
     let rpc_holder = RPCHolder::new(args[3].to_owned(), args[4].to_owned(), args[5].to_owned());
 
+    // Archive mode arg, to keep the historical state by rollup height.
+    let archive = match args.get(6).map(|arg| arg.as_str()) {
+        Some("--archive") => true,
+        None => false,
+        Some(_) => {
+            println!("{}", "Invalid [--archive].".red());
+            return;
+        }
+    };
+
     // Key holder
     let key_holder = {
         println!("{}", "Enter nsec:".magenta());
@@ -92,8 +102,8 @@ fn main() {
 
     // Match run
     match operating_mode {
-        OperatingMode::Node => node::run(key_holder, network, rpc_holder),
-        OperatingMode::Operator => operator::run(key_holder, network, rpc_holder),
-        OperatingMode::Coordinator => coordinator::run(key_holder, network, rpc_holder),
+        OperatingMode::Node => node::run(key_holder, network, rpc_holder, archive),
+        OperatingMode::Operator => operator::run(key_holder, network, rpc_holder, archive),
+        OperatingMode::Coordinator => coordinator::run(key_holder, network, rpc_holder, archive),
     }
 }
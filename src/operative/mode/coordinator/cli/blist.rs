@@ -1,24 +1,30 @@
 use secp::Point;
 
-use crate::{constructive::entity::account::Account, inscriptive::blacklist::BLIST_DIRECTORY};
+use crate::{
+    constructive::entity::account::Account,
+    inscriptive::blacklist::BLIST_DIRECTORY,
+    operative::Chain,
+    transmutive::codec::entity_address::{is_npub, parse_account},
+};
 
 // blist <account> <until>
-pub async fn blist_command(parts: Vec<&str>, blacklist_dir: &BLIST_DIRECTORY) {
+pub async fn blist_command(chain: Chain, parts: Vec<&str>, blacklist_dir: &BLIST_DIRECTORY) {
     if parts.len() != 3 {
         return eprintln!("Incorrect usage.");
     }
 
     match parts.get(1) {
         Some(account_str) => {
-            let bytes: [u8; 32] = match hex::decode(account_str.to_owned()) {
-                Ok(bytes) => match bytes.try_into() {
-                    Ok(bytes) => bytes,
-                    Err(_) => return eprintln!("Invalid <account>."),
-                },
-                Err(_) => return eprintln!("Invalid <account>."),
+            if is_npub(account_str) {
+                return eprintln!("<account> is an npub. Use the account address instead.");
+            }
+
+            let (key_bytes, _) = match parse_account(chain, account_str) {
+                Some(account) => account,
+                None => return eprintln!("Invalid <account>."),
             };
 
-            let key = match Point::from_slice(&bytes) {
+            let key = match Point::from_slice(&key_bytes) {
                 Ok(point) => point,
                 Err(_) => return eprintln!("Invalid <account>."),
             };
@@ -9,6 +9,10 @@ use crate::communicative::rpc::bitcoin::rpcholder::RPCHolder;
 use crate::communicative::tcp;
 use crate::communicative::tcp::tcp::open_port;
 use crate::communicative::tcp::tcp::port_number;
+use crate::inscriptive::archive::dir::ArchiveDirectory;
+use crate::inscriptive::archive::dir::ARCHIVE_DIRECTORY;
+use crate::inscriptive::balance::dir::BalanceDirectory;
+use crate::inscriptive::balance::dir::BALANCE_DIRECTORY;
 use crate::inscriptive::blacklist::BlacklistDirectory;
 use crate::inscriptive::blacklist::BLIST_DIRECTORY;
 use crate::inscriptive::epoch::dir::EpochDirectory;
@@ -17,10 +21,18 @@ use crate::inscriptive::lp::dir::LPDirectory;
 use crate::inscriptive::lp::dir::LP_DIRECTORY;
 use crate::inscriptive::registery::registery::Registery;
 use crate::inscriptive::registery::registery::REGISTERY;
+use crate::inscriptive::repo::repo::ProgramRepo;
+use crate::inscriptive::repo::repo::PROGRAM_REPO;
 use crate::inscriptive::rollup::dir::RollupDirectory;
 use crate::inscriptive::rollup::dir::ROLLUP_DIRECTORY;
 use crate::inscriptive::set::set::CoinSet;
 use crate::inscriptive::set::set::COIN_SET;
+use crate::inscriptive::state::dir::StateDirectory;
+use crate::inscriptive::state::dir::STATE_DIRECTORY;
+use crate::inscriptive::storage::dir::StorageDirectory;
+use crate::inscriptive::storage::dir::STORAGE_DIRECTORY;
+use crate::inscriptive::undo::dir::UndoDirectory;
+use crate::inscriptive::undo::dir::UNDO_DIRECTORY;
 use crate::operative::mode::ccli;
 use crate::operative::mode::coordinator::dkgops::DKGOps;
 use crate::operative::session::ccontext::CContextRunner;
@@ -38,7 +50,7 @@ use std::io::{self, BufRead};
 use std::sync::Arc;
 
 #[tokio::main]
-pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
+pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder, archive: bool) {
     let mode = OperatingMode::Coordinator;
 
     // #1 Validate Bitcoin RPC.
@@ -85,6 +97,63 @@ pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
         }
     };
 
+    // Initialize undo directory.
+    let undo_dir: UNDO_DIRECTORY = match UndoDirectory::new(chain) {
+        Some(dir) => dir,
+        None => {
+            println!("{}", "Error initializing undo directory.".red());
+            return;
+        }
+    };
+
+    // Initialize program repository.
+    let program_repo: PROGRAM_REPO = match ProgramRepo::new(chain) {
+        Some(repo) => repo,
+        None => {
+            println!("{}", "Error initializing program repository.".red());
+            return;
+        }
+    };
+
+    // Initialize storage directory.
+    let storage_dir: STORAGE_DIRECTORY = match StorageDirectory::new(chain) {
+        Some(dir) => dir,
+        None => {
+            println!("{}", "Error initializing storage directory.".red());
+            return;
+        }
+    };
+
+    // Initialize balance directory.
+    let balance_dir: BALANCE_DIRECTORY = match BalanceDirectory::new(chain) {
+        Some(dir) => dir,
+        None => {
+            println!("{}", "Error initializing balance directory.".red());
+            return;
+        }
+    };
+
+    // Initialize state directory.
+    let state_dir: STATE_DIRECTORY = match StateDirectory::new(chain) {
+        Some(dir) => dir,
+        None => {
+            println!("{}", "Error initializing state directory.".red());
+            return;
+        }
+    };
+
+    // Initialize archive directory, if running in archive mode.
+    let archive_dir: Option<ARCHIVE_DIRECTORY> = match archive {
+        true => match ArchiveDirectory::new(chain) {
+            Some(dir) => Some(dir),
+            None => {
+                println!("{}", "Error initializing archive directory.".red());
+                return;
+            }
+        },
+        false => None,
+    };
+
     // #7 Initialize the coin set.
     let coin_set: COIN_SET = match CoinSet::new(chain) {
         Some(coin_set) => coin_set,
@@ -104,6 +173,12 @@ pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
         let registery = Arc::clone(&registery);
         let rollup_dir = Arc::clone(&rollup_dir);
         let coin_set = Arc::clone(&coin_set);
+        let program_repo = Arc::clone(&program_repo);
+        let storage_dir = Arc::clone(&storage_dir);
+        let balance_dir = Arc::clone(&balance_dir);
+        let state_dir = Arc::clone(&state_dir);
+        let archive_dir = archive_dir.as_ref().map(Arc::clone);
+        let undo_dir = Arc::clone(&undo_dir);
         tokio::spawn(async move {
             let _ = rollup_dir
                 .sync(
@@ -113,8 +188,14 @@ pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
                     &epoch_dir,
                     &lp_dir,
                     &registery,
+                    &program_repo,
+                    &storage_dir,
                     None,
                     &coin_set,
+                    &balance_dir,
+                    &state_dir,
+                    archive_dir.as_ref(),
+                    &undo_dir,
                 )
                 .await;
         });
@@ -186,8 +267,14 @@ pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
     };
 
     // #18 Construct CSession.
-    let csession_ctx: CSESSION_CTX =
-        CSessionCtx::construct(&dkg_manager, &peer_manager, &blacklist_dir, &registery);
+    let csession_ctx: CSESSION_CTX = CSessionCtx::construct(
+        &dkg_manager,
+        &peer_manager,
+        &blacklist_dir,
+        &registery,
+        &state_dir,
+        &rollup_dir,
+    );
 
     // #19 Run CSession.
     {
@@ -217,10 +304,17 @@ pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
     }
 
     // #21 Initialize CLI.
-    cli(&mut peer_manager, &mut dkg_manager, &mut blacklist_dir).await;
+    cli(
+        chain,
+        &mut peer_manager,
+        &mut dkg_manager,
+        &mut blacklist_dir,
+    )
+    .await;
 }
 
 pub async fn cli(
+    chain: Chain,
     peer_manager: &mut PEER_MANAGER,
     dkg_manager: &mut DKG_MANAGER,
     blacklist_dir: &mut BLIST_DIRECTORY,
@@ -254,7 +348,7 @@ pub async fn cli(
             "clear" => ccli::clear::clear_command(),
             "dkg" => ccli::dkg::dkg_command(parts, peer_manager, dkg_manager).await,
             "ops" => ccli::ops::ops_command(peer_manager).await,
-            "blist" => ccli::blist::blist_command(parts, blacklist_dir).await,
+            "blist" => ccli::blist::blist_command(chain, parts, blacklist_dir).await,
             _ => eprintln!("{}", format!("Unknown commmand.").yellow()),
         }
     }
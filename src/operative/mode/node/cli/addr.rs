@@ -2,7 +2,10 @@ use crate::{
     constructive::{taproot::P2TR, txo::lift::Lift},
     inscriptive::epoch::dir::EPOCH_DIRECTORY,
     operative::Chain,
-    transmutive::{codec::address::encode_p2tr, key::KeyHolder},
+    transmutive::{
+        codec::{address::encode_p2tr, entity_address::encode_account},
+        key::KeyHolder,
+    },
 };
 
 /// Returns the list of Taproot scriptpubkeys to scan.
@@ -29,11 +32,19 @@ pub async fn lift_address(
 
 // addr
 pub async fn addr_command(chain: Chain, epoch_dir: &EPOCH_DIRECTORY, key_holder: &KeyHolder) {
-    let npub = key_holder.npub();
+    // Off-chain account address, rather than the npub, which is a social identity.
+    let account_address =
+        match encode_account(chain, key_holder.public_key().serialize_xonly(), None) {
+            Some(address) => address,
+            None => "-".to_string(),
+        };
     let lift_address = match lift_address(chain, key_holder, epoch_dir).await {
         Some(address) => address,
         None => "-".to_string(),
     };
 
-    println!("off-chain : {}\non-chain  : {}", npub, lift_address);
+    println!(
+        "off-chain : {}\non-chain  : {}",
+        account_address, lift_address
+    );
 }
@@ -6,3 +6,4 @@ pub mod lift;
 pub mod r#move;
 pub mod npub;
 pub mod ping;
+pub mod vtxo;
@@ -1,5 +1,82 @@
-use crate::{communicative::peer::peer::PEER, inscriptive::wallet::wallet::WALLET};
+use crate::{
+    communicative::peer::peer::PEER,
+    constructive::{
+        entity::account::Account,
+        entry::{combinator::r#move::Move, entry::Entry, estimate::EntryEstimate},
+        valtype::maybe_common::common_table::CommonTable,
+    },
+    inscriptive::{rollup::dir::ROLLUP_DIRECTORY, wallet::wallet::WALLET},
+    operative::Chain,
+    transmutive::codec::entity_address::{is_npub, parse_account},
+};
 use secp::{Point, Scalar};
 
-// move <npub> <amount>
-pub async fn move_command(_coordinator: &PEER, _wallet: &WALLET, _sk: Scalar, _pk: Point) {}
+/// Default fee rate used to show the entry fee, in sat/vB.
+const DEFAULT_FEE_RATE: u64 = 1;
+
+// move <account> <amount> [<sat/vB>]
+pub async fn move_command(
+    chain: Chain,
+    _coordinator: &PEER,
+    _wallet: &WALLET,
+    rollup_dir: &ROLLUP_DIRECTORY,
+    _sk: Scalar,
+    pk: Point,
+    parts: Vec<&str>,
+) {
+    if parts.len() != 3 && parts.len() != 4 {
+        return eprintln!("Incorrect usage.");
+    }
+
+    if is_npub(parts[1]) {
+        return eprintln!("<account> is an npub. Use the account address instead.");
+    }
+
+    let to = match parse_account(chain, parts[1]) {
+        Some((key_bytes, _)) => match Point::from_slice(&key_bytes) {
+            Ok(point) => point,
+            Err(_) => return eprintln!("Invalid <account>."),
+        },
+        None => return eprintln!("Invalid <account>."),
+    };
+
+    let amount: u32 = match parts[2].parse() {
+        Ok(amount) => amount,
+        Err(_) => return eprintln!("Invalid <amount>."),
+    };
+
+    let fee_rate: u64 = match parts.get(3) {
+        Some(part) => match part.parse() {
+            Ok(fee_rate) => fee_rate,
+            Err(_) => return eprintln!("Invalid <sat/vB>."),
+        },
+        None => DEFAULT_FEE_RATE,
+    };
+
+    let (from, to) = match (Account::new(pk, None, None), Account::new(to, None, None)) {
+        (Some(from), Some(to)) => (from, to),
+        _ => return eprintln!("Invalid <account>."),
+    };
+
+    // Entries are priced with the common table active at the synced Bitcoin height.
+    let common_table = {
+        let _rollup_dir = rollup_dir.lock().await;
+        CommonTable::latest(_rollup_dir.bitcoin_sync_height())
+    };
+
+    // Ranks are not known to the wallet, so accounts are priced unranked.
+    // This is an upper bound; the coordinator prices the entry against the registery.
+    let entry = Entry::new_move(from, Move::new(from, to, amount), None, None);
+    let estimate = match EntryEstimate::from_entry(&entry, &common_table) {
+        Some(estimate) => estimate,
+        None => return eprintln!("Failed to estimate the entry."),
+    };
+
+    println!(
+        "Entry size: {} bits ({:.2} vB). Fee at {} sat/vB: up to {} sats.",
+        estimate.bits(),
+        estimate.vbytes(),
+        fee_rate,
+        estimate.fee(fee_rate)
+    );
+}
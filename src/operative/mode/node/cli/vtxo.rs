@@ -0,0 +1,119 @@
+use crate::{
+    constructive::txn::exit::{self_spk, ExitPackage},
+    inscriptive::wallet::wallet::WALLET,
+    transmutive::key::KeyHolder,
+};
+
+/// Lists the VTXOs in the wallet, or writes out a unilateral exit package.
+pub async fn vtxo_command(wallet: &WALLET, key_holder: &KeyHolder, parts: Vec<&str>) {
+    match parts.get(1) {
+        Some(part) => match part.to_owned() {
+            "list" => vtxo_list(wallet).await,
+            "exit" => vtxo_exit(wallet, key_holder, parts).await,
+            _ => eprintln!("Unknown command."),
+        },
+        None => eprintln!("Incorrect usage."),
+    }
+}
+
+async fn vtxo_list(wallet: &WALLET) {
+    let set = {
+        let vtxo_wallet = {
+            let _wallet = wallet.lock().await;
+            _wallet.vtxo_wallet()
+        };
+
+        let _vtxo_wallet = vtxo_wallet.lock().await;
+        _vtxo_wallet.vtxos()
+    };
+
+    // Create a vector of JSON values from each VTXO
+    let vtxo_jsons: Vec<serde_json::Value> = set.iter().map(|vtxo| vtxo.json()).collect();
+
+    // Create a JSON array from the vector
+    let json_array = serde_json::Value::Array(vtxo_jsons);
+
+    // Print the JSON array
+    match serde_json::to_string_pretty(&json_array) {
+        Ok(json) => println!("{}", json),
+        Err(_) => eprintln!("Error serializing VTXOs."),
+    }
+}
+
+/// Usage: vtxo exit <index> <sweep fee> <path>
+async fn vtxo_exit(wallet: &WALLET, key_holder: &KeyHolder, parts: Vec<&str>) {
+    let (index, sweep_fee, path) = match (
+        parts.get(2).and_then(|part| part.parse::<usize>().ok()),
+        parts.get(3).and_then(|part| part.parse::<u64>().ok()),
+        parts.get(4),
+    ) {
+        (Some(index), Some(sweep_fee), Some(path)) => (index, sweep_fee, path.to_owned()),
+        _ => {
+            eprintln!("Incorrect usage.");
+            return;
+        }
+    };
+
+    // Retrieve the VTXO and its virtual tree branch.
+    let (vtxo, branch) = {
+        let vtxo_wallet = {
+            let _wallet = wallet.lock().await;
+            _wallet.vtxo_wallet()
+        };
+
+        let _vtxo_wallet = vtxo_wallet.lock().await;
+        let vtxo = match _vtxo_wallet.vtxos().get(index) {
+            Some(vtxo) => vtxo.to_owned(),
+            None => {
+                eprintln!("VTXO not found.");
+                return;
+            }
+        };
+        let branch = match _vtxo_wallet.branch(&vtxo) {
+            Some(branch) => branch,
+            None => {
+                eprintln!("Virtual tree branch not found.");
+                return;
+            }
+        };
+
+        (vtxo, branch)
+    };
+
+    // Sweep to the account's own key.
+    let sweep_spk = match self_spk(key_holder.public_key()) {
+        Some(spk) => spk,
+        None => {
+            eprintln!("Error constructing the sweep output.");
+            return;
+        }
+    };
+
+    let package = match ExitPackage::new(
+        &vtxo,
+        &branch,
+        key_holder.secret_key_bytes(),
+        sweep_spk,
+        sweep_fee,
+    ) {
+        Some(package) => package,
+        None => {
+            eprintln!("Error constructing the exit package.");
+            return;
+        }
+    };
+
+    // Write the package out for later broadcast.
+    let json = match serde_json::to_string_pretty(&package.json()) {
+        Ok(json) => json,
+        Err(_) => {
+            eprintln!("Error serializing the exit package.");
+            return;
+        }
+    };
+
+    match std::fs::write(path, json) {
+        Ok(_) => println!("Exit package written to {}.", path),
+        Err(_) => eprintln!("Error writing the exit package."),
+    }
+}
@@ -6,6 +6,10 @@ use crate::communicative::peer::peer::PEER;
 use crate::communicative::rpc::bitcoin::rpc::validate_rpc;
 use crate::communicative::rpc::bitcoin::rpcholder::RPCHolder;
 use crate::constructive::entity::account::Account;
+use crate::inscriptive::archive::dir::ArchiveDirectory;
+use crate::inscriptive::archive::dir::ARCHIVE_DIRECTORY;
+use crate::inscriptive::balance::dir::BalanceDirectory;
+use crate::inscriptive::balance::dir::BALANCE_DIRECTORY;
 use crate::inscriptive::epoch::dir::EpochDirectory;
 use crate::inscriptive::epoch::dir::EPOCH_DIRECTORY;
 use crate::inscriptive::lp::dir::LPDirectory;
@@ -13,10 +17,18 @@ use crate::inscriptive::lp::dir::LP_DIRECTORY;
 use crate::inscriptive::registery::account_registery::ACCOUNT_REGISTERY;
 use crate::inscriptive::registery::registery::Registery;
 use crate::inscriptive::registery::registery::REGISTERY;
+use crate::inscriptive::repo::repo::ProgramRepo;
+use crate::inscriptive::repo::repo::PROGRAM_REPO;
 use crate::inscriptive::rollup::dir::RollupDirectory;
 use crate::inscriptive::rollup::dir::ROLLUP_DIRECTORY;
 use crate::inscriptive::set::set::CoinSet;
 use crate::inscriptive::set::set::COIN_SET;
+use crate::inscriptive::state::dir::StateDirectory;
+use crate::inscriptive::state::dir::STATE_DIRECTORY;
+use crate::inscriptive::storage::dir::StorageDirectory;
+use crate::inscriptive::storage::dir::STORAGE_DIRECTORY;
+use crate::inscriptive::undo::dir::UndoDirectory;
+use crate::inscriptive::undo::dir::UNDO_DIRECTORY;
 use crate::inscriptive::wallet::wallet::Wallet;
 use crate::inscriptive::wallet::wallet::WALLET;
 use crate::operative::mode::ncli;
@@ -30,7 +42,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 #[tokio::main]
-pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
+pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder, archive: bool) {
     let _operating_mode = OperatingMode::Node;
 
     // #1 Validate Bitcoin RPC.
@@ -95,6 +107,63 @@ pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
         }
     };
 
+    // Initialize undo directory.
+    let undo_dir: UNDO_DIRECTORY = match UndoDirectory::new(chain) {
+        Some(dir) => dir,
+        None => {
+            println!("{}", "Error initializing undo directory.".red());
+            return;
+        }
+    };
+
+    // Initialize program repository.
+    let program_repo: PROGRAM_REPO = match ProgramRepo::new(chain) {
+        Some(repo) => repo,
+        None => {
+            println!("{}", "Error initializing program repository.".red());
+            return;
+        }
+    };
+
+    // Initialize storage directory.
+    let storage_dir: STORAGE_DIRECTORY = match StorageDirectory::new(chain) {
+        Some(dir) => dir,
+        None => {
+            println!("{}", "Error initializing storage directory.".red());
+            return;
+        }
+    };
+
+    // Initialize balance directory.
+    let balance_dir: BALANCE_DIRECTORY = match BalanceDirectory::new(chain) {
+        Some(dir) => dir,
+        None => {
+            println!("{}", "Error initializing balance directory.".red());
+            return;
+        }
+    };
+
+    // Initialize state directory.
+    let state_dir: STATE_DIRECTORY = match StateDirectory::new(chain) {
+        Some(dir) => dir,
+        None => {
+            println!("{}", "Error initializing state directory.".red());
+            return;
+        }
+    };
+
+    // Initialize archive directory, if running in archive mode.
+    let archive_dir: Option<ARCHIVE_DIRECTORY> = match archive {
+        true => match ArchiveDirectory::new(chain) {
+            Some(dir) => Some(dir),
+            None => {
+                println!("{}", "Error initializing archive directory.".red());
+                return;
+            }
+        },
+        false => None,
+    };
+
     // #8 Spawn syncer
     {
         let chain = chain.clone();
@@ -106,6 +175,12 @@ pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
         let wallet = Arc::clone(&wallet);
         let rollup_dir = Arc::clone(&rollup_dir);
         let coin_set = Arc::clone(&coin_set);
+        let program_repo = Arc::clone(&program_repo);
+        let storage_dir = Arc::clone(&storage_dir);
+        let balance_dir = Arc::clone(&balance_dir);
+        let state_dir = Arc::clone(&state_dir);
+        let archive_dir = archive_dir.as_ref().map(Arc::clone);
+        let undo_dir = Arc::clone(&undo_dir);
 
         tokio::spawn(async move {
             let _ = rollup_dir
@@ -116,8 +191,14 @@ pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
                     &epoch_dir,
                     &lp_dir,
                     &registery,
+                    &program_repo,
+                    &storage_dir,
                     Some(&wallet),
                     &coin_set,
+                    &balance_dir,
+                    &state_dir,
+                    archive_dir.as_ref(),
+                    &undo_dir,
                 )
                 .await;
         });
@@ -175,6 +256,7 @@ pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
         &account,
         &wallet,
         &epoch_dir,
+        &rollup_dir,
     )
     .await;
 }
@@ -186,6 +268,7 @@ pub async fn cli(
     _account: &Account,
     wallet: &WALLET,
     epoch_dir: &EPOCH_DIRECTORY,
+    rollup_dir: &ROLLUP_DIRECTORY,
 ) {
     println!(
         "{}",
@@ -220,12 +303,16 @@ pub async fn cli(
             "addr" => ncli::addr::addr_command(chain, epoch_dir, key_holder).await,
             "lift" => ncli::lift::lift_command(wallet, epoch_dir, chain, key_holder, parts).await,
             "decomp" => ncli::decomp::decomp_command(parts),
+            "vtxo" => ncli::vtxo::vtxo_command(wallet, key_holder, parts).await,
             "move" => {
                 ncli::r#move::move_command(
+                    chain,
                     coordinator_conn,
                     wallet,
+                    rollup_dir,
                     key_holder.secret_key(),
                     key_holder.public_key(),
+                    parts,
                 )
                 .await
             }
@@ -9,6 +9,10 @@ use crate::communicative::rpc::bitcoin::rpcholder::RPCHolder;
 use crate::communicative::tcp;
 use crate::communicative::tcp::tcp::open_port;
 use crate::communicative::tcp::tcp::port_number;
+use crate::inscriptive::archive::dir::ArchiveDirectory;
+use crate::inscriptive::archive::dir::ARCHIVE_DIRECTORY;
+use crate::inscriptive::balance::dir::BalanceDirectory;
+use crate::inscriptive::balance::dir::BALANCE_DIRECTORY;
 use crate::inscriptive::epoch::dir::EpochDirectory;
 use crate::inscriptive::epoch::dir::EPOCH_DIRECTORY;
 use crate::inscriptive::lp::dir::LPDirectory;
@@ -16,10 +20,18 @@ use crate::inscriptive::lp::dir::LP_DIRECTORY;
 use crate::inscriptive::registery::account_registery::ACCOUNT_REGISTERY;
 use crate::inscriptive::registery::registery::Registery;
 use crate::inscriptive::registery::registery::REGISTERY;
+use crate::inscriptive::repo::repo::ProgramRepo;
+use crate::inscriptive::repo::repo::PROGRAM_REPO;
 use crate::inscriptive::rollup::dir::RollupDirectory;
 use crate::inscriptive::rollup::dir::ROLLUP_DIRECTORY;
 use crate::inscriptive::set::set::CoinSet;
 use crate::inscriptive::set::set::COIN_SET;
+use crate::inscriptive::state::dir::StateDirectory;
+use crate::inscriptive::state::dir::STATE_DIRECTORY;
+use crate::inscriptive::storage::dir::StorageDirectory;
+use crate::inscriptive::storage::dir::STORAGE_DIRECTORY;
+use crate::inscriptive::undo::dir::UndoDirectory;
+use crate::inscriptive::undo::dir::UNDO_DIRECTORY;
 use crate::operative::mode::ocli;
 use crate::operative::sync::rollup::RollupSync;
 use crate::operative::Chain;
@@ -33,7 +45,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 #[tokio::main]
-pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
+pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder, archive: bool) {
     let mode = OperatingMode::Operator;
 
     // #1 Validate Bitcoin RPC.
@@ -80,6 +92,63 @@ pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
         }
     };
 
+    // Initialize undo directory.
+    let undo_dir: UNDO_DIRECTORY = match UndoDirectory::new(chain) {
+        Some(dir) => dir,
+        None => {
+            println!("{}", "Error initializing undo directory.".red());
+            return;
+        }
+    };
+
+    // Initialize program repository.
+    let program_repo: PROGRAM_REPO = match ProgramRepo::new(chain) {
+        Some(repo) => repo,
+        None => {
+            println!("{}", "Error initializing program repository.".red());
+            return;
+        }
+    };
+
+    // Initialize storage directory.
+    let storage_dir: STORAGE_DIRECTORY = match StorageDirectory::new(chain) {
+        Some(dir) => dir,
+        None => {
+            println!("{}", "Error initializing storage directory.".red());
+            return;
+        }
+    };
+
+    // Initialize balance directory.
+    let balance_dir: BALANCE_DIRECTORY = match BalanceDirectory::new(chain) {
+        Some(dir) => dir,
+        None => {
+            println!("{}", "Error initializing balance directory.".red());
+            return;
+        }
+    };
+
+    // Initialize state directory.
+    let state_dir: STATE_DIRECTORY = match StateDirectory::new(chain) {
+        Some(dir) => dir,
+        None => {
+            println!("{}", "Error initializing state directory.".red());
+            return;
+        }
+    };
+
+    // Initialize archive directory, if running in archive mode.
+    let archive_dir: Option<ARCHIVE_DIRECTORY> = match archive {
+        true => match ArchiveDirectory::new(chain) {
+            Some(dir) => Some(dir),
+            None => {
+                println!("{}", "Error initializing archive directory.".red());
+                return;
+            }
+        },
+        false => None,
+    };
+
     // #7 Initialize the coin set.
     let coin_set: COIN_SET = match CoinSet::new(chain) {
         Some(coin_set) => coin_set,
@@ -99,6 +168,12 @@ pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
         let registery = Arc::clone(&registery);
         let rollup_dir = Arc::clone(&rollup_dir);
         let coin_set = Arc::clone(&coin_set);
+        let program_repo = Arc::clone(&program_repo);
+        let storage_dir = Arc::clone(&storage_dir);
+        let balance_dir = Arc::clone(&balance_dir);
+        let state_dir = Arc::clone(&state_dir);
+        let archive_dir = archive_dir.as_ref().map(Arc::clone);
+        let undo_dir = Arc::clone(&undo_dir);
 
         tokio::spawn(async move {
             let _ = rollup_dir
@@ -109,8 +184,14 @@ pub async fn run(key_holder: KeyHolder, chain: Chain, rpc_holder: RPCHolder) {
                     &epoch_dir,
                     &lp_dir,
                     &registery,
+                    &program_repo,
+                    &storage_dir,
                     None,
                     &coin_set,
+                    &balance_dir,
+                    &state_dir,
+                    archive_dir.as_ref(),
+                    &undo_dir,
                 )
                 .await;
         });
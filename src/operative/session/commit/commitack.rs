@@ -1,10 +1,9 @@
 use crate::{
-    constructive::{entity::account::Account, entry::entry::Entry, txo::lift::Lift},
-    transmutive::{
-        hash::{Hash, HashTag},
-        musig::session::MusigSessionCtx,
-        secp::authenticable::AuthSighash,
+    constructive::{
+        entity::account::Account, entry::entry::Entry, payload::payload::Payload, txo::lift::Lift,
+        valtype::maybe_common::common_table::CommonTable,
     },
+    transmutive::musig::session::MusigSessionCtx,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -18,6 +17,10 @@ pub struct CSessionCommitAck {
     // Msg sender
     account: Account,
     session_id: [u8; 32],
+    // Payload header
+    ops_price: u32,
+    common_table: CommonTable,
+    state_root: [u8; 32],
     // Entries
     entries: Vec<Entry>,
     // Payload auth
@@ -38,6 +41,9 @@ impl CSessionCommitAck {
     pub fn new(
         account: Account,
         session_id: [u8; 32],
+        ops_price: u32,
+        common_table: CommonTable,
+        state_root: [u8; 32],
         entries: Vec<Entry>,
         payload_auth_musig_ctx: MusigSessionCtx,
         vtxo_projector_musig_ctx: Option<MusigSessionCtx>,
@@ -49,6 +55,9 @@ impl CSessionCommitAck {
         CSessionCommitAck {
             account,
             session_id,
+            ops_price,
+            common_table,
+            state_root,
             entries,
             payload_auth_musig_ctx,
             vtxo_projector_musig_ctx,
@@ -67,6 +76,18 @@ impl CSessionCommitAck {
         self.session_id
     }
 
+    pub fn ops_price(&self) -> u32 {
+        self.ops_price
+    }
+
+    pub fn common_table(&self) -> CommonTable {
+        self.common_table.clone()
+    }
+
+    pub fn state_root(&self) -> [u8; 32] {
+        self.state_root
+    }
+
     pub fn entries(&self) -> Vec<Entry> {
         self.entries.clone()
     }
@@ -96,19 +117,13 @@ impl CSessionCommitAck {
     }
 
     fn payload_auth_msg(&self) -> [u8; 32] {
-        let mut preimage = Vec::<u8>::new();
-
-        // Session ID
-        preimage.extend(self.session_id);
-
-        // Entries
-        for (index, entry) in self.entries.iter().enumerate() {
-            let entry_sighash = entry.auth_sighash();
-            preimage.extend((index as u32).to_le_bytes());
-            preimage.extend(entry_sighash);
-        }
-
-        preimage.hash(Some(HashTag::PayloadAuth))
+        Payload::auth_msg_from_parts(
+            self.session_id,
+            self.ops_price,
+            &self.common_table,
+            self.state_root,
+            &self.entries,
+        )
     }
 
     pub fn validate_payload_auth_msg(&self) -> bool {
@@ -9,25 +9,28 @@ use crate::{
     },
     constructive::{
         entity::account::Account,
-        entry::entry::Entry,
+        entry::{entry::Entry, estimate::EntryEstimate},
+        payload::payload::Payload,
         txo::{
             connector::Connector,
             lift::Lift,
             projector::{Projector, ProjectorTag},
         },
+        valtype::maybe_common::common_table::CommonTable,
     },
     inscriptive::{
         blacklist::BLIST_DIRECTORY,
         registery::{account_registery::ACCOUNT_REGISTERY, registery::REGISTERY},
+        rollup::dir::ROLLUP_DIRECTORY,
+        state::dir::STATE_DIRECTORY,
     },
     operative::session::{
         allowance::allowance, commit::NSessionCommit, commitack::CSessionCommitAck,
     },
     transmutive::{
-        hash::{Hash, HashTag},
         musig::{keyagg::MusigKeyAggCtx, session::MusigSessionCtx},
         noist::{dkg::directory::DKG_DIRECTORY, manager::DKG_MANAGER, session::NOISTSessionCtx},
-        secp::authenticable::{AuthSighash, Authenticable},
+        secp::authenticable::Authenticable,
     },
 };
 use async_trait::async_trait;
@@ -66,9 +69,15 @@ pub struct CSessionCtx {
     peer_manager: PEER_MANAGER,
     blacklist_dir: BLIST_DIRECTORY,
     registery: REGISTERY,
+    state_dir: STATE_DIRECTORY,
+    rollup_dir: ROLLUP_DIRECTORY,
     //
     session_id: [u8; 32],
     stage: CSessionStage,
+    // Payload header, fixed upon lock.
+    ops_price: u32,
+    common_table: CommonTable,
+    state_root: [u8; 32],
     // Commit pool.
     commit_pool: Vec<NSessionCommit>,
     // Post-commit-pool pruned (failed) commits
@@ -142,14 +151,21 @@ impl CSessionCtx {
         peer_manager: &PEER_MANAGER,
         blacklist_dir: &BLIST_DIRECTORY,
         registery: &REGISTERY,
+        state_dir: &STATE_DIRECTORY,
+        rollup_dir: &ROLLUP_DIRECTORY,
     ) -> CSESSION_CTX {
         let session = CSessionCtx {
             dkg_manager: Arc::clone(dkg_manager),
             peer_manager: Arc::clone(peer_manager),
             blacklist_dir: Arc::clone(blacklist_dir),
             registery: Arc::clone(registery),
+            state_dir: Arc::clone(state_dir),
+            rollup_dir: Arc::clone(rollup_dir),
             session_id: [0xffu8; 32],
             stage: CSessionStage::Off,
+            ops_price: 0,
+            common_table: CommonTable::genesis(),
+            state_root: [0x00u8; 32],
             commit_pool: Vec::<NSessionCommit>::new(),
             pruned_commits: Vec::<NSessionCommit>::new(),
             // Post-commit-pool valid commits
@@ -199,8 +215,9 @@ impl CSessionCtx {
         Arc::clone(&self.peer_manager)
     }
 
-    pub fn init(&mut self, session_id: [u8; 32]) {
+    pub fn init(&mut self, session_id: [u8; 32], ops_price: u32) {
         self.session_id = session_id;
+        self.ops_price = ops_price;
         self.reset();
         self.on();
     }
@@ -221,6 +238,102 @@ impl CSessionCtx {
         self.entries.iter().map(|entry| entry.account()).collect()
     }
 
+    /// Prices the session entries at the given sat/vB fee rate.
+    /// Returns the data-availability fee of each entry, keyed by the entry account.
+    pub async fn entry_fees(
+        &self,
+        common_table: &CommonTable,
+        sat_per_vb: u64,
+    ) -> Option<HashMap<Account, u64>> {
+        let mut entry_fees = HashMap::<Account, u64>::new();
+
+        for entry in self.entries.iter() {
+            let estimate = EntryEstimate::new(entry, &self.registery, common_table).await?;
+            entry_fees.insert(entry.account(), estimate.fee(sat_per_vb));
+        }
+
+        Some(entry_fees)
+    }
+
+    /// Returns the session `Payload` once the payload auth signature is fully aggregated.
+    /// The payload carries the header fixed upon lock, which the payload auth signature commits to.
+    pub fn payload(&self) -> Option<Payload> {
+        let (_, _, _, payload_auth_musig_ctx) = self.payload_auth_ctxes.as_ref()?;
+        let payload_auth_sig = payload_auth_musig_ctx.full_agg_sig()?;
+
+        Payload::new(
+            self.session_id,
+            self.ops_price,
+            &self.common_table,
+            self.state_root,
+            self.entries.clone(),
+            payload_auth_sig,
+        )
+    }
+
+    /// Returns the operator key of the session, i.e. the payload auth NOIST group key.
+    pub fn operator_key(&self) -> Option<Point> {
+        let (_, _, noist_ctx, _) = self.payload_auth_ctxes.as_ref()?;
+        Some(noist_ctx.group_key())
+    }
+
+    /// Returns the `VTXO Projector` of the session, if any.
+    pub fn vtxo_projector(&self) -> Option<Projector> {
+        let (_, _, noist_ctx, _) = self.vtxo_projector_ctxes.as_ref()?;
+        let remote_keys: Vec<Point> = self
+            .vtxo_projector_nonces
+            .iter()
+            .map(|(account, _)| account.key())
+            .collect();
+
+        Some(Projector::new(
+            &remote_keys,
+            noist_ctx.group_key(),
+            ProjectorTag::VTXOProjector,
+        ))
+    }
+
+    /// Returns the `Connector Projector` of the session, if any.
+    pub fn connector_projector(&self) -> Option<Projector> {
+        let (_, _, noist_ctx, _) = self.connector_projector_ctxes.as_ref()?;
+        let remote_keys: Vec<Point> = self
+            .connector_projector_nonces
+            .iter()
+            .map(|(account, _)| account.key())
+            .collect();
+
+        Some(Projector::new(
+            &remote_keys,
+            noist_ctx.group_key(),
+            ProjectorTag::ConnectorProjector,
+        ))
+    }
+
+    /// Returns the number of connectors projected by the session.
+    pub fn num_connectors(&self) -> usize {
+        self.connector_txo_ctxes
+            .iter()
+            .map(|(_, ctxes)| ctxes.len())
+            .sum()
+    }
+
+    /// Returns the lifts spent by the session along with their aggregated signatures.
+    /// Returns `None` if any of the lift signatures is not fully aggregated.
+    pub fn lift_prevtxo_sigs(&self) -> Option<Vec<(Lift, [u8; 64])>> {
+        let mut lift_sigs = Vec::<(Lift, [u8; 64])>::new();
+
+        for (_, ctxes) in self.lift_prevtxo_ctxes.iter() {
+            for (lift, (_, _, _, musig_ctx)) in ctxes.iter() {
+                lift_sigs.push((lift.to_owned(), musig_ctx.full_agg_sig()?));
+            }
+        }
+
+        // Order lifts by outpoint for a deterministic input ordering.
+        lift_sigs.sort_by(|(a, _), (b, _)| a.outpoint().cmp(&b.outpoint()));
+
+        Some(lift_sigs)
+    }
+
     fn is_account(&self, account: &Account) -> bool {
         self.entries
             .iter()
@@ -436,19 +549,13 @@ impl CSessionCtx {
     }
 
     fn payload_auth_msg(&self) -> [u8; 32] {
-        let mut preimage = Vec::<u8>::new();
-
-        // Session ID
-        preimage.extend(self.session_id);
-
-        // Entries
-        for (index, entry) in self.entries.iter().enumerate() {
-            let entry_sighash = entry.auth_sighash();
-            preimage.extend((index as u32).to_le_bytes());
-            preimage.extend(entry_sighash);
-        }
-
-        preimage.hash(Some(HashTag::PayloadAuth))
+        Payload::auth_msg_from_parts(
+            self.session_id,
+            self.ops_price,
+            &self.common_table,
+            self.state_root,
+            &self.entries,
+        )
     }
 
     /// Sets the NOIST and MuSig contexes upon collecting `NSessionCommit`s, triggered by `lock`.
@@ -458,6 +565,16 @@ impl CSessionCtx {
             return false;
         }
 
+        // #2 Fix the payload header the payload auth signature commits to.
+        self.common_table = {
+            let _rollup_dir = self.rollup_dir.lock().await;
+            CommonTable::latest(_rollup_dir.bitcoin_sync_height())
+        };
+        self.state_root = {
+            let _state_dir = self.state_dir.lock().await;
+            _state_dir.latest_state_root()
+        };
+
         let dkg_manager: DKG_MANAGER = Arc::clone(&self.dkg_manager);
 
         let active_dkg_dir: DKG_DIRECTORY = {
@@ -1045,6 +1162,9 @@ impl CSessionCtx {
         let commitack = CSessionCommitAck::new(
             account,
             session_id,
+            self.ops_price,
+            self.common_table.clone(),
+            self.state_root,
             entries,
             payload_auth_musig_ctx,
             vtxo_projector_musig_ctx,
@@ -1667,9 +1787,10 @@ impl CContextRunner for CSESSION_CTX {
             {
                 // TODO:
                 let session_id = [0x00u8; 32];
+                let ops_price = 0;
 
                 let mut _session_ctx = self.lock().await;
-                _session_ctx.init(session_id);
+                _session_ctx.init(session_id, ops_price);
             }
 
             // Wait for other commits.
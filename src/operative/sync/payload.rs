@@ -0,0 +1,563 @@
+use crate::{
+    communicative::rpc::bitcoin::{rpc::get_transaction, rpcholder::RPCHolder},
+    constructive::{
+        entity::{account::Account, contract::Contract},
+        entry::{combinator::combinator::Combinator, decode_ctx::EntryDecodeCtx},
+        payload::{envelope::parse_envelope, payload::Payload},
+        taproot::P2TR,
+        txn::{
+            txholder::TxHolder,
+            vtxo_tree::{VTXOTree, DEFAULT_RADIX},
+        },
+        txo::{
+            projector::{Projector, ProjectorTag},
+            vtxo::VTXO,
+        },
+    },
+    inscriptive::{
+        archive::dir::ARCHIVE_DIRECTORY,
+        baked::EXPIRY_BLOCKS,
+        balance::dir::BALANCE_DIRECTORY,
+        registery::registery::REGISTERY,
+        repo::repo::PROGRAM_REPO,
+        rollup::{dir::ROLLUP_DIRECTORY, pool_tip::PoolTip},
+        set::set::COIN_SET,
+        state::{dir::STATE_DIRECTORY, tree::StateTree},
+        storage::dir::STORAGE_DIRECTORY,
+        undo::journal::{UndoJournal, UndoOp},
+    },
+};
+use bitcoin::{OutPoint, Sequence, Transaction, TxOut, Txid};
+use secp::Point;
+use std::collections::HashMap;
+
+/// Returns the payload bytes revealed by a transaction, if it is a `Pool Transaction`.
+///
+/// A pool transaction spends the previous payload output from its operator path as its first input,
+/// whose tapscript carries the previous payload envelope.
+pub fn revealed_payload(tx: &Transaction) -> Option<Vec<u8>> {
+    let payload_input = tx.input.first()?;
+    let tapscript = payload_input.witness.tapscript()?;
+
+    parse_envelope(tapscript.as_bytes())
+}
+
+/// Returns the operator key of the payload revealed by a `Pool Transaction`.
+///
+/// The key is read from the operator path spent by the payload input, following its hashlocks,
+/// and must be one of the given operator group keys, which carry its parity.
+pub fn revealed_operator_key(tx: &Transaction, group_keys: &[Point]) -> Option<Point> {
+    let payload_input = tx.input.first()?;
+    let tapscript = payload_input.witness.tapscript()?;
+    let script = tapscript.as_bytes();
+
+    // Skip the hashlocks: `OP_SHA256 OP_PUSHDATA_32 <hashlock> OP_EQUALVERIFY`.
+    let mut index = 0;
+    while script.get(index) == Some(&0xa8) {
+        index += 35;
+    }
+
+    // Operator key: `OP_PUSHDATA_32 <operator key> OP_CHECKSIG`.
+    if script.get(index) != Some(&0x20) || script.get(index + 33) != Some(&0xac) {
+        return None;
+    }
+    let operator_key = script.get(index + 1..index + 33)?;
+
+    group_keys
+        .iter()
+        .find(|group_key| group_key.serialize_xonly() == operator_key)
+        .copied()
+}
+
+/// Returns the number of expired projectors swept by a `Pool Transaction`.
+///
+/// Expired projectors follow the payload input, and are the only inputs timelocked by the expiry.
+pub fn extra_in(tx: &Transaction) -> u8 {
+    tx.input
+        .iter()
+        .skip(1)
+        .take_while(|txin| txin.sequence == Sequence::from_consensus(EXPIRY_BLOCKS))
+        .count() as u8
+}
+
+/// Returns the Bitcoin heights of the expired projectors swept by a `Pool Transaction`.
+///
+/// Projectors are outputs of earlier pool transactions, whose heights are retrieved from the Bitcoin RPC.
+pub fn swept_projector_heights(rpc_holder: &RPCHolder, tx: &Transaction) -> Option<Vec<u32>> {
+    let mut projector_heights = Vec::<u32>::new();
+    for txin in tx.input.iter().skip(1).take(extra_in(tx) as usize) {
+        let (_, bitcoin_height) = get_transaction(rpc_holder, &txin.previous_output.txid).ok()?;
+        projector_heights.push(bitcoin_height as u32);
+    }
+
+    Some(projector_heights)
+}
+
+/// Moves the VTXOs of the swept projectors from the VTXO set to the swept set, where they can be claimed.
+///
+/// VTXOs are confirmed along with their projector, so the VTXOs of a swept projector
+/// are the ones confirmed at its Bitcoin height.
+pub async fn sweep_vtxos(
+    projector_heights: &[u32],
+    coin_set: &COIN_SET,
+    journal: &mut UndoJournal,
+) {
+    if projector_heights.is_empty() {
+        return;
+    }
+
+    let (vtxo_set, swept_set) = {
+        let _coin_set = coin_set.lock().await;
+        (_coin_set.vtxo_set(), _coin_set.swept_set())
+    };
+
+    let swept_vtxos: Vec<VTXO> = {
+        let _vtxo_set = vtxo_set.lock().await;
+        _vtxo_set
+            .vtxos()
+            .into_iter()
+            .filter(|vtxo| match vtxo.at_bitcoin_height() {
+                Some(bitcoin_height) => projector_heights.contains(&bitcoin_height),
+                None => false,
+            })
+            .collect()
+    };
+
+    for vtxo in swept_vtxos {
+        {
+            let mut _vtxo_set = vtxo_set.lock().await;
+            if !_vtxo_set.remove_vtxo(&vtxo) {
+                continue;
+            }
+        }
+
+        let mut _swept_set = swept_set.lock().await;
+        _swept_set.insert_vtxo(&vtxo);
+        journal.record(UndoOp::VTXOSwept(vtxo));
+    }
+}
+
+/// Output index of the `VTXO Projector` in a `Pool Transaction`.
+const VTXO_PROJECTOR_VOUT: u32 = 1;
+
+/// Returns the VTXOs issued by the `VTXO Projector` of a pool transaction, for the entries of its payload.
+///
+/// Each entry lifting up issues a VTXO worth its lifts to its account. The projector is shared by the
+/// lifting accounts and the operator, and must match the projector output of the pool transaction,
+/// whose value must cover the issued VTXOs. VTXOs are confirmed along with the projector,
+/// at the leaves of its `VTXO Tree`.
+/// Returns `None` if the projector output does not match the issued VTXOs.
+pub fn issued_vtxos(
+    payload: &Payload,
+    pool_tip: &PoolTip,
+    operator_key: Point,
+    rollup_height: u32,
+) -> Option<Vec<VTXO>> {
+    let bitcoin_height = pool_tip.bitcoin_height() as u32;
+
+    let mut vtxos = Vec::<VTXO>::new();
+    for entry in payload.entries().iter() {
+        if let Some(liftup) = entry.liftup() {
+            let mut value = 0u64;
+            for lift in liftup.lifts().iter() {
+                value = value.checked_add(lift.value()?)?;
+            }
+
+            vtxos.push(VTXO::new(
+                entry.account().key(),
+                operator_key,
+                None,
+                Some(value),
+                Some(rollup_height),
+                Some(bitcoin_height),
+            ));
+        }
+    }
+
+    if vtxos.is_empty() {
+        return Some(vtxos);
+    }
+
+    // Make sure the projector output matches the issued VTXOs.
+    let remote_keys: Vec<Point> = vtxos.iter().map(|vtxo| vtxo.account_key()).collect();
+    let projector = Projector::new(&remote_keys, operator_key, ProjectorTag::VTXOProjector);
+
+    let tx = pool_tip.tx();
+    let projector_txout = tx.output.get(VTXO_PROJECTOR_VOUT as usize)?;
+    if projector_txout.script_pubkey.as_bytes() != projector.spk()?.as_slice() {
+        return None;
+    }
+
+    let issued_value = vtxos
+        .iter()
+        .try_fold(0u64, |sum, vtxo| sum.checked_add(vtxo.value()?))?;
+    if projector_txout.value.to_sat() < issued_value {
+        return None;
+    }
+
+    // Place the VTXOs at the leaves of the tree.
+    let projector_outpoint = OutPoint::new(tx.compute_txid(), VTXO_PROJECTOR_VOUT);
+    let vtxo_tree = VTXOTree::new(&projector, projector_outpoint, &vtxos, DEFAULT_RADIX)?;
+
+    vtxos
+        .iter()
+        .map(|vtxo| {
+            Some(VTXO::new(
+                vtxo.account_key(),
+                operator_key,
+                Some(vtxo_tree.leaf_outpoint(vtxo)?),
+                vtxo.value(),
+                Some(rollup_height),
+                Some(bitcoin_height),
+            ))
+        })
+        .collect()
+}
+
+/// Retrieves a pool transaction along with its prevouts from the Bitcoin RPC.
+///
+/// Used to bootstrap the pool tip from the first payload reveal synced,
+/// whose pool transaction was not recognised as such when synced.
+pub fn pool_tip_from_rpc(rpc_holder: &RPCHolder, txid: &Txid) -> Option<PoolTip> {
+    let (tx, bitcoin_height) = get_transaction(rpc_holder, txid).ok()?;
+
+    let mut prevouts = Vec::<(OutPoint, TxOut)>::new();
+    for txin in tx.input.iter() {
+        let outpoint = txin.previous_output;
+        let (prev_tx, _) = get_transaction(rpc_holder, &outpoint.txid).ok()?;
+        let txout = prev_tx.output.get(outpoint.vout as usize)?.to_owned();
+
+        prevouts.push((outpoint, txout));
+    }
+
+    Some(PoolTip::new(tx, prevouts, bitcoin_height))
+}
+
+/// Decodes the payload of a pool transaction, revealed by the next pool transaction.
+pub async fn decode_payload(
+    payload_bytes: &[u8],
+    pool_tip: &PoolTip,
+    coin_set: &COIN_SET,
+    ctx: &EntryDecodeCtx,
+) -> Option<Payload> {
+    let utxo_set = {
+        let _coin_set = coin_set.lock().await;
+        _coin_set.utxo_set()
+    };
+
+    let tx = pool_tip.tx();
+    let extra_in = extra_in(&tx);
+
+    let mut txholder = TxHolder::new(&utxo_set, tx, extra_in)?.with_prevouts(pool_tip.prevouts());
+
+    Payload::decode(payload_bytes, &mut txholder, ctx)
+        .await
+        .ok()
+}
+
+/// Applies the entries of a payload to the rollup state, and advances the rollup sync height.
+///
+/// The payload auth signature must verify against msg.senders and the operator key,
+/// and the payload must commit to the current state root. New accounts and
+/// contracts are registered, deployed programs are stored and their storage is seeded,
+/// recharged and revived VTXOs are removed from the VTXO set, claimed VTXOs are removed
+/// from the swept set, and the balances are updated.
+/// The VTXOs issued by the pool transaction are inserted into the VTXO set at the new rollup height,
+/// the state root is then recorded at it, and the state is archived at it if running in archive mode.
+///
+/// The payload is applied as a whole or not at all: mutations are recorded in a scratch journal,
+/// which is reverted if the payload cannot be applied, and appended to the block journal otherwise.
+/// Returns `false` if the payload cannot be applied.
+pub async fn apply_payload(
+    payload: &Payload,
+    pool_tip: &PoolTip,
+    operator_key: Point,
+    registery: &REGISTERY,
+    program_repo: &PROGRAM_REPO,
+    storage_dir: &STORAGE_DIRECTORY,
+    coin_set: &COIN_SET,
+    balance_dir: &BALANCE_DIRECTORY,
+    state_dir: &STATE_DIRECTORY,
+    rollup_dir: &ROLLUP_DIRECTORY,
+    archive_dir: Option<&ARCHIVE_DIRECTORY>,
+    journal: &mut UndoJournal,
+) -> bool {
+    // Payloads not authorized by msg.senders and the operator are rejected upfront.
+    if !payload.verify_auth_sig(operator_key) {
+        return false;
+    }
+
+    let mut scratch_journal = UndoJournal::new(journal.block_hash());
+
+    let applied = record_payload(
+        payload,
+        pool_tip,
+        operator_key,
+        registery,
+        program_repo,
+        storage_dir,
+        coin_set,
+        balance_dir,
+        state_dir,
+        rollup_dir,
+        archive_dir,
+        &mut scratch_journal,
+    )
+    .await;
+
+    match applied {
+        true => {
+            for op in scratch_journal.ops() {
+                journal.record(op);
+            }
+        }
+        false => {
+            scratch_journal
+                .revert(
+                    coin_set,
+                    None,
+                    registery,
+                    program_repo,
+                    balance_dir,
+                    storage_dir,
+                    state_dir,
+                    rollup_dir,
+                    archive_dir,
+                )
+                .await
+        }
+    }
+
+    applied
+}
+
+/// Applies the entries of a payload, recording every mutation in the journal.
+/// Returns `false` on the first step that fails, leaving the prior steps applied.
+async fn record_payload(
+    payload: &Payload,
+    pool_tip: &PoolTip,
+    operator_key: Point,
+    registery: &REGISTERY,
+    program_repo: &PROGRAM_REPO,
+    storage_dir: &STORAGE_DIRECTORY,
+    coin_set: &COIN_SET,
+    balance_dir: &BALANCE_DIRECTORY,
+    state_dir: &STATE_DIRECTORY,
+    rollup_dir: &ROLLUP_DIRECTORY,
+    archive_dir: Option<&ARCHIVE_DIRECTORY>,
+    journal: &mut UndoJournal,
+) -> bool {
+    let entries = payload.entries();
+    let bitcoin_height = pool_tip.bitcoin_height() as u32;
+
+    let (vtxo_set, swept_set) = {
+        let _coin_set = coin_set.lock().await;
+        (_coin_set.vtxo_set(), _coin_set.swept_set())
+    };
+
+    // #1 Make sure the payload commits to the current state.
+    match StateTree::from_state(balance_dir, registery, storage_dir, &vtxo_set).await {
+        Some(state_tree) if state_tree.root() == payload.state_root() => (),
+        _ => return false,
+    }
+
+    // #2 Make sure the entries can be applied to the balances.
+    let prior_balances = {
+        let _balance_dir = balance_dir.lock().await;
+        match _balance_dir.prior_balances(&entries) {
+            Some(prior_balances) => prior_balances,
+            None => return false,
+        }
+    };
+
+    // #3 Update the registery.
+    let (account_registery, contract_registery) = {
+        let _registery = registery.lock().await;
+        (
+            _registery.account_registery(),
+            _registery.contract_registery(),
+        )
+    };
+
+    let mut accounts_called = HashMap::<Account, u64>::new();
+    let mut contracts_called = HashMap::<Contract, u64>::new();
+    for entry in entries.iter() {
+        let account = entry.account();
+        if account.registery_index().is_some() {
+            *accounts_called.entry(account).or_insert(0) += 1;
+        }
+
+        if let Some(Combinator::Call(call)) = entry.main_combinator() {
+            *contracts_called.entry(call.contract()).or_insert(0) += 1;
+        }
+    }
+
+    let accounts_to_register: Vec<_> = payload
+        .new_accounts()
+        .iter()
+        .map(|account| account.key())
+        .collect();
+
+    // Registrations must be new, so that the registery is either fully updated or left untouched.
+    {
+        let _account_registery = account_registery.lock().await;
+        if accounts_to_register
+            .iter()
+            .any(|account_key| _account_registery.is_registered(*account_key))
+        {
+            return false;
+        }
+    }
+    {
+        let _contract_registery = contract_registery.lock().await;
+        if payload
+            .new_contracts()
+            .iter()
+            .any(|contract_id| _contract_registery.is_registered(*contract_id))
+        {
+            return false;
+        }
+    }
+
+    {
+        let mut _account_registery = account_registery.lock().await;
+        if !_account_registery.batch_update(accounts_to_register.clone(), accounts_called.clone()) {
+            return false;
+        }
+    }
+    journal.record(UndoOp::AccountsRegistered(
+        accounts_to_register,
+        accounts_called.into_iter().collect(),
+    ));
+
+    {
+        let mut _contract_registery = contract_registery.lock().await;
+        if !_contract_registery.batch_update(payload.new_contracts(), contracts_called.clone()) {
+            return false;
+        }
+    }
+    journal.record(UndoOp::ContractsRegistered(
+        payload.new_contracts(),
+        contracts_called.into_iter().collect(),
+    ));
+
+    // #4 Store the deployed programs, and seed their storage.
+    for entry in entries.iter() {
+        if let Some(Combinator::Deploy(deploy)) = entry.main_combinator() {
+            if let Some(program) = deploy.program() {
+                let mut _program_repo = program_repo.lock().await;
+                if _program_repo.insert_program(deploy.contract_id(), &program) {
+                    journal.record(UndoOp::ProgramInserted(deploy.contract_id()));
+                }
+            }
+
+            let mut _storage_dir = storage_dir.lock().await;
+            if !_storage_dir.seed_storage(deploy.contract_id(), &deploy.storage_seed()) {
+                return false;
+            }
+            journal.record(UndoOp::StorageSeeded(deploy.contract_id()));
+        }
+    }
+
+    // #5 Remove the recharged and revived VTXOs from the VTXO set, and the claimed VTXOs from the swept set.
+    for entry in entries.iter() {
+        let mut spent_vtxos = match entry.recharge() {
+            Some(recharge) => recharge.vtxos(),
+            None => vec![],
+        };
+
+        match entry.main_combinator() {
+            Some(Combinator::Revive(revive)) => {
+                let _vtxo_set = vtxo_set.lock().await;
+                if !revive.validate(&_vtxo_set, bitcoin_height) {
+                    return false;
+                }
+                spent_vtxos.extend(revive.vtxos());
+            }
+            Some(Combinator::Claim(claim)) => {
+                let mut _swept_set = swept_set.lock().await;
+                if !claim.validate(&_swept_set) {
+                    return false;
+                }
+                for swept_vtxo in claim.swept_vtxos() {
+                    if !_swept_set.remove_vtxo(&swept_vtxo) {
+                        return false;
+                    }
+                    journal.record(UndoOp::SweptVTXOClaimed(swept_vtxo));
+                }
+            }
+            _ => (),
+        }
+
+        for vtxo in spent_vtxos {
+            let mut _vtxo_set = vtxo_set.lock().await;
+            if _vtxo_set.remove_vtxo(&vtxo) {
+                journal.record(UndoOp::VTXORemoved(vtxo));
+            }
+        }
+    }
+
+    // #6 Update the balances.
+    {
+        let mut _balance_dir = balance_dir.lock().await;
+        if !_balance_dir.apply_entries(&entries) {
+            return false;
+        }
+    }
+    journal.record(UndoOp::BalancesUpdated(prior_balances));
+
+    // #7 Advance the rollup sync height.
+    let rollup_height = {
+        let mut _rollup_dir = rollup_dir.lock().await;
+        let prior_rollup_height = _rollup_dir.rollup_sync_height();
+        _rollup_dir.set_rollup_sync_height(prior_rollup_height + 1);
+        journal.record(UndoOp::RollupSyncHeightSet(prior_rollup_height));
+
+        prior_rollup_height + 1
+    };
+
+    // #8 Insert the VTXOs issued by the pool transaction into the VTXO set.
+    let vtxos = match issued_vtxos(payload, pool_tip, operator_key, rollup_height as u32) {
+        Some(vtxos) => vtxos,
+        None => return false,
+    };
+    for vtxo in vtxos {
+        let mut _vtxo_set = vtxo_set.lock().await;
+        if !_vtxo_set.insert_vtxo(&vtxo) {
+            return false;
+        }
+        journal.record(UndoOp::VTXOInserted(vtxo));
+    }
+
+    // #9 Record the state root at the new rollup height.
+    {
+        let mut _state_dir = state_dir.lock().await;
+        if _state_dir
+            .commit_state_root(
+                rollup_height,
+                balance_dir,
+                registery,
+                storage_dir,
+                &vtxo_set,
+            )
+            .await
+            .is_none()
+        {
+            return false;
+        }
+    }
+    journal.record(UndoOp::StateRootInserted(rollup_height));
+
+    // #10 Archive the state at the new rollup height.
+    if let Some(archive_dir) = archive_dir {
+        let mut _archive_dir = archive_dir.lock().await;
+        if _archive_dir
+            .archive_state(rollup_height, balance_dir, registery)
+            .await
+        {
+            journal.record(UndoOp::StateArchived(rollup_height));
+        }
+    }
+
+    true
+}
@@ -3,18 +3,38 @@ use crate::{
         rpc::{get_block, get_chain_height},
         rpcholder::RPCHolder,
     },
-    constructive::{taproot::P2TR, txo::lift::Lift},
+    constructive::{entry::decode_ctx::EntryDecodeCtx, taproot::P2TR, txo::lift::Lift},
     inscriptive::{
-        baked, epoch::dir::EPOCH_DIRECTORY, lp::dir::LP_DIRECTORY, registery::registery::REGISTERY,
-        rollup::dir::ROLLUP_DIRECTORY, set::set::COIN_SET, wallet::wallet::WALLET,
+        archive::dir::ARCHIVE_DIRECTORY,
+        baked,
+        balance::dir::BALANCE_DIRECTORY,
+        epoch::dir::EPOCH_DIRECTORY,
+        lp::dir::LP_DIRECTORY,
+        registery::registery::REGISTERY,
+        repo::repo::PROGRAM_REPO,
+        rollup::{dir::ROLLUP_DIRECTORY, pool_tip::PoolTip},
+        set::set::COIN_SET,
+        state::dir::STATE_DIRECTORY,
+        storage::dir::STORAGE_DIRECTORY,
+        undo::{
+            dir::UNDO_DIRECTORY,
+            journal::{UndoJournal, UndoOp},
+        },
+        wallet::wallet::WALLET,
+    },
+    operative::{
+        sync::payload::{
+            apply_payload, decode_payload, pool_tip_from_rpc, revealed_operator_key,
+            revealed_payload, sweep_vtxos, swept_projector_heights,
+        },
+        Chain,
     },
-    operative::Chain,
     transmutive::key::KeyHolder,
 };
 use async_trait::async_trait;
-use bitcoin::OutPoint;
+use bitcoin::{hashes::Hash, OutPoint, Txid};
 use secp::Point;
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 use tokio::time::sleep;
 
 type LiftSPK = Vec<u8>;
@@ -54,9 +74,15 @@ pub trait RollupSync {
         key_holder: &KeyHolder,
         _epoch_dir: &EPOCH_DIRECTORY,
         _lp_dir: &LP_DIRECTORY,
-        _registery: &REGISTERY,
+        registery: &REGISTERY,
+        program_repo: &PROGRAM_REPO,
+        storage_dir: &STORAGE_DIRECTORY,
         wallet: Option<&WALLET>,
         coin_set: &COIN_SET,
+        balance_dir: &BALANCE_DIRECTORY,
+        state_dir: &STATE_DIRECTORY,
+        archive_dir: Option<&ARCHIVE_DIRECTORY>,
+        undo_dir: &UNDO_DIRECTORY,
     );
 
     /// Awaits the rollup to be synced to the latest Bitcoin chain tip.
@@ -86,12 +112,27 @@ impl RollupSync for ROLLUP_DIRECTORY {
         key_holder: &KeyHolder,
         epoch_dir: &EPOCH_DIRECTORY,
         _lp_dir: &LP_DIRECTORY,
-        _registery: &REGISTERY,
+        registery: &REGISTERY,
+        program_repo: &PROGRAM_REPO,
+        storage_dir: &STORAGE_DIRECTORY,
         wallet: Option<&WALLET>,
         coin_set: &COIN_SET,
+        balance_dir: &BALANCE_DIRECTORY,
+        state_dir: &STATE_DIRECTORY,
+        archive_dir: Option<&ARCHIVE_DIRECTORY>,
+        undo_dir: &UNDO_DIRECTORY,
     ) {
         let mut synced: bool = false;
 
+        let (utxo_set, vtxo_set, swept_set) = {
+            let _coin_set = coin_set.lock().await;
+            (
+                _coin_set.utxo_set(),
+                _coin_set.vtxo_set(),
+                _coin_set.swept_set(),
+            )
+        };
+
         let rollup_dir: &ROLLUP_DIRECTORY = self;
 
         let sync_start_height = match chain {
@@ -159,6 +200,81 @@ impl RollupSync for ROLLUP_DIRECTORY {
                         }
                     };
 
+                    // Detect a reorg by comparing the block's parent to the last processed block.
+                    let prev_height = height_to_sync - 1;
+                    let prev_block_journal = {
+                        let _undo_dir = undo_dir.lock().await;
+                        _undo_dir.journal(prev_height)
+                    };
+
+                    // The last processed block is always within the reorg depth, so its journal must exist.
+                    // A missing journal means the reorg cannot be detected, let alone rolled back.
+                    if prev_block_journal.is_none() && prev_height >= sync_start_height {
+                        println!(
+                            "Undo journal of height #{} is missing. Stopping the sync.",
+                            prev_height
+                        );
+                        return;
+                    }
+
+                    if let Some(prev_block_journal) = prev_block_journal {
+                        if prev_block_journal.block_hash()
+                            != block.header.prev_blockhash.to_byte_array()
+                        {
+                            // Roll back the last processed block, and re-sync from its parent.
+                            prev_block_journal
+                                .revert(
+                                    coin_set,
+                                    wallet,
+                                    registery,
+                                    program_repo,
+                                    balance_dir,
+                                    storage_dir,
+                                    state_dir,
+                                    rollup_dir,
+                                    archive_dir,
+                                )
+                                .await;
+
+                            {
+                                let mut _undo_dir = undo_dir.lock().await;
+                                _undo_dir.remove_journal(prev_height);
+                            }
+
+                            {
+                                let mut _rollup_dir = rollup_dir.lock().await;
+                                _rollup_dir.set_bitcoin_sync_height(prev_height - 1);
+                            }
+
+                            println!("Reorg detected, rolled back height #{}.", prev_height);
+                            continue;
+                        }
+                    }
+
+                    // Heights of the projectors swept by the pool transactions of the block.
+                    let block_swept_projector_heights: Option<HashMap<Txid, Vec<u32>>> = block
+                        .txdata
+                        .iter()
+                        .filter(|transaction| revealed_payload(transaction).is_some())
+                        .map(|transaction| {
+                            Some((
+                                transaction.compute_txid(),
+                                swept_projector_heights(rpc_holder, transaction)?,
+                            ))
+                        })
+                        .collect();
+
+                    let block_swept_projector_heights = match block_swept_projector_heights {
+                        Some(heights) => heights,
+                        None => {
+                            sleep(Duration::from_secs(5)).await;
+                            continue;
+                        }
+                    };
+
+                    // Journal of the mutations made while syncing the block.
+                    let mut journal = UndoJournal::new(block.block_hash().to_byte_array());
+
                     let lift_spks_to_scan = match wallet {
                         Some(_) => match lifts_spks_to_scan(key_holder, epoch_dir).await {
                             Some(spks) => spks,
@@ -167,12 +283,141 @@ impl RollupSync for ROLLUP_DIRECTORY {
                         None => vec![],
                     };
 
+                    // Operator group keys authorizing the revealed payloads.
+                    let operator_group_keys = {
+                        let _epoch_dir = epoch_dir.lock().await;
+                        _epoch_dir.active_group_keys()
+                    };
+
                     // Scan block..
                     for transaction in block.txdata.iter() {
                         let inputs = transaction.input.clone();
                         let outputs = transaction.output.clone();
                         let txid = transaction.compute_txid();
 
+                        // Apply the payload revealed by a pool transaction.
+                        if let Some(payload_bytes) = revealed_payload(transaction) {
+                            let payload_outpoint = transaction.input[0].previous_output;
+
+                            let prev_pool_tip = {
+                                let _rollup_dir = rollup_dir.lock().await;
+                                _rollup_dir.pool_tip()
+                            };
+
+                            // Pool transactions must extend the latest synced one.
+                            // The first one synced is bootstrapped from the Bitcoin RPC.
+                            let revealed_pool_tip = match &prev_pool_tip {
+                                Some(pool_tip) => {
+                                    match pool_tip.payload_outpoint() == payload_outpoint {
+                                        true => Some(pool_tip.to_owned()),
+                                        false => None,
+                                    }
+                                }
+                                None => pool_tip_from_rpc(rpc_holder, &payload_outpoint.txid),
+                            };
+
+                            if let Some(revealed_pool_tip) = revealed_pool_tip {
+                                let ctx = EntryDecodeCtx::new(
+                                    epoch_dir,
+                                    registery,
+                                    program_repo,
+                                    &vtxo_set,
+                                    &swept_set,
+                                    revealed_pool_tip.bitcoin_height() as u32,
+                                );
+
+                                // The rollup state must follow every payload confirmed on Bitcoin,
+                                // so a payload that cannot be applied reverts the block and stops the sync.
+                                let applied = match decode_payload(
+                                    &payload_bytes,
+                                    &revealed_pool_tip,
+                                    coin_set,
+                                    &ctx,
+                                )
+                                .await
+                                {
+                                    Some(payload) => match revealed_operator_key(
+                                        transaction,
+                                        &operator_group_keys,
+                                    ) {
+                                        Some(operator_key) => match apply_payload(
+                                            &payload,
+                                            &revealed_pool_tip,
+                                            operator_key,
+                                            registery,
+                                            program_repo,
+                                            storage_dir,
+                                            coin_set,
+                                            balance_dir,
+                                            state_dir,
+                                            rollup_dir,
+                                            archive_dir,
+                                            &mut journal,
+                                        )
+                                        .await
+                                        {
+                                            true => Ok(()),
+                                            false => Err("could not be applied"),
+                                        },
+                                        None => Err("has no known operator"),
+                                    },
+                                    None => Err("could not be decoded"),
+                                };
+
+                                if let Err(reason) = applied {
+                                    journal
+                                        .revert(
+                                            coin_set,
+                                            wallet,
+                                            registery,
+                                            program_repo,
+                                            balance_dir,
+                                            storage_dir,
+                                            state_dir,
+                                            rollup_dir,
+                                            archive_dir,
+                                        )
+                                        .await;
+
+                                    println!(
+                                        "Payload of pool transaction {} {}. Stopping the sync.",
+                                        payload_outpoint.txid, reason
+                                    );
+                                    return;
+                                }
+
+                                // This transaction is the new pool tip, whose payload is revealed by the next pool transaction.
+                                let mut prevouts = Vec::new();
+                                {
+                                    let _utxo_set = utxo_set.lock().await;
+                                    for txn_input in inputs.iter() {
+                                        let outpoint = txn_input.previous_output;
+                                        if let Some(txout) = _utxo_set.txout_by_outpoint(&outpoint)
+                                        {
+                                            prevouts.push((outpoint, txout));
+                                        }
+                                    }
+                                }
+
+                                {
+                                    let mut _rollup_dir = rollup_dir.lock().await;
+                                    _rollup_dir.set_pool_tip(Some(PoolTip::new(
+                                        transaction.to_owned(),
+                                        prevouts,
+                                        height_to_sync,
+                                    )));
+                                }
+                                journal.record(UndoOp::PoolTipSet(prev_pool_tip));
+
+                                // The VTXOs of the projectors swept by this transaction become claimable.
+                                if let Some(projector_heights) =
+                                    block_swept_projector_heights.get(&txid)
+                                {
+                                    sweep_vtxos(projector_heights, coin_set, &mut journal).await;
+                                }
+                            }
+                        }
+
                         // Iterate over inputs.
                         for txn_input in inputs.iter() {
                             let txn_input_outpoint = txn_input.previous_output;
@@ -191,7 +436,11 @@ impl RollupSync for ROLLUP_DIRECTORY {
                                                 };
 
                                                 let mut _lift_wallet = lift_wallet.lock().await;
-                                                _lift_wallet.remove_lift(lift);
+                                                if _lift_wallet.remove_lift(lift) {
+                                                    journal.record(UndoOp::LiftRemoved(
+                                                        lift.to_owned(),
+                                                    ));
+                                                }
                                             }
                                         }
                                     }
@@ -206,7 +455,9 @@ impl RollupSync for ROLLUP_DIRECTORY {
                                 };
 
                                 let mut _utxo_set = utxo_set.lock().await;
-                                _utxo_set.remove_txout(&txn_input_outpoint);
+                                if let Some(txout) = _utxo_set.remove_txout(&txn_input_outpoint) {
+                                    journal.record(UndoOp::UTXORemoved(txn_input_outpoint, txout));
+                                }
                             }
                         }
 
@@ -238,7 +489,9 @@ impl RollupSync for ROLLUP_DIRECTORY {
                                             };
 
                                             let mut _lift_wallet = lift_wallet.lock().await;
-                                            _lift_wallet.insert_lift(&lift);
+                                            if _lift_wallet.insert_lift(&lift) {
+                                                journal.record(UndoOp::LiftInserted(lift));
+                                            }
                                         }
                                     }
                                 }
@@ -252,19 +505,48 @@ impl RollupSync for ROLLUP_DIRECTORY {
                                 };
 
                                 let mut _utxo_set = utxo_set.lock().await;
-                                _utxo_set.insert_txout(&txn_output_outpoint, txn_output);
+                                if _utxo_set.insert_txout(&txn_output_outpoint, txn_output) {
+                                    journal.record(UndoOp::UTXOInserted(txn_output_outpoint));
+                                }
                             }
                         }
                     }
 
+                    // Record the undo journal of the block.
+                    // Without it the block could not be rolled back, so it is reverted and the sync stops.
+                    let journal_saved = {
+                        let mut _undo_dir = undo_dir.lock().await;
+                        _undo_dir.insert_journal(height_to_sync, &journal)
+                    };
+
+                    if !journal_saved {
+                        journal
+                            .revert(
+                                coin_set,
+                                wallet,
+                                registery,
+                                program_repo,
+                                balance_dir,
+                                storage_dir,
+                                state_dir,
+                                rollup_dir,
+                                archive_dir,
+                            )
+                            .await;
+
+                        println!(
+                            "Undo journal of height #{} could not be saved. Stopping the sync.",
+                            height_to_sync
+                        );
+                        return;
+                    }
+
                     // Set the new rollup bitcoin sync height.
                     {
                         let mut _rollup_dir = rollup_dir.lock().await;
                         _rollup_dir.set_bitcoin_sync_height(height_to_sync);
                     }
 
-                    // TODO set the new rollup sync height.
-
                     println!("Synced height #{}.", height_to_sync);
                 }
             }
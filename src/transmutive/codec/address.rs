@@ -1,5 +1,6 @@
 use crate::operative::Chain;
 use bech32::{segwit, Hrp};
+use bitcoin::base58;
 
 type ScriptPubKey = Vec<u8>;
 
@@ -17,6 +18,14 @@ fn hrp_from_chain(chain: Chain) -> Option<Hrp> {
     }
 }
 
+/// Returns the base58 version bytes for P2PKH and P2SH addresses of a given network.
+fn base58_versions_from_chain(chain: Chain) -> (u8, u8) {
+    match chain {
+        Chain::Signet => (0x6f, 0xc4),
+        Chain::Mainnet => (0x00, 0x05),
+    }
+}
+
 /// Encodes a Taproot key into a Bech32-encoded Bitcoin address.
 ///
 /// This function takes a network type and a Taproot key, and returns the corresponding
@@ -59,10 +68,61 @@ pub fn encode_p2wpkh(chain: Chain, witness_program: [u8; 20]) -> Option<String>
     Some(address)
 }
 
-/// Decodes a Bech32-encoded Bitcoin address into a ScriptPubKey.
+/// Encodes a public key hash into a base58-encoded legacy P2PKH address.
+pub fn encode_p2pkh(chain: Chain, pubkey_hash: [u8; 20]) -> String {
+    let (p2pkh_version, _) = base58_versions_from_chain(chain);
+
+    let mut payload = vec![p2pkh_version];
+    payload.extend(pubkey_hash);
+
+    base58::encode_check(&payload)
+}
+
+/// Encodes a script hash into a base58-encoded legacy P2SH address.
+pub fn encode_p2sh(chain: Chain, script_hash: [u8; 20]) -> String {
+    let (_, p2sh_version) = base58_versions_from_chain(chain);
+
+    let mut payload = vec![p2sh_version];
+    payload.extend(script_hash);
+
+    base58::encode_check(&payload)
+}
+
+/// Decodes a base58-encoded legacy P2PKH or P2SH address into a ScriptPubKey.
+fn base58_address_to_spk(chain: Chain, address: &str) -> Option<ScriptPubKey> {
+    let payload = base58::decode_check(address).ok()?;
+
+    if payload.len() != 21 {
+        return None;
+    }
+
+    let (p2pkh_version, p2sh_version) = base58_versions_from_chain(chain);
+    let hash = &payload[1..];
+
+    let mut spk = Vec::<u8>::new();
+
+    if payload[0] == p2pkh_version {
+        // OP_DUP OP_HASH160 <20> OP_EQUALVERIFY OP_CHECKSIG
+        spk.extend([0x76, 0xa9, 0x14]);
+        spk.extend(hash);
+        spk.extend([0x88, 0xac]);
+    } else if payload[0] == p2sh_version {
+        // OP_HASH160 <20> OP_EQUAL
+        spk.extend([0xa9, 0x14]);
+        spk.extend(hash);
+        spk.push(0x87);
+    } else {
+        return None;
+    }
+
+    Some(spk)
+}
+
+/// Decodes a Bitcoin address into a ScriptPubKey.
 ///
 /// This function takes a Bitcoin address and a network type, and returns the corresponding
-/// ScriptPubKey if the address is valid. Legacy addresses are not supported.
+/// ScriptPubKey if the address is valid. Bech32/Bech32m segwit addresses of any version,
+/// as well as base58 legacy P2PKH and P2SH addresses are supported.
 ///
 /// # Arguments
 ///
@@ -77,7 +137,7 @@ pub fn address_to_spk(chain: Chain, address: &str) -> Option<ScriptPubKey> {
 
     let (hrp, version, program) = match segwit::decode(&address) {
         Ok(result) => result,
-        Err(_) => return None,
+        Err(_) => return base58_address_to_spk(chain, address),
     };
 
     // Check if the network is valid
@@ -132,7 +192,11 @@ pub fn address_to_spk(chain: Chain, address: &str) -> Option<ScriptPubKey> {
             }
         }
         _ => {
-            return None;
+            // Future segwit versions 2 to 16, with 2 to 40 bytes of witness program
+            // (enforced by the segwit decoder).
+            spk.push(0x50 + version.to_u8());
+            spk.push(program.len() as u8);
+            spk.extend(&program);
         }
     }
 
@@ -0,0 +1,100 @@
+/// Standard base64 alphabet (RFC 4648).
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Padding character.
+const PAD: u8 = b'=';
+
+/// Encodes bytes into a padded standard base64 string.
+///
+/// This is the encoding PSBTs are exchanged in (BIP-174).
+pub fn encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() + 2) / 3 * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+
+        encoded.push(ALPHABET[(triple >> 18 & 0x3f) as usize] as char);
+        encoded.push(ALPHABET[(triple >> 12 & 0x3f) as usize] as char);
+
+        match chunk.len() {
+            1 => {
+                encoded.push(PAD as char);
+                encoded.push(PAD as char);
+            }
+            2 => {
+                encoded.push(ALPHABET[(triple >> 6 & 0x3f) as usize] as char);
+                encoded.push(PAD as char);
+            }
+            _ => {
+                encoded.push(ALPHABET[(triple >> 6 & 0x3f) as usize] as char);
+                encoded.push(ALPHABET[(triple & 0x3f) as usize] as char);
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Decodes a padded standard base64 string into bytes.
+///
+/// Returns `None` if the string is not canonically encoded.
+pub fn decode(encoded: &str) -> Option<Vec<u8>> {
+    let encoded = encoded.trim().as_bytes();
+
+    if encoded.len() % 4 != 0 {
+        return None;
+    }
+
+    let mut decoded = Vec::<u8>::with_capacity(encoded.len() / 4 * 3);
+
+    let num_quads = encoded.len() / 4;
+    for (index, quad) in encoded.chunks(4).enumerate() {
+        // Padding may only appear at the end of the last quad.
+        let padding = quad.iter().rev().take_while(|c| **c == PAD).count();
+        if padding > 2 || (padding > 0 && index != num_quads - 1) {
+            return None;
+        }
+
+        let mut triple: u32 = 0;
+        for c in quad[..4 - padding].iter() {
+            triple = (triple << 6) | value(*c)? as u32;
+        }
+        triple <<= 6 * padding as u32;
+
+        let bytes = [(triple >> 16) as u8, (triple >> 8) as u8, triple as u8];
+
+        // Non-canonical trailing bits are rejected.
+        match padding {
+            0 => decoded.extend(bytes),
+            1 => {
+                if bytes[2] != 0 {
+                    return None;
+                }
+                decoded.extend(&bytes[..2]);
+            }
+            _ => {
+                if bytes[1] != 0 || bytes[2] != 0 {
+                    return None;
+                }
+                decoded.push(bytes[0]);
+            }
+        }
+    }
+
+    Some(decoded)
+}
+
+/// Returns the 6-bit value of a base64 character.
+fn value(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{constructive::valtype::short_val::ShortVal, operative::Chain};
+use bech32::{primitives::decode::CheckedHrpstring, Bech32m, Hrp};
+
+/// Human-readable part of mainnet account addresses.
+const ACCOUNT_HRP_MAINNET: &str = "bra";
+
+/// Human-readable part of signet account addresses.
+const ACCOUNT_HRP_SIGNET: &str = "tbra";
+
+/// Human-readable part of mainnet contract addresses.
+const CONTRACT_HRP_MAINNET: &str = "brc";
+
+/// Human-readable part of signet contract addresses.
+const CONTRACT_HRP_SIGNET: &str = "tbrc";
+
+/// Human-readable part of nostr public keys.
+const NPUB_HRP: &str = "npub";
+
+/// The kind of entity an address refers to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EntityKind {
+    Account,
+    Contract,
+}
+
+/// Returns the Human-Readable Part (HRP) for a given entity kind and network.
+fn hrp(kind: EntityKind, chain: Chain) -> Option<Hrp> {
+    let hrp = match (kind, chain) {
+        (EntityKind::Account, Chain::Mainnet) => ACCOUNT_HRP_MAINNET,
+        (EntityKind::Account, Chain::Signet) => ACCOUNT_HRP_SIGNET,
+        (EntityKind::Contract, Chain::Mainnet) => CONTRACT_HRP_MAINNET,
+        (EntityKind::Contract, Chain::Signet) => CONTRACT_HRP_SIGNET,
+    };
+
+    Hrp::parse(hrp).ok()
+}
+
+/// Encodes a 32-byte entity identifier, and optionally its registery index hint,
+/// into a Bech32m address.
+///
+/// The hint is appended in its `ShortVal` compact byte form.
+fn encode(
+    kind: EntityKind,
+    chain: Chain,
+    id: [u8; 32],
+    registery_index: Option<u32>,
+) -> Option<String> {
+    let mut data = id.to_vec();
+
+    if let Some(registery_index) = registery_index {
+        data.extend(ShortVal::new(registery_index).compact_bytes());
+    }
+
+    bech32::encode::<Bech32m>(hrp(kind, chain)?, &data).ok()
+}
+
+/// Decodes a Bech32m address into its 32-byte entity identifier and the optional registery index hint.
+fn decode(kind: EntityKind, chain: Chain, address: &str) -> Option<([u8; 32], Option<u32>)> {
+    // Bech32 (non-m) checksums are rejected.
+    let checked = CheckedHrpstring::new::<Bech32m>(address).ok()?;
+
+    if checked.hrp() != hrp(kind, chain)? {
+        return None;
+    }
+
+    let data: Vec<u8> = checked.byte_iter().collect();
+    if data.len() < 32 {
+        return None;
+    }
+
+    let id: [u8; 32] = data[..32].try_into().ok()?;
+
+    let registery_index = match data.len() {
+        32 => None,
+        _ => {
+            let hint = ShortVal::from_compact_bytes(&data[32..])?;
+
+            // The hint must be in its shortest form.
+            if hint.compact_bytes() != data[32..] {
+                return None;
+            }
+
+            Some(hint.value())
+        }
+    };
+
+    Some((id, registery_index))
+}
+
+/// Encodes an account key into a Bech32m account address.
+pub fn encode_account(chain: Chain, key: [u8; 32], registery_index: Option<u32>) -> Option<String> {
+    encode(EntityKind::Account, chain, key, registery_index)
+}
+
+/// Decodes a Bech32m account address into the account key and the optional registery index hint.
+pub fn decode_account(chain: Chain, address: &str) -> Option<([u8; 32], Option<u32>)> {
+    decode(EntityKind::Account, chain, address)
+}
+
+/// Encodes a contract id into a Bech32m contract address.
+pub fn encode_contract(
+    chain: Chain,
+    contract_id: [u8; 32],
+    registery_index: Option<u32>,
+) -> Option<String> {
+    encode(EntityKind::Contract, chain, contract_id, registery_index)
+}
+
+/// Decodes a Bech32m contract address into the contract id and the optional registery index hint.
+pub fn decode_contract(chain: Chain, address: &str) -> Option<([u8; 32], Option<u32>)> {
+    decode(EntityKind::Contract, chain, address)
+}
+
+/// Returns true if the string looks like a nostr `npub`.
+///
+/// An `npub` is a social identity, and is not accepted where an account is expected.
+pub fn is_npub(address: &str) -> bool {
+    address
+        .to_lowercase()
+        .starts_with(&format!("{}1", NPUB_HRP))
+}
+
+/// Parses an account from either its Bech32m address, or its 32-byte hex key.
+pub fn parse_account(chain: Chain, account: &str) -> Option<([u8; 32], Option<u32>)> {
+    if let Some(decoded) = decode_account(chain, account) {
+        return Some(decoded);
+    }
+
+    let key: [u8; 32] = hex::decode(account).ok()?.try_into().ok()?;
+    Some((key, None))
+}
+
+/// Parses a contract from either its Bech32m address, or its 32-byte hex id.
+pub fn parse_contract(chain: Chain, contract: &str) -> Option<([u8; 32], Option<u32>)> {
+    if let Some(decoded) = decode_contract(chain, contract) {
+        return Some(decoded);
+    }
+
+    let contract_id: [u8; 32] = hex::decode(contract).ok()?.try_into().ok()?;
+    Some((contract_id, None))
+}
@@ -1,3 +1,5 @@
 pub mod address;
+pub mod base64;
 pub mod csv;
+pub mod entity_address;
 pub mod prefix;
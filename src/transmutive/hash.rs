@@ -7,6 +7,7 @@ pub enum HashTag {
     Sighash,
     SighashCombinator,
     SighashEntry,
+    ContractId,
     PayloadAuth,
     SignatureChallenge,
     BIP340Challenge,
@@ -25,6 +26,12 @@ pub enum HashTag {
     MusigNonceCoef,
     // BLSSecretKey
     BLSSecretKey,
+    // Sparse Merkle tree
+    SMTLeaf,
+    SMTBranch,
+    // State tree
+    StateKey,
+    StateValue,
     // Custom
     CustomString(String),
     CustomBytes(Vec<u8>),
@@ -37,6 +44,7 @@ impl HashTag {
             HashTag::Sighash => format!("{}/{}", baked::PROJECT_TAG, "sighash"),
             HashTag::SighashCombinator => format!("{}/{}", baked::PROJECT_TAG, "sighashcombinator"),
             HashTag::SighashEntry => format!("{}/{}", baked::PROJECT_TAG, "sighashentry"),
+            HashTag::ContractId => format!("{}/{}", baked::PROJECT_TAG, "contractid"),
             HashTag::SignatureChallenge => format!("{}/{}", baked::PROJECT_TAG, "challenge"),
             HashTag::BIP340Challenge => format!("{}/{}", "BIP0340", "challenge"),
             HashTag::GroupCommitment => format!("{}/{}", baked::PROJECT_TAG, "groupcommitment"),
@@ -53,6 +61,10 @@ impl HashTag {
             HashTag::MusigNonceCoef => format!("MuSig/noncecoef"),
             HashTag::PayloadAuth => format!("{}/{}", baked::PROJECT_TAG, "payloadauth"),
             HashTag::BLSSecretKey => format!("{}/{}", baked::PROJECT_TAG, "bls/secretkey"),
+            HashTag::SMTLeaf => format!("{}/{}", baked::PROJECT_TAG, "smt/leaf"),
+            HashTag::SMTBranch => format!("{}/{}", baked::PROJECT_TAG, "smt/branch"),
+            HashTag::StateKey => format!("{}/{}", baked::PROJECT_TAG, "state/key"),
+            HashTag::StateValue => format!("{}/{}", baked::PROJECT_TAG, "state/value"),
             HashTag::CustomString(tag) => tag.clone(),
             HashTag::CustomBytes(tag) => tag.clone().into_iter().map(|b| b as char).collect(),
         }
@@ -5,3 +5,4 @@ pub mod key;
 pub mod musig;
 pub mod noist;
 pub mod secp;
+pub mod smt;
@@ -0,0 +1,86 @@
+use super::smt::{branch_hash, key_bit, leaf_hash, EMPTY_ROOT, SMT_DEPTH};
+use serde::{Deserialize, Serialize};
+
+/// Inclusion or exclusion proof of a key in a `SparseMerkleTree`.
+///
+/// Siblings are ordered from the root down. The proof ends at the position of the key,
+/// which is either empty, holds the key itself, or holds another key sharing the path.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SMTProof {
+    siblings: Vec<[u8; 32]>,
+    // Leaf (key, value hash) at the end of the path, if any.
+    leaf: Option<([u8; 32], [u8; 32])>,
+}
+
+impl SMTProof {
+    /// Creates a new proof.
+    pub fn new(siblings: Vec<[u8; 32]>, leaf: Option<([u8; 32], [u8; 32])>) -> SMTProof {
+        SMTProof { siblings, leaf }
+    }
+
+    /// Returns the siblings, ordered from the root down.
+    pub fn siblings(&self) -> Vec<[u8; 32]> {
+        self.siblings.clone()
+    }
+
+    /// Returns the leaf at the end of the path.
+    pub fn leaf(&self) -> Option<([u8; 32], [u8; 32])> {
+        self.leaf
+    }
+
+    /// Serializes the proof.
+    pub fn serialize(&self) -> Vec<u8> {
+        match serde_json::to_vec(self) {
+            Ok(bytes) => bytes,
+            Err(_) => vec![],
+        }
+    }
+
+    /// Verifies that the key holds the value hash under the root.
+    pub fn verify_inclusion(&self, root: [u8; 32], key: [u8; 32], value_hash: [u8; 32]) -> bool {
+        if self.leaf != Some((key, value_hash)) {
+            return false;
+        }
+
+        self.compute_root(&key) == Some(root)
+    }
+
+    /// Verifies that the key is absent under the root.
+    pub fn verify_exclusion(&self, root: [u8; 32], key: [u8; 32]) -> bool {
+        if let Some((leaf_key, _)) = self.leaf {
+            if leaf_key == key {
+                return false;
+            }
+
+            // The other leaf must sit on the path of the key.
+            if (0..self.siblings.len())
+                .any(|depth| key_bit(&leaf_key, depth) != key_bit(&key, depth))
+            {
+                return false;
+            }
+        }
+
+        self.compute_root(&key) == Some(root)
+    }
+
+    /// Folds the siblings from the end of the path up to the root.
+    fn compute_root(&self, key: &[u8; 32]) -> Option<[u8; 32]> {
+        if self.siblings.len() > SMT_DEPTH {
+            return None;
+        }
+
+        let mut hash = match &self.leaf {
+            Some((leaf_key, value_hash)) => leaf_hash(leaf_key, value_hash),
+            None => EMPTY_ROOT,
+        };
+
+        for (depth, sibling) in self.siblings.iter().enumerate().rev() {
+            hash = match key_bit(key, depth) {
+                true => branch_hash(sibling, &hash),
+                false => branch_hash(&hash, sibling),
+            };
+        }
+
+        Some(hash)
+    }
+}
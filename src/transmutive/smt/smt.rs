@@ -0,0 +1,143 @@
+use super::proof::SMTProof;
+use crate::transmutive::hash::{Hash, HashTag};
+use std::collections::BTreeMap;
+
+/// Number of key bits, i.e. the maximum depth of the tree.
+pub const SMT_DEPTH: usize = 256;
+
+/// Root of an empty (sub)tree.
+pub const EMPTY_ROOT: [u8; 32] = [0x00; 32];
+
+/// Sparse Merkle tree over 256-bit keys.
+///
+/// Keys are walked from the most significant bit; a zero bit goes left.
+/// Empty subtrees hash to `EMPTY_ROOT`, and a subtree holding a single leaf hashes to that leaf,
+/// so the tree is only as deep as it takes to tell its keys apart.
+#[derive(Clone, Default, Debug)]
+pub struct SparseMerkleTree {
+    // Value hashes by key.
+    leaves: BTreeMap<[u8; 32], [u8; 32]>,
+}
+
+impl SparseMerkleTree {
+    /// Creates an empty tree.
+    pub fn new() -> SparseMerkleTree {
+        SparseMerkleTree {
+            leaves: BTreeMap::new(),
+        }
+    }
+
+    /// Inserts or updates the value hash of a key.
+    pub fn insert(&mut self, key: [u8; 32], value_hash: [u8; 32]) {
+        self.leaves.insert(key, value_hash);
+    }
+
+    /// Removes a key, returning its value hash if it was present.
+    pub fn remove(&mut self, key: [u8; 32]) -> Option<[u8; 32]> {
+        self.leaves.remove(&key)
+    }
+
+    /// Returns the value hash of a key.
+    pub fn get(&self, key: [u8; 32]) -> Option<[u8; 32]> {
+        self.leaves.get(&key).copied()
+    }
+
+    /// Returns the number of leaves.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Returns whether the tree has no leaves.
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> [u8; 32] {
+        subtree_root(&self.sorted_leaves(), 0)
+    }
+
+    /// Returns the inclusion proof of a key if it is present, or its exclusion proof otherwise.
+    pub fn proof(&self, key: [u8; 32]) -> SMTProof {
+        let leaves = self.sorted_leaves();
+
+        let mut subtree = &leaves[..];
+        let mut siblings = Vec::<[u8; 32]>::new();
+        let mut depth = 0;
+
+        // Walk down until the subtree is empty or a single leaf.
+        while subtree.len() > 1 {
+            let (left, right) = split(subtree, depth);
+
+            match key_bit(&key, depth) {
+                true => {
+                    siblings.push(subtree_root(left, depth + 1));
+                    subtree = right;
+                }
+                false => {
+                    siblings.push(subtree_root(right, depth + 1));
+                    subtree = left;
+                }
+            }
+
+            depth += 1;
+        }
+
+        SMTProof::new(siblings, subtree.first().copied())
+    }
+
+    /// Returns the leaves in key order.
+    fn sorted_leaves(&self) -> Vec<([u8; 32], [u8; 32])> {
+        self.leaves
+            .iter()
+            .map(|(key, value_hash)| (*key, *value_hash))
+            .collect()
+    }
+}
+
+/// Returns the bit of the key at the given depth, most significant bit first.
+pub(super) fn key_bit(key: &[u8; 32], depth: usize) -> bool {
+    (key[depth / 8] >> (7 - depth % 8)) & 1 == 1
+}
+
+/// Hashes a leaf.
+pub(super) fn leaf_hash(key: &[u8; 32], value_hash: &[u8; 32]) -> [u8; 32] {
+    let mut preimage = Vec::<u8>::with_capacity(64);
+    preimage.extend(key);
+    preimage.extend(value_hash);
+
+    preimage.hash(Some(HashTag::SMTLeaf))
+}
+
+/// Hashes a branch.
+pub(super) fn branch_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut preimage = Vec::<u8>::with_capacity(64);
+    preimage.extend(left);
+    preimage.extend(right);
+
+    preimage.hash(Some(HashTag::SMTBranch))
+}
+
+/// Splits sorted leaves sharing a prefix of `depth` bits into the left and right subtrees.
+fn split(leaves: &[([u8; 32], [u8; 32])], depth: usize) -> SubtreePair<'_> {
+    let index = leaves.partition_point(|(key, _)| !key_bit(key, depth));
+    leaves.split_at(index)
+}
+
+/// Left and right subtree leaves.
+type SubtreePair<'a> = (&'a [([u8; 32], [u8; 32])], &'a [([u8; 32], [u8; 32])]);
+
+/// Returns the root of a subtree of sorted leaves sharing a prefix of `depth` bits.
+fn subtree_root(leaves: &[([u8; 32], [u8; 32])], depth: usize) -> [u8; 32] {
+    match leaves {
+        [] => EMPTY_ROOT,
+        [(key, value_hash)] => leaf_hash(key, value_hash),
+        _ => {
+            let (left, right) = split(leaves, depth);
+            branch_hash(
+                &subtree_root(left, depth + 1),
+                &subtree_root(right, depth + 1),
+            )
+        }
+    }
+}
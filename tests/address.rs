@@ -2,7 +2,15 @@
 mod address_tests {
     use brollup::{
         operative::Chain,
-        transmutive::codec::address::{address_to_spk, encode_p2tr, encode_p2wpkh, encode_p2wsh},
+        transmutive::codec::{
+            address::{
+                address_to_spk, encode_p2pkh, encode_p2sh, encode_p2tr, encode_p2wpkh, encode_p2wsh,
+            },
+            entity_address::{
+                decode_account, decode_contract, encode_account, encode_contract, is_npub,
+                parse_account,
+            },
+        },
     };
 
     #[test]
@@ -130,4 +138,180 @@ mod address_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn p2pkh_test() -> Result<(), String> {
+        let pubkey_hash: [u8; 20] = hex::decode("7680adec8eabcabac676be9e83854ade0bd22cdb")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let signet_address = encode_p2pkh(Chain::Signet, pubkey_hash);
+        let mainnet_address = encode_p2pkh(Chain::Mainnet, pubkey_hash);
+        assert_eq!(mainnet_address, "1BoatSLRHtKNngkdXEeobR76b53LETtpyT");
+
+        // OP_DUP OP_HASH160 <20> OP_EQUALVERIFY OP_CHECKSIG
+        let expected_spk =
+            hex::decode("76a9147680adec8eabcabac676be9e83854ade0bd22cdb88ac").unwrap();
+
+        assert_eq!(
+            address_to_spk(Chain::Signet, &signet_address).unwrap(),
+            expected_spk
+        );
+        assert_eq!(
+            address_to_spk(Chain::Mainnet, &mainnet_address).unwrap(),
+            expected_spk
+        );
+
+        // Network mismatch.
+        assert!(address_to_spk(Chain::Signet, &mainnet_address).is_none());
+        assert!(address_to_spk(Chain::Mainnet, &signet_address).is_none());
+
+        // Bad checksum.
+        assert!(address_to_spk(Chain::Mainnet, "1BoatSLRHtKNngkdXEeobR76b53LETtpyU").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn p2sh_test() -> Result<(), String> {
+        let script_hash: [u8; 20] = hex::decode("b472a266d0bd89c13706a4132ccfb16f7c3b9fcb")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(
+            encode_p2sh(Chain::Mainnet, script_hash),
+            "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy"
+        );
+        assert_eq!(
+            encode_p2sh(Chain::Signet, script_hash),
+            "2N9hLwkSqr1cPQAPxbrGVUjxyjD11G2e1he"
+        );
+
+        // OP_HASH160 <20> OP_EQUAL
+        let expected_spk = hex::decode("a914b472a266d0bd89c13706a4132ccfb16f7c3b9fcb87").unwrap();
+
+        assert_eq!(
+            address_to_spk(Chain::Mainnet, "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy").unwrap(),
+            expected_spk
+        );
+        assert_eq!(
+            address_to_spk(Chain::Signet, "2N9hLwkSqr1cPQAPxbrGVUjxyjD11G2e1he").unwrap(),
+            expected_spk
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn future_segwit_to_spk_test() -> Result<(), String> {
+        // BIP-350 test vectors.
+        assert_eq!(
+            address_to_spk(Chain::Mainnet, "BC1SW50QGDZ25J").unwrap(),
+            hex::decode("6002751e").unwrap()
+        );
+        assert_eq!(
+            address_to_spk(Chain::Mainnet, "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs").unwrap(),
+            hex::decode("5210751e76e8199196d454941c45d1b3a323").unwrap()
+        );
+
+        // Segwit v1 programs other than 32 bytes are not P2TR.
+        assert!(address_to_spk(
+            Chain::Mainnet,
+            "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y"
+        )
+        .is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn account_address_test() -> Result<(), String> {
+        let key: [u8; 32] =
+            hex::decode("9f5e030b7111d8e1e757d31944df5bf0714c94368cc0b499bef0badacb67c2a8")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        let mainnet_address = encode_account(Chain::Mainnet, key, None).unwrap();
+        let signet_address = encode_account(Chain::Signet, key, None).unwrap();
+        assert!(mainnet_address.starts_with("bra1"));
+        assert!(signet_address.starts_with("tbra1"));
+
+        assert_eq!(
+            decode_account(Chain::Mainnet, &mainnet_address),
+            Some((key, None))
+        );
+        assert_eq!(
+            decode_account(Chain::Signet, &signet_address),
+            Some((key, None))
+        );
+
+        // Network mismatch.
+        assert!(decode_account(Chain::Signet, &mainnet_address).is_none());
+        assert!(decode_account(Chain::Mainnet, &signet_address).is_none());
+
+        // Registery index hints of every compact size round-trip.
+        for registery_index in [0, 255, 256, 65_536, 16_777_216] {
+            let address = encode_account(Chain::Mainnet, key, Some(registery_index)).unwrap();
+            assert_eq!(
+                decode_account(Chain::Mainnet, &address),
+                Some((key, Some(registery_index)))
+            );
+        }
+
+        // Registery index hints must be in their shortest form.
+        let hrp = bech32::Hrp::parse("bra").unwrap();
+        let mut data = key.to_vec();
+        data.extend([0x05, 0x00]);
+        let non_canonical = bech32::encode::<bech32::Bech32m>(hrp, &data).unwrap();
+        assert!(decode_account(Chain::Mainnet, &non_canonical).is_none());
+
+        // Account addresses are not contract addresses.
+        assert!(decode_contract(Chain::Mainnet, &mainnet_address).is_none());
+
+        // Bech32 (non-m) checksums are rejected.
+        let hrp = bech32::Hrp::parse("bra").unwrap();
+        let bech32_address = bech32::encode::<bech32::Bech32>(hrp, &key).unwrap();
+        assert!(decode_account(Chain::Mainnet, &bech32_address).is_none());
+
+        // Hex keys are accepted, npubs are flagged.
+        assert_eq!(
+            parse_account(Chain::Mainnet, &hex::encode(key)),
+            Some((key, None))
+        );
+        assert_eq!(
+            parse_account(Chain::Mainnet, &mainnet_address),
+            Some((key, None))
+        );
+        let npub = "npub1na0qxzm3z8vwre6h6vv5fh6m7pc5e9pk3nqtfxd77zad4jm8c25qf0xkfu";
+        assert!(is_npub(npub));
+        assert!(parse_account(Chain::Mainnet, npub).is_none());
+        assert!(!is_npub(&mainnet_address));
+
+        Ok(())
+    }
+
+    #[test]
+    fn contract_address_test() -> Result<(), String> {
+        let contract_id = [0xab; 32];
+
+        let mainnet_address = encode_contract(Chain::Mainnet, contract_id, Some(7)).unwrap();
+        let signet_address = encode_contract(Chain::Signet, contract_id, None).unwrap();
+        assert!(mainnet_address.starts_with("brc1"));
+        assert!(signet_address.starts_with("tbrc1"));
+
+        assert_eq!(
+            decode_contract(Chain::Mainnet, &mainnet_address),
+            Some((contract_id, Some(7)))
+        );
+        assert_eq!(
+            decode_contract(Chain::Signet, &signet_address),
+            Some((contract_id, None))
+        );
+        assert!(decode_account(Chain::Mainnet, &mainnet_address).is_none());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod archive_tests {
+    use brollup::inscriptive::{
+        archive::dir::ArchiveDirectory, balance::dir::BalanceDirectory,
+        registery::registery::Registery,
+    };
+    use secp::Point;
+
+    #[tokio::test]
+    async fn archive_test() -> Result<(), String> {
+        let archive_dir = ArchiveDirectory::new_temporary().unwrap();
+        let balance_dir = BalanceDirectory::new_temporary().unwrap();
+        let registery = Registery::new_temporary().unwrap();
+
+        let account_key =
+            Point::from_hex("02cb70281face51a77d51400612196032bb12422d4c07fa42997a0ab39c2431455")
+                .unwrap();
+        let account_key_bytes = account_key.serialize_xonly();
+
+        let mut _archive_dir = archive_dir.lock().await;
+        let start_height = u64::MAX - 10;
+
+        // Archive the balance as it moves from 100 to 250, and is then emptied.
+        for (height, balance) in [
+            (start_height, 100),
+            (start_height + 1, 250),
+            (start_height + 2, 0),
+        ] {
+            {
+                let mut _balance_dir = balance_dir.lock().await;
+                assert!(
+                    _balance_dir.restore_balances(&(vec![(account_key_bytes, balance)], vec![]))
+                );
+            }
+
+            assert!(
+                _archive_dir
+                    .archive_state(height, &balance_dir, &registery)
+                    .await
+            );
+        }
+
+        assert_eq!(
+            _archive_dir.archived_heights(),
+            Some((start_height, start_height + 2))
+        );
+        assert_eq!(
+            _archive_dir.account_balance_at(account_key, start_height),
+            Some(100)
+        );
+        assert_eq!(
+            _archive_dir.account_balance_at(account_key, start_height + 1),
+            Some(250)
+        );
+        assert_eq!(
+            _archive_dir.account_balance_at(account_key, start_height + 2),
+            Some(0)
+        );
+
+        // Heights outside the archive are unknown.
+        assert_eq!(
+            _archive_dir.account_balance_at(account_key, start_height - 1),
+            None
+        );
+        assert_eq!(
+            _archive_dir.account_balance_at(account_key, start_height + 3),
+            None
+        );
+
+        // Only the last archived height can be removed.
+        assert!(!_archive_dir.remove_height(start_height + 1));
+        assert!(_archive_dir.remove_height(start_height + 2));
+        assert_eq!(
+            _archive_dir.archived_heights(),
+            Some((start_height, start_height + 1))
+        );
+        assert_eq!(
+            _archive_dir.account_balance_at(account_key, start_height + 1),
+            Some(250)
+        );
+
+        // Heights must increase.
+        assert!(
+            !_archive_dir
+                .archive_state(start_height + 1, &balance_dir, &registery)
+                .await
+        );
+
+        // Skipping heights keeps the archived ones, and records a full snapshot at the new height.
+        {
+            let mut _balance_dir = balance_dir.lock().await;
+            assert!(_balance_dir.restore_balances(&(vec![(account_key_bytes, 250)], vec![])));
+        }
+        assert!(
+            _archive_dir
+                .archive_state(start_height + 5, &balance_dir, &registery)
+                .await
+        );
+        assert_eq!(
+            _archive_dir.archived_heights(),
+            Some((start_height, start_height + 5))
+        );
+        assert_eq!(
+            _archive_dir.account_balance_at(account_key, start_height),
+            Some(100)
+        );
+        assert_eq!(
+            _archive_dir.account_balance_at(account_key, start_height + 5),
+            Some(250)
+        );
+
+        // Skipped heights are unknown.
+        for height in start_height + 2..start_height + 5 {
+            assert_eq!(_archive_dir.account_balance_at(account_key, height), None);
+        }
+
+        // Removing the snapshot falls back to the heights archived before it.
+        assert!(_archive_dir.remove_height(start_height + 5));
+        assert_eq!(
+            _archive_dir.archived_heights(),
+            Some((start_height, start_height + 1))
+        );
+        assert_eq!(
+            _archive_dir.account_balance_at(account_key, start_height + 1),
+            Some(250)
+        );
+
+        // Removing every archived height empties the archive.
+        assert!(_archive_dir.remove_height(start_height + 1));
+        assert!(_archive_dir.remove_height(start_height));
+        assert_eq!(_archive_dir.archived_heights(), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod balance_tests {
+    use bitcoin::OutPoint;
+    use brollup::{
+        constructive::{
+            entity::account::Account,
+            entry::{
+                combinator::{
+                    batch_move::BatchMove, deploy::Deploy, liftup::Liftup, r#move::Move,
+                    recharge::Recharge, swapout::Swapout,
+                },
+                entry::Entry,
+            },
+            txo::{lift::Lift, vtxo::VTXO},
+        },
+        executive::{
+            opcode::{op::push::op_true::OP_TRUE, opcode::Opcode},
+            program::{
+                method::{method::ProgramMethod, method_type::MethodType},
+                program::Program,
+            },
+        },
+        inscriptive::balance::dir::BalanceDirectory,
+    };
+    use secp::Point;
+
+    #[tokio::test]
+    async fn balance_test() -> Result<(), String> {
+        let balance_dir = BalanceDirectory::new_temporary().unwrap();
+        let mut _balance_dir = balance_dir.lock().await;
+
+        let key_1 =
+            Point::from_hex("021123864025e2c24bd82e6e19729eaa93cf02c57149bbfc84d239a0369f471316")
+                .unwrap();
+        let key_2 =
+            Point::from_hex("02fbdf138aaa8e1e0446b3641cdc7f2d92b4442a80bbb191b8d96b653ccd270e44")
+                .unwrap();
+        let account_1 = Account::new(key_1, None, None).unwrap();
+        let account_2 = Account::new(key_2, None, None).unwrap();
+
+        // Liftups and recharges credit the entry account.
+        let lift = Lift::new(key_1, key_2, Some(OutPoint::null()), Some(10_000));
+        let vtxo = VTXO::new(
+            key_1,
+            key_2,
+            Some(OutPoint::null()),
+            Some(2_500),
+            Some(1),
+            None,
+        );
+        let entries = vec![Entry::new_nop(
+            account_1,
+            Some(Liftup::new(vec![lift]).unwrap()),
+            Some(Recharge::new(vec![vtxo]).unwrap()),
+        )];
+        assert!(_balance_dir.apply_entries(&entries));
+        assert_eq!(_balance_dir.account_balance(key_1), 12_500);
+
+        // Moves debit the sender and credit the receiver.
+        let entries = vec![
+            Entry::new_move(
+                account_1,
+                Move::new(account_1, account_2, 4_000),
+                None,
+                None,
+            ),
+            Entry::new_batch_move(
+                account_1,
+                BatchMove::new(account_1, vec![(account_2, 1_000), (account_2, 500)]).unwrap(),
+                None,
+                None,
+            ),
+        ];
+        assert!(_balance_dir.validate_entries(&entries));
+        assert!(_balance_dir.apply_entries(&entries));
+        assert_eq!(_balance_dir.account_balance(key_1), 7_000);
+        assert_eq!(_balance_dir.account_balance(key_2), 5_500);
+
+        // Entries are applied in order; a later entry can spend an earlier credit.
+        let entries = vec![
+            Entry::new_move(
+                account_1,
+                Move::new(account_1, account_2, 1_000),
+                None,
+                None,
+            ),
+            Entry::new_swapout(
+                account_2,
+                Swapout::new_p2tr(account_2, 6_500, [0x22; 32]),
+                None,
+                None,
+            ),
+        ];
+        assert!(_balance_dir.apply_entries(&entries));
+        assert_eq!(_balance_dir.account_balance(key_2), 0);
+        assert_eq!(_balance_dir.account_balance(key_1), 6_000);
+
+        // An overdrawing entry fails the whole session, leaving the balances untouched.
+        let balance_1 = _balance_dir.account_balance(key_1);
+        let balance_2 = _balance_dir.account_balance(key_2);
+        let entries = vec![
+            Entry::new_move(
+                account_1,
+                Move::new(account_1, account_2, 1_000),
+                None,
+                None,
+            ),
+            Entry::new_swapout(
+                account_2,
+                Swapout::new_p2tr(account_2, u32::MAX, [0x22; 32]),
+                None,
+                None,
+            ),
+        ];
+        assert!(!_balance_dir.validate_entries(&entries));
+        assert!(!_balance_dir.apply_entries(&entries));
+        assert_eq!(_balance_dir.account_balance(key_1), balance_1);
+        assert_eq!(_balance_dir.account_balance(key_2), balance_2);
+
+        // Deploys move the balance seed from the deployer to the contract.
+        let method = ProgramMethod::new(
+            "ok".to_string(),
+            MethodType::Callable,
+            vec![],
+            vec![Opcode::OP_TRUE(OP_TRUE); 4],
+        )
+        .unwrap();
+        let program = Program::new("Seeded".to_string(), vec![method]).unwrap();
+        let deploy = Deploy::new(account_1, 0, &program, vec![], 1_500).unwrap();
+        let contract_id = deploy.contract_id();
+        let entries = vec![Entry::new_deploy(account_1, deploy, None, None)];
+        let (prior_accounts, prior_contracts) = _balance_dir.prior_balances(&entries).unwrap();
+        assert_eq!(prior_accounts, vec![(key_1.serialize_xonly(), 6_000)]);
+        assert_eq!(prior_contracts, vec![(contract_id, 0)]);
+        assert!(_balance_dir.apply_entries(&entries));
+        assert_eq!(_balance_dir.account_balance(key_1), 4_500);
+        assert_eq!(_balance_dir.contract_balance(contract_id), 1_500);
+
+        // A balance seed the deployer cannot cover fails the deploy.
+        let deploy = Deploy::new(account_2, 0, &program, vec![], 1).unwrap();
+        let entries = vec![Entry::new_deploy(account_2, deploy, None, None)];
+        assert!(!_balance_dir.apply_entries(&entries));
+
+        // Swapouts spend the rest.
+        let entries = vec![Entry::new_swapout(
+            account_1,
+            Swapout::new_p2tr(account_1, 4_500, [0x11; 32]),
+            None,
+            None,
+        )];
+        assert!(_balance_dir.apply_entries(&entries));
+        assert_eq!(_balance_dir.account_balance(key_1), 0);
+        assert_eq!(_balance_dir.account_balance(key_2), 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod channel_tests {
+    use bitcoin::{hashes::Hash, OutPoint, Txid};
+    use brollup::constructive::{
+        taproot::P2TR,
+        txo::channel::{Channel, ChannelState, CHANNEL_END_DAYS, CHANNEL_PERIODS},
+    };
+    use secp::{Point, Scalar};
+
+    fn key(seed: u8) -> Point {
+        Scalar::from_slice(&[seed; 32]).unwrap().base_point_mul()
+    }
+
+    #[test]
+    fn channel_test() -> Result<(), String> {
+        let mut channel = Channel::new(
+            key(0x01),
+            key(0x7f),
+            Some(OutPoint::new(Txid::all_zeros(), 0)),
+            Some(100_000),
+        );
+
+        // 128 state leaves, each behind a 7-level merkle path.
+        let taproot = channel.taproot().unwrap();
+        assert_eq!(taproot.tree().unwrap().leaves().len(), 128);
+        assert_eq!(
+            taproot.control_block(127).unwrap().to_vec().len(),
+            33 + 7 * 32
+        );
+        assert_eq!(channel.spk().unwrap().len(), 34);
+
+        // First state spends from the first leaf after 141 days.
+        let (first, revocation) = channel.next_state(60_000, 40_000).unwrap();
+        assert!(revocation.is_none());
+        assert_eq!(first.number(), 1);
+        assert_eq!(first.days(), 141);
+        assert_eq!(first.n_sequence(), 141 * 144);
+        let leaf = taproot.tree().unwrap().leaves()[first.leaf_index()].tap_script();
+        assert_eq!(&leaf[..5], &[0x02, 0x50, 0x4f, 0xb2, 0x75]);
+        assert_eq!(&leaf[6..38], &channel.agg_key().unwrap().serialize_xonly());
+
+        // Values may not exceed the channel value.
+        assert!(channel.next_state(60_000, 40_001).is_none());
+
+        // States must be applied in order.
+        assert!(!channel.update(ChannelState::new(2, 0, 0).unwrap()));
+        assert!(channel.update(first));
+
+        // The second state revokes the first one by a day.
+        let (second, revocation) = channel.next_state(55_000, 45_000).unwrap();
+        let revocation = revocation.unwrap();
+        assert_eq!(second.days(), 140);
+        assert_eq!(revocation.revoked_state(), 1);
+        assert_eq!(revocation.revoked_sequence(), first.n_sequence());
+        assert_eq!(revocation.precedence(), 144);
+        assert!(revocation.revokes(&first));
+        assert!(!revocation.revokes(&second));
+        assert!(channel.update(second));
+        assert_eq!(channel.states_left(), 126);
+
+        // The channel is exhausted after the last state.
+        for _ in 3..=CHANNEL_PERIODS {
+            let (next, _) = channel.next_state(50_000, 50_000).unwrap();
+            assert!(channel.update(next));
+        }
+        assert_eq!(channel.state().unwrap().days(), CHANNEL_END_DAYS);
+        assert_eq!(CHANNEL_END_DAYS, 14);
+        assert!(channel.next_state(50_000, 50_000).is_none());
+        assert!(ChannelState::new(0, 0, 0).is_none());
+        assert!(ChannelState::new(129, 0, 0).is_none());
+
+        Ok(())
+    }
+}
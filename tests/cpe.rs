@@ -1,22 +1,56 @@
 #[cfg(test)]
 mod cpe_tests {
     use bit_vec::BitVec;
+    use bitcoin::{absolute::LockTime, transaction::Version, Transaction};
     use brollup::{
         constructive::{
+            calldata::element::CallElement,
             cpe::cpe::CompactPayloadEncoding,
             entity::{account::Account, contract::Contract},
+            entry::{
+                combinator::{
+                    add::Add,
+                    batch_move::BatchMove,
+                    call::Call,
+                    deploy::{Deploy, MAX_BYTECODE_LENGTH},
+                    r#move::Move,
+                    reserved::Reserved,
+                    sub::Sub,
+                    swapout::Swapout,
+                },
+                decode_ctx::EntryDecodeCtx,
+                entry::Entry,
+                estimate::EntryEstimate,
+            },
+            txn::txholder::TxHolder,
             valtype::{
                 atomic_val::AtomicVal,
                 long_val::{LongVal, LongValTier},
                 maybe_common::{
-                    common_long::CommonLongVal, common_short::CommonShortVal,
+                    common_long::CommonLongVal,
+                    common_short::CommonShortVal,
+                    common_table::{CommonTable, CommonValueStats},
                     maybe_common::MaybeCommon,
                 },
                 short_val::{ShortVal, ShortValTier},
             },
         },
-        inscriptive::registery::registery::Registery,
-        operative::Chain,
+        executive::{
+            lang::compiler::compile_contract,
+            opcode::{op::push::op_true::OP_TRUE, opcode::Opcode},
+            program::{
+                compiler::compiler::ProgramCompiler,
+                method::{method::ProgramMethod, method_type::MethodType},
+                program::Program,
+            },
+        },
+        inscriptive::{
+            epoch::dir::EpochDirectory,
+            registery::registery::Registery,
+            repo::repo::ProgramRepo,
+            set::{swept_set::SweptSet, utxo_set::UTXOSet, vtxo_set::VTXOSet},
+        },
+        transmutive::secp::authenticable::AuthSighash,
     };
     use secp::Point;
     use std::collections::HashMap;
@@ -406,7 +440,7 @@ mod cpe_tests {
     #[tokio::test]
     async fn account_and_contract_test() -> Result<(), String> {
         // Get the registery.
-        let registery = Registery::new(Chain::Signet).unwrap();
+        let registery = Registery::new_temporary().unwrap();
 
         // Get the account registery.
         let account_registery = {
@@ -999,6 +1033,61 @@ mod cpe_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_common_table() -> Result<(), String> {
+        let genesis = CommonTable::genesis();
+
+        // The genesis table keeps the original indexes.
+        assert_eq!(genesis.version(), 0);
+        assert_eq!(genesis.short_index(50), Some(0));
+        assert_eq!(genesis.short_index(1_000), Some(11));
+        assert_eq!(genesis.short_index(100_000_000), Some(63));
+        assert_eq!(genesis.long_index(10_000_000_000_000_000), Some(127));
+        assert_eq!(CommonTable::scheduled(0), Some(genesis.clone()));
+        assert_eq!(CommonTable::latest(0), genesis);
+
+        // Tables must be complete and free of duplicates.
+        assert!(CommonTable::new(1, 10, vec![1; 64], genesis.long_values()).is_none());
+        assert!(CommonTable::new(1, 10, vec![], genesis.long_values()).is_none());
+
+        // Record traffic favouring amounts that are uncommon in the genesis table.
+        let mut stats = CommonValueStats::new();
+        for _ in 0..100 {
+            stats.insert_short(21_000);
+            stats.insert_short(1_000);
+        }
+        stats.insert_short(123_456_789);
+        stats.insert_long(u64::MAX);
+        assert_eq!(stats.short_count(), 201);
+        assert_eq!(stats.long_count(), 0);
+
+        let proposed = CommonTable::propose(1, 10, &stats).unwrap();
+        assert_eq!(proposed.version(), 1);
+        assert!(!proposed.is_active(9));
+        assert!(proposed.is_active(10));
+        assert!(proposed.short_index(21_000).is_some());
+        assert!(proposed.short_index(123_456_789).is_some());
+        assert!(proposed.short_index(1_000).is_some());
+        assert_eq!(proposed.short_values().len(), 64);
+        assert_eq!(proposed.long_values(), genesis.long_values());
+        assert!(stats.saved_bits(&proposed) > stats.saved_bits(&genesis));
+
+        // Values round-trip through the proposed table.
+        let maybe_common = MaybeCommon::new_with_table(ShortVal::new(21_000), &proposed);
+        assert!(maybe_common.is_common());
+        assert_eq!(maybe_common.encode_cpe().unwrap().len(), 7);
+        let encoded = maybe_common.encode_cpe().unwrap();
+        let decoded =
+            MaybeCommon::<ShortVal>::decode_cpe_with_table(&mut encoded.iter(), &proposed).unwrap();
+        assert_eq!(decoded.value().value(), 21_000);
+
+        // The same bits decode differently under the genesis table.
+        let decoded = MaybeCommon::<ShortVal>::decode_cpe(&mut encoded.iter()).unwrap();
+        assert_ne!(decoded.value().value(), 21_000);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn varbytes_0_to_4096_12_bit_test() -> Result<(), String> {
         // 0 to 4095
@@ -1059,4 +1148,426 @@ mod cpe_tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn deploy_test() -> Result<(), String> {
+        let program = compile_contract(
+            "contract Vault { storage owners: map<u8, u8>; readonly fn ping() -> bool { return true; } }",
+        )
+        .map_err(|e| e.to_string())?;
+
+        let deployer = Account::new(
+            Point::from_hex("021123864025e2c24bd82e6e19729eaa93cf02c57149bbfc84d239a0369f471316")
+                .unwrap(),
+            None,
+            None,
+        )
+        .unwrap();
+
+        let storage_seed = vec![(vec![0x01, 0x00], vec![0xaa; 32])];
+        let deploy = Deploy::new(deployer, 7, &program, storage_seed.clone(), 1000).unwrap();
+
+        // The deployed program decompiles back to the original.
+        assert_eq!(deploy.program().unwrap(), program);
+
+        // Round-trip the deploy.
+        let encoded = deploy.encode_cpe().unwrap();
+        let mut bit_stream = encoded.iter();
+        let decoded = Deploy::decode_cpe(&mut bit_stream, deployer).unwrap();
+        assert!(decoded == deploy);
+        assert_eq!(decoded.nonce(), 7);
+        assert_eq!(decoded.storage_seed(), storage_seed);
+        assert_eq!(decoded.balance_seed(), 1000);
+        assert!(bit_stream.next().is_none());
+
+        // The contract id depends on the deployer, the nonce and the bytecode, but not on the seed.
+        let reseeded = Deploy::new(deployer, 7, &program, vec![], 0).unwrap();
+        assert_eq!(reseeded.contract_id(), deploy.contract_id());
+
+        // Redeployments under a new nonce get a new contract id.
+        let redeployed = Deploy::new(deployer, 8, &program, vec![], 0).unwrap();
+        assert_ne!(redeployed.contract_id(), deploy.contract_id());
+
+        let other_deployer = Account::new(
+            Point::from_hex("02fbdf138aaa8e1e0446b3641cdc7f2d92b4442a80bbb191b8d96b653ccd270e44")
+                .unwrap(),
+            None,
+            None,
+        )
+        .unwrap();
+        let other = Deploy::new(other_deployer, 7, &program, vec![], 0).unwrap();
+        assert_ne!(other.contract_id(), deploy.contract_id());
+
+        // Invalid storage seeds are rejected.
+        assert!(Deploy::new(deployer, 0, &program, vec![(vec![], vec![0x01])], 0).is_none());
+        assert!(Deploy::new(deployer, 0, &program, vec![(vec![0x01], vec![])], 0).is_none());
+        assert!(Deploy::new(
+            deployer,
+            0,
+            &program,
+            vec![(vec![0x01], vec![0x01]), (vec![0x01], vec![0x02])],
+            0
+        )
+        .is_none());
+
+        // Programs are deployable up to the decodable bytecode length.
+        let program_of = |opcode_count: usize| {
+            let method = ProgramMethod::new(
+                "fill".to_string(),
+                MethodType::Callable,
+                vec![],
+                vec![Opcode::OP_TRUE(OP_TRUE); opcode_count],
+            )
+            .unwrap();
+            Program::new("Filler".to_string(), vec![method]).unwrap()
+        };
+        let overhead = program_of(4).compile().unwrap().len() - 4;
+        let largest = program_of(MAX_BYTECODE_LENGTH - overhead);
+        assert_eq!(largest.compile().unwrap().len(), MAX_BYTECODE_LENGTH);
+
+        let deploy = Deploy::new(deployer, 0, &largest, vec![], 0).unwrap();
+        let encoded = deploy.encode_cpe().unwrap();
+        let decoded = Deploy::decode_cpe(&mut encoded.iter(), deployer).unwrap();
+        assert!(decoded == deploy);
+
+        // Programs past the bound are rejected rather than encoded undecodable.
+        let oversized = program_of(MAX_BYTECODE_LENGTH - overhead + 1);
+        assert_eq!(oversized.compile().unwrap().len(), MAX_BYTECODE_LENGTH + 1);
+        assert!(Deploy::new(deployer, 0, &oversized, vec![], 0).is_none());
+
+        let mut deploy_json: serde_json::Value =
+            serde_json::from_slice(&deploy.serialize()).unwrap();
+        deploy_json["bytecode"] = serde_json::to_value(oversized.compile().unwrap()).unwrap();
+        let oversized_deploy: Deploy = serde_json::from_value(deploy_json).unwrap();
+        assert!(oversized_deploy.encode_cpe().is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn entry_test() -> Result<(), String> {
+        let registery = Registery::new_temporary().unwrap();
+        let program_repo = ProgramRepo::new_temporary().unwrap();
+        let ctx = EntryDecodeCtx::new(
+            &EpochDirectory::new_temporary().unwrap(),
+            &registery,
+            &program_repo,
+            &VTXOSet::new_temporary().unwrap(),
+            &SweptSet::new_temporary().unwrap(),
+            900_000,
+        );
+
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        };
+        let mut txholder = TxHolder::new(&UTXOSet::new_temporary().unwrap(), tx, 0).unwrap();
+
+        // Unregistered accounts.
+        let account = Account::new(
+            Point::from_hex("021123864025e2c24bd82e6e19729eaa93cf02c57149bbfc84d239a0369f471316")
+                .unwrap(),
+            None,
+            None,
+        )
+        .unwrap();
+        let to = Account::new(
+            Point::from_hex("02fbdf138aaa8e1e0446b3641cdc7f2d92b4442a80bbb191b8d96b653ccd270e44")
+                .unwrap(),
+            None,
+            None,
+        )
+        .unwrap();
+
+        // Deploy and register a contract to call.
+        let program = compile_contract(
+            "contract Token { storage balances: map<account, u64>; callable fn mint(to: account, amount: u64, memo: bool) { balances[to] = amount; } }",
+        )
+        .map_err(|e| e.to_string())?;
+        let deploy = Deploy::new(account, 0, &program, vec![], 0).unwrap();
+        let contract_registery = {
+            let _registery = registery.lock().await;
+            _registery.contract_registery()
+        };
+        let is_registered = {
+            let _contract_registery = contract_registery.lock().await;
+            _contract_registery.is_registered(deploy.contract_id())
+        };
+        if !is_registered {
+            assert!(deploy.register(&contract_registery, &program_repo).await);
+        }
+        let contract = {
+            let _contract_registery = contract_registery.lock().await;
+            _contract_registery
+                .contract_by_contract_id(deploy.contract_id())
+                .unwrap()
+        };
+
+        let call = Call::new(
+            account,
+            contract,
+            &program,
+            0,
+            vec![
+                CallElement::Account(to),
+                CallElement::U64(LongVal::new(10_000)),
+                CallElement::Bool(true),
+            ],
+        )
+        .unwrap();
+
+        // Calldata must match the method call element types.
+        assert!(Call::new(account, contract, &program, 0, vec![]).is_none());
+
+        let entries = vec![
+            Entry::new_move(account, Move::new(account, to, 5_000), None, None),
+            Entry::new_batch_move(
+                account,
+                BatchMove::new(account, vec![(to, 5_000), (account, 1), (to, 250_000)]).unwrap(),
+                None,
+                None,
+            ),
+            Entry::new_call(account, call, None, None),
+            Entry::new_add(account, Add::new(account, 100), None, None),
+            Entry::new_sub(account, Sub::new(account, 123_456), None, None),
+            Entry::new_deploy(account, deploy, None, None),
+            Entry::new_swapout(
+                account,
+                Swapout::new_p2wpkh(account, 7_000, [0x11; 20]),
+                None,
+                None,
+            ),
+            Entry::new_swapout(
+                account,
+                Swapout::new_p2tr(account, 7_000, [0x22; 32]),
+                None,
+                None,
+            ),
+            Entry::new_swapout(
+                account,
+                Swapout::new_p2pkh(account, 7_000, [0x33; 20]),
+                None,
+                None,
+            ),
+            Entry::new_swapout(
+                account,
+                Swapout::new_p2sh(account, 7_000, [0x44; 20]),
+                None,
+                None,
+            ),
+            Entry::new_swapout(
+                account,
+                Swapout::new_witness_program(account, 7_000, 16, vec![0x75, 0x1e]).unwrap(),
+                None,
+                None,
+            ),
+        ];
+
+        // Round-trip the entries.
+        for entry in entries.iter() {
+            let encoded = entry.encode_cpe().unwrap();
+            let mut bit_stream = encoded.iter();
+            let decoded = Entry::decode_cpe(
+                &mut bit_stream,
+                &mut txholder,
+                &ctx,
+                &CommonTable::genesis(),
+            )
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+            assert!(decoded == *entry);
+            assert!(bit_stream.next().is_none());
+        }
+
+        // Branch bits of an `Add` entry: left off, right on, upper right, liquidity, add.
+        let encoded = entries[3].encode_cpe().unwrap();
+        let account_bits_len = account.encode_cpe().unwrap().len();
+        let branch_bits: Vec<bool> = encoded.iter().skip(account_bits_len).take(5).collect();
+        assert_eq!(branch_bits, vec![false, true, true, false, false]);
+
+        // Branch bits of a `Move` entry: left off, right on, transactive, move.
+        let encoded = entries[0].encode_cpe().unwrap();
+        let branch_bits: Vec<bool> = encoded.iter().skip(account_bits_len).take(4).collect();
+        assert_eq!(branch_bits, vec![false, true, false, false]);
+        assert_eq!(
+            encoded.len(),
+            account_bits_len + 4 + Move::new(account, to, 5_000).encode_cpe().unwrap().len()
+        );
+
+        // Branch bits of a `BatchMove` entry: left off, right on, upper right, right, lower right, reserved, batch move.
+        let encoded = entries[1].encode_cpe().unwrap();
+        let branch_bits: Vec<bool> = encoded.iter().skip(account_bits_len).take(7).collect();
+        assert_eq!(
+            branch_bits,
+            vec![false, true, true, true, true, true, false]
+        );
+
+        // P2TR swapouts take a single type bit, other types a type bit and an `AtomicVal`.
+        let amount_bits_len = MaybeCommon::<ShortVal>::new(ShortVal::new(7_000))
+            .encode_cpe()
+            .unwrap()
+            .len();
+        let p2tr = Swapout::new_p2tr(account, 7_000, [0x22; 32]);
+        assert_eq!(p2tr.encode_cpe().unwrap().len(), amount_bits_len + 1 + 256);
+        let p2pkh = Swapout::new_p2pkh(account, 7_000, [0x33; 20]);
+        assert_eq!(
+            p2pkh.encode_cpe().unwrap().len(),
+            amount_bits_len + 1 + 3 + 160
+        );
+
+        // Swapouts round-trip through their scriptPubKeys.
+        for spk in [
+            "76a9147680adec8eabcabac676be9e83854ade0bd22cdb88ac",
+            "a914b472a266d0bd89c13706a4132ccfb16f7c3b9fcb87",
+            "0014841b80d2cc75f5345c482af96294d04fdd66b2b7",
+            "51209f5e030b7111d8e1e757d31944df5bf0714c94368cc0b499bef0badacb67c2a8",
+            "5210751e76e8199196d454941c45d1b3a323",
+        ] {
+            let spk = hex::decode(spk).unwrap();
+            let swapout = Swapout::from_spk(account, 7_000, spk.clone()).unwrap();
+            assert_eq!(swapout.spk(), spk);
+        }
+        assert!(Swapout::from_spk(account, 7_000, hex::decode("6a0474616773").unwrap()).is_none());
+        assert!(Swapout::new_witness_program(account, 7_000, 1, vec![0x00; 32]).is_none());
+        assert!(Swapout::new_witness_program(account, 7_000, 2, vec![0x00; 41]).is_none());
+
+        // Legacy hashes of the same length as a witness program sign differently.
+        let p2wpkh = Swapout::new_p2wpkh(account, 7_000, [0x33; 20]);
+        assert_ne!(p2pkh.auth_sighash(), p2wpkh.auth_sighash());
+
+        // Reserved entries cannot be encoded.
+        let reserved = Entry::new_reserved(account, Reserved {}, None, None);
+        assert!(reserved.encode_cpe().is_none());
+
+        // A reserved branch fails decoding.
+        let mut bits = account.encode_cpe().unwrap();
+        bits.extend(BitVec::from_fn(7, |i| i != 0));
+        let mut bit_stream = bits.iter();
+        assert!(Entry::decode_cpe(
+            &mut bit_stream,
+            &mut txholder,
+            &ctx,
+            &CommonTable::genesis()
+        )
+        .await
+        .is_err());
+
+        // Empty entries fail decoding.
+        let mut bits = account.encode_cpe().unwrap();
+        bits.extend(BitVec::from_elem(2, false));
+        let mut bit_stream = bits.iter();
+        assert!(Entry::decode_cpe(
+            &mut bit_stream,
+            &mut txholder,
+            &ctx,
+            &CommonTable::genesis()
+        )
+        .await
+        .is_err());
+
+        // Entries are priced by their exact bit length against the registery.
+        for entry in entries.iter() {
+            let estimate = EntryEstimate::new(entry, &registery, &CommonTable::genesis())
+                .await
+                .unwrap();
+            assert_eq!(estimate.bits(), entry.encode_cpe().unwrap().len() as u64);
+        }
+
+        // 32 payload bits make up a witness vbyte; fees round up.
+        let estimate = EntryEstimate::from_entry(&entries[0], &CommonTable::genesis()).unwrap();
+        let amount_bits_len = MaybeCommon::<ShortVal>::new(ShortVal::new(5_000))
+            .encode_cpe()
+            .unwrap()
+            .len() as u64;
+        assert_eq!(estimate.bits(), 266 + 4 + 266 + amount_bits_len);
+        assert_eq!(estimate.vbytes(), estimate.bits() as f64 / 32.0);
+        assert_eq!(estimate.fee(0), 0);
+        assert_eq!(estimate.fee(1), estimate.bits().div_ceil(32));
+        assert_eq!(estimate.fee(10), (estimate.bits() * 10).div_ceil(32));
+
+        // Ranks carried by the entry are re-resolved against the registery.
+        let mut ranked_to = to;
+        ranked_to.set_rank(Some(1));
+        let ranked = Entry::new_move(account, Move::new(account, ranked_to, 5_000), None, None);
+        let ranked_estimate = EntryEstimate::from_entry(&ranked, &CommonTable::genesis()).unwrap();
+        assert_eq!(ranked_estimate.bits(), estimate.bits() - 256);
+        let ranked_estimate = EntryEstimate::new(&ranked, &registery, &CommonTable::genesis())
+            .await
+            .unwrap();
+        assert_eq!(ranked_estimate, estimate);
+
+        Ok(())
+    }
+
+    #[test]
+    fn batch_move_test() -> Result<(), String> {
+        let from = Account::new(
+            Point::from_hex("021123864025e2c24bd82e6e19729eaa93cf02c57149bbfc84d239a0369f471316")
+                .unwrap(),
+            None,
+            None,
+        )
+        .unwrap();
+        let to_1 = Account::new(
+            Point::from_hex("02fbdf138aaa8e1e0446b3641cdc7f2d92b4442a80bbb191b8d96b653ccd270e44")
+                .unwrap(),
+            None,
+            None,
+        )
+        .unwrap();
+        let to_2 = Account::new(
+            Point::from_hex("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap(),
+            None,
+            None,
+        )
+        .unwrap();
+
+        // A batch carries at least one, at most 64 payments.
+        assert!(BatchMove::new(from, vec![]).is_none());
+        assert!(BatchMove::new(from, vec![(to_1, 1); 65]).is_none());
+        assert!(BatchMove::new(from, vec![(to_1, 1); 64]).is_some());
+
+        let batch_move =
+            BatchMove::new(from, vec![(to_1, 1_000), (to_2, 500), (to_1, 250)]).unwrap();
+        assert_eq!(batch_move.total_amount(), 1_750);
+        assert_eq!(batch_move.recipients(), vec![to_1, to_2, to_1]);
+
+        // The sighash commits to the whole list, in order.
+        let reordered =
+            BatchMove::new(from, vec![(to_2, 500), (to_1, 1_000), (to_1, 250)]).unwrap();
+        let truncated = BatchMove::new(from, vec![(to_1, 1_000), (to_2, 500)]).unwrap();
+        assert_ne!(batch_move.auth_sighash(), reordered.auth_sighash());
+        assert_ne!(batch_move.auth_sighash(), truncated.auth_sighash());
+
+        let from_key = from.key().serialize_xonly();
+        let to_1_key = to_1.key().serialize_xonly();
+        let to_2_key = to_2.key().serialize_xonly();
+
+        // Insufficient sender balance leaves every balance untouched.
+        let mut balances = HashMap::<[u8; 32], u64>::new();
+        balances.insert(from_key, 1_749);
+        balances.insert(to_1_key, 10);
+        let before = balances.clone();
+        assert!(!batch_move.apply(&mut balances));
+        assert_eq!(balances, before);
+
+        // A receiving balance overflow leaves every balance untouched.
+        balances.insert(from_key, 2_000);
+        balances.insert(to_2_key, u64::MAX);
+        let before = balances.clone();
+        assert!(!batch_move.apply(&mut balances));
+        assert_eq!(balances, before);
+
+        // Otherwise every payment goes through.
+        balances.insert(to_2_key, 0);
+        assert!(batch_move.apply(&mut balances));
+        assert_eq!(balances.get(&from_key), Some(&250));
+        assert_eq!(balances.get(&to_1_key), Some(&1_260));
+        assert_eq!(balances.get(&to_2_key), Some(&500));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod lang_tests {
+    use brollup::{
+        constructive::calldata::element_type::CallElementType,
+        executive::{
+            lang::{
+                compiler::compile_contract,
+                lang_error::{CodegenError, LangError, ParseError},
+            },
+            opcode::{
+                op::{
+                    flow::{op_returnall::OP_RETURNALL, op_verify::OP_VERIFY},
+                    storage::{op_sread::OP_SREAD, op_swrite::OP_SWRITE},
+                },
+                opcode::Opcode,
+            },
+            program::method::method_type::MethodType,
+        },
+    };
+
+    const COUNTER: &str = r#"
+        contract Counter {
+            storage counts: map<account, u32>;
+
+            // Increments the count of an account.
+            callable fn increment(who: account, by: u32) -> u32 {
+                let next: u32 = counts[who] + by;
+                require(next <= 1000);
+                counts[who] = next;
+                return next;
+            }
+
+            readonly fn count(who: account) -> u32 {
+                return counts[who];
+            }
+
+            internal fn clamp(value: u64) -> (u8, bool) {
+                if value > 255 {
+                    return 255, true;
+                } else {
+                    return value as u8, false;
+                }
+            }
+        }
+    "#;
+
+    #[test]
+    fn compile_contract_test() -> Result<(), LangError> {
+        let program = compile_contract(COUNTER)?;
+
+        assert_eq!(program.program_name(), "Counter");
+        assert_eq!(program.methods_len(), 3);
+
+        // Callable and read-only methods are ordered first.
+        let increment = program.method_by_index(0).unwrap();
+        assert_eq!(increment.method_name(), "increment");
+        assert_eq!(increment.method_type(), MethodType::Callable);
+        assert_eq!(
+            increment.call_element_types(),
+            vec![CallElementType::Account, CallElementType::U32]
+        );
+
+        // Reads and writes storage, and checks the requirement.
+        let script = increment.script();
+        assert!(script.contains(&Opcode::OP_SREAD(OP_SREAD)));
+        assert!(script.contains(&Opcode::OP_SWRITE(OP_SWRITE)));
+        assert!(script.contains(&Opcode::OP_VERIFY(OP_VERIFY)));
+        assert_eq!(script.last(), Some(&Opcode::OP_RETURNALL(OP_RETURNALL)));
+
+        let count = program.method_by_index(1).unwrap();
+        assert_eq!(count.method_type(), MethodType::ReadOnly);
+        assert!(!count.script().contains(&Opcode::OP_SWRITE(OP_SWRITE)));
+
+        let clamp = program.method_by_index(2).unwrap();
+        assert_eq!(clamp.method_type(), MethodType::Internal);
+        assert_eq!(clamp.call_element_types(), vec![CallElementType::U64]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn compile_error_test() {
+        let compile_err = |source: &str| compile_contract(source).unwrap_err();
+
+        // Mismatched types.
+        assert!(matches!(
+            compile_err(
+                "contract Test { callable fn run(a: u32, b: bool) { let c: u32 = a + b; } }"
+            ),
+            LangError::CodegenError(CodegenError::TypeMismatch { .. })
+        ));
+
+        // Literal out of range for the target type.
+        assert_eq!(
+            compile_err("contract Test { callable fn run() { let a: u8 = 256; } }"),
+            LangError::CodegenError(CodegenError::LiteralOutOfRange(256, CallElementType::U8))
+        );
+
+        // Storage writes in read-only methods.
+        assert!(matches!(
+            compile_err(
+                "contract Test { storage m: map<u8, u8>; readonly fn run() { m[1] = 2; } }"
+            ),
+            LangError::CodegenError(CodegenError::StorageWriteInReadOnlyMethod(_))
+        ));
+
+        // Missing return.
+        assert!(matches!(
+            compile_err("contract Test { callable fn run(a: bool) -> u8 { if a { return 1; } } }"),
+            LangError::CodegenError(CodegenError::MissingReturn(_))
+        ));
+
+        // Unknown variable.
+        assert!(matches!(
+            compile_err("contract Test { callable fn run() { require(x); } }"),
+            LangError::CodegenError(CodegenError::UnknownVariable(_))
+        ));
+
+        // Unknown type.
+        assert!(matches!(
+            compile_err("contract Test { callable fn run(a: i32) { } }"),
+            LangError::ParseError(ParseError::UnknownType(1, _))
+        ));
+
+        // Variable-length storage keys.
+        assert!(matches!(
+            compile_err("contract Test { storage m: map<varbytes, u8>; callable fn run() { } }"),
+            LangError::CodegenError(CodegenError::InvalidStorageKeyType(
+                CallElementType::Varbytes
+            ))
+        ));
+    }
+}
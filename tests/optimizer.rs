@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod optimizer_tests {
+    use brollup::executive::opcode::{
+        compiler::compiler_error::OpcodeCompileError,
+        op::{
+            arithmetic::{
+                op_1add::OP_1ADD, op_add::OP_ADD, op_not::OP_NOT, op_numequal::OP_NUMEQUAL,
+                op_numequalverify::OP_NUMEQUALVERIFY,
+            },
+            bitwise::{op_equal::OP_EQUAL, op_equalverify::OP_EQUALVERIFY},
+            flow::{
+                op_else::OP_ELSE, op_endif::OP_ENDIF, op_fail::OP_FAIL, op_if::OP_IF,
+                op_nop::OP_NOP, op_notif::OP_NOTIF, op_returnall::OP_RETURNALL,
+                op_verify::OP_VERIFY,
+            },
+            push::{
+                op_2::OP_2, op_3::OP_3, op_false::OP_FALSE, op_pushdata::OP_PUSHDATA,
+                op_true::OP_TRUE,
+            },
+            stack::{op_2drop::OP_2DROP, op_drop::OP_DROP, op_dup::OP_DUP},
+        },
+        opcode::Opcode,
+        optimizer::optimizer::ScriptOptimizer,
+    };
+
+    #[test]
+    fn peephole_rewrite_test() -> Result<(), OpcodeCompileError> {
+        let script = vec![
+            Opcode::OP_DUP(OP_DUP),
+            Opcode::OP_DROP(OP_DROP),
+            Opcode::OP_EQUAL(OP_EQUAL),
+            Opcode::OP_VERIFY(OP_VERIFY),
+            Opcode::OP_NUMEQUAL(OP_NUMEQUAL),
+            Opcode::OP_VERIFY(OP_VERIFY),
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_ADD(OP_ADD),
+            Opcode::OP_VERIFY(OP_VERIFY),
+            Opcode::OP_DROP(OP_DROP),
+            Opcode::OP_DROP(OP_DROP),
+            Opcode::OP_PUSHDATA(OP_PUSHDATA(vec![0xde, 0xad])),
+            Opcode::OP_DROP(OP_DROP),
+            Opcode::OP_RETURNALL(OP_RETURNALL),
+        ];
+
+        let (optimized, report) = script.optimize()?;
+
+        assert_eq!(
+            optimized,
+            vec![
+                Opcode::OP_EQUALVERIFY(OP_EQUALVERIFY),
+                Opcode::OP_NUMEQUALVERIFY(OP_NUMEQUALVERIFY),
+                Opcode::OP_1ADD(OP_1ADD),
+                Opcode::OP_VERIFY(OP_VERIFY),
+                Opcode::OP_2DROP(OP_2DROP),
+                Opcode::OP_RETURNALL(OP_RETURNALL),
+            ]
+        );
+
+        assert_eq!(report.opcodes_before(), 14);
+        assert_eq!(report.opcodes_after(), 6);
+        assert_eq!(report.bytes_before(), 16);
+        assert_eq!(report.bytes_after(), 6);
+        assert_eq!(report.bytes_saved(), 10);
+        // DUP DROP (2), PUSH DROP (4), OP_1 OP_ADD (1).
+        assert_eq!(report.ops_saved(), 7);
+
+        // `OP_1 OP_ADD` without a verify is left as is.
+        let script = vec![
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_ADD(OP_ADD),
+            Opcode::OP_RETURNALL(OP_RETURNALL),
+            Opcode::OP_FAIL(OP_FAIL),
+        ];
+        let (optimized, report) = script.optimize()?;
+        assert_eq!(optimized, script);
+        assert_eq!(report.rewrites(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn dead_branch_elimination_test() -> Result<(), OpcodeCompileError> {
+        // Constant true takes the if branch, including nested branches.
+        let script = vec![
+            Opcode::OP_TRUE(OP_TRUE),
+            Opcode::OP_IF(OP_IF),
+            Opcode::OP_2(OP_2),
+            Opcode::OP_IF(OP_IF),
+            Opcode::OP_3(OP_3),
+            Opcode::OP_ENDIF(OP_ENDIF),
+            Opcode::OP_ELSE(OP_ELSE),
+            Opcode::OP_FAIL(OP_FAIL),
+            Opcode::OP_ENDIF(OP_ENDIF),
+            Opcode::OP_RETURNALL(OP_RETURNALL),
+        ];
+        let (optimized, _) = script.optimize()?;
+        assert_eq!(
+            optimized,
+            vec![
+                Opcode::OP_3(OP_3),
+                Opcode::OP_RETURNALL(OP_RETURNALL),
+                Opcode::OP_NOP(OP_NOP),
+                Opcode::OP_NOP(OP_NOP),
+            ]
+        );
+
+        // Constant false takes the else branch; `OP_NOTIF` inverts it.
+        let script = vec![
+            Opcode::OP_FALSE(OP_FALSE),
+            Opcode::OP_NOTIF(OP_NOTIF),
+            Opcode::OP_2(OP_2),
+            Opcode::OP_ELSE(OP_ELSE),
+            Opcode::OP_FAIL(OP_FAIL),
+            Opcode::OP_ENDIF(OP_ENDIF),
+            Opcode::OP_FALSE(OP_FALSE),
+            Opcode::OP_IF(OP_IF),
+            Opcode::OP_FAIL(OP_FAIL),
+            Opcode::OP_ENDIF(OP_ENDIF),
+            Opcode::OP_RETURNALL(OP_RETURNALL),
+        ];
+        let (optimized, report) = script.optimize()?;
+        assert_eq!(
+            optimized,
+            vec![
+                Opcode::OP_2(OP_2),
+                Opcode::OP_RETURNALL(OP_RETURNALL),
+                Opcode::OP_NOP(OP_NOP),
+                Opcode::OP_NOP(OP_NOP),
+            ]
+        );
+        // PUSH NOTIF ELSE ENDIF (4) + PUSH IF ENDIF (3).
+        assert_eq!(report.ops_saved(), 7);
+
+        // Negated conditions on unknown values fold into the opposite opcode.
+        let script = vec![
+            Opcode::OP_NOT(OP_NOT),
+            Opcode::OP_IF(OP_IF),
+            Opcode::OP_FAIL(OP_FAIL),
+            Opcode::OP_ENDIF(OP_ENDIF),
+        ];
+        let (optimized, _) = script.optimize()?;
+        assert_eq!(
+            optimized,
+            vec![
+                Opcode::OP_NOTIF(OP_NOTIF),
+                Opcode::OP_FAIL(OP_FAIL),
+                Opcode::OP_ENDIF(OP_ENDIF),
+                Opcode::OP_NOP(OP_NOP),
+            ]
+        );
+
+        Ok(())
+    }
+}
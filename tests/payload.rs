@@ -0,0 +1,688 @@
+#[cfg(test)]
+mod payload_tests {
+    use bitcoin::{
+        absolute::LockTime,
+        hashes::{sha256, Hash},
+        transaction::Version,
+        Amount, OutPoint, ScriptBuf, Transaction, TxOut,
+    };
+    use brollup::{
+        constructive::{
+            entity::account::Account,
+            entry::{
+                combinator::{add::Add, deploy::Deploy, liftup::Liftup, r#move::Move, sub::Sub},
+                decode_ctx::EntryDecodeCtx,
+                entry::Entry,
+            },
+            payload::{
+                envelope::{envelope, parse_envelope},
+                payload::Payload,
+            },
+            taproot::P2TR,
+            txn::{
+                txholder::TxHolder,
+                vtxo_tree::{VTXOTree, DEFAULT_RADIX},
+            },
+            txo::{
+                lift::Lift,
+                payload::{PayloadTXO, OPERATOR_PATH_INDEX, SENDERS_PATH_INDEX},
+                projector::{Projector, ProjectorTag},
+                vtxo::VTXO,
+            },
+            valtype::maybe_common::common_table::CommonTable,
+        },
+        executive::lang::compiler::compile_contract,
+        inscriptive::{
+            balance::dir::BalanceDirectory,
+            epoch::dir::EpochDirectory,
+            registery::registery::Registery,
+            repo::repo::ProgramRepo,
+            rollup::{dir::RollupDirectory, pool_tip::PoolTip},
+            set::{set::CoinSet, swept_set::SweptSet, utxo_set::UTXOSet, vtxo_set::VTXOSet},
+            state::{
+                dir::StateDirectory,
+                tree::{verify_state_inclusion, StateKey, StateTree},
+            },
+            storage::dir::StorageDirectory,
+            undo::journal::UndoJournal,
+        },
+        operative::sync::payload::{apply_payload, issued_vtxos},
+        transmutive::musig::{keyagg::MusigKeyAggCtx, session::MusigSessionCtx},
+    };
+    use secp::{Point, Scalar};
+
+    /// Returns a payload signed by the given msg.senders and operator secret keys.
+    fn signed_payload(state_root: [u8; 32], entries: &[Entry], secret_keys: &[Scalar]) -> Payload {
+        let message = Payload::auth_msg_from_parts(
+            [0x01; 32],
+            250,
+            &CommonTable::genesis(),
+            state_root,
+            entries,
+        );
+
+        let keys: Vec<Point> = secret_keys.iter().map(|key| key.base_point_mul()).collect();
+        let key_agg_ctx = MusigKeyAggCtx::new(&keys, None).unwrap();
+        let mut musig_ctx = MusigSessionCtx::new(&key_agg_ctx, message).unwrap();
+
+        let nonces: Vec<(Scalar, Scalar)> = (0..secret_keys.len() as u8)
+            .map(|i| {
+                (
+                    Scalar::from_slice(&[0x10 + i; 32]).unwrap(),
+                    Scalar::from_slice(&[0x20 + i; 32]).unwrap(),
+                )
+            })
+            .collect();
+        for (key, (hiding, binding)) in keys.iter().zip(nonces.iter()) {
+            assert!(musig_ctx.insert_nonce(
+                *key,
+                hiding.base_point_mul(),
+                binding.base_point_mul()
+            ));
+        }
+        for (secret_key, (hiding, binding)) in secret_keys.iter().zip(nonces.iter()) {
+            let partial_sig = musig_ctx
+                .partial_sign(*secret_key, *hiding, *binding)
+                .unwrap();
+            assert!(musig_ctx.insert_partial_sig(secret_key.base_point_mul(), partial_sig));
+        }
+
+        Payload::new(
+            [0x01; 32],
+            250,
+            &CommonTable::genesis(),
+            state_root,
+            entries.to_vec(),
+            musig_ctx.full_agg_sig().unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn envelope_test() -> Result<(), String> {
+        // Short payload fits in a single chunk.
+        let bytes = vec![0xab; 100];
+        let script = envelope(&bytes);
+        assert_eq!(&script[..2], &[0x00, 0x63]);
+        assert_eq!(script[script.len() - 1], 0x68);
+        assert_eq!(parse_envelope(&script), Some(bytes));
+
+        // Long payload whose last chunk would be a single zero byte.
+        let mut bytes = vec![0xcd; 1040];
+        bytes.push(0x00);
+        let script = envelope(&bytes);
+        assert_eq!(parse_envelope(&script), Some(bytes));
+
+        // Envelope following a spending condition.
+        let bytes = vec![0x01; 600];
+        let mut script = vec![0x20];
+        script.extend([0x11; 32]);
+        script.push(0xac);
+        script.extend(envelope(&bytes));
+        assert_eq!(parse_envelope(&script), Some(bytes));
+
+        // Unclosed envelope.
+        let script = envelope(&vec![0xef; 10]);
+        assert_eq!(parse_envelope(&script[..script.len() - 1]), None);
+
+        // No envelope.
+        assert_eq!(parse_envelope(&[0x51, 0x63, 0x68]), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn payload_test() -> Result<(), String> {
+        let ctx = EntryDecodeCtx::new(
+            &EpochDirectory::new_temporary().unwrap(),
+            &Registery::new_temporary().unwrap(),
+            &ProgramRepo::new_temporary().unwrap(),
+            &VTXOSet::new_temporary().unwrap(),
+            &SweptSet::new_temporary().unwrap(),
+            900_000,
+        );
+
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        };
+        let mut txholder = TxHolder::new(&UTXOSet::new_temporary().unwrap(), tx, 0).unwrap();
+
+        // Unregistered accounts.
+        let account = Account::new(
+            Point::from_hex("021123864025e2c24bd82e6e19729eaa93cf02c57149bbfc84d239a0369f471316")
+                .unwrap(),
+            None,
+            None,
+        )
+        .unwrap();
+        let to = Account::new(
+            Point::from_hex("02fbdf138aaa8e1e0446b3641cdc7f2d92b4442a80bbb191b8d96b653ccd270e44")
+                .unwrap(),
+            None,
+            None,
+        )
+        .unwrap();
+
+        let entries = vec![
+            Entry::new_move(account, Move::new(account, to, 5_000), None, None),
+            Entry::new_add(account, Add::new(account, 100), None, None),
+            Entry::new_sub(account, Sub::new(account, 123_456), None, None),
+        ];
+
+        // Payloads must carry at least one entry.
+        assert!(Payload::new(
+            [0x01; 32],
+            250,
+            &CommonTable::genesis(),
+            [0x05; 32],
+            vec![],
+            [0x02; 64]
+        )
+        .is_none());
+
+        let payload = Payload::new(
+            [0x01; 32],
+            250,
+            &CommonTable::genesis(),
+            [0x05; 32],
+            entries.clone(),
+            [0x02; 64],
+        )
+        .unwrap();
+        assert_eq!(payload.new_accounts(), vec![account, to]);
+        assert!(payload.new_contracts().is_empty());
+
+        // Round-trip through the envelope.
+        let script = payload.envelope().unwrap();
+        let decoded = Payload::decode_envelope(&script, &mut txholder, &ctx)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        assert_eq!(decoded.version(), payload.version());
+        assert_eq!(decoded.common_table(), payload.common_table());
+        assert_eq!(decoded.state_root(), [0x05; 32]);
+        assert_eq!(decoded.session_id(), payload.session_id());
+        assert_eq!(decoded.ops_price(), 250);
+        assert!(decoded.entries() == entries);
+        assert_eq!(decoded.new_accounts(), payload.new_accounts());
+        assert_eq!(decoded.payload_auth_sig(), payload.payload_auth_sig());
+        assert_eq!(decoded.auth_msg(), payload.auth_msg());
+
+        // The payload auth signature commits to every header field and the ops price.
+        let auth_msg_of = |ops_price: u32, common_table: &CommonTable, state_root: [u8; 32]| {
+            Payload::auth_msg_from_parts([0x01; 32], ops_price, common_table, state_root, &entries)
+        };
+        let genesis = CommonTable::genesis();
+        let other_table =
+            CommonTable::new(1, 0, genesis.short_values(), genesis.long_values()).unwrap();
+        assert_eq!(auth_msg_of(250, &genesis, [0x05; 32]), payload.auth_msg());
+        assert_ne!(auth_msg_of(251, &genesis, [0x05; 32]), payload.auth_msg());
+        assert_ne!(
+            auth_msg_of(250, &other_table, [0x05; 32]),
+            payload.auth_msg()
+        );
+        assert_ne!(auth_msg_of(250, &genesis, [0x06; 32]), payload.auth_msg());
+
+        // msg.senders are the entry accounts.
+        assert_eq!(payload.msg_senders(), vec![account.key()]);
+
+        // The payload output commits to the envelope in its operator path.
+        let preimages = vec![vec![0xaa; 32], vec![0xbb; 16]];
+        let hashlocks: Vec<[u8; 32]> = preimages
+            .iter()
+            .map(|preimage| sha256::Hash::hash(preimage).to_byte_array())
+            .collect();
+        assert!(PayloadTXO::new(&payload, to.key(), &vec![]).is_none());
+        let payload_txo = PayloadTXO::new(&payload, to.key(), &hashlocks).unwrap();
+        let taproot = payload_txo.taproot().unwrap();
+        let leaves = taproot.tree().unwrap().leaves();
+        let operator_script = leaves[OPERATOR_PATH_INDEX].tap_script();
+        assert_eq!(parse_envelope(&operator_script), payload.to_bytes());
+        assert_eq!(&operator_script[..2], &[0xa8, 0x20]);
+        assert_eq!(payload_txo.spk().unwrap().len(), 34);
+
+        // Operator spend reveals the preimages, first hashlock on top of the stack.
+        assert!(payload_txo
+            .operator_witness([0x03; 64], &vec![vec![0xaa; 32], vec![0xcc; 16]])
+            .is_none());
+        let witness = payload_txo
+            .operator_witness([0x03; 64], &preimages)
+            .unwrap();
+        let witness: Vec<Vec<u8>> = witness.iter().map(|item| item.to_vec()).collect();
+        assert_eq!(witness.len(), 5);
+        assert_eq!(witness[0], vec![0x03; 64]);
+        assert_eq!(witness[1], preimages[1]);
+        assert_eq!(witness[2], preimages[0]);
+        assert_eq!(witness[3], operator_script);
+
+        // msg.senders reclaim after one week.
+        let senders_script = leaves[SENDERS_PATH_INDEX].tap_script();
+        assert_eq!(&senders_script[..5], &[0x02, 0xf0, 0x03, 0xb2, 0x75]);
+        assert_eq!(payload_txo.senders_sequence(), 1008);
+        let witness = payload_txo.senders_witness([0x04; 64]).unwrap();
+        assert_eq!(witness.len(), 3);
+        assert_eq!(witness.nth(1).unwrap(), senders_script.as_slice());
+
+        // Unsupported version.
+        let mut bytes = payload.to_bytes().unwrap();
+        bytes[0] = 0x02;
+        assert!(Payload::decode(&bytes, &mut txholder, &ctx).await.is_err());
+
+        // Unscheduled common table.
+        let mut bytes = payload.to_bytes().unwrap();
+        bytes[1] = 0x07;
+        assert!(Payload::decode(&bytes, &mut txholder, &ctx).await.is_err());
+
+        // Trailing bytes.
+        let mut bytes = payload.to_bytes().unwrap();
+        bytes.push(0x00);
+        assert!(Payload::decode(&bytes, &mut txholder, &ctx).await.is_err());
+
+        // Truncated header.
+        let bytes = payload.to_bytes().unwrap();
+        assert!(Payload::decode(&bytes[..50], &mut txholder, &ctx)
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn apply_payload_test() -> Result<(), String> {
+        let registery = Registery::new_temporary().unwrap();
+        let program_repo = ProgramRepo::new_temporary().unwrap();
+        let storage_dir = StorageDirectory::new_temporary().unwrap();
+        let coin_set = CoinSet::new_temporary().unwrap();
+        let balance_dir = BalanceDirectory::new_temporary().unwrap();
+        let state_dir = StateDirectory::new_temporary().unwrap();
+        let rollup_dir = RollupDirectory::new_temporary().unwrap();
+
+        let (account_registery, contract_registery) = {
+            let _registery = registery.lock().await;
+            (
+                _registery.account_registery(),
+                _registery.contract_registery(),
+            )
+        };
+        let vtxo_set = {
+            let _coin_set = coin_set.lock().await;
+            _coin_set.vtxo_set()
+        };
+
+        // Unregistered deployer, and the operator.
+        let account_secret_key =
+            Scalar::from_hex("1cc5906ab936b1e29db24fffe9f87b33a4c64f2d3b59aed6c3c4faeb8fcba6da")
+                .unwrap();
+        let operator_secret_key =
+            Scalar::from_hex("4882eef979baa5c88fd9e62c698de201f0a991af65877becf683e988f3024b0f")
+                .unwrap();
+        let operator_key = operator_secret_key.base_point_mul();
+        let account = Account::new(account_secret_key.base_point_mul(), None, None).unwrap();
+        let secret_keys = vec![account_secret_key, operator_secret_key];
+
+        let program = compile_contract(
+            "contract Vault { storage owners: map<u8, u8>; readonly fn ping() -> bool { return true; } }",
+        )
+        .map_err(|e| e.to_string())?;
+        let deploy = Deploy::new(account, 0, &program, vec![(vec![0x01], vec![0xaa])], 0).unwrap();
+        let contract_id = deploy.contract_id();
+        let entries = vec![Entry::new_deploy(account, deploy, None, None)];
+
+        let state_root = StateTree::from_state(&balance_dir, &registery, &storage_dir, &vtxo_set)
+            .await
+            .unwrap()
+            .root();
+
+        let mut journal = UndoJournal::new([0xaa; 32]);
+
+        // Pool transaction of the payload, issuing no VTXOs.
+        let pool_tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        };
+        let pool_tip = PoolTip::new(pool_tx, vec![], 900_000);
+
+        // Payloads not signed by msg.senders and the operator are rejected.
+        let payload = Payload::new(
+            [0x01; 32],
+            250,
+            &CommonTable::genesis(),
+            state_root,
+            entries.clone(),
+            [0x02; 64],
+        )
+        .unwrap();
+        assert!(!payload.verify_auth_sig(operator_key));
+        assert!(
+            !apply_payload(
+                &payload,
+                &pool_tip,
+                operator_key,
+                &registery,
+                &program_repo,
+                &storage_dir,
+                &coin_set,
+                &balance_dir,
+                &state_dir,
+                &rollup_dir,
+                None,
+                &mut journal,
+            )
+            .await
+        );
+        assert!(journal.ops().is_empty());
+
+        let payload = signed_payload(state_root, &entries, &secret_keys);
+        assert!(payload.verify_auth_sig(operator_key));
+        assert!(!payload.verify_auth_sig(account.key()));
+
+        // Payloads committing to another state root are rejected.
+        let payload = signed_payload([0x05; 32], &entries, &secret_keys);
+        assert!(
+            !apply_payload(
+                &payload,
+                &pool_tip,
+                operator_key,
+                &registery,
+                &program_repo,
+                &storage_dir,
+                &coin_set,
+                &balance_dir,
+                &state_dir,
+                &rollup_dir,
+                None,
+                &mut journal,
+            )
+            .await
+        );
+        assert!(journal.ops().is_empty());
+
+        let payload = signed_payload(state_root, &entries, &secret_keys);
+
+        // Payloads failing halfway are reverted: the deployed contract already has storage.
+        {
+            let mut _storage_dir = storage_dir.lock().await;
+            assert!(_storage_dir.seed_storage(contract_id, &[(vec![0x02], vec![0xbb])]));
+        }
+        let seeded_state_root =
+            StateTree::from_state(&balance_dir, &registery, &storage_dir, &vtxo_set)
+                .await
+                .unwrap()
+                .root();
+        assert_ne!(seeded_state_root, state_root);
+        let seeded_payload = signed_payload(seeded_state_root, &entries, &secret_keys);
+        assert!(
+            !apply_payload(
+                &seeded_payload,
+                &pool_tip,
+                operator_key,
+                &registery,
+                &program_repo,
+                &storage_dir,
+                &coin_set,
+                &balance_dir,
+                &state_dir,
+                &rollup_dir,
+                None,
+                &mut journal,
+            )
+            .await
+        );
+        assert!(journal.ops().is_empty());
+        assert!(!account_registery.lock().await.is_registered(account.key()));
+        assert!(!contract_registery.lock().await.is_registered(contract_id));
+        assert!(!program_repo.lock().await.is_stored(contract_id));
+        assert_eq!(rollup_dir.lock().await.rollup_sync_height(), 0);
+        {
+            let mut _storage_dir = storage_dir.lock().await;
+            assert!(_storage_dir.remove_storage(contract_id));
+        }
+
+        // Valid payloads register the deployer and the contract, and seed its storage.
+        assert!(
+            apply_payload(
+                &payload,
+                &pool_tip,
+                operator_key,
+                &registery,
+                &program_repo,
+                &storage_dir,
+                &coin_set,
+                &balance_dir,
+                &state_dir,
+                &rollup_dir,
+                None,
+                &mut journal,
+            )
+            .await
+        );
+        assert!(account_registery.lock().await.is_registered(account.key()));
+        assert!(contract_registery.lock().await.is_registered(contract_id));
+        assert!(program_repo.lock().await.is_stored(contract_id));
+        assert_eq!(
+            storage_dir.lock().await.value(contract_id, &[0x01]),
+            Some(vec![0xaa])
+        );
+        assert_eq!(rollup_dir.lock().await.rollup_sync_height(), 1);
+
+        // The seeded storage slot is proven against the recorded state root.
+        let state_tree = StateTree::from_state(&balance_dir, &registery, &storage_dir, &vtxo_set)
+            .await
+            .unwrap();
+        let recorded_root = state_dir.lock().await.state_root(1).unwrap();
+        assert_eq!(state_tree.root(), recorded_root);
+        let state_key = StateKey::ContractStorage(contract_id, vec![0x01]);
+        let proof = state_tree.proof(&state_key);
+        assert!(verify_state_inclusion(
+            recorded_root,
+            &proof,
+            &state_key,
+            &[0xaa]
+        ));
+        assert!(!verify_state_inclusion(
+            recorded_root,
+            &proof,
+            &state_key,
+            &[0xbb]
+        ));
+
+        // Reverting the block journal undoes the payload.
+        journal
+            .revert(
+                &coin_set,
+                None,
+                &registery,
+                &program_repo,
+                &balance_dir,
+                &storage_dir,
+                &state_dir,
+                &rollup_dir,
+                None,
+            )
+            .await;
+        assert!(!account_registery.lock().await.is_registered(account.key()));
+        assert!(!contract_registery.lock().await.is_registered(contract_id));
+        assert!(storage_dir.lock().await.storage(contract_id).is_empty());
+        assert!(!program_repo.lock().await.is_stored(contract_id));
+        assert_eq!(rollup_dir.lock().await.rollup_sync_height(), 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn issued_vtxos_test() -> Result<(), String> {
+        let registery = Registery::new_temporary().unwrap();
+        let program_repo = ProgramRepo::new_temporary().unwrap();
+        let storage_dir = StorageDirectory::new_temporary().unwrap();
+        let coin_set = CoinSet::new_temporary().unwrap();
+        let balance_dir = BalanceDirectory::new_temporary().unwrap();
+        let state_dir = StateDirectory::new_temporary().unwrap();
+        let rollup_dir = RollupDirectory::new_temporary().unwrap();
+
+        let vtxo_set = {
+            let _coin_set = coin_set.lock().await;
+            _coin_set.vtxo_set()
+        };
+
+        let account_secret_key =
+            Scalar::from_hex("1cc5906ab936b1e29db24fffe9f87b33a4c64f2d3b59aed6c3c4faeb8fcba6da")
+                .unwrap();
+        let operator_secret_key =
+            Scalar::from_hex("4882eef979baa5c88fd9e62c698de201f0a991af65877becf683e988f3024b0f")
+                .unwrap();
+        let operator_key = operator_secret_key.base_point_mul();
+        let account_key = account_secret_key.base_point_mul();
+        let account = Account::new(account_key, None, None).unwrap();
+        let secret_keys = vec![account_secret_key, operator_secret_key];
+
+        // An entry lifting up two lifts.
+        let lifts = vec![
+            Lift::new(
+                account_key,
+                operator_key,
+                Some(OutPoint::null()),
+                Some(20_000),
+            ),
+            Lift::new(
+                account_key,
+                operator_key,
+                Some(OutPoint::null()),
+                Some(30_000),
+            ),
+        ];
+        let entries = vec![Entry::new_nop(
+            account,
+            Some(Liftup::new(lifts).unwrap()),
+            None,
+        )];
+
+        let state_root = StateTree::from_state(&balance_dir, &registery, &storage_dir, &vtxo_set)
+            .await
+            .unwrap()
+            .root();
+        let payload = signed_payload(state_root, &entries, &secret_keys);
+
+        // Pool transaction funding the VTXO projector of the lifting account.
+        let projector = Projector::new(
+            &vec![account_key],
+            operator_key,
+            ProjectorTag::VTXOProjector,
+        );
+        let pool_tx = |projector: &Projector| Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![
+                TxOut {
+                    value: Amount::from_sat(1_000),
+                    script_pubkey: ScriptBuf::new(),
+                },
+                TxOut {
+                    value: Amount::from_sat(50_000),
+                    script_pubkey: ScriptBuf::from(projector.spk().unwrap()),
+                },
+            ],
+        };
+        let pool_tip = PoolTip::new(pool_tx(&projector), vec![], 900_000);
+
+        // The issued VTXO is a leaf of the projector's VTXO tree.
+        let vtxos = issued_vtxos(&payload, &pool_tip, operator_key, 1).unwrap();
+        let vtxo_tree = VTXOTree::new(
+            &projector,
+            OutPoint::new(pool_tip.tx().compute_txid(), 1),
+            &vec![VTXO::new(
+                account_key,
+                operator_key,
+                None,
+                Some(50_000),
+                Some(1),
+                Some(900_000),
+            )],
+            DEFAULT_RADIX,
+        )
+        .unwrap();
+        let leaf_outpoint = vtxo_tree.leaf_outpoint(&vtxo_tree.vtxos()[0]).unwrap();
+        let vtxo = VTXO::new(
+            account_key,
+            operator_key,
+            Some(leaf_outpoint),
+            Some(50_000),
+            Some(1),
+            Some(900_000),
+        );
+        assert!(vtxos == vec![vtxo.clone()]);
+
+        // Pool transactions whose projector does not match the lifting accounts issue no VTXOs.
+        let other_projector = Projector::new(
+            &vec![account_key, operator_key],
+            operator_key,
+            ProjectorTag::VTXOProjector,
+        );
+        let other_pool_tip = PoolTip::new(pool_tx(&other_projector), vec![], 900_000);
+        assert!(issued_vtxos(&payload, &other_pool_tip, operator_key, 1).is_none());
+
+        let mut journal = UndoJournal::new([0xaa; 32]);
+        assert!(
+            !apply_payload(
+                &payload,
+                &other_pool_tip,
+                operator_key,
+                &registery,
+                &program_repo,
+                &storage_dir,
+                &coin_set,
+                &balance_dir,
+                &state_dir,
+                &rollup_dir,
+                None,
+                &mut journal,
+            )
+            .await
+        );
+        assert!(journal.ops().is_empty());
+        assert!(vtxo_set.lock().await.vtxos().is_empty());
+
+        // Applied payloads insert the issued VTXOs into the VTXO set.
+        assert!(
+            apply_payload(
+                &payload,
+                &pool_tip,
+                operator_key,
+                &registery,
+                &program_repo,
+                &storage_dir,
+                &coin_set,
+                &balance_dir,
+                &state_dir,
+                &rollup_dir,
+                None,
+                &mut journal,
+            )
+            .await
+        );
+        assert!(vtxo_set.lock().await.vtxos() == vec![vtxo]);
+
+        // Reverting the block journal removes them.
+        journal
+            .revert(
+                &coin_set,
+                None,
+                &registery,
+                &program_repo,
+                &balance_dir,
+                &storage_dir,
+                &state_dir,
+                &rollup_dir,
+                None,
+            )
+            .await;
+        assert!(vtxo_set.lock().await.vtxos().is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,293 @@
+#[cfg(test)]
+mod pool_tests {
+    use bitcoin::{
+        absolute::LockTime,
+        hashes::{sha256, Hash},
+        transaction::Version,
+        Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxOut, Txid,
+    };
+    use brollup::{
+        constructive::{
+            entity::account::Account,
+            entry::{combinator::add::Add, entry::Entry},
+            payload::payload::Payload,
+            taproot::P2TR,
+            txn::{
+                pool::{PoolTxBuilder, CONNECTOR_VALUE, MIN_PAYLOAD_VALUE},
+                pool_error::PoolTxError,
+            },
+            txo::{
+                lift::Lift,
+                payload::PayloadTXO,
+                projector::{Projector, ProjectorTag},
+            },
+            valtype::maybe_common::common_table::CommonTable,
+        },
+        inscriptive::{baked::EXPIRY_BLOCKS, set::utxo_set::UTXOSet},
+    };
+    use secp::Point;
+
+    #[tokio::test]
+    async fn pool_test() -> Result<(), String> {
+        let operator_key =
+            Point::from_hex("020a8a4ce4663eaee2c2c2a0426db9cc503e9eb28c349377c264f87902d726a41b")
+                .unwrap();
+        let account_key_1 =
+            Point::from_hex("021123864025e2c24bd82e6e19729eaa93cf02c57149bbfc84d239a0369f471316")
+                .unwrap();
+        let account_key_2 =
+            Point::from_hex("023206ce056a69d097b3bf511ee15689babb11586518f78e29b33b48986c78e1c1")
+                .unwrap();
+
+        let account = Account::new(account_key_1, None, None).unwrap();
+        let preimage = vec![0xaa; 32];
+        let hashlocks = vec![sha256::Hash::hash(&preimage).to_byte_array()];
+
+        // Previous payload output carrying the operator liquidity.
+        let prev_payload = Payload::new(
+            [0x01; 32],
+            250,
+            &CommonTable::genesis(),
+            [0x05; 32],
+            vec![Entry::new_add(account, Add::new(account, 100), None, None)],
+            [0x02; 64],
+        )
+        .unwrap();
+        let prev_payload_txo = PayloadTXO::new(&prev_payload, operator_key, &hashlocks).unwrap();
+        let prev_pool_tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                value: Amount::from_sat(100_000),
+                script_pubkey: ScriptBuf::from(prev_payload_txo.spk().unwrap()),
+            }],
+        };
+        let prev_payload_outpoint = OutPoint::new(prev_pool_tx.compute_txid(), 0);
+
+        // Fixture session: one payload, two lifts ordered by outpoint, and two connectors.
+        let payload = Payload::new(
+            [0x03; 32],
+            250,
+            &CommonTable::genesis(),
+            [0x06; 32],
+            vec![Entry::new_add(account, Add::new(account, 200), None, None)],
+            [0x04; 64],
+        )
+        .unwrap();
+        let remote_keys = vec![account_key_1, account_key_2];
+        let vtxo_projector =
+            Projector::new(&remote_keys, operator_key, ProjectorTag::VTXOProjector);
+        let connector_projector =
+            Projector::new(&remote_keys, operator_key, ProjectorTag::ConnectorProjector);
+        let lift_1 = Lift::new(
+            account_key_1,
+            operator_key,
+            Some(OutPoint::new(Txid::from_byte_array([0x11; 32]), 0)),
+            Some(20_000),
+        );
+        let lift_2 = Lift::new(
+            account_key_2,
+            operator_key,
+            Some(OutPoint::new(Txid::from_byte_array([0x22; 32]), 1)),
+            Some(30_000),
+        );
+        let lifts = vec![(lift_1.clone(), [0x07; 64]), (lift_2.clone(), [0x08; 64])];
+        let num_connectors = 2;
+
+        let mut builder = PoolTxBuilder::from_parts(
+            &prev_pool_tx,
+            &payload,
+            operator_key,
+            &hashlocks,
+            vtxo_projector.clone(),
+            connector_projector.clone(),
+            lifts,
+            num_connectors,
+        )
+        .map_err(|e| format!("{:?}", e))?;
+
+        // The previous payload must be revealed first.
+        assert!(matches!(
+            builder.build(1_000),
+            Err(PoolTxError::PrevPayloadNotRevealed)
+        ));
+
+        // Preimages must unlock the hashlocks.
+        assert!(matches!(
+            builder.reveal_prev_payload(&prev_payload_txo, [0x09; 64], &vec![vec![0xbb; 32]]),
+            Err(PoolTxError::InvalidPreimages)
+        ));
+        builder
+            .reveal_prev_payload(&prev_payload_txo, [0x09; 64], &vec![preimage])
+            .map_err(|e| format!("{:?}", e))?;
+
+        // Expired projector swept as an extra input.
+        let expired_projector = Projector::new(
+            &vec![account_key_2],
+            operator_key,
+            ProjectorTag::VTXOProjector,
+        );
+        let expired_outpoint = OutPoint::new(Txid::from_byte_array([0x33; 32]), 1);
+        let expired_txout = TxOut {
+            value: Amount::from_sat(5_000),
+            script_pubkey: ScriptBuf::from(expired_projector.spk().unwrap()),
+        };
+        assert!(matches!(
+            builder.add_expired_projector(
+                expired_outpoint,
+                expired_txout.clone(),
+                &vtxo_projector,
+                [0x0a; 64]
+            ),
+            Err(PoolTxError::ExpiredProjectorMismatch)
+        ));
+        builder
+            .add_expired_projector(
+                expired_outpoint,
+                expired_txout.clone(),
+                &expired_projector,
+                [0x0a; 64],
+            )
+            .map_err(|e| format!("{:?}", e))?;
+        assert_eq!(builder.extra_in(), 1);
+
+        // Inputs: payload, expired projector, lifts.
+        let fee = 1_000;
+        let tx = builder.build(fee).map_err(|e| format!("{:?}", e))?;
+        let previous_outputs: Vec<OutPoint> =
+            tx.input.iter().map(|txin| txin.previous_output).collect();
+        assert_eq!(
+            previous_outputs,
+            vec![
+                prev_payload_outpoint,
+                expired_outpoint,
+                lift_1.outpoint().unwrap(),
+                lift_2.outpoint().unwrap(),
+            ]
+        );
+        assert_eq!(
+            tx.input[1].sequence,
+            Sequence::from_consensus(EXPIRY_BLOCKS)
+        );
+        assert!(tx.input.iter().all(|txin| !txin.witness.is_empty()));
+
+        // Outputs: payload, VTXO projector, connector projector.
+        let vtxo_projector_value = 50_000;
+        let connector_projector_value = num_connectors as u64 * CONNECTOR_VALUE;
+        let input_value = 100_000 + 5_000 + vtxo_projector_value;
+        assert_eq!(builder.vtxo_projector_value(), vtxo_projector_value);
+        assert_eq!(
+            builder.connector_projector_value(),
+            connector_projector_value
+        );
+        assert_eq!(tx.output.len(), 3);
+        assert_eq!(
+            tx.output[0].value.to_sat(),
+            input_value - vtxo_projector_value - connector_projector_value - fee
+        );
+        assert_eq!(
+            tx.output[0].script_pubkey.as_bytes(),
+            builder.payload_txo().spk().unwrap().as_slice()
+        );
+        assert_eq!(tx.output[1].value.to_sat(), vtxo_projector_value);
+        assert_eq!(
+            tx.output[1].script_pubkey.as_bytes(),
+            vtxo_projector.spk().unwrap().as_slice()
+        );
+        assert_eq!(tx.output[2].value.to_sat(), connector_projector_value);
+        assert_eq!(
+            tx.output[2].script_pubkey.as_bytes(),
+            connector_projector.spk().unwrap().as_slice()
+        );
+
+        // The payload output must not fall below the minimum payload value.
+        let max_fee =
+            input_value - vtxo_projector_value - connector_projector_value - MIN_PAYLOAD_VALUE;
+        let tx_min = builder.build(max_fee).map_err(|e| format!("{:?}", e))?;
+        assert_eq!(tx_min.output[0].value.to_sat(), MIN_PAYLOAD_VALUE);
+        assert!(matches!(
+            builder.build(max_fee + 1),
+            Err(PoolTxError::InsufficientFunds)
+        ));
+        assert!(matches!(
+            builder.build(input_value),
+            Err(PoolTxError::InsufficientFunds)
+        ));
+
+        // UTXO set holding every prevout.
+        let utxo_set = UTXOSet::new_temporary().unwrap();
+        {
+            let mut _utxo_set = utxo_set.lock().await;
+            assert!(_utxo_set.insert_txout(&prev_payload_outpoint, &prev_pool_tx.output[0]));
+            assert!(_utxo_set.insert_txout(&expired_outpoint, &expired_txout));
+            for lift in [&lift_1, &lift_2] {
+                let txout = TxOut {
+                    value: Amount::from_sat(lift.value().unwrap()),
+                    script_pubkey: ScriptBuf::from(lift.spk().unwrap()),
+                };
+                assert!(_utxo_set.insert_txout(&lift.outpoint().unwrap(), &txout));
+            }
+        }
+        builder
+            .verify(&tx, &utxo_set)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        assert!(builder.build_and_verify(fee, &utxo_set).await.is_ok());
+
+        // Inputs out of order.
+        let mut swapped_tx = tx.clone();
+        swapped_tx.input.swap(2, 3);
+        assert!(matches!(
+            builder.verify(&swapped_tx, &utxo_set).await,
+            Err(PoolTxError::PrevoutMismatch(2))
+        ));
+
+        // Missing witness.
+        let mut unsigned_tx = tx.clone();
+        unsigned_tx.input[1].witness.clear();
+        assert!(matches!(
+            builder.verify(&unsigned_tx, &utxo_set).await,
+            Err(PoolTxError::MissingWitness(1))
+        ));
+
+        // Outputs exceeding the inputs.
+        let mut inflated_tx = tx.clone();
+        inflated_tx.output[0].value = Amount::from_sat(input_value);
+        assert!(matches!(
+            builder.verify(&inflated_tx, &utxo_set).await,
+            Err(PoolTxError::InsufficientFunds)
+        ));
+
+        // Prevout in the UTXO set not matching the expected output.
+        {
+            let mut _utxo_set = utxo_set.lock().await;
+            let txout = TxOut {
+                value: Amount::from_sat(4_000),
+                script_pubkey: expired_txout.script_pubkey.clone(),
+            };
+            assert!(_utxo_set.remove_txout(&expired_outpoint).is_some());
+            assert!(_utxo_set.insert_txout(&expired_outpoint, &txout));
+        }
+        assert!(matches!(
+            builder.verify(&tx, &utxo_set).await,
+            Err(PoolTxError::PrevoutMismatch(1))
+        ));
+
+        // Spent prevout.
+        {
+            let mut _utxo_set = utxo_set.lock().await;
+            assert!(_utxo_set
+                .remove_txout(&lift_2.outpoint().unwrap())
+                .is_some());
+            assert!(_utxo_set.remove_txout(&expired_outpoint).is_some());
+            assert!(_utxo_set.insert_txout(&expired_outpoint, &expired_txout));
+        }
+        assert!(matches!(
+            builder.verify(&tx, &utxo_set).await,
+            Err(PoolTxError::PrevoutNotFound(3))
+        ));
+
+        Ok(())
+    }
+}
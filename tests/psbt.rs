@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod psbt_tests {
+    use bitcoin::{
+        absolute::LockTime,
+        hashes::Hash,
+        key::XOnlyPublicKey,
+        secp256k1::schnorr,
+        sighash::TapSighashType,
+        taproot::{LeafVersion, Signature, TapLeafHash},
+        transaction::Version,
+        Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid, Witness,
+    };
+    use brollup::{
+        constructive::{
+            taproot::P2TR,
+            txn::{
+                exit::{self_spk, ExitPackage},
+                psbt::{
+                    combine_psbt, exit_psbt, export_psbt, extract_tx, finalize_psbt, import_psbt,
+                    lift_funding_psbt, lift_recovery_psbt,
+                },
+                psbt_error::PsbtError,
+                sighash::{script_path_sighash, sweep_witness},
+            },
+            txo::{lift::Lift, vtxo::VTXO},
+        },
+        inscriptive::baked::EXPIRY_BLOCKS,
+        transmutive::{
+            codec::base64,
+            secp::schnorr::{self as brollup_schnorr, SchnorrSigningMode},
+        },
+    };
+    use secp::{Point, Scalar};
+
+    fn key(seed: u8) -> Point {
+        Scalar::from_slice(&[seed; 32]).unwrap().base_point_mul()
+    }
+
+    /// Signs the script-path input of a PSBT as an external signer would.
+    fn sign_script_path(psbt: &mut bitcoin::psbt::Psbt, secret_key: [u8; 32], account: Point) {
+        let prevouts = vec![psbt.inputs[0].witness_utxo.clone().unwrap()];
+        let (_, (script, _)) = psbt.inputs[0].tap_scripts.iter().next().unwrap();
+        let script = script.to_owned();
+
+        let sighash =
+            script_path_sighash(&psbt.unsigned_tx, 0, &prevouts, &script.to_bytes()).unwrap();
+        let sig = brollup_schnorr::sign(secret_key, sighash, SchnorrSigningMode::BIP340).unwrap();
+
+        psbt.inputs[0].tap_script_sigs.insert(
+            (
+                XOnlyPublicKey::from_slice(&account.serialize_xonly()).unwrap(),
+                TapLeafHash::from_script(&script, LeafVersion::TapScript),
+            ),
+            Signature {
+                signature: schnorr::Signature::from_slice(&sig).unwrap(),
+                sighash_type: TapSighashType::Default,
+            },
+        );
+    }
+
+    #[test]
+    fn base64_test() -> Result<(), String> {
+        // RFC 4648 test vectors.
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (decoded, encoded) in vectors {
+            assert_eq!(base64::encode(decoded.as_bytes()), encoded);
+            assert_eq!(base64::decode(encoded).unwrap(), decoded.as_bytes());
+        }
+
+        // Non-canonical encodings.
+        assert!(base64::decode("Zg=").is_none());
+        assert!(base64::decode("Zh==").is_none());
+        assert!(base64::decode("Zg==Zg==").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn lift_psbt_test() -> Result<(), String> {
+        let secret_key = [0x01; 32];
+        let account = key(0x01);
+        let operator = key(0x7f);
+
+        // Funding from an external wallet.
+        let lift = Lift::new(account, operator, None, None);
+        let funding = vec![(
+            OutPoint::new(Txid::all_zeros(), 3),
+            TxOut {
+                value: Amount::from_sat(100_000),
+                script_pubkey: ScriptBuf::from(self_spk(account).unwrap()),
+            },
+        )];
+        let change = TxOut {
+            value: Amount::from_sat(49_000),
+            script_pubkey: ScriptBuf::from(self_spk(account).unwrap()),
+        };
+
+        assert!(matches!(
+            lift_funding_psbt(&lift, 60_000, &funding, Some(change.clone())),
+            Err(PsbtError::InsufficientFunds)
+        ));
+        let psbt = lift_funding_psbt(&lift, 50_000, &funding, Some(change)).unwrap();
+        assert_eq!(
+            psbt.unsigned_tx.output[0].script_pubkey.as_bytes(),
+            lift.spk().unwrap()
+        );
+        assert_eq!(psbt.inputs[0].witness_utxo, Some(funding[0].1.clone()));
+
+        // The lift output carries its internal key and tap tree.
+        let taproot = lift.taproot().unwrap();
+        let output = &psbt.outputs[0];
+        assert_eq!(
+            output.tap_internal_key.unwrap().serialize(),
+            taproot.inner_key().serialize_xonly()
+        );
+        assert_eq!(
+            output.tap_tree.clone().unwrap().root_hash().to_byte_array(),
+            taproot.tap_branch()
+        );
+
+        // Round-trip through base64.
+        let encoded = export_psbt(&psbt);
+        assert!(encoded.starts_with("cHNidP8"));
+        assert_eq!(import_psbt(&encoded).unwrap(), psbt);
+        assert!(matches!(
+            import_psbt("cHNidP8"),
+            Err(PsbtError::Base64DecodingError)
+        ));
+
+        // Timelocked self-recovery.
+        let lift = Lift::new(
+            account,
+            operator,
+            Some(OutPoint::new(Txid::all_zeros(), 0)),
+            Some(50_000),
+        );
+        let sweep_spk = self_spk(account).unwrap();
+        let mut psbt = lift_recovery_psbt(&lift, sweep_spk, 500).unwrap();
+        assert_eq!(
+            psbt.unsigned_tx.input[0].sequence,
+            Sequence::from_consensus(EXPIRY_BLOCKS)
+        );
+        assert_eq!(psbt.unsigned_tx.output[0].value.to_sat(), 49_500);
+        assert_eq!(psbt.inputs[0].tap_scripts.len(), 1);
+        assert_eq!(
+            psbt.inputs[0].tap_merkle_root.unwrap().to_byte_array(),
+            taproot.tap_branch()
+        );
+
+        // Unsigned inputs are not finalized.
+        assert_eq!(finalize_psbt(&mut psbt).unwrap(), 0);
+        assert!(matches!(
+            extract_tx(&psbt),
+            Err(PsbtError::InputNotFinalized(0))
+        ));
+
+        // Signed externally, then imported and merged back.
+        let mut signed = import_psbt(&export_psbt(&psbt)).unwrap();
+        sign_script_path(&mut signed, secret_key, account);
+        combine_psbt(&mut psbt, import_psbt(&export_psbt(&signed)).unwrap()).unwrap();
+
+        assert_eq!(finalize_psbt(&mut psbt).unwrap(), 1);
+        assert!(psbt.inputs[0].tap_scripts.is_empty());
+        let tx = extract_tx(&psbt).unwrap();
+        let sig: [u8; 64] = tx.input[0].witness.nth(0).unwrap().try_into().unwrap();
+        assert_eq!(tx.input[0].witness, sweep_witness(&lift, sig).unwrap());
+
+        Ok(())
+    }
+
+    #[test]
+    fn exit_psbt_test() -> Result<(), String> {
+        let secret_key = [0x01; 32];
+        let account = key(0x01);
+        let operator = key(0x7f);
+
+        // A single pre-signed leaf transaction paying to the VTXO.
+        let vtxo = VTXO::new(account, operator, None, Some(20_000), None, None);
+        let leaf_tx = Transaction {
+            version: Version(3),
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(Txid::all_zeros(), 1),
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::from_slice(&[vec![0x01; 64]]),
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(20_000),
+                script_pubkey: ScriptBuf::from(vtxo.spk().unwrap()),
+            }],
+        };
+        let outpoint = OutPoint::new(leaf_tx.compute_txid(), 0);
+        let vtxo = VTXO::new(account, operator, Some(outpoint), Some(20_000), None, None);
+        let branch = vec![leaf_tx];
+
+        // Unsigned package for an external signer.
+        let sweep_spk = self_spk(account).unwrap();
+        let mut package =
+            ExitPackage::new_unsigned(&vtxo, &branch, sweep_spk.clone(), 500).unwrap();
+        assert!(!package.is_signed());
+
+        let mut psbt = exit_psbt(&package).unwrap();
+        assert_eq!(psbt.inputs[0].tap_scripts.len(), 1);
+        sign_script_path(&mut psbt, secret_key, account);
+        assert_eq!(finalize_psbt(&mut psbt).unwrap(), 1);
+        let signed_sweep = extract_tx(&psbt).unwrap();
+
+        // The externally signed sweep matches the locally signed one.
+        assert!(package.set_sweep(&signed_sweep));
+        assert!(package.is_signed());
+        let local = ExitPackage::new(&vtxo, &branch, secret_key, sweep_spk, 500).unwrap();
+        assert_eq!(local.sweep().compute_txid(), package.sweep().compute_txid());
+
+        // Signed packages export finalized.
+        let psbt = exit_psbt(&package).unwrap();
+        assert_eq!(extract_tx(&psbt).unwrap(), package.sweep());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+#[cfg(test)]
+mod sighash_tests {
+    use bitcoin::{
+        absolute::LockTime,
+        consensus::encode::deserialize_hex,
+        hashes::Hash,
+        sighash::{Prevouts, SighashCache, TapSighashType},
+        taproot::{LeafVersion, TapLeafHash},
+        transaction::Version,
+        Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid, Witness,
+    };
+    use brollup::{
+        constructive::{
+            taproot::{TapRoot, P2TR},
+            txn::{
+                sighash::{
+                    key_path_sighash, key_path_witness, prevouts, script_path_sighash,
+                    sweep_sighash, sweep_witness, verify_key_path,
+                },
+                sighash_error::SighashError,
+            },
+            txo::{connector::Connector, vtxo::VTXO},
+        },
+        inscriptive::{baked::EXPIRY_BLOCKS, set::utxo_set::UTXOSet},
+        transmutive::secp::schnorr::{self, SchnorrSigningMode},
+    };
+    use secp::{Point, Scalar};
+
+    /// Key-path only output of a single key.
+    struct SingleKey(Point);
+
+    impl P2TR for SingleKey {
+        fn taproot(&self) -> Option<TapRoot> {
+            Some(TapRoot::key_path_only(self.0))
+        }
+
+        fn spk(&self) -> Option<Vec<u8>> {
+            self.taproot()?.spk()
+        }
+    }
+
+    fn key(seed: u8) -> Point {
+        Scalar::from_slice(&[seed; 32]).unwrap().base_point_mul()
+    }
+
+    fn spend(prevouts: &Vec<(OutPoint, Sequence)>) -> Transaction {
+        Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: prevouts
+                .iter()
+                .map(|(outpoint, sequence)| TxIn {
+                    previous_output: outpoint.to_owned(),
+                    script_sig: ScriptBuf::new(),
+                    sequence: sequence.to_owned(),
+                    witness: Witness::new(),
+                })
+                .collect(),
+            output: vec![TxOut {
+                value: Amount::from_sat(15_000),
+                script_pubkey: ScriptBuf::from(vec![0x51, 0x02, 0x4e, 0x73]),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn sighash_test() -> Result<(), String> {
+        let secret_key = [0x01; 32];
+        let account = key(0x01);
+        let operator = key(0x7f);
+
+        let single_key = SingleKey(account);
+        let vtxo = VTXO::new(account, operator, None, Some(10_000), None, None);
+
+        let key_outpoint = OutPoint::new(Txid::from_byte_array([0x38; 32]), 0);
+        let vtxo_outpoint = OutPoint::new(Txid::from_byte_array([0x38; 32]), 1);
+        let key_txout = TxOut {
+            value: Amount::from_sat(6_000),
+            script_pubkey: ScriptBuf::from(single_key.spk().unwrap()),
+        };
+        let vtxo_txout = TxOut {
+            value: Amount::from_sat(10_000),
+            script_pubkey: ScriptBuf::from(vtxo.spk().unwrap()),
+        };
+
+        let tx = spend(&vec![
+            (key_outpoint, Sequence::ENABLE_RBF_NO_LOCKTIME),
+            (vtxo_outpoint, Sequence::from_consensus(EXPIRY_BLOCKS)),
+        ]);
+
+        // Prevouts are collected from the UTXO set in input order.
+        let utxo_set = UTXOSet::new_temporary().unwrap();
+        assert!(matches!(
+            prevouts(&tx, &utxo_set).await,
+            Err(SighashError::PrevoutNotFound(0))
+        ));
+        {
+            let mut _utxo_set = utxo_set.lock().await;
+            _utxo_set.insert_txout(&key_outpoint, &key_txout);
+            _utxo_set.insert_txout(&vtxo_outpoint, &vtxo_txout);
+        }
+        let prevouts = prevouts(&tx, &utxo_set).await.unwrap();
+        assert_eq!(prevouts, vec![key_txout, vtxo_txout.clone()]);
+
+        // Key-path spend verifies against the tweaked key.
+        let sighash = key_path_sighash(&tx, 0, &prevouts).unwrap();
+        let sig = schnorr::sign(secret_key, sighash, SchnorrSigningMode::BIP340).unwrap();
+        assert!(verify_key_path(&single_key, &tx, 0, &prevouts, sig).unwrap());
+        assert!(!verify_key_path(&single_key, &tx, 1, &prevouts, sig).unwrap());
+        assert_eq!(key_path_witness(sig).to_vec(), vec![sig.to_vec()]);
+
+        // The sighash commits to every prevout.
+        let mut other_prevouts = prevouts.clone();
+        other_prevouts[1].value = Amount::from_sat(10_001);
+        assert_ne!(key_path_sighash(&tx, 0, &other_prevouts).unwrap(), sighash);
+
+        // Out of bounds and missing prevouts.
+        assert!(matches!(
+            key_path_sighash(&tx, 2, &prevouts),
+            Err(SighashError::InputIndexOutOfBounds(2))
+        ));
+        assert!(matches!(
+            key_path_sighash(&tx, 0, &prevouts[..1].to_vec()),
+            Err(SighashError::PrevoutsLengthMismatch)
+        ));
+
+        // Script-path spend from the VTXO exit path, signed by the account.
+        let sighash = sweep_sighash(&vtxo, &tx, 1, &prevouts).unwrap();
+        assert_ne!(sighash, key_path_sighash(&tx, 1, &prevouts).unwrap());
+        let sig = schnorr::sign(secret_key, sighash, SchnorrSigningMode::BIP340).unwrap();
+        assert!(schnorr::verify_xonly(
+            account.serialize_xonly(),
+            sighash,
+            sig,
+            SchnorrSigningMode::BIP340
+        ));
+
+        // Witness carries the signature, the exit script and the control block.
+        let taproot = vtxo.taproot().unwrap();
+        let witness = sweep_witness(&vtxo, sig).unwrap().to_vec();
+        assert_eq!(witness.len(), 3);
+        assert_eq!(witness[0], sig.to_vec());
+        assert_eq!(witness[1], taproot.tree().unwrap().leaves()[0].tap_script());
+        assert_eq!(witness[2], taproot.control_block(0).unwrap().to_vec());
+
+        // Connectors have no script path.
+        let connector = Connector::new(account, operator);
+        assert!(matches!(
+            sweep_sighash(&connector, &tx, 0, &prevouts),
+            Err(SighashError::NoScriptPath)
+        ));
+        assert!(sweep_witness(&connector, sig).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn bip341_key_path_vector_test() -> Result<(), String> {
+        // BIP-341 `keyPathSpending` vector, input #4 with `SIGHASH_DEFAULT`.
+        let tx: Transaction = deserialize_hex(concat!(
+            "02000000097de20cbff686da83a54981d2b9bab3586f4ca7e48f57f5b55963115f3b334e9c01000000000000",
+            "0000d7b7cab57b1393ace2d064f4d4a2cb8af6def61273e127517d44759b6dafdd990000000000fffffffff8",
+            "e1f583384333689228c5d28eac13366be082dc57441760d957275419a418420000000000fffffffff0689180",
+            "aa63b30cb162a73c6d2a38b7eeda2a83ece74310fda0843ad604853b0100000000feffffffaa5202bdf6d8cc",
+            "d2ee0f0202afbbb7461d9264a25e5bfd3c5a52ee1239e0ba6c0000000000feffffff956149bdc66faa968eb2",
+            "be2d2faa29718acbfe3941215893a2a3446d32acd050000000000000000000e664b9773b88c09c32cb70a2a3",
+            "e4da0ced63b7ba3b22f848531bbb1d5d5f4c94010000000000000000e9aa6b8e6c9de67619e6a3924ae25696",
+            "bb7b694bb677a632a74ef7eadfd4eabf0000000000ffffffffa778eb6a263dc090464cd125c466b5a9966772",
+            "0b1c110468831d058aa1b82af10100000000ffffffff0200ca9a3b000000001976a91406afd46bcdfd22ef94",
+            "ac122aa11f241244a37ecc88ac807840cb0000000020ac9a87f5594be208f8532db38cff670c450ed2fea8fc",
+            "defcc9a663f78bab962b0065cd1d",
+        ))
+        .unwrap();
+        let prevouts: Vec<TxOut> = vec![
+            (
+                "512053a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343",
+                420000000,
+            ),
+            (
+                "5120147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3",
+                462000000,
+            ),
+            (
+                "76a914751e76e8199196d454941c45d1b3a323f1433bd688ac",
+                294000000,
+            ),
+            (
+                "5120e4d810fd50586274face62b8a807eb9719cef49c04177cc6b76a9a4251d5450e",
+                504000000,
+            ),
+            (
+                "512091b64d5324723a985170e4dc5a0f84c041804f2cd12660fa5dec09fc21783605",
+                630000000,
+            ),
+            ("00147dd65592d0ab2fe0d0257d571abf032cd9db93dc", 378000000),
+            (
+                "512075169f4001aa68f15bbed28b218df1d0a62cbbcf1188c6665110c293c907b831",
+                672000000,
+            ),
+            (
+                "5120712447206d7a5238acc7ff53fbe94a3b64539ad291c7cdbc490b7577e4b17df5",
+                546000000,
+            ),
+            (
+                "512077e30a5522dd9f894c3f8b8bd4c4b2cf82ca7da8a3ea6a239655c39c050ab220",
+                588000000,
+            ),
+        ]
+        .into_iter()
+        .map(|(spk, value)| TxOut {
+            value: Amount::from_sat(value),
+            script_pubkey: ScriptBuf::from(hex::decode(spk).unwrap()),
+        })
+        .collect();
+
+        let sighash = key_path_sighash(&tx, 4, &prevouts).unwrap();
+        assert_eq!(
+            hex::encode(sighash),
+            "4f900a0bae3f1446fd48490c2958b5a023228f01661cda3496a11da502a7f7ef"
+        );
+
+        // The expected witness signature verifies against the output key.
+        let sig: [u8; 64] = hex::decode(
+            "b4010dd48a617db09926f729e79c33ae0b4e94b79f04a1ae93ede6315eb3669de185a17d2b0ac9ee09fd4c64b678a0b61a0a86fa888a273c8511be83bfd6810f",
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        let output_key: [u8; 32] = prevouts[4].script_pubkey.as_bytes()[2..]
+            .try_into()
+            .unwrap();
+        assert!(schnorr::verify_xonly(
+            output_key,
+            sighash,
+            sig,
+            SchnorrSigningMode::BIP340
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn script_path_vector_test() -> Result<(), String> {
+        // Bitcoin Core script-path spending vector, a single input spending a tapscript leaf.
+        let tx: Transaction = deserialize_hex(concat!(
+            "020000000189fc651483f9296b906455dd939813bf086b1bbe7c77635e157c8e14ae2906219501000000",
+            "4445b5c7044561320000000000160014331414dbdada7fb578f700f38fb69995fc9b5ab9580200000000",
+            "00001976a914268db0a8104cc6d8afd91233cc8b3d1ace8ac3ef88ac580200000000000017a914ec00dc",
+            "b368d6a693e11986d265f659d2f59e8be2875802000000000000160014c715799a49a0bae3956df9c17c",
+            "b4440a673ac0df6f010000",
+        ))
+        .unwrap();
+        let prevouts = vec![TxOut {
+            value: Amount::from_sat(3_468_315),
+            script_pubkey: ScriptBuf::from(
+                hex::decode("512028055142ea437db73382e991861446040b61dd2185c4891d7daf6893d79f7182")
+                    .unwrap(),
+            ),
+        }];
+        let tap_script =
+            hex::decode("20cc4e1107aea1d170c5ff5b6817e1303010049724fb3caa7941792ea9d29b3e2bacab")
+                .unwrap();
+
+        // The vector's leaf hash and `SIGHASH_ALL` sighash.
+        let leaf_hash =
+            TapLeafHash::from_script(&ScriptBuf::from(tap_script.clone()), LeafVersion::TapScript);
+        assert_eq!(
+            hex::encode(leaf_hash.to_byte_array()),
+            "15a2530514e399f8b5cf0b3d3112cf5b289eaa3e308ba2071b58392fdc6da68a"
+        );
+        let sighash_all = SighashCache::new(&tx)
+            .taproot_script_spend_signature_hash(
+                0,
+                &Prevouts::All(prevouts.as_slice()),
+                leaf_hash,
+                TapSighashType::All,
+            )
+            .unwrap();
+        assert_eq!(
+            hex::encode(sighash_all.to_byte_array()),
+            "d66de5274a60400c7b08c86ba6b7f198f40660079edf53aca89d2a9501317f2e"
+        );
+
+        // Brollup signs with `SIGHASH_DEFAULT`, which only differs in the committed hash type.
+        // Derived from the BIP-341 signature message for the same transaction, prevout and leaf.
+        let sighash = script_path_sighash(&tx, 0, &prevouts, &tap_script).unwrap();
+        assert_eq!(
+            hex::encode(sighash),
+            "99e7fce632beae3a85128899c57a4ade3b180bd1dadb1d60f41c52326585c70e"
+        );
+
+        Ok(())
+    }
+}
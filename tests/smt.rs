@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod smt_tests {
+    use bitcoin::OutPoint;
+    use brollup::{
+        inscriptive::state::tree::{
+            verify_state_exclusion, verify_state_inclusion, StateKey, StateTree,
+        },
+        transmutive::smt::{
+            proof::SMTProof,
+            smt::{SparseMerkleTree, EMPTY_ROOT},
+        },
+    };
+    use secp::Point;
+
+    #[test]
+    fn smt_test() -> Result<(), String> {
+        let mut smt = SparseMerkleTree::new();
+
+        // An empty tree has the empty root, and excludes every key.
+        assert_eq!(smt.root(), EMPTY_ROOT);
+        let proof = smt.proof([0x01; 32]);
+        assert!(proof.verify_exclusion(EMPTY_ROOT, [0x01; 32]));
+        assert!(!proof.verify_inclusion(EMPTY_ROOT, [0x01; 32], [0xaa; 32]));
+
+        // Keys sharing long prefixes.
+        let mut key_1 = [0x00; 32];
+        key_1[31] = 0x01;
+        let mut key_2 = [0x00; 32];
+        key_2[31] = 0x03;
+        let key_3 = [0xff; 32];
+        let mut key_4 = [0x00; 32];
+        key_4[31] = 0x02;
+
+        smt.insert(key_1, [0xaa; 32]);
+        let single_root = smt.root();
+        assert_ne!(single_root, EMPTY_ROOT);
+
+        smt.insert(key_2, [0xbb; 32]);
+        smt.insert(key_3, [0xcc; 32]);
+        assert_eq!(smt.len(), 3);
+        let root = smt.root();
+
+        // Inclusion proofs.
+        for (key, value_hash) in [
+            (key_1, [0xaa; 32]),
+            (key_2, [0xbb; 32]),
+            (key_3, [0xcc; 32]),
+        ] {
+            let proof = smt.proof(key);
+            assert!(proof.verify_inclusion(root, key, value_hash));
+            assert!(!proof.verify_exclusion(root, key));
+
+            // A wrong value or a wrong root fails.
+            assert!(!proof.verify_inclusion(root, key, [0xdd; 32]));
+            assert!(!proof.verify_inclusion(single_root, key, value_hash));
+        }
+
+        // Exclusion proofs, ending at an empty subtree or another leaf on the path.
+        for key in [key_4, [0x80; 32], [0x7f; 32]] {
+            let proof = smt.proof(key);
+            assert!(proof.verify_exclusion(root, key));
+            assert!(!proof.verify_inclusion(root, key, [0xaa; 32]));
+        }
+
+        // An exclusion proof of one key does not exclude another.
+        let proof = smt.proof(key_4);
+        assert!(!proof.verify_exclusion(root, key_1));
+
+        // Proofs survive serialization.
+        let proof = smt.proof(key_2);
+        let deserialized: SMTProof =
+            serde_json::from_slice(&proof.serialize()).map_err(|e| e.to_string())?;
+        assert_eq!(deserialized, proof);
+        assert!(deserialized.verify_inclusion(root, key_2, [0xbb; 32]));
+
+        // Updates change the root, and removals restore it.
+        smt.insert(key_4, [0xee; 32]);
+        assert_ne!(smt.root(), root);
+        assert_eq!(smt.remove(key_4), Some([0xee; 32]));
+        assert_eq!(smt.root(), root);
+
+        smt.remove(key_2);
+        smt.remove(key_3);
+        assert_eq!(smt.root(), single_root);
+        smt.remove(key_1);
+        assert!(smt.is_empty());
+        assert_eq!(smt.root(), EMPTY_ROOT);
+
+        // The root does not depend on the insertion order.
+        let mut smt_a = SparseMerkleTree::new();
+        let mut smt_b = SparseMerkleTree::new();
+        for key in [key_1, key_2, key_3] {
+            smt_a.insert(key, key);
+        }
+        for key in [key_3, key_1, key_2] {
+            smt_b.insert(key, key);
+        }
+        assert_eq!(smt_a.root(), smt_b.root());
+
+        Ok(())
+    }
+
+    #[test]
+    fn state_tree_test() -> Result<(), String> {
+        let account_key =
+            Point::from_hex("021123864025e2c24bd82e6e19729eaa93cf02c57149bbfc84d239a0369f471316")
+                .unwrap();
+
+        let mut tree = StateTree::new();
+        tree.insert(
+            &StateKey::AccountBalance(account_key),
+            &10_000u64.to_le_bytes(),
+        );
+        tree.insert(
+            &StateKey::AccountRegistery(account_key),
+            &0u64.to_le_bytes(),
+        );
+        tree.insert(
+            &StateKey::ContractStorage([0x01; 32], vec![0x00]),
+            &[0xde, 0xad],
+        );
+        let root = tree.root();
+
+        // Slots are domain separated.
+        assert_ne!(
+            StateKey::AccountBalance(account_key).key(),
+            StateKey::AccountRegistery(account_key).key()
+        );
+        assert_ne!(
+            StateKey::ContractBalance([0x01; 32]).key(),
+            StateKey::ContractRegistery([0x01; 32]).key()
+        );
+
+        let state_key = StateKey::AccountBalance(account_key);
+        let proof = tree.proof(&state_key);
+        assert!(verify_state_inclusion(
+            root,
+            &proof,
+            &state_key,
+            &10_000u64.to_le_bytes()
+        ));
+        assert!(!verify_state_inclusion(
+            root,
+            &proof,
+            &state_key,
+            &9_999u64.to_le_bytes()
+        ));
+
+        let state_key = StateKey::ContractStorage([0x01; 32], vec![0x00]);
+        let proof = tree.proof(&state_key);
+        assert!(verify_state_inclusion(
+            root,
+            &proof,
+            &state_key,
+            &[0xde, 0xad]
+        ));
+
+        let state_key = StateKey::VTXO(OutPoint::null());
+        let proof = tree.proof(&state_key);
+        assert!(verify_state_exclusion(root, &proof, &state_key));
+
+        let state_key = StateKey::ContractBalance([0x01; 32]);
+        let proof = tree.proof(&state_key);
+        assert!(verify_state_exclusion(root, &proof, &state_key));
+
+        // Removing a slot excludes it.
+        let state_key = StateKey::AccountRegistery(account_key);
+        tree.remove(&state_key);
+        assert_ne!(tree.root(), root);
+        let proof = tree.proof(&state_key);
+        assert!(verify_state_exclusion(tree.root(), &proof, &state_key));
+
+        Ok(())
+    }
+}
@@ -1,28 +1,35 @@
 #[cfg(test)]
 mod stack_tests {
 
-    use brollup::executive::{
-        opcode::op::{
-            altstack::{op_fromaltstack::OP_FROMALTSTACK, op_toaltstack::OP_TOALTSTACK},
-            arithmetic::op_add::OP_ADD,
-            bitwise::op_equalverify::OP_EQUALVERIFY,
-            flow::{
-                op_else::OP_ELSE, op_endif::OP_ENDIF, op_if::OP_IF, op_returnerr::OP_RETURNERR,
-                op_verify::OP_VERIFY,
+    use brollup::{
+        executive::{
+            opcode::op::{
+                altstack::{op_fromaltstack::OP_FROMALTSTACK, op_toaltstack::OP_TOALTSTACK},
+                arithmetic::op_add::OP_ADD,
+                bitwise::op_equalverify::OP_EQUALVERIFY,
+                call::op_trycall::OP_TRYCALL,
+                flow::{
+                    op_else::OP_ELSE, op_endif::OP_ENDIF, op_if::OP_IF, op_returnerr::OP_RETURNERR,
+                    op_verify::OP_VERIFY,
+                },
+                push::{
+                    op_2::OP_2, op_3::OP_3, op_4::OP_4, op_5::OP_5, op_6::OP_6, op_7::OP_7,
+                    op_8::OP_8, op_false::OP_FALSE, op_true::OP_TRUE,
+                },
+                splice::op_cat::OP_CAT,
+                storage::{op_sfree::OP_SFREE, op_sread::OP_SREAD, op_swrite::OP_SWRITE},
             },
-            push::{
-                op_2::OP_2, op_3::OP_3, op_4::OP_4, op_5::OP_5, op_6::OP_6, op_7::OP_7, op_8::OP_8,
-                op_false::OP_FALSE, op_true::OP_TRUE,
+            stack::{
+                balance_move::BalanceMove,
+                event::Event,
+                stack::Stack,
+                stack_error::StackError,
+                stack_holder::StackHolder,
+                stack_item::StackItem,
+                stack_uint::{StackItemUintExt, StackUint},
             },
-            splice::op_cat::OP_CAT,
-        },
-        stack::{
-            stack::Stack,
-            stack_error::StackError,
-            stack_holder::StackHolder,
-            stack_item::StackItem,
-            stack_uint::{StackItemUintExt, StackUint},
         },
+        inscriptive::storage::dir::StorageDirectory,
     };
 
     #[test]
@@ -769,4 +776,216 @@ mod stack_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn try_call_test() -> Result<(), StackError> {
+        let mut internal_ops_counter = 0;
+        let mut external_ops_counter = 0;
+
+        // Initialize stack.
+        let mut stack_holder = StackHolder::new(
+            [0; 32],
+            [0; 32],
+            500,
+            &mut internal_ops_counter,
+            &mut external_ops_counter,
+        )?;
+
+        // Write to the contract memory before the call.
+        stack_holder
+            .memory_mut()
+            .insert(vec![0xaa], vec![0xde, 0xad]);
+        stack_holder.update_memory_size(3);
+
+        // Failing inner call.
+        {
+            // Push the call argument, argument count, method index and contract id.
+            stack_holder.push(StackItem::new(vec![0xbe, 0xef]))?;
+            stack_holder.push(StackItem::from_stack_uint(StackUint::from(1)))?;
+            stack_holder.push(StackItem::from_stack_uint(StackUint::from(2)))?;
+            stack_holder.push(StackItem::new(vec![0x01; 32]))?;
+
+            OP_TRYCALL::execute(
+                &mut stack_holder,
+                |holder, contract_id, method_index, args| {
+                    assert_eq!(contract_id, [0x01; 32]);
+                    assert_eq!(method_index, 2);
+                    assert_eq!(args, vec![StackItem::new(vec![0xbe, 0xef])]);
+                    assert_eq!(holder.call_depth(), 1);
+
+                    // Mutate the state, then fail.
+                    holder.memory_mut().insert(vec![0xaa], vec![0xff]);
+                    holder.memory_mut().insert(vec![0xbb], vec![0xff]);
+                    holder.update_memory_size(5);
+                    holder.write_storage(vec![0xcc], vec![0x01]);
+                    holder.record_balance_move(BalanceMove::new([0x01; 32], [0x02; 32], 100));
+                    holder.emit_event(Event::new([0x01; 32], vec![0x01]));
+                    holder.push(StackItem::new(vec![0x01]))?;
+
+                    Err(StackError::FailError)
+                },
+            )?;
+
+            // The call must fail without failing the caller.
+            assert_eq!(stack_holder.pop()?, StackItem::false_item());
+            assert_eq!(stack_holder.stack_items_count(), 0);
+
+            // The state must be rolled back.
+            assert_eq!(
+                stack_holder.memory().get(&vec![0xaa]),
+                Some(&vec![0xde, 0xad])
+            );
+            assert_eq!(stack_holder.memory().get(&vec![0xbb]), None);
+            assert_eq!(stack_holder.memory_size(), 3);
+            assert!(stack_holder.storage_overlay().is_empty());
+            assert!(stack_holder.balance_moves().is_empty());
+            assert!(stack_holder.events().is_empty());
+            assert_eq!(stack_holder.call_depth(), 0);
+        }
+
+        // Succeeding inner call.
+        {
+            // Push zero arguments, method index and contract id.
+            stack_holder.push(StackItem::from_stack_uint(StackUint::from(0)))?;
+            stack_holder.push(StackItem::from_stack_uint(StackUint::from(0)))?;
+            stack_holder.push(StackItem::new(vec![0x01; 32]))?;
+
+            OP_TRYCALL::execute(&mut stack_holder, |holder, _, _, _| {
+                holder.record_balance_move(BalanceMove::new([0x01; 32], [0x02; 32], 100));
+                holder.emit_event(Event::new([0x01; 32], vec![0x01]));
+
+                Ok(vec![StackItem::new(vec![0xca, 0xfe])])
+            })?;
+
+            // The call must succeed and keep its state changes.
+            assert_eq!(stack_holder.pop()?, StackItem::true_item());
+            assert_eq!(stack_holder.pop()?, StackItem::new(vec![0xca, 0xfe]));
+            assert_eq!(stack_holder.balance_moves().len(), 1);
+            assert_eq!(stack_holder.events().len(), 1);
+            assert_eq!(stack_holder.call_depth(), 0);
+        }
+
+        // Running out of ops inside the inner call fails the caller.
+        {
+            stack_holder.push(StackItem::from_stack_uint(StackUint::from(0)))?;
+            stack_holder.push(StackItem::from_stack_uint(StackUint::from(0)))?;
+            stack_holder.push(StackItem::new(vec![0x01; 32]))?;
+
+            let result = OP_TRYCALL::execute(&mut stack_holder, |_, _, _, _| {
+                Err(StackError::InternalOpsBudgetExceeded)
+            });
+
+            assert!(result.is_err());
+        }
+
+        // Exceeding the call depth limit leaves the caller's stack intact.
+        {
+            let mut internal_ops_counter = 0;
+            let mut external_ops_counter = 0;
+
+            let mut stack_holder = StackHolder::new(
+                [0; 32],
+                [0; 32],
+                500,
+                &mut internal_ops_counter,
+                &mut external_ops_counter,
+            )?;
+
+            while stack_holder.enter_call().is_ok() {}
+
+            stack_holder.push(StackItem::new(vec![0xbe, 0xef]))?;
+            stack_holder.push(StackItem::from_stack_uint(StackUint::from(1)))?;
+            stack_holder.push(StackItem::from_stack_uint(StackUint::from(0)))?;
+            stack_holder.push(StackItem::new(vec![0x01; 32]))?;
+
+            let result = OP_TRYCALL::execute(&mut stack_holder, |_, _, _, _| Ok(vec![]));
+
+            assert!(matches!(result, Err(StackError::CallDepthLimitExceeded)));
+            assert_eq!(stack_holder.stack_items_count(), 4);
+            assert_eq!(stack_holder.pop()?, StackItem::new(vec![0x01; 32]));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn storage_test() -> Result<(), StackError> {
+        let contract_id = [0x01; 32];
+
+        // Stored contract storage.
+        let storage_dir = StorageDirectory::new_temporary().unwrap();
+        {
+            let mut _storage_dir = storage_dir.lock().await;
+            assert!(_storage_dir.seed_storage(
+                contract_id,
+                &vec![(vec![0xaa], vec![0x01]), (vec![0xbb], vec![0x02])],
+            ));
+        }
+
+        let mut internal_ops_counter = 0;
+        let mut external_ops_counter = 0;
+
+        let mut stack_holder = StackHolder::new(
+            contract_id,
+            [0; 32],
+            500,
+            &mut internal_ops_counter,
+            &mut external_ops_counter,
+        )?;
+        stack_holder.load_storage(storage_dir.lock().await.storage(contract_id));
+
+        // Read a stored value.
+        stack_holder.push(StackItem::new(vec![0xaa]))?;
+        OP_SREAD::execute(&mut stack_holder)?;
+        assert_eq!(stack_holder.pop()?, StackItem::new(vec![0x01]));
+
+        // Overwrite a stored value.
+        stack_holder.push(StackItem::new(vec![0x03]))?;
+        stack_holder.push(StackItem::new(vec![0xaa]))?;
+        OP_SWRITE::execute(&mut stack_holder)?;
+        assert_eq!(stack_holder.pop()?, StackItem::new(vec![0x01]));
+
+        stack_holder.push(StackItem::new(vec![0xaa]))?;
+        OP_SREAD::execute(&mut stack_holder)?;
+        assert_eq!(stack_holder.pop()?, StackItem::new(vec![0x03]));
+
+        // Write a new value.
+        stack_holder.push(StackItem::new(vec![0x04]))?;
+        stack_holder.push(StackItem::new(vec![0xcc]))?;
+        OP_SWRITE::execute(&mut stack_holder)?;
+        assert_eq!(stack_holder.pop()?, StackItem::new(vec![]));
+
+        // Free a stored value.
+        stack_holder.push(StackItem::new(vec![0xbb]))?;
+        OP_SFREE::execute(&mut stack_holder)?;
+        assert_eq!(stack_holder.pop()?, StackItem::new(vec![0x01]));
+
+        stack_holder.push(StackItem::new(vec![0xbb]))?;
+        OP_SREAD::execute(&mut stack_holder)?;
+        assert_eq!(stack_holder.pop()?, StackItem::new(vec![]));
+
+        // Freeing a missing value.
+        stack_holder.push(StackItem::new(vec![0xdd]))?;
+        OP_SFREE::execute(&mut stack_holder)?;
+        assert_eq!(stack_holder.pop()?, StackItem::new(vec![]));
+
+        // The stored storage is untouched until the changes are written back.
+        {
+            let _storage_dir = storage_dir.lock().await;
+            assert_eq!(_storage_dir.value(contract_id, &[0xaa]), Some(vec![0x01]));
+            assert_eq!(_storage_dir.value(contract_id, &[0xbb]), Some(vec![0x02]));
+        }
+
+        // Write the changes back.
+        {
+            let mut _storage_dir = storage_dir.lock().await;
+            assert!(_storage_dir.update_storage(contract_id, stack_holder.storage_overlay()));
+            assert_eq!(_storage_dir.value(contract_id, &[0xaa]), Some(vec![0x03]));
+            assert_eq!(_storage_dir.value(contract_id, &[0xbb]), None);
+            assert_eq!(_storage_dir.value(contract_id, &[0xcc]), Some(vec![0x04]));
+            assert_eq!(_storage_dir.storage(contract_id).len(), 2);
+        }
+
+        Ok(())
+    }
 }
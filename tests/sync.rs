@@ -0,0 +1,242 @@
+#[cfg(test)]
+mod sync_tests {
+    use bitcoin::{
+        absolute::LockTime,
+        hashes::{sha256, Hash},
+        transaction::Version,
+        OutPoint, ScriptBuf, Sequence, Transaction, TxIn, Txid, Witness,
+    };
+    use brollup::{
+        constructive::{
+            entity::account::Account,
+            entry::{
+                combinator::{add::Add, claim::Claim, revive::Revive},
+                entry::Entry,
+            },
+            payload::{envelope::envelope, payload::Payload},
+            txo::{payload::PayloadTXO, vtxo::VTXO},
+            valtype::maybe_common::common_table::CommonTable,
+        },
+        inscriptive::{
+            baked, balance::dir::BalanceDirectory, registery::registery::Registery,
+            repo::repo::ProgramRepo, rollup::dir::RollupDirectory, set::set::CoinSet,
+            state::dir::StateDirectory, storage::dir::StorageDirectory, undo::journal::UndoJournal,
+        },
+        operative::sync::payload::{
+            extra_in, revealed_operator_key, revealed_payload, sweep_vtxos,
+        },
+    };
+    use secp::Point;
+
+    fn txin(sequence: Sequence, witness: Witness) -> TxIn {
+        TxIn {
+            previous_output: OutPoint::null(),
+            script_sig: ScriptBuf::new(),
+            sequence,
+            witness,
+        }
+    }
+
+    #[test]
+    fn revealed_payload_test() -> Result<(), String> {
+        let payload_bytes = vec![0xde, 0xad, 0xbe, 0xef];
+
+        // Operator path witness: signature, tapscript carrying the envelope, control block.
+        let witness = Witness::from_slice(&vec![
+            vec![0x01; 64],
+            envelope(&payload_bytes),
+            vec![0xc0; 33],
+        ]);
+
+        let expired = Sequence::from_consensus(baked::EXPIRY_BLOCKS);
+        let pool_tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![
+                txin(Sequence::ENABLE_RBF_NO_LOCKTIME, witness),
+                txin(expired, Witness::new()),
+                txin(expired, Witness::new()),
+                txin(Sequence::ENABLE_RBF_NO_LOCKTIME, Witness::new()),
+            ],
+            output: vec![],
+        };
+
+        assert_eq!(revealed_payload(&pool_tx), Some(payload_bytes));
+        assert_eq!(extra_in(&pool_tx), 2);
+
+        // A transaction without an envelope reveals nothing.
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![txin(Sequence::ENABLE_RBF_NO_LOCKTIME, Witness::new())],
+            output: vec![],
+        };
+
+        assert_eq!(revealed_payload(&tx), None);
+        assert_eq!(extra_in(&tx), 0);
+        assert_eq!(revealed_operator_key(&tx, &[]), None);
+
+        // The operator key is read from the spent operator path, among the operator group keys.
+        let operator_key =
+            Point::from_hex("020a8a4ce4663eaee2c2c2a0426db9cc503e9eb28c349377c264f87902d726a41b")
+                .unwrap();
+        let other_key =
+            Point::from_hex("021123864025e2c24bd82e6e19729eaa93cf02c57149bbfc84d239a0369f471316")
+                .unwrap();
+        let account = Account::new(other_key, None, None).unwrap();
+        let payload = Payload::new(
+            [0x01; 32],
+            250,
+            &CommonTable::genesis(),
+            [0x05; 32],
+            vec![Entry::new_add(account, Add::new(account, 100), None, None)],
+            [0x02; 64],
+        )
+        .unwrap();
+        let preimages = vec![vec![0xaa; 32], vec![0xbb; 32]];
+        let hashlocks = preimages
+            .iter()
+            .map(|preimage| sha256::Hash::hash(preimage).to_byte_array())
+            .collect();
+        let payload_txo = PayloadTXO::new(&payload, operator_key, &hashlocks).unwrap();
+        let witness = payload_txo
+            .operator_witness([0x01; 64], &preimages)
+            .unwrap();
+        let pool_tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![txin(Sequence::ENABLE_RBF_NO_LOCKTIME, witness)],
+            output: vec![],
+        };
+
+        assert_eq!(
+            revealed_operator_key(&pool_tx, &[other_key, operator_key]),
+            Some(operator_key)
+        );
+        assert_eq!(revealed_operator_key(&pool_tx, &[other_key]), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn recovery_test() -> Result<(), String> {
+        let coin_set = CoinSet::new_temporary().unwrap();
+        let (vtxo_set, swept_set) = {
+            let _coin_set = coin_set.lock().await;
+            (_coin_set.vtxo_set(), _coin_set.swept_set())
+        };
+
+        let account_key =
+            Point::from_hex("021123864025e2c24bd82e6e19729eaa93cf02c57149bbfc84d239a0369f471316")
+                .unwrap();
+        let operator_key =
+            Point::from_hex("02fbdf138aaa8e1e0446b3641cdc7f2d92b4442a80bbb191b8d96b653ccd270e44")
+                .unwrap();
+        let account = Account::new(account_key, None, None).unwrap();
+
+        // VTXOs of two projectors, confirmed at consecutive heights.
+        let txid = Txid::from_byte_array([0xaa; 32]);
+        let vtxo_1 = VTXO::new(
+            account_key,
+            operator_key,
+            Some(OutPoint::new(txid, 0)),
+            Some(3_000),
+            Some(1),
+            Some(800_000),
+        );
+        let vtxo_2 = VTXO::new(
+            account_key,
+            operator_key,
+            Some(OutPoint::new(txid, 1)),
+            Some(2_000),
+            Some(2),
+            Some(800_001),
+        );
+        {
+            let mut _vtxo_set = vtxo_set.lock().await;
+            assert!(_vtxo_set.insert_vtxo(&vtxo_1));
+            assert!(_vtxo_set.insert_vtxo(&vtxo_2));
+        }
+
+        // Revived VTXOs must be expired.
+        let revive = Revive::new(account, vec![vtxo_1.clone()]).unwrap();
+        {
+            let _vtxo_set = vtxo_set.lock().await;
+            assert!(!revive.validate(&_vtxo_set, 800_000 + baked::EXPIRY_BLOCKS - 1));
+            assert!(revive.validate(&_vtxo_set, 800_000 + baked::EXPIRY_BLOCKS));
+        }
+
+        // Sweeping the first projector moves its VTXOs to the swept set.
+        let mut journal = UndoJournal::new([0xbb; 32]);
+        sweep_vtxos(&[800_000], &coin_set, &mut journal).await;
+        assert_eq!(journal.ops().len(), 1);
+
+        let claim = Claim::new(account, vec![vtxo_1.clone()]).unwrap();
+        let revive = Revive::new(account, vec![vtxo_2.clone()]).unwrap();
+        {
+            let _vtxo_set = vtxo_set.lock().await;
+            let _swept_set = swept_set.lock().await;
+
+            // Swept VTXOs can be claimed, but no longer revived.
+            assert!(claim.validate(&_swept_set));
+            assert!(!Revive::new(account, vec![vtxo_1.clone()])
+                .unwrap()
+                .validate(&_vtxo_set, 900_000));
+
+            // Unswept VTXOs can be revived, but not claimed.
+            assert!(revive.validate(&_vtxo_set, 900_000));
+            assert!(!Claim::new(account, vec![vtxo_2.clone()])
+                .unwrap()
+                .validate(&_swept_set));
+        }
+
+        // Claims collect every swept VTXO of the account.
+        let decoded = Claim::decode_cpe(account, &swept_set)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        assert!(decoded == claim);
+
+        // Revives and claims credit the recovered value.
+        let balance_dir = BalanceDirectory::new_temporary().unwrap();
+        {
+            let mut _balance_dir = balance_dir.lock().await;
+            let entries = vec![
+                Entry::new_claim(account, claim.clone(), None, None),
+                Entry::new_revive(account, revive, None, None),
+            ];
+            assert!(_balance_dir.apply_entries(&entries));
+            assert_eq!(_balance_dir.account_balance(account_key), 5_000);
+        }
+
+        // Swept VTXOs can only be claimed once.
+        {
+            let mut _swept_set = swept_set.lock().await;
+            assert_eq!(claim.apply(&mut _swept_set), Some(3_000));
+            assert_eq!(claim.apply(&mut _swept_set), None);
+            assert!(_swept_set.insert_vtxo(&vtxo_1));
+        }
+
+        // Reverting the sweep moves the VTXOs back to the VTXO set.
+        journal
+            .revert(
+                &coin_set,
+                None,
+                &Registery::new_temporary().unwrap(),
+                &ProgramRepo::new_temporary().unwrap(),
+                &balance_dir,
+                &StorageDirectory::new_temporary().unwrap(),
+                &StateDirectory::new_temporary().unwrap(),
+                &RollupDirectory::new_temporary().unwrap(),
+                None,
+            )
+            .await;
+        {
+            let _vtxo_set = vtxo_set.lock().await;
+            let _swept_set = swept_set.lock().await;
+            assert!(!_swept_set.is_swept(&vtxo_1));
+            assert_eq!(_vtxo_set.vtxos().len(), 2);
+        }
+
+        Ok(())
+    }
+}
@@ -397,4 +397,127 @@ mod taproot_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_weighted_tap_tree_bip341() -> Result<(), Box<dyn Error>> {
+        // BIP-341 `scriptPubKey` vectors, with weights reproducing the given script trees.
+        let vectors: Vec<(&str, Vec<(u32, u8, &str)>, &str, &str, Vec<&str>)> = vec![
+            (
+                "ee4fe085983462a184015d1f782d6a5f8b9c2b60130aff050ce221ecf3786592",
+                vec![
+                    (1, 0xc0, "20387671353e273264c495656e27e39ba899ea8fee3bb69fb2a680e22093447d48ac"),
+                    (1, 0xfa, "06424950333431"),
+                ],
+                "6c2dc106ab816b73f9d07e3cd1ef2c8c1256f519748e0813e4edd2405d277bef",
+                "5120712447206d7a5238acc7ff53fbe94a3b64539ad291c7cdbc490b7577e4b17df5",
+                vec![
+                    "c0ee4fe085983462a184015d1f782d6a5f8b9c2b60130aff050ce221ecf3786592f224a923cd0021ab202ab139cc56802ddb92dcfc172b9212261a539df79a112a",
+                    "faee4fe085983462a184015d1f782d6a5f8b9c2b60130aff050ce221ecf37865928ad69ec7cf41c2a4001fd1f738bf1e505ce2277acdcaa63fe4765192497f47a7",
+                ],
+            ),
+            (
+                "e0dfe2300b0dd746a3f8674dfd4525623639042569d829c7f0eed9602d263e6f",
+                vec![
+                    (2, 0xc0, "2072ea6adcf1d371dea8fba1035a09f3d24ed5a059799bae114084130ee5898e69ac"),
+                    (1, 0xc0, "202352d137f2f3ab38d1eaa976758873377fa5ebb817372c71e2c542313d4abda8ac"),
+                    (1, 0xc0, "207337c0dd4253cb86f2c43a2351aadd82cccb12a172cd120452b9bb8324f2186aac"),
+                ],
+                "ccbd66c6f7e8fdab47b3a486f59d28262be857f30d4773f2d5ea47f7761ce0e2",
+                "512091b64d5324723a985170e4dc5a0f84c041804f2cd12660fa5dec09fc21783605",
+                vec![
+                    "c0e0dfe2300b0dd746a3f8674dfd4525623639042569d829c7f0eed9602d263e6fffe578e9ea769027e4f5a3de40732f75a88a6353a09d767ddeb66accef85e553",
+                    "c0e0dfe2300b0dd746a3f8674dfd4525623639042569d829c7f0eed9602d263e6f9e31407bffa15fefbf5090b149d53959ecdf3f62b1246780238c24501d5ceaf62645a02e0aac1fe69d69755733a9b7621b694bb5b5cde2bbfc94066ed62b9817",
+                    "c0e0dfe2300b0dd746a3f8674dfd4525623639042569d829c7f0eed9602d263e6fba982a91d4fc552163cb1c0da03676102d5b7a014304c01f0c77b2b8e888de1c2645a02e0aac1fe69d69755733a9b7621b694bb5b5cde2bbfc94066ed62b9817",
+                ],
+            ),
+            (
+                "55adf4e8967fbd2e29f20ac896e60c3b0f1d5b0efa9d34941b5958c7b0a0312d",
+                vec![
+                    (2, 0xc0, "2071981521ad9fc9036687364118fb6ccd2035b96a423c59c5430e98310a11abe2ac"),
+                    (1, 0xc0, "20d5094d2dbe9b76e2c245a2b89b6006888952e2faa6a149ae318d69e520617748ac"),
+                    (1, 0xc0, "20c440b462ad48c7a77f94cd4532d8f2119dcebbd7c9764557e62726419b08ad4cac"),
+                ],
+                "2f6b2c5397b6d68ca18e09a3f05161668ffe93a988582d55c6f07bd5b3329def",
+                "512075169f4001aa68f15bbed28b218df1d0a62cbbcf1188c6665110c293c907b831",
+                vec![
+                    "c155adf4e8967fbd2e29f20ac896e60c3b0f1d5b0efa9d34941b5958c7b0a0312d3cd369a528b326bc9d2133cbd2ac21451acb31681a410434672c8e34fe757e91",
+                    "c155adf4e8967fbd2e29f20ac896e60c3b0f1d5b0efa9d34941b5958c7b0a0312dd7485025fceb78b9ed667db36ed8b8dc7b1f0b307ac167fa516fe4352b9f4ef7f154e8e8e17c31d3462d7132589ed29353c6fafdb884c5a6e04ea938834f0d9d",
+                    "c155adf4e8967fbd2e29f20ac896e60c3b0f1d5b0efa9d34941b5958c7b0a0312d737ed1fe30bc42b8022d717b44f0d93516617af64a64753b7a06bf16b26cd711f154e8e8e17c31d3462d7132589ed29353c6fafdb884c5a6e04ea938834f0d9d",
+                ],
+            ),
+        ];
+
+        for (internal_key, leaves, merkle_root, spk, control_blocks) in vectors {
+            let inner_key =
+                Point::from_slice(&hex::decode(format!("02{}", internal_key))?).unwrap();
+
+            let weighted_leaves: Vec<(u32, TapLeaf)> = leaves
+                .iter()
+                .map(|(weight, leaf_version, script)| {
+                    (
+                        *weight,
+                        TapLeaf::new_version(hex::decode(script).unwrap(), *leaf_version),
+                    )
+                })
+                .collect();
+
+            let tap_root = TapRoot::key_and_script_path_weighted(inner_key, weighted_leaves);
+
+            assert_eq!(tap_root.tap_branch().to_vec(), hex::decode(merkle_root)?);
+            assert_eq!(tap_root.spk().unwrap(), hex::decode(spk)?);
+
+            for (index, control_block) in control_blocks.iter().enumerate() {
+                assert_eq!(
+                    tap_root.control_block(index).unwrap().to_vec(),
+                    hex::decode(control_block)?
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_weighted_tap_tree() -> Result<(), Box<dyn Error>> {
+        let leaves: Vec<TapLeaf> = (0..4).map(|i| TapLeaf::new(vec![i as u8])).collect();
+
+        // Test - Equal weights over a power of two leaves build the balanced tree
+
+        let balanced = TapTree::new(leaves.clone());
+        let weighted = TapTree::new_weighted(leaves.iter().map(|leaf| (1, leaf.clone())).collect());
+
+        assert_eq!(weighted.tap_branch(), balanced.tap_branch());
+        for index in 0..4 {
+            assert_eq!(weighted.path(index), balanced.path(index));
+        }
+
+        // Test - A likely leaf sits right below the root, rare leaves deeper
+
+        let weighted = TapTree::new_weighted(vec![
+            (1, leaves[0].clone()),
+            (1, leaves[1].clone()),
+            (2, leaves[2].clone()),
+            (16, leaves[3].clone()),
+        ]);
+
+        assert_eq!(weighted.path(3).len(), 32);
+        assert_eq!(weighted.path(2).len(), 64);
+        assert_eq!(weighted.path(1).len(), 96);
+        assert_eq!(weighted.path(0).len(), 96);
+        assert_eq!(weighted.depth_first_order(), vec![2, 0, 1, 3]);
+
+        // Test - Script path spends of every leaf resolve to the same root
+
+        let tap_root = TapRoot::script_path_only_weighted(vec![
+            (1, leaves[0].clone()),
+            (1, leaves[1].clone()),
+            (2, leaves[2].clone()),
+            (16, leaves[3].clone()),
+        ]);
+
+        assert_eq!(tap_root.tap_branch(), weighted.tap_branch());
+        assert_eq!(tap_root.control_block(3).unwrap().to_vec().len(), 33 + 32);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod undo_tests {
+    use bitcoin::{hashes::Hash, Amount, OutPoint, ScriptBuf, TxOut, Txid};
+    use brollup::inscriptive::{
+        baked,
+        balance::dir::BalanceDirectory,
+        registery::registery::Registery,
+        repo::repo::ProgramRepo,
+        rollup::dir::RollupDirectory,
+        set::set::CoinSet,
+        state::dir::StateDirectory,
+        storage::dir::StorageDirectory,
+        undo::{
+            dir::UndoDirectory,
+            journal::{UndoJournal, UndoOp},
+        },
+    };
+
+    #[tokio::test]
+    async fn undo_test() -> Result<(), String> {
+        let coin_set = CoinSet::new_temporary().unwrap();
+        let utxo_set = {
+            let _coin_set = coin_set.lock().await;
+            _coin_set.utxo_set()
+        };
+
+        let outpoint_1 = OutPoint::new(Txid::from_byte_array([0xee; 32]), 0);
+        let outpoint_2 = OutPoint::new(Txid::from_byte_array([0xee; 32]), 1);
+        let txout = TxOut {
+            value: Amount::from_sat(1_000),
+            script_pubkey: ScriptBuf::from(vec![0x51, 0x20]),
+        };
+
+        // Sync a block that spends one output and creates another.
+        let mut journal = UndoJournal::new([0xaa; 32]);
+        {
+            let mut _utxo_set = utxo_set.lock().await;
+            assert!(_utxo_set.insert_txout(&outpoint_1, &txout));
+            assert!(!_utxo_set.insert_txout(&outpoint_1, &txout));
+
+            let removed = _utxo_set.remove_txout(&outpoint_1).unwrap();
+            journal.record(UndoOp::UTXORemoved(outpoint_1, removed));
+
+            assert!(_utxo_set.insert_txout(&outpoint_2, &txout));
+            journal.record(UndoOp::UTXOInserted(outpoint_2));
+        }
+
+        // Reverting restores the spent output and drops the created one.
+        journal
+            .revert(
+                &coin_set,
+                None,
+                &Registery::new_temporary().unwrap(),
+                &ProgramRepo::new_temporary().unwrap(),
+                &BalanceDirectory::new_temporary().unwrap(),
+                &StorageDirectory::new_temporary().unwrap(),
+                &StateDirectory::new_temporary().unwrap(),
+                &RollupDirectory::new_temporary().unwrap(),
+                None,
+            )
+            .await;
+        {
+            let mut _utxo_set = utxo_set.lock().await;
+            assert_eq!(_utxo_set.txout_by_outpoint(&outpoint_1), Some(txout));
+            assert_eq!(_utxo_set.txout_by_outpoint(&outpoint_2), None);
+        }
+
+        // Journals survive serialization.
+        let deserialized: UndoJournal =
+            serde_json::from_slice(&journal.serialize()).map_err(|e| e.to_string())?;
+        assert_eq!(deserialized.block_hash(), [0xaa; 32]);
+        assert_eq!(deserialized.ops().len(), 2);
+
+        // Only the most recent journals are kept.
+        let undo_dir = UndoDirectory::new_temporary().unwrap();
+        let mut _undo_dir = undo_dir.lock().await;
+
+        let start_height = u64::MAX - 2 * baked::MAX_REORG_DEPTH;
+        let tip_height = start_height + baked::MAX_REORG_DEPTH;
+        for height in start_height..=tip_height {
+            let mut block_hash = [0x00; 32];
+            block_hash[..8].copy_from_slice(&height.to_be_bytes());
+            assert!(_undo_dir.insert_journal(height, &UndoJournal::new(block_hash)));
+        }
+
+        assert!(_undo_dir.block_hash(start_height).is_none());
+        assert!(_undo_dir
+            .block_hash(tip_height - baked::MAX_REORG_DEPTH)
+            .is_none());
+        assert!(_undo_dir
+            .block_hash(tip_height - baked::MAX_REORG_DEPTH + 1)
+            .is_some());
+        assert_eq!(
+            _undo_dir.block_hash(tip_height).unwrap()[..8],
+            tip_height.to_be_bytes()
+        );
+
+        // Journals can be removed.
+        assert!(_undo_dir.remove_journal(tip_height).is_some());
+        assert!(_undo_dir.journal(tip_height).is_none());
+
+        Ok(())
+    }
+}
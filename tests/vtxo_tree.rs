@@ -0,0 +1,208 @@
+#[cfg(test)]
+mod vtxo_tree_tests {
+    use bitcoin::{
+        hashes::Hash,
+        sighash::{Prevouts, SighashCache, TapSighashType},
+        taproot::{LeafVersion, TapLeafHash},
+        Amount, OutPoint, ScriptBuf, TxOut, Txid,
+    };
+    use brollup::{
+        constructive::{
+            taproot::P2TR,
+            txn::{
+                exit::{self_spk, ExitPackage},
+                vtxo_tree::{VTXOTree, VTXOTreeOutput, ANCHOR_SPK},
+            },
+            txo::{
+                projector::{Projector, ProjectorTag},
+                vtxo::VTXO,
+            },
+        },
+        inscriptive::baked::EXPIRY_BLOCKS,
+        transmutive::secp::schnorr::{self, SchnorrSigningMode},
+    };
+    use secp::{Point, Scalar};
+
+    fn key(seed: u8) -> Point {
+        Scalar::from_slice(&[seed; 32]).unwrap().base_point_mul()
+    }
+
+    #[test]
+    fn vtxo_tree_test() -> Result<(), String> {
+        let operator = key(0x7f);
+        let accounts: Vec<Point> = (1..=5).map(key).collect();
+
+        // Two VTXOs per account.
+        let vtxos: Vec<VTXO> = (0..10)
+            .map(|i| {
+                VTXO::new(
+                    accounts[i % 5],
+                    operator,
+                    None,
+                    Some(10_000 + i as u64),
+                    None,
+                    None,
+                )
+            })
+            .collect();
+
+        let projector = Projector::new(&accounts, operator, ProjectorTag::VTXOProjector);
+        let projector_outpoint = OutPoint::new(Txid::all_zeros(), 1);
+
+        // Radix out of bounds.
+        assert!(VTXOTree::new(&projector, projector_outpoint, &vtxos, 1).is_none());
+        assert!(VTXOTree::new(&projector, projector_outpoint, &vec![], 4).is_none());
+
+        // 10 leaves with radix 4 split into 3, 3, 2 and 2 under the root.
+        let mut tree = VTXOTree::new(&projector, projector_outpoint, &vtxos, 4).unwrap();
+        assert_eq!(tree.size(), 5);
+        assert_eq!(tree.depth(), 2);
+
+        // The root spends the projector.
+        let root = tree.nodes()[0].unsigned_tx();
+        assert_eq!(root.input[0].previous_output, projector_outpoint);
+        let total: u64 = vtxos.iter().map(|vtxo| vtxo.value().unwrap()).sum();
+        let root_total: u64 = root.output.iter().map(|o| o.value.to_sat()).sum();
+        assert_eq!(root_total, total);
+
+        // Every child spends its parent output.
+        for node in tree.nodes().iter().skip(1) {
+            let (parent, vout) = node.parent().unwrap();
+            let parent_tx = tree.nodes()[parent].unsigned_tx();
+            let prevout = node.unsigned_tx().input[0].previous_output;
+            assert_eq!(prevout, OutPoint::new(parent_tx.compute_txid(), vout));
+            assert!(matches!(
+                tree.nodes()[parent].outputs()[vout as usize],
+                VTXOTreeOutput::Node(_)
+            ));
+        }
+
+        // Leaf outputs pay to the VTXO script pubkey.
+        let vtxo = &vtxos[3];
+        let (index, vout) = tree.leaf_position(vtxo).unwrap();
+        let leaf_txout = &tree.nodes()[index].unsigned_tx().output[vout as usize];
+        assert_eq!(leaf_txout.script_pubkey.as_bytes(), vtxo.spk().unwrap());
+        assert_eq!(leaf_txout.value.to_sat(), vtxo.value().unwrap());
+
+        // Exit paths are unavailable until signed.
+        assert!(tree.exit_path(vtxo).is_none());
+        for index in 0..tree.size() {
+            assert!(tree.key_agg_ctx(index).is_some());
+            assert!(tree.set_sig(index, [0x01; 64]));
+        }
+        assert!(tree.is_signed());
+
+        // Exit path goes from the root down to the leaf.
+        let exit_path = tree.exit_path(vtxo).unwrap();
+        assert_eq!(exit_path.len(), 2);
+        assert_eq!(exit_path[0].compute_txid(), root.compute_txid());
+        assert_eq!(
+            exit_path[1].compute_txid(),
+            tree.leaf_outpoint(vtxo).unwrap().txid
+        );
+
+        // Each account has two exit paths.
+        let exit_paths = tree.exit_paths(accounts[0]).unwrap();
+        assert_eq!(exit_paths.len(), 2);
+
+        // Small trees are a single transaction.
+        let tree = VTXOTree::new(&projector, projector_outpoint, &vtxos[..3].to_vec(), 4).unwrap();
+        assert_eq!(tree.size(), 1);
+        assert_eq!(tree.depth(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn exit_package_test() -> Result<(), String> {
+        let operator = key(0x7f);
+        let secret_key = [0x01; 32];
+        let account = key(0x01);
+
+        let vtxos: Vec<VTXO> = (0..6)
+            .map(|i| VTXO::new(key(i + 1), operator, None, Some(20_000), None, None))
+            .collect();
+        let accounts: Vec<Point> = vtxos.iter().map(|vtxo| vtxo.account_key()).collect();
+        let projector = Projector::new(&accounts, operator, ProjectorTag::VTXOProjector);
+
+        let mut tree =
+            VTXOTree::new(&projector, OutPoint::new(Txid::all_zeros(), 1), &vtxos, 2).unwrap();
+        for index in 0..tree.size() {
+            tree.set_sig(index, [0x01; 64]);
+        }
+
+        // The VTXO as held in the wallet, at its leaf outpoint.
+        let vtxo = vtxos[0].clone();
+        let branch = tree.exit_path(&vtxo).unwrap();
+        let outpoint = tree.leaf_outpoint(&vtxo).unwrap();
+        let wallet_vtxo = VTXO::new(account, operator, Some(outpoint), Some(20_000), None, None);
+
+        let sweep_spk = self_spk(account).unwrap();
+        let package =
+            ExitPackage::new(&wallet_vtxo, &branch, secret_key, sweep_spk.clone(), 500).unwrap();
+
+        // Branch followed by the sweep.
+        let txs = package.txs();
+        assert_eq!(txs.len(), branch.len() + 1);
+
+        // The sweep spends the VTXO after the expiry timelock.
+        let sweep = package.sweep();
+        assert_eq!(sweep.input[0].previous_output, outpoint);
+        assert_eq!(sweep.input[0].sequence.to_consensus_u32(), EXPIRY_BLOCKS);
+        assert_eq!(sweep.output[0].value.to_sat(), 19_500);
+        assert_eq!(sweep.output[0].script_pubkey.as_bytes(), sweep_spk);
+
+        // The sweep signature commits to the exit path.
+        let witness: Vec<Vec<u8>> = sweep.input[0].witness.iter().map(|w| w.to_vec()).collect();
+        assert_eq!(witness.len(), 3);
+        let exit_script = ScriptBuf::from(witness[1].clone());
+        let prevout = TxOut {
+            value: Amount::from_sat(20_000),
+            script_pubkey: ScriptBuf::from(wallet_vtxo.spk().unwrap()),
+        };
+        let sighash = SighashCache::new(&sweep)
+            .taproot_script_spend_signature_hash(
+                0,
+                &Prevouts::All(&[prevout]),
+                TapLeafHash::from_script(&exit_script, LeafVersion::TapScript),
+                TapSighashType::Default,
+            )
+            .unwrap();
+        assert!(schnorr::verify_xonly(
+            account.serialize_xonly(),
+            sighash.to_byte_array(),
+            witness[0].clone().try_into().unwrap(),
+            SchnorrSigningMode::BIP340,
+        ));
+
+        // A branch not leading to the VTXO is rejected.
+        assert!(
+            ExitPackage::new(&wallet_vtxo, &branch[..1].to_vec(), secret_key, vec![], 0).is_none()
+        );
+
+        // CPFP child spends the anchor of the root.
+        let funding = TxOut {
+            value: Amount::from_sat(10_000),
+            script_pubkey: ScriptBuf::from(sweep_spk.clone()),
+        };
+        let child = ExitPackage::cpfp(
+            &branch[0],
+            OutPoint::new(Txid::all_zeros(), 7),
+            &funding,
+            sweep_spk,
+            2_000,
+        )
+        .unwrap();
+        let anchor = child.input[0].previous_output;
+        assert_eq!(anchor.txid, branch[0].compute_txid());
+        assert_eq!(
+            branch[0].output[anchor.vout as usize]
+                .script_pubkey
+                .as_bytes(),
+            ANCHOR_SPK
+        );
+        assert_eq!(child.output[0].value.to_sat(), 8_000);
+
+        Ok(())
+    }
+}